@@ -0,0 +1,100 @@
+//! Scans an edit plan for files large enough to bloat git history before
+//! they're written and committed. Severity is controlled by
+//! [`LargeBlobPolicy`]. Edit plans only ever add or modify content (see
+//! [`crate::edit_plan::EditAction`]), so there's no deleted or renamed
+//! entry to exempt here.
+
+use crate::edit_plan::EditPlan;
+use std::fmt;
+use tdd_core::LargeBlobPolicy;
+
+/// An oversized file found in `path`, under [`LargeBlobPolicy::Reject`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OversizedBlob {
+    pub path: String,
+    pub size_bytes: u64,
+}
+
+impl fmt::Display for OversizedBlob {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} is {} KB, over the workspace.max_blob_kb limit; consider generating this fixture at test time instead of committing it",
+            self.path,
+            self.size_bytes / 1024
+        )
+    }
+}
+
+impl std::error::Error for OversizedBlob {}
+
+/// An oversized file found under [`LargeBlobPolicy::Warn`]: the write
+/// still goes ahead, but the caller should flag this loudly.
+#[derive(Debug)]
+pub struct LargeBlobHit {
+    pub path: String,
+    pub size_bytes: u64,
+}
+
+/// Scans every file in `plan` against `max_bytes`. Under
+/// [`LargeBlobPolicy::Reject`], returns the first oversized file as an
+/// `Err` so the caller can reject the step before anything is written.
+/// Under [`LargeBlobPolicy::Warn`], returns every hit as `Ok` for the
+/// caller to flag in the commit body and log without blocking the write.
+pub fn scan_edit_plan(plan: &EditPlan, max_bytes: u64, policy: LargeBlobPolicy) -> Result<Vec<LargeBlobHit>, OversizedBlob> {
+    let mut hits = Vec::new();
+    for edit in &plan.edits {
+        if let Some(size_bytes) = tdd_core::blob_size::check(&edit.content, max_bytes) {
+            if policy == LargeBlobPolicy::Reject {
+                return Err(OversizedBlob { path: edit.path.clone(), size_bytes });
+            }
+            hits.push(LargeBlobHit { path: edit.path.clone(), size_bytes });
+        }
+    }
+    Ok(hits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edit_plan::{EditAction, FileEdit};
+
+    fn plan_with(content: &str) -> EditPlan {
+        EditPlan {
+            edits: vec![FileEdit {
+                path: "tests/fixtures/huge.json".to_string(),
+                action: EditAction::Upsert,
+                content: content.to_string(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn an_oversized_file_is_rejected_under_reject_policy() {
+        let plan = plan_with(&"x".repeat(2048));
+
+        let error = scan_edit_plan(&plan, 1024, LargeBlobPolicy::Reject).unwrap_err();
+
+        assert_eq!(error.path, "tests/fixtures/huge.json");
+        assert_eq!(error.size_bytes, 2048);
+    }
+
+    #[test]
+    fn an_oversized_file_is_reported_but_not_rejected_under_warn_policy() {
+        let plan = plan_with(&"x".repeat(2048));
+
+        let hits = scan_edit_plan(&plan, 1024, LargeBlobPolicy::Warn).unwrap();
+
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].path, "tests/fixtures/huge.json");
+        assert_eq!(hits[0].size_bytes, 2048);
+    }
+
+    #[test]
+    fn a_normal_sized_plan_is_unaffected() {
+        let plan = plan_with("use string_calculator::add;\n");
+
+        assert!(scan_edit_plan(&plan, 1024, LargeBlobPolicy::Reject).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,158 @@
+//! The structured JSON edit plan agents produce instead of diffs, and the
+//! logic that turns it into filesystem writes.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// The action to take for a single file in an edit plan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EditAction {
+    Upsert,
+}
+
+/// One file's worth of change in an edit plan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileEdit {
+    pub path: String,
+    pub action: EditAction,
+    pub content: String,
+}
+
+/// One ordered group of `edits`' paths to commit on its own, when a plan
+/// is too large for the per-step file count to go into a single commit.
+/// See [`EditPlan::commits`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitGroup {
+    /// Overrides the commit message the orchestrator would otherwise
+    /// derive from the role and step index for this group.
+    pub commit_message: Option<String>,
+    #[serde(default)]
+    pub notes: String,
+    /// Paths into this plan's `edits`, committed together and in the
+    /// order the groups themselves are listed.
+    pub files: Vec<String>,
+}
+
+/// A full edit plan, as produced by an agent and consumed by the executor.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct EditPlan {
+    pub edits: Vec<FileEdit>,
+    /// Splits `edits` into ordered commits instead of the single implicit
+    /// one a flat plan gets, for a change too large for one commit's file
+    /// count without raising that limit. Every path across every group
+    /// must resolve to exactly one entry in `edits`; see
+    /// [`Self::validate_commit_groups`]. Empty for the common flat form.
+    #[serde(default)]
+    pub commits: Vec<CommitGroup>,
+}
+
+impl EditPlan {
+    /// Parses an edit plan from the JSON payload an agent returned.
+    pub fn from_json(raw: &str) -> anyhow::Result<Self> {
+        Ok(serde_json::from_str(raw)?)
+    }
+
+    /// Whether this plan uses the grouped `commits` form rather than the
+    /// flat form.
+    pub fn is_grouped(&self) -> bool {
+        !self.commits.is_empty()
+    }
+
+    /// Checks that `commits`, when present, accounts for every edit in
+    /// the plan exactly once: every group references paths that exist in
+    /// `edits`, no path appears in more than one group, and no edit is
+    /// left out of every group.
+    pub fn validate_commit_groups(&self) -> anyhow::Result<()> {
+        if self.commits.is_empty() {
+            return Ok(());
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        for group in &self.commits {
+            if group.files.is_empty() {
+                anyhow::bail!("a commit group must list at least one file");
+            }
+            for path in &group.files {
+                if !self.edits.iter().any(|edit| &edit.path == path) {
+                    anyhow::bail!("commit group references {path:?}, which isn't in this plan's edits");
+                }
+                if !seen.insert(path.clone()) {
+                    anyhow::bail!("{path:?} appears in more than one commit group");
+                }
+            }
+        }
+        if seen.len() != self.edits.len() {
+            anyhow::bail!("every edited file must belong to exactly one commit group when `commits` is used");
+        }
+        Ok(())
+    }
+}
+
+/// Writes every edit in `plan` under `repo_root`, creating parent
+/// directories as needed, and returns the repo-relative paths touched.
+pub fn apply_edit_plan(plan: &EditPlan, repo_root: &Path) -> anyhow::Result<Vec<String>> {
+    let mut touched = Vec::with_capacity(plan.edits.len());
+    for edit in &plan.edits {
+        let target = repo_root.join(&edit.path);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        match edit.action {
+            EditAction::Upsert => std::fs::write(&target, &edit.content)?,
+        }
+        touched.push(edit.path.clone());
+    }
+    Ok(touched)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit(path: &str) -> FileEdit {
+        FileEdit { path: path.to_string(), action: EditAction::Upsert, content: String::new() }
+    }
+
+    fn group(files: &[&str]) -> CommitGroup {
+        CommitGroup { commit_message: None, notes: String::new(), files: files.iter().map(|f| f.to_string()).collect() }
+    }
+
+    #[test]
+    fn a_plan_with_no_commits_is_not_grouped_and_always_validates() {
+        let plan = EditPlan { edits: vec![edit("src/lib.rs")], commits: Vec::new() };
+        assert!(!plan.is_grouped());
+        assert!(plan.validate_commit_groups().is_ok());
+    }
+
+    #[test]
+    fn groups_covering_every_edit_exactly_once_validate() {
+        let plan = EditPlan {
+            edits: vec![edit("src/lib.rs"), edit("src/new_module.rs")],
+            commits: vec![group(&["src/lib.rs"]), group(&["src/new_module.rs"])],
+        };
+        assert!(plan.is_grouped());
+        assert!(plan.validate_commit_groups().is_ok());
+    }
+
+    #[test]
+    fn a_group_referencing_a_path_outside_edits_is_rejected() {
+        let plan = EditPlan { edits: vec![edit("src/lib.rs")], commits: vec![group(&["src/lib.rs", "src/missing.rs"])] };
+        let error = plan.validate_commit_groups().unwrap_err();
+        assert!(error.to_string().contains("src/missing.rs"));
+    }
+
+    #[test]
+    fn a_path_claimed_by_two_groups_is_rejected() {
+        let plan = EditPlan { edits: vec![edit("src/lib.rs")], commits: vec![group(&["src/lib.rs"]), group(&["src/lib.rs"])] };
+        let error = plan.validate_commit_groups().unwrap_err();
+        assert!(error.to_string().contains("more than one commit group"));
+    }
+
+    #[test]
+    fn an_edit_left_out_of_every_group_is_rejected() {
+        let plan = EditPlan { edits: vec![edit("src/lib.rs"), edit("src/extra.rs")], commits: vec![group(&["src/lib.rs"])] };
+        let error = plan.validate_commit_groups().unwrap_err();
+        assert!(error.to_string().contains("exactly one commit group"));
+    }
+}
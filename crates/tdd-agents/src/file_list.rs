@@ -0,0 +1,145 @@
+//! Picks and renders a bounded, relevance-ordered slice of the repo's
+//! tracked files for the "Tracked files" prompt section: on any real
+//! project the full path list runs into the hundreds, and alphabetical
+//! order buries `src/` and `tests/` under `.github/` and `Cargo.lock`.
+
+/// A path's category, used as the primary sort key (lower sorts first).
+/// Source and test files are what an agent actually needs to see; the
+/// manifest is occasionally relevant; everything else is noise it should
+/// only be shown if there's room left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Category {
+    Code,
+    Manifest,
+    Other,
+}
+
+impl Category {
+    fn of(path: &str) -> Category {
+        if path.starts_with("src/") || path.starts_with("tests/") || path.ends_with(".rs") {
+            Category::Code
+        } else if path == "Cargo.toml" || path == "Cargo.lock" {
+            Category::Manifest
+        } else {
+            Category::Other
+        }
+    }
+
+    fn heading(self) -> &'static str {
+        match self {
+            Category::Code => "Source and tests",
+            Category::Manifest => "Manifest",
+            Category::Other => "Other",
+        }
+    }
+}
+
+/// Selects up to `limit` paths from `paths`, ordered by category first
+/// (source/tests, then the manifest, then everything else) and within a
+/// category by whether the path appears in `recently_changed` (in the
+/// order given, most recent first), then alphabetically. Ties are broken
+/// deterministically so the same inputs always produce the same order.
+fn select_ordered(paths: &[String], recently_changed: &[String], limit: usize) -> (Vec<String>, usize) {
+    let mut sorted: Vec<&String> = paths.iter().collect();
+    sorted.sort_by(|a, b| {
+        let category = Category::of(a).cmp(&Category::of(b));
+        if category != std::cmp::Ordering::Equal {
+            return category;
+        }
+        let recency = recency_rank(a, recently_changed).cmp(&recency_rank(b, recently_changed));
+        if recency != std::cmp::Ordering::Equal {
+            return recency;
+        }
+        a.cmp(b)
+    });
+
+    let total = sorted.len();
+    let selected = sorted.into_iter().take(limit).cloned().collect();
+    (selected, total.saturating_sub(limit))
+}
+
+fn recency_rank(path: &str, recently_changed: &[String]) -> usize {
+    recently_changed.iter().position(|p| p == path).unwrap_or(recently_changed.len())
+}
+
+/// Renders the "Tracked files" section body: a bounded, category-grouped
+/// list (see [`select_ordered`]) with a trailing "...and N more" count
+/// when `paths` doesn't fit within `limit`.
+pub fn render_tracked_files(paths: &[String], recently_changed: &[String], limit: usize) -> String {
+    let (selected, omitted) = select_ordered(paths, recently_changed, limit);
+
+    let mut groups: Vec<(Category, Vec<&String>)> = Vec::new();
+    for path in &selected {
+        let category = Category::of(path);
+        match groups.last_mut() {
+            Some((last_category, group)) if *last_category == category => group.push(path),
+            _ => groups.push((category, vec![path])),
+        }
+    }
+
+    let mut out = String::new();
+    for (index, (category, group)) in groups.iter().enumerate() {
+        if index > 0 {
+            out.push('\n');
+        }
+        out.push_str(category.heading());
+        out.push(':');
+        for path in group {
+            out.push('\n');
+            out.push_str(path);
+        }
+    }
+
+    if omitted > 0 {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(&format!("...and {omitted} more"));
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_recently_changed_test_file_outranks_an_untouched_asset() {
+        let paths = vec!["assets/logo.png".to_string(), "tests/add_test.rs".to_string()];
+        let rendered = render_tracked_files(&paths, &["tests/add_test.rs".to_string()], 10);
+
+        let test_pos = rendered.find("tests/add_test.rs").unwrap();
+        let asset_pos = rendered.find("assets/logo.png").unwrap();
+        assert!(test_pos < asset_pos);
+    }
+
+    #[test]
+    fn the_limit_is_respected_with_a_trailing_count() {
+        let paths: Vec<String> = (0..5).map(|i| format!("src/file_{i}.rs")).collect();
+
+        let rendered = render_tracked_files(&paths, &[], 3);
+
+        assert_eq!(rendered.lines().filter(|l| l.starts_with("src/")).count(), 3);
+        assert!(rendered.ends_with("...and 2 more"));
+    }
+
+    #[test]
+    fn categories_are_grouped_under_their_own_heading() {
+        let paths = vec!["Cargo.toml".to_string(), "src/lib.rs".to_string(), ".github/workflows/ci.yml".to_string()];
+
+        let rendered = render_tracked_files(&paths, &[], 10);
+
+        let code_pos = rendered.find("Source and tests:").unwrap();
+        let manifest_pos = rendered.find("Manifest:").unwrap();
+        let other_pos = rendered.find("Other:").unwrap();
+        assert!(code_pos < manifest_pos && manifest_pos < other_pos);
+    }
+
+    #[test]
+    fn nothing_omitted_means_no_trailing_count() {
+        let paths = vec!["src/lib.rs".to_string()];
+        let rendered = render_tracked_files(&paths, &[], 10);
+        assert!(!rendered.contains("more"));
+    }
+}
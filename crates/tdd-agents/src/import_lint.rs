@@ -0,0 +1,100 @@
+//! Flags a Tester edit plan that imports the library crate under the
+//! wrong identifier — a recurring first-step failure where the Tester
+//! writes `use my_kata::add;` when the crate is actually `string_calculator`,
+//! the test fails to compile, and (with only one attempt) the run dies.
+
+use crate::edit_plan::EditPlan;
+use std::fmt;
+
+/// A `use <ident>::...` statement that names neither the workspace's own
+/// crate nor a Rust built-in, with the correct crate name suggested.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportMismatch {
+    pub found: String,
+    pub crate_name: String,
+}
+
+impl fmt::Display for ImportMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "test imports `{}`, but the library crate is `{}`", self.found, self.crate_name)
+    }
+}
+
+impl std::error::Error for ImportMismatch {}
+
+/// Identifiers a `use` statement may reference without naming the
+/// workspace's own crate: Rust's path keywords and the modules every kata
+/// can reach without a dependency.
+const ALWAYS_ALLOWED: &[&str] = &["crate", "self", "super", "std", "core", "alloc"];
+
+/// Scans every edit in `plan` for a `use <ident>::` statement whose first
+/// path segment doesn't match `crate_name` or [`ALWAYS_ALLOWED`], returning
+/// the first one found. This is a best-effort catch for the common
+/// "guessed the wrong kata crate name" mistake, not a full dependency
+/// check: a plan that legitimately imports another crate the kata depends
+/// on will also be flagged.
+pub fn lint_imports(plan: &EditPlan, crate_name: &str) -> Result<(), ImportMismatch> {
+    for edit in &plan.edits {
+        for line in edit.content.lines() {
+            let Some(ident) = use_target(line) else { continue };
+            if ident != crate_name && !ALWAYS_ALLOWED.contains(&ident) {
+                return Err(ImportMismatch {
+                    found: ident.to_string(),
+                    crate_name: crate_name.to_string(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the first path segment of a `use ident::...;` statement, or
+/// `None` if `line` isn't one.
+fn use_target(line: &str) -> Option<&str> {
+    let rest = line.trim().strip_prefix("use ")?;
+    let ident = rest.split(|c: char| !(c.is_alphanumeric() || c == '_')).next()?;
+    (!ident.is_empty()).then_some(ident)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edit_plan::{EditAction, FileEdit};
+
+    fn plan_with(content: &str) -> EditPlan {
+        EditPlan {
+            edits: vec![FileEdit {
+                path: "tests/api.rs".to_string(),
+                action: EditAction::Upsert,
+                content: content.to_string(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn rejects_a_use_statement_naming_the_wrong_crate() {
+        let plan = plan_with("use my_kata::add;\n\n#[test]\nfn adds() { assert_eq!(my_kata::add(1, 2), 3); }\n");
+
+        let error = lint_imports(&plan, "string_calculator").unwrap_err();
+
+        assert_eq!(error.found, "my_kata");
+        assert_eq!(error.crate_name, "string_calculator");
+        assert!(error.to_string().contains("my_kata"));
+        assert!(error.to_string().contains("string_calculator"));
+    }
+
+    #[test]
+    fn accepts_a_use_statement_naming_the_correct_crate() {
+        let plan = plan_with("use string_calculator::add;\n");
+
+        assert!(lint_imports(&plan, "string_calculator").is_ok());
+    }
+
+    #[test]
+    fn accepts_imports_of_rust_built_ins() {
+        let plan = plan_with("use std::collections::HashMap;\nuse crate::helpers::setup;\n");
+
+        assert!(lint_imports(&plan, "string_calculator").is_ok());
+    }
+}
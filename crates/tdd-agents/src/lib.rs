@@ -0,0 +1,146 @@
+//! Role implementations that turn an LLM's response into filesystem edits,
+//! following the JSON edit-plan protocol described in the constitution.
+
+pub mod blob_scan;
+pub mod edit_plan;
+pub mod file_list;
+pub mod import_lint;
+pub mod manifest_scan;
+pub mod prompt_messages;
+pub mod prompts;
+pub mod readonly_guard;
+pub mod role_agent;
+pub mod secret_scan;
+pub mod support;
+pub mod unicode_scan;
+
+pub use blob_scan::{LargeBlobHit, OversizedBlob};
+pub use edit_plan::{apply_edit_plan, EditAction, EditPlan, FileEdit};
+pub use file_list::render_tracked_files;
+pub use import_lint::{lint_imports, ImportMismatch};
+pub use manifest_scan::{ManifestChange, ManifestPolicy, ManifestViolation};
+pub use prompt_messages::{context_message, edit_messages, plan_messages};
+pub use readonly_guard::ReadonlyViolation;
+pub use role_agent::RoleAgent;
+pub use secret_scan::{scan_edit_plan, scan_text, SecretHit, SecretLeak};
+pub use support::{render_requested_files, suspected_untracked_paths, EditResponse, MAX_REQUESTED_FILES};
+pub use unicode_scan::{UnicodeHit, UnicodeRejection};
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use tdd_core::{LargeBlobPolicy, Role, SecretScanMode, UnicodePolicy};
+use tdd_llm::LlmClient;
+
+/// The checks run over every edit plan before it's written, bundled
+/// together since [`agent_for_role`] already has enough standalone flags:
+/// the secret and Unicode hygiene scans, the blob size ceiling, the
+/// manifest-delta analyzer (see [`manifest_scan`]), plus the globs (see
+/// [`readonly_guard`]) that protect a path from being touched at all.
+#[derive(Debug, Clone)]
+pub struct ScanPolicy {
+    pub secret_scan: SecretScanMode,
+    pub unicode_policy: UnicodePolicy,
+    pub max_blob_bytes: u64,
+    pub large_blob_policy: LargeBlobPolicy,
+    pub readonly_paths: Vec<String>,
+    pub manifest_policy: ManifestPolicy,
+}
+
+/// The role's configured temperature and how much to add to it per retry
+/// attempt, bundled for the same reason as [`ScanPolicy`]: so
+/// [`agent_for_role_with_temperature`] doesn't grow yet another pair of
+/// standalone flags. See [`RoleAgent::with_temperature_escalation`].
+#[derive(Debug, Clone, Copy)]
+pub struct TemperaturePolicy {
+    pub base_temperature: f32,
+    pub retry_temperature_bump: f32,
+}
+
+/// Builds the [`RoleAgent`] for `role`, wiring in the matching system
+/// prompt from [`prompts`], with the follow-up file-request protocol
+/// enabled or disabled per `allow_file_requests`, the Tester's
+/// wrong-crate-name import lint per `lint_imports`, and the edit-plan
+/// checks in `scan`.
+pub fn agent_for_role(
+    role: Role,
+    llm: Arc<dyn LlmClient>,
+    repo_root: impl Into<PathBuf>,
+    allow_file_requests: bool,
+    lint_imports: bool,
+    scan: ScanPolicy,
+    commit_prefix: Option<String>,
+) -> RoleAgent {
+    agent_for_role_with_temperature(
+        role,
+        llm,
+        repo_root,
+        allow_file_requests,
+        lint_imports,
+        scan,
+        commit_prefix,
+        TemperaturePolicy { base_temperature: 0.0, retry_temperature_bump: 0.0 },
+        None,
+    )
+}
+
+/// Like [`agent_for_role`], but also wires in `temperature` (see
+/// [`RoleAgent::with_temperature_escalation`]), for callers that route
+/// per-role model settings from `tdd.yaml` rather than leaving the agent
+/// at a fixed temperature. `system_prompt_override`, when set, replaces
+/// the role's default prompt verbatim instead of looking it up in
+/// [`prompts`] — used by `tdd-cli experiment` to substitute a variant
+/// prompt template for one role without otherwise changing how its
+/// agent is built.
+#[allow(clippy::too_many_arguments)]
+pub fn agent_for_role_with_temperature(
+    role: Role,
+    llm: Arc<dyn LlmClient>,
+    repo_root: impl Into<PathBuf>,
+    allow_file_requests: bool,
+    lint_imports: bool,
+    scan: ScanPolicy,
+    commit_prefix: Option<String>,
+    temperature: TemperaturePolicy,
+    system_prompt_override: Option<String>,
+) -> RoleAgent {
+    let system_prompt = system_prompt_override.map(std::borrow::Cow::Owned).unwrap_or(std::borrow::Cow::Borrowed(match role {
+        Role::Tester => prompts::TESTER_SYSTEM_PROMPT,
+        Role::Implementor => prompts::IMPLEMENTOR_SYSTEM_PROMPT,
+        Role::Refactorer => prompts::REFACTORER_SYSTEM_PROMPT,
+    }));
+    RoleAgent::new(role, system_prompt, llm, repo_root)
+        .with_file_requests(allow_file_requests)
+        .with_lint_imports(lint_imports)
+        .with_secret_scan(scan.secret_scan)
+        .with_unicode_policy(scan.unicode_policy)
+        .with_max_blob_size(scan.max_blob_bytes, scan.large_blob_policy)
+        .with_commit_prefix(commit_prefix)
+        .with_readonly_paths(scan.readonly_paths)
+        .with_manifest_policy(scan.manifest_policy)
+        .with_temperature_escalation(temperature.base_temperature, temperature.retry_temperature_bump)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use edit_plan::{EditAction, EditPlan, FileEdit};
+    use tempfile::tempdir;
+
+    #[test]
+    fn apply_edit_plan_writes_files_relative_to_repo_root() {
+        let dir = tempdir().unwrap();
+        let plan = EditPlan {
+            edits: vec![FileEdit {
+                path: "src/lib.rs".to_string(),
+                action: EditAction::Upsert,
+                content: "pub fn add(a: i32, b: i32) -> i32 { a + b }\n".to_string(),
+            }],
+            ..Default::default()
+        };
+
+        let touched = apply_edit_plan(&plan, dir.path()).unwrap();
+
+        assert_eq!(touched, vec!["src/lib.rs".to_string()]);
+        assert!(dir.path().join("src/lib.rs").exists());
+    }
+}
@@ -0,0 +1,75 @@
+//! Scans an edit plan's `Cargo.toml` edit, if any, against the manifest
+//! already on disk, so [`tdd_core::manifest_guard`]'s pure classification
+//! can see a real before/after pair. Only ever one edit to look at — an
+//! edit plan upserts whole files (see [`crate::edit_plan::EditAction`]),
+//! so the manifest's previous text is whatever's currently checked out.
+
+use crate::edit_plan::EditPlan;
+use std::path::Path;
+use tdd_core::manifest_guard;
+pub use tdd_core::{ManifestChange, ManifestPolicy, ManifestViolation};
+
+const MANIFEST_PATH: &str = "Cargo.toml";
+
+/// Classifies `plan`'s `Cargo.toml` edit (if it has one) against `policy`.
+/// `Ok(&[])` when the plan doesn't touch `Cargo.toml` at all.
+pub fn scan_edit_plan(plan: &EditPlan, repo_root: &Path, policy: &ManifestPolicy) -> Result<Vec<ManifestChange>, ManifestViolation> {
+    let Some(edit) = plan.edits.iter().find(|edit| edit.path == MANIFEST_PATH) else {
+        return Ok(Vec::new());
+    };
+    let before = std::fs::read_to_string(repo_root.join(MANIFEST_PATH)).unwrap_or_default();
+    manifest_guard::check(&before, &edit.content, policy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edit_plan::{EditAction, FileEdit};
+    use tdd_core::ManifestChangePolicy;
+    use tempfile::tempdir;
+
+    fn plan_with_manifest(content: &str) -> EditPlan {
+        EditPlan { edits: vec![FileEdit { path: MANIFEST_PATH.to_string(), action: EditAction::Upsert, content: content.to_string() }], ..Default::default() }
+    }
+
+    #[test]
+    fn a_plan_that_never_touches_the_manifest_is_not_scanned() {
+        let dir = tempdir().unwrap();
+        let plan = EditPlan { edits: vec![FileEdit { path: "src/lib.rs".to_string(), action: EditAction::Upsert, content: String::new() }], ..Default::default() };
+
+        assert_eq!(scan_edit_plan(&plan, dir.path(), &ManifestPolicy::default()).unwrap(), Vec::new());
+    }
+
+    #[test]
+    fn an_edition_bump_against_the_checked_out_manifest_is_rejected() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join(MANIFEST_PATH), "[package]\nname = \"kata\"\nedition = \"2021\"\n").unwrap();
+        let plan = plan_with_manifest("[package]\nname = \"kata\"\nedition = \"2024\"\n");
+
+        let error = scan_edit_plan(&plan, dir.path(), &ManifestPolicy::default()).unwrap_err();
+
+        assert!(matches!(error.0, ManifestChange::Edition { .. }));
+    }
+
+    #[test]
+    fn an_allowed_policy_surfaces_the_classification_instead_of_rejecting() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join(MANIFEST_PATH), "[package]\nname = \"kata\"\nedition = \"2021\"\n").unwrap();
+        let plan = plan_with_manifest("[package]\nname = \"kata\"\nedition = \"2024\"\n");
+        let policy = ManifestPolicy { edition: ManifestChangePolicy::Allow, profile: ManifestChangePolicy::Reject };
+
+        let changes = scan_edit_plan(&plan, dir.path(), &policy).unwrap();
+
+        assert_eq!(changes.len(), 1);
+    }
+
+    #[test]
+    fn a_missing_on_disk_manifest_is_treated_as_empty() {
+        let dir = tempdir().unwrap();
+        let plan = plan_with_manifest("[package]\nname = \"kata\"\nversion = \"0.1.0\"\n");
+
+        let changes = scan_edit_plan(&plan, dir.path(), &ManifestPolicy::default()).unwrap();
+
+        assert_eq!(changes.len(), 2);
+    }
+}
@@ -0,0 +1,71 @@
+//! Pure construction of the messages sent to an LLM for each role's plan
+//! and edit phases, extracted out of [`crate::role_agent::RoleAgent`] so a
+//! change to prompt structure is visible as a reviewed diff against the
+//! golden files in `tests/snapshots/` rather than silently reaching
+//! production. See `tests/prompt_snapshots.rs`.
+
+use tdd_core::{Role, StepContext};
+use tdd_llm::Message;
+
+/// The follow-up instruction appended during the edit phase, asking for a
+/// JSON edit plan or, if more context is needed first, a file request.
+const EDIT_INSTRUCTION: &str = "Respond with a JSON edit plan only, matching {\"edits\": [{\"path\", \"action\", \"content\"}]}. \
+     If you need to see specific files first, respond with {\"request_files\": [\"path\", ...]} instead.";
+
+/// Renders `ctx` into the single user-turn message shared by the plan and
+/// edit phases, in a fixed section order: standing instructions, a
+/// pending operator goal, readonly paths, the resolved crate name,
+/// previously proposed plans, the since-last-turn delta, then always the
+/// kata description, last commit message, last diff, and tracked files.
+/// Each optional section is omitted entirely when absent rather than
+/// rendered empty, so a dropped section shows up as a missing block in a
+/// snapshot diff instead of a blank one.
+pub fn context_message(ctx: &StepContext) -> Message {
+    let mut sections = Vec::new();
+    if !ctx.standing_instructions.is_empty() {
+        sections.push(format!("Standing instructions:\n{}", ctx.standing_instructions));
+    }
+    if let Some(goal) = &ctx.user_goal {
+        sections.push(match ctx.role {
+            Role::Tester => format!("The human operator requests that this step focus on:\n{goal}"),
+            Role::Implementor | Role::Refactorer => format!(
+                "The human operator requests that this step focus on:\n{goal}\n\nHonor this without overriding your role's constraints above."
+            ),
+        });
+    }
+    if !ctx.readonly_paths.is_empty() {
+        sections.push(format!("Do not modify (protected by readonly_paths):\n{}", ctx.readonly_paths.join("\n")));
+    }
+    if let Some(crate_name) = &ctx.crate_name {
+        sections.push(format!("The library crate is imported as `{crate_name}`."));
+    }
+    if !ctx.previously_proposed.is_empty() {
+        sections.push(format!("Previously proposed (not yet applied):\n{}", ctx.previously_proposed.join("\n\n")));
+    }
+    if let Some(since_last_turn) = &ctx.since_last_turn {
+        sections.push(format!("Since your last turn:\n{since_last_turn}"));
+    }
+    sections.push(format!("Kata description:\n{}", ctx.kata_description));
+    sections.push(format!("Last commit message:\n{}", ctx.git_last_commit_msg));
+    sections.push(format!("Last diff:\n{}", ctx.git_last_diff));
+    sections.push(format!(
+        "Tracked files:\n{}",
+        crate::file_list::render_tracked_files(&ctx.repo_snapshot_paths, &ctx.recently_changed_paths, ctx.file_list_limit)
+    ));
+    Message::user(sections.join("\n\n"))
+}
+
+/// The messages sent for a role's `plan()` call: the system prompt
+/// followed by [`context_message`].
+pub fn plan_messages(system_prompt: &str, ctx: &StepContext) -> Vec<Message> {
+    vec![Message::system(system_prompt), context_message(ctx)]
+}
+
+/// The messages sent for a role's `edit()` call before any follow-up
+/// file-request turn: [`plan_messages`] plus the instruction to respond
+/// with a JSON edit plan.
+pub fn edit_messages(system_prompt: &str, ctx: &StepContext) -> Vec<Message> {
+    let mut messages = plan_messages(system_prompt, ctx);
+    messages.push(Message::user(EDIT_INSTRUCTION));
+    messages
+}
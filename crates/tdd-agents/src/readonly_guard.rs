@@ -0,0 +1,70 @@
+//! Rejects an edit plan that touches a path protected by
+//! `workspace.readonly_paths`, so a provided interface (e.g. a kata's
+//! `contracts/` directory) can't be "improved" by an agent just because
+//! it's an ordinary source path. Edit plans in this codebase only ever
+//! upsert a file (see [`crate::edit_plan::EditAction`]) — there's no
+//! delete or rename to protect separately — so this checks exactly that:
+//! every edit's own path against the configured globs.
+
+use crate::edit_plan::EditPlan;
+use std::fmt;
+
+/// An edit plan path that matched a `workspace.readonly_paths` glob.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadonlyViolation {
+    pub path: String,
+    pub glob: String,
+}
+
+impl fmt::Display for ReadonlyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is protected by readonly_paths (matches `{}`)", self.path, self.glob)
+    }
+}
+
+impl std::error::Error for ReadonlyViolation {}
+
+/// Returns an error naming the first edit in `plan` whose path matches one
+/// of `globs`, if any; `Ok(())` when `globs` is empty or nothing matches.
+pub fn check_edit_plan(plan: &EditPlan, globs: &[String]) -> Result<(), ReadonlyViolation> {
+    for edit in &plan.edits {
+        if let Some(glob) = globs.iter().find(|glob| tdd_core::path_glob::matches(glob, &edit.path)) {
+            return Err(ReadonlyViolation { path: edit.path.clone(), glob: glob.clone() });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edit_plan::{EditAction, FileEdit};
+
+    fn plan_touching(path: &str) -> EditPlan {
+        EditPlan {
+            edits: vec![FileEdit {
+                path: path.to_string(),
+                action: EditAction::Upsert,
+                content: String::new(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn a_plan_touching_a_protected_path_is_rejected() {
+        let error = check_edit_plan(&plan_touching("contracts/billing.rs"), &["contracts/**".to_string()]).unwrap_err();
+        assert_eq!(error.path, "contracts/billing.rs");
+        assert_eq!(error.glob, "contracts/**");
+    }
+
+    #[test]
+    fn a_plan_touching_an_unprotected_path_is_accepted() {
+        assert!(check_edit_plan(&plan_touching("src/lib.rs"), &["contracts/**".to_string()]).is_ok());
+    }
+
+    #[test]
+    fn no_configured_globs_accepts_everything() {
+        assert!(check_edit_plan(&plan_touching("contracts/billing.rs"), &[]).is_ok());
+    }
+}
@@ -0,0 +1,133 @@
+//! The follow-up file-request protocol shared by every role's `edit()`:
+//! instead of guessing which files to include up front, an agent may ask
+//! for up to five specific paths and get them in a single follow-up turn.
+
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// The maximum number of files an agent may request in one follow-up turn.
+pub const MAX_REQUESTED_FILES: usize = 5;
+
+/// An edit-phase response is either the real edit plan or a request for
+/// more context; `serde(untagged)` picks whichever shape matches.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum EditResponse {
+    FileRequest { request_files: Vec<String> },
+    Plan(crate::edit_plan::EditPlan),
+}
+
+/// Resolves `path` under `repo_root`, rejecting anything that escapes it
+/// via `..` or an absolute path.
+fn resolve_in_repo(repo_root: &Path, path: &str) -> Option<PathBuf> {
+    let candidate = Path::new(path);
+    if candidate.is_absolute() || candidate.components().any(|c| matches!(c, std::path::Component::ParentDir)) {
+        return None;
+    }
+    Some(repo_root.join(candidate))
+}
+
+/// Renders the follow-up message body for a file-request turn: each
+/// requested path gets its full content, or an inline error if it's
+/// missing or escapes the repo, rather than failing the whole step.
+pub fn render_requested_files(repo_root: &Path, paths: &[String]) -> String {
+    let mut out = String::new();
+    for path in paths {
+        out.push_str(&format!("--- {path} ---\n"));
+        match resolve_in_repo(repo_root, path) {
+            Some(full_path) if full_path.is_file() => match std::fs::read_to_string(&full_path) {
+                Ok(content) => out.push_str(&content),
+                Err(error) => out.push_str(&format!("error: could not read {path}: {error}")),
+            },
+            _ => out.push_str(&format!("error: {path} does not exist or is outside the workspace")),
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// File extensions worth flagging when a path-like token mentioning one
+/// appears in an edit plan's notes but not its file list, see
+/// [`suspected_untracked_paths`].
+const TRACKED_EXTENSIONS: [&str; 3] = ["rs", "toml", "md"];
+
+/// Scans `text` for path-like tokens (a run of letters, digits, `_`, `/`,
+/// and `.`, ending in one of [`TRACKED_EXTENSIONS`]) that don't appear in
+/// `known_paths`, in first-seen order with no duplicates. A model that
+/// truncates its JSON edit plan mid-array still often describes the
+/// dropped files in its notes, so this catches a truncation the JSON
+/// parser alone can't see (the JSON itself stayed valid).
+pub fn suspected_untracked_paths(text: &str, known_paths: &[String]) -> Vec<String> {
+    let mut found = Vec::new();
+    for raw_token in text.split_whitespace() {
+        let token = raw_token.trim_matches(|c: char| !(c.is_alphanumeric() || c == '_' || c == '/' || c == '.'));
+        let Some(dot_at) = token.rfind('.') else {
+            continue;
+        };
+        let (stem, extension) = (&token[..dot_at], &token[dot_at + 1..]);
+        if stem.is_empty() || !TRACKED_EXTENSIONS.contains(&extension) {
+            continue;
+        }
+        if !token.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '/' || c == '.') {
+            continue;
+        }
+        if known_paths.iter().any(|path| path == token) || found.contains(&token.to_string()) {
+            continue;
+        }
+        found.push(token.to_string());
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn renders_existing_file_contents() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("a.rs"), "fn a() {}").unwrap();
+
+        let out = render_requested_files(dir.path(), &["a.rs".to_string()]);
+        assert!(out.contains("fn a() {}"));
+    }
+
+    #[test]
+    fn reports_a_missing_file_inline_instead_of_erroring() {
+        let dir = tempdir().unwrap();
+        let out = render_requested_files(dir.path(), &["missing.rs".to_string()]);
+        assert!(out.contains("does not exist"));
+    }
+
+    #[test]
+    fn refuses_to_escape_the_repo_root() {
+        let dir = tempdir().unwrap();
+        let out = render_requested_files(dir.path(), &["../secret".to_string()]);
+        assert!(out.contains("outside the workspace"));
+    }
+
+    #[test]
+    fn a_path_mentioned_in_notes_but_missing_from_the_known_list_is_flagged() {
+        let found = suspected_untracked_paths("I also updated src/extra.rs for the new helper.", &["src/lib.rs".to_string()]);
+        assert_eq!(found, vec!["src/extra.rs".to_string()]);
+    }
+
+    #[test]
+    fn a_path_that_is_already_known_is_not_flagged() {
+        let found = suspected_untracked_paths("Updated src/lib.rs with the new function.", &["src/lib.rs".to_string()]);
+        assert!(found.is_empty());
+    }
+
+    #[test]
+    fn a_bare_extension_or_non_tracked_extension_is_ignored() {
+        let found = suspected_untracked_paths("See the .rs file and notes.txt for details.", &[]);
+        assert!(found.is_empty());
+    }
+
+    #[test]
+    fn the_same_untracked_path_mentioned_twice_is_reported_once() {
+        let found = suspected_untracked_paths("src/extra.rs needs work. Don't forget src/extra.rs!", &[]);
+        assert_eq!(found, vec!["src/extra.rs".to_string()]);
+    }
+}
@@ -0,0 +1,186 @@
+//! Scans an edit plan for pathological Unicode (bidi overrides,
+//! zero-width characters, identifiers mixing normalization forms) before
+//! it's written to disk, with the same severity shape as
+//! [`crate::secret_scan`] plus a strip mode, since these — unlike a
+//! secret — can just be fixed in place.
+
+use crate::edit_plan::EditPlan;
+use std::fmt;
+use tdd_core::{UnicodeFinding, UnicodePolicy, UnicodeSeverity};
+
+/// A flagged character found in `path`, under [`UnicodeSeverity::Reject`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnicodeRejection {
+    pub path: String,
+    pub finding: UnicodeFinding,
+}
+
+impl fmt::Display for UnicodeRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "pathological Unicode in {}: {}", self.path, self.finding)
+    }
+}
+
+impl std::error::Error for UnicodeRejection {}
+
+/// A flagged character found under [`UnicodeSeverity::Warn`] or
+/// [`UnicodeSeverity::Strip`]: the write still goes ahead, but the caller
+/// should flag this loudly.
+#[derive(Debug)]
+pub struct UnicodeHit {
+    pub path: String,
+    pub finding: UnicodeFinding,
+}
+
+/// Scans every file in `plan` for pathological Unicode, applying
+/// `policy`'s per-class severity to each hit. The first hit whose class
+/// is [`UnicodeSeverity::Reject`] returns an `Err` before anything is
+/// written, with the offending code point (or, for a mixed-normalization
+/// identifier, the identifier itself) and byte offset named. Hits whose
+/// class is [`UnicodeSeverity::Strip`] are fixed in `plan` in place —
+/// the offending characters removed, or a mixed-normalization identifier
+/// renormalized to NFC; hits whose class is [`UnicodeSeverity::Warn`] are
+/// left alone. Either way, every non-rejected hit is returned for the
+/// caller to flag.
+pub fn scan_edit_plan(plan: &mut EditPlan, policy: UnicodePolicy) -> Result<Vec<UnicodeHit>, UnicodeRejection> {
+    let mut hits = Vec::new();
+
+    for edit in &mut plan.edits {
+        let findings = tdd_core::content_checks::scan(&edit.content);
+        if let Some(finding) = findings.iter().find(|finding| policy.severity_for(finding.char_class) == UnicodeSeverity::Reject) {
+            return Err(UnicodeRejection { path: edit.path.clone(), finding: finding.clone() });
+        }
+        if !findings.is_empty() {
+            edit.content = tdd_core::content_checks::strip_matching(&edit.content, |class| policy.severity_for(class) == UnicodeSeverity::Strip);
+            hits.extend(findings.into_iter().map(|finding| UnicodeHit { path: edit.path.clone(), finding }));
+        }
+
+        let identifier_findings = tdd_core::content_checks::scan_identifiers(&edit.content);
+        if let Some(finding) = identifier_findings.iter().find(|finding| policy.severity_for(finding.char_class) == UnicodeSeverity::Reject) {
+            return Err(UnicodeRejection { path: edit.path.clone(), finding: finding.clone() });
+        }
+        if !identifier_findings.is_empty() {
+            if identifier_findings.iter().any(|finding| policy.severity_for(finding.char_class) == UnicodeSeverity::Strip) {
+                edit.content = tdd_core::content_checks::normalize_identifiers(&edit.content);
+            }
+            hits.extend(identifier_findings.into_iter().map(|finding| UnicodeHit { path: edit.path.clone(), finding }));
+        }
+    }
+    Ok(hits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edit_plan::{EditAction, FileEdit};
+
+    fn plan_with(content: &str) -> EditPlan {
+        EditPlan {
+            edits: vec![FileEdit {
+                path: "tests/api.rs".to_string(),
+                action: EditAction::Upsert,
+                content: content.to_string(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn policy_of(bidi: UnicodeSeverity, zero_width: UnicodeSeverity) -> UnicodePolicy {
+        UnicodePolicy { bidi, zero_width, mixed_normalization: UnicodeSeverity::Warn }
+    }
+
+    #[test]
+    fn a_bidi_override_is_rejected_under_reject_severity() {
+        let mut plan = plan_with("let s = \"safe\u{202E}evil\";\n");
+
+        let error = scan_edit_plan(&mut plan, policy_of(UnicodeSeverity::Reject, UnicodeSeverity::Warn)).unwrap_err();
+
+        assert_eq!(error.path, "tests/api.rs");
+        assert_eq!(error.finding.code_point, 0x202E);
+    }
+
+    #[test]
+    fn strip_severity_rewrites_the_plan_and_reports_what_it_removed() {
+        let mut plan = plan_with("let s = \"safe\u{202E}evil\";\n");
+
+        let hits = scan_edit_plan(&mut plan, policy_of(UnicodeSeverity::Strip, UnicodeSeverity::Warn)).unwrap();
+
+        assert_eq!(hits.len(), 1);
+        assert_eq!(plan.edits[0].content, "let s = \"safeevil\";\n");
+    }
+
+    #[test]
+    fn warn_severity_reports_without_modifying_the_plan() {
+        let mut plan = plan_with("a\u{200D}b");
+
+        let hits = scan_edit_plan(&mut plan, policy_of(UnicodeSeverity::Reject, UnicodeSeverity::Warn)).unwrap();
+
+        assert_eq!(hits.len(), 1);
+        assert_eq!(plan.edits[0].content, "a\u{200D}b");
+    }
+
+    #[test]
+    fn clean_content_is_accepted_untouched() {
+        let mut plan = plan_with("use string_calculator::add;\n");
+        let original = plan.edits[0].content.clone();
+
+        assert!(scan_edit_plan(&mut plan, UnicodePolicy::default()).unwrap().is_empty());
+        assert_eq!(plan.edits[0].content, original);
+    }
+
+    #[test]
+    fn the_default_policy_rejects_a_bidi_override_but_only_warns_on_zero_width() {
+        let mut bidi_plan = plan_with("safe\u{202E}evil");
+        let error = scan_edit_plan(&mut bidi_plan, UnicodePolicy::default()).unwrap_err();
+        assert_eq!(error.finding.code_point, 0x202E);
+
+        let mut zero_width_plan = plan_with("a\u{200D}b");
+        let hits = scan_edit_plan(&mut zero_width_plan, UnicodePolicy::default()).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(zero_width_plan.edits[0].content, "a\u{200D}b");
+    }
+
+    fn policy_with_mixed_normalization(mixed_normalization: UnicodeSeverity) -> UnicodePolicy {
+        UnicodePolicy { bidi: UnicodeSeverity::Reject, zero_width: UnicodeSeverity::Warn, mixed_normalization }
+    }
+
+    #[test]
+    fn a_mixed_normalization_identifier_is_rejected_under_reject_severity() {
+        let mut plan = plan_with("let cafe\u{0301}_caf\u{00E9} = 1;\n");
+
+        let error = scan_edit_plan(&mut plan, policy_with_mixed_normalization(UnicodeSeverity::Reject)).unwrap_err();
+
+        assert_eq!(error.path, "tests/api.rs");
+        assert_eq!(error.finding.identifier.as_deref(), Some("cafe\u{0301}_caf\u{00E9}"));
+    }
+
+    #[test]
+    fn strip_severity_renormalizes_a_mixed_identifier_to_nfc() {
+        let mut plan = plan_with("let cafe\u{0301}_caf\u{00E9} = 1;\n");
+
+        let hits = scan_edit_plan(&mut plan, policy_with_mixed_normalization(UnicodeSeverity::Strip)).unwrap();
+
+        assert_eq!(hits.len(), 1);
+        assert_eq!(plan.edits[0].content, "let caf\u{00E9}_caf\u{00E9} = 1;\n");
+    }
+
+    #[test]
+    fn warn_severity_reports_a_mixed_identifier_without_modifying_the_plan() {
+        let mut plan = plan_with("let cafe\u{0301}_caf\u{00E9} = 1;\n");
+        let original = plan.edits[0].content.clone();
+
+        let hits = scan_edit_plan(&mut plan, policy_with_mixed_normalization(UnicodeSeverity::Warn)).unwrap();
+
+        assert_eq!(hits.len(), 1);
+        assert_eq!(plan.edits[0].content, original);
+    }
+
+    #[test]
+    fn an_identifier_consistently_in_one_normalization_form_is_not_flagged() {
+        let mut plan = plan_with("let caf\u{00E9} = 1;\n");
+
+        let hits = scan_edit_plan(&mut plan, policy_with_mixed_normalization(UnicodeSeverity::Reject)).unwrap();
+
+        assert!(hits.is_empty());
+    }
+}
@@ -0,0 +1,108 @@
+//! Golden-file coverage for [`tdd_agents::prompt_messages`]: every
+//! role×phase combination is rendered from a canonical `StepContext` and
+//! compared byte-for-byte against a checked-in snapshot under
+//! `tests/snapshots/`, so a change to prompt structure (a dropped,
+//! reordered, or reworded section) shows up as a reviewed diff instead of
+//! silently reaching an agent. Each role gets a "full" fixture (every
+//! optional section present) and a "minimal" one (every optional section
+//! absent).
+//!
+//! To accept an intentional change, rerun with `UPDATE_SNAPSHOTS=1` and
+//! review the resulting diff to the `.txt` files before committing it.
+
+use tdd_agents::prompts::{IMPLEMENTOR_SYSTEM_PROMPT, REFACTORER_SYSTEM_PROMPT, TESTER_SYSTEM_PROMPT};
+use tdd_agents::{edit_messages, plan_messages};
+use tdd_core::{Role, StepContext};
+use tdd_llm::Message;
+
+fn full_context(role: Role) -> StepContext {
+    StepContext {
+        role,
+        step_index: 3,
+        kata_description: "String Calculator: support comma-separated numbers.".to_string(),
+        git_last_commit_msg: "test: add failing test for an empty string".to_string(),
+        git_last_diff: "diff --git a/tests/api.rs b/tests/api.rs\n+fn empty_string_returns_zero() {\n+    assert_eq!(add(\"\"), 0);\n+}\n".to_string(),
+        repo_snapshot_paths: vec!["src/lib.rs".to_string(), "tests/api.rs".to_string()],
+        recently_changed_paths: vec!["tests/api.rs".to_string()],
+        file_list_limit: 30,
+        standing_instructions: "Never use unwrap in production code.".to_string(),
+        user_goal: Some("handle negative numbers".to_string()),
+        crate_name: Some("string_calculator".to_string()),
+        readonly_paths: vec!["contracts/**".to_string()],
+        previously_proposed: vec!["tester: write a failing test for whitespace-only input".to_string()],
+        since_last_turn: Some("files added: none; files modified: src/lib.rs; files removed: none; kata unchanged".to_string()),
+        attempt_index: 0,
+    }
+}
+
+fn minimal_context(role: Role) -> StepContext {
+    StepContext {
+        role,
+        step_index: 0,
+        kata_description: "String Calculator: support comma-separated numbers.".to_string(),
+        git_last_commit_msg: "chore: scaffold the kata".to_string(),
+        git_last_diff: String::new(),
+        repo_snapshot_paths: vec!["src/lib.rs".to_string()],
+        recently_changed_paths: Vec::new(),
+        file_list_limit: 30,
+        standing_instructions: String::new(),
+        user_goal: None,
+        crate_name: None,
+        readonly_paths: Vec::new(),
+        previously_proposed: Vec::new(),
+        since_last_turn: None,
+        attempt_index: 0,
+    }
+}
+
+fn render(messages: &[Message]) -> String {
+    messages.iter().map(|message| format!("--- {} ---\n{}", message.role, message.content)).collect::<Vec<_>>().join("\n\n")
+}
+
+/// Compares `actual` against `tests/snapshots/{name}.txt`, writing it
+/// instead of comparing when `UPDATE_SNAPSHOTS` is set.
+fn assert_snapshot(name: &str, actual: &str) {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots").join(format!("{name}.txt"));
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|error| panic!("missing snapshot {}: {error}; rerun with UPDATE_SNAPSHOTS=1 to create it", path.display()));
+    assert_eq!(actual, expected, "snapshot `{name}` changed; after reviewing the diff, rerun with UPDATE_SNAPSHOTS=1 to update {}", path.display());
+}
+
+macro_rules! snapshot_tests {
+    ($($name:ident: $role:expr, $system_prompt:expr;)*) => {
+        $(
+            mod $name {
+                use super::*;
+
+                #[test]
+                fn plan_full() {
+                    assert_snapshot(concat!(stringify!($name), "_plan_full"), &render(&plan_messages($system_prompt, &full_context($role))));
+                }
+
+                #[test]
+                fn plan_minimal() {
+                    assert_snapshot(concat!(stringify!($name), "_plan_minimal"), &render(&plan_messages($system_prompt, &minimal_context($role))));
+                }
+
+                #[test]
+                fn edit_full() {
+                    assert_snapshot(concat!(stringify!($name), "_edit_full"), &render(&edit_messages($system_prompt, &full_context($role))));
+                }
+
+                #[test]
+                fn edit_minimal() {
+                    assert_snapshot(concat!(stringify!($name), "_edit_minimal"), &render(&edit_messages($system_prompt, &minimal_context($role))));
+                }
+            }
+        )*
+    };
+}
+
+snapshot_tests! {
+    tester: Role::Tester, TESTER_SYSTEM_PROMPT;
+    implementor: Role::Implementor, IMPLEMENTOR_SYSTEM_PROMPT;
+    refactorer: Role::Refactorer, REFACTORER_SYSTEM_PROMPT;
+}
@@ -0,0 +1,76 @@
+//! Demonstrates a custom `Runner` that wraps another one with logging,
+//! e.g. to pipe fmt/check/test outcomes into a CI log.
+//!
+//! Run with `cargo run --example custom_runner`.
+
+use std::path::Path;
+use tdd_cli::init::{self, InitArgs};
+use tdd_core::{Runner, RunnerOutcome};
+use tdd_exec::CargoRunner;
+
+/// Wraps any [`Runner`] and logs each stage's outcome to stdout before
+/// returning it unchanged.
+pub struct LoggingRunner<R: Runner> {
+    inner: R,
+    label: &'static str,
+}
+
+impl<R: Runner> LoggingRunner<R> {
+    pub fn new(inner: R, label: &'static str) -> Self {
+        Self { inner, label }
+    }
+
+    fn log(&self, stage: &str, outcome: &RunnerOutcome) {
+        println!("[{}] {stage}: {}", self.label, if outcome.ok { "ok" } else { "failed" });
+    }
+}
+
+impl<R: Runner> Runner for LoggingRunner<R> {
+    fn fmt_check(&self) -> anyhow::Result<RunnerOutcome> {
+        let outcome = self.inner.fmt_check()?;
+        self.log("fmt_check", &outcome);
+        Ok(outcome)
+    }
+
+    fn fmt(&self) -> anyhow::Result<RunnerOutcome> {
+        let outcome = self.inner.fmt()?;
+        self.log("fmt", &outcome);
+        Ok(outcome)
+    }
+
+    fn check(&self) -> anyhow::Result<RunnerOutcome> {
+        let outcome = self.inner.check()?;
+        self.log("check", &outcome);
+        Ok(outcome)
+    }
+
+    fn test(&self) -> anyhow::Result<RunnerOutcome> {
+        let outcome = self.inner.test()?;
+        self.log("test", &outcome);
+        Ok(outcome)
+    }
+}
+
+/// Runs fmt, check, and test against `repo_root` through a
+/// [`LoggingRunner`] wrapping the real [`CargoRunner`], returning the
+/// `test` stage's outcome.
+pub fn run_with_logging(repo_root: &Path) -> anyhow::Result<RunnerOutcome> {
+    let runner = LoggingRunner::new(CargoRunner::new(repo_root.to_path_buf()), "kata");
+    runner.fmt()?;
+    runner.check()?;
+    runner.test()
+}
+
+#[allow(dead_code)]
+fn main() -> anyhow::Result<()> {
+    let workspace = tempfile::tempdir()?;
+    init::run(&InitArgs {
+        path: workspace.path().to_path_buf(),
+        kata_url: None,
+    })?;
+
+    let outcome = run_with_logging(workspace.path())?;
+    println!("test stage ok: {}", outcome.ok);
+
+    Ok(())
+}
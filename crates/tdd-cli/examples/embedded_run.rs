@@ -0,0 +1,141 @@
+//! Demonstrates embedding the orchestrator in another program: build a
+//! throwaway kata workspace, wire it up with a hand-written `LlmClient`
+//! (no test-only mocks), and run a couple of steps through the public API.
+//!
+//! Run with `cargo run --example embedded_run`.
+
+use async_trait::async_trait;
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tdd_agents::agent_for_role;
+use tdd_cli::init::{self, InitArgs};
+use tdd_cli::orchestrator::LoopOrchestrator;
+use tdd_cli::status::{self, StatusReport};
+use tdd_core::{Agent, Orchestrator, Role};
+use tdd_exec::{CommitAuthor, GitVcs};
+use tdd_llm::{LlmClient, Message};
+
+/// A minimal [`LlmClient`] that plays back one scripted response per call.
+/// This is what a real integration would look like; `tdd_fixtures`'s
+/// `ScriptedLlmClient` does the same thing but is test-only.
+struct CannedLlmClient {
+    responses: Mutex<VecDeque<String>>,
+}
+
+impl CannedLlmClient {
+    fn new(responses: impl IntoIterator<Item = &'static str>) -> Self {
+        Self {
+            responses: Mutex::new(responses.into_iter().map(str::to_string).collect()),
+        }
+    }
+}
+
+#[async_trait]
+impl LlmClient for CannedLlmClient {
+    async fn chat(&self, _messages: Vec<Message>) -> anyhow::Result<String> {
+        self.responses
+            .lock()
+            .expect("responses mutex poisoned")
+            .pop_front()
+            .ok_or_else(|| anyhow::anyhow!("CannedLlmClient ran out of scripted responses"))
+    }
+}
+
+const TESTER_PLAN: &str = "Add a failing test asserting add(2, 3) == 5.";
+const TESTER_EDIT_PLAN: &str = "{\"edits\": [{\"path\": \"tests/add_test.rs\", \"action\": \"upsert\", \"content\": \"#[test]\\nfn adds_two_numbers() {\\n    assert_eq!(kata::add(2, 3), 5);\\n}\\n\"}]}";
+
+const IMPLEMENTOR_PLAN: &str = "Implement add() so the new test passes.";
+const IMPLEMENTOR_EDIT_PLAN: &str = "{\"edits\": [{\"path\": \"src/lib.rs\", \"action\": \"upsert\", \"content\": \"pub fn add(a: i32, b: i32) -> i32 {\\n    a + b\\n}\\n\"}]}";
+
+/// Builds a throwaway kata workspace, runs a tester step and an
+/// implementor step against it, and returns the run's status plus the
+/// commit message left by each step.
+pub async fn run_embedded_example(root: &Path) -> anyhow::Result<(StatusReport, Vec<String>)> {
+    init::run(&InitArgs {
+        path: root.to_path_buf(),
+        kata_url: None,
+    })?;
+    std::fs::write(root.join("kata.md"), "# Add two numbers\n\nImplement `add(a, b)` returning their sum.\n")?;
+    // A deliberately wrong stub, so the tester's failing test compiles
+    // against real code instead of a function that doesn't exist yet.
+    std::fs::write(root.join("src/lib.rs"), "pub fn add(_a: i32, _b: i32) -> i32 {\n    0\n}\n")?;
+
+    let agents: Vec<Box<dyn Agent>> = vec![
+        Box::new(agent_for_role(
+            Role::Tester,
+            Arc::new(CannedLlmClient::new([TESTER_PLAN, TESTER_EDIT_PLAN])),
+            root,
+            false,
+            false,
+            tdd_agents::ScanPolicy {
+                secret_scan: tdd_core::SecretScanMode::Error,
+                unicode_policy: tdd_core::UnicodePolicy::default(),
+                max_blob_bytes: 1024 * 1024,
+                large_blob_policy: tdd_core::LargeBlobPolicy::Reject,
+                readonly_paths: Vec::new(),
+                manifest_policy: tdd_core::ManifestPolicy::default(),
+            },
+            None,
+        )),
+        Box::new(agent_for_role(
+            Role::Implementor,
+            Arc::new(CannedLlmClient::new([IMPLEMENTOR_PLAN, IMPLEMENTOR_EDIT_PLAN])),
+            root,
+            false,
+            false,
+            tdd_agents::ScanPolicy {
+                secret_scan: tdd_core::SecretScanMode::Error,
+                unicode_policy: tdd_core::UnicodePolicy::default(),
+                max_blob_bytes: 1024 * 1024,
+                large_blob_policy: tdd_core::LargeBlobPolicy::Reject,
+                readonly_paths: Vec::new(),
+                manifest_policy: tdd_core::ManifestPolicy::default(),
+            },
+            None,
+        )),
+        Box::new(agent_for_role(
+            Role::Refactorer,
+            Arc::new(CannedLlmClient::new([])),
+            root,
+            false,
+            false,
+            tdd_agents::ScanPolicy {
+                secret_scan: tdd_core::SecretScanMode::Error,
+                unicode_policy: tdd_core::UnicodePolicy::default(),
+                max_blob_bytes: 1024 * 1024,
+                large_blob_policy: tdd_core::LargeBlobPolicy::Reject,
+                readonly_paths: Vec::new(),
+                manifest_policy: tdd_core::ManifestPolicy::default(),
+            },
+            None,
+        )),
+    ];
+
+    let vcs = Box::new(GitVcs::new(root, CommitAuthor::default()));
+    let kata_description = std::fs::read_to_string(root.join("kata.md"))?;
+    let mut orchestrator = LoopOrchestrator::new(agents, vcs, root.to_path_buf(), kata_description, 3)?;
+
+    let mut commits = Vec::new();
+    for _ in 0..2 {
+        orchestrator.next().await?;
+        commits.push(status::read_status(root)?.last_commit_message);
+    }
+
+    Ok((status::read_status(root)?, commits))
+}
+
+#[allow(dead_code)]
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let workspace = tempfile::tempdir()?;
+    let (status, commits) = run_embedded_example(workspace.path()).await?;
+
+    println!("steps completed: {}", status.step_count);
+    println!("last commit: {}", status.last_commit_message.trim());
+    for (index, message) in commits.iter().enumerate() {
+        println!("step {index} commit: {}", message.trim());
+    }
+
+    Ok(())
+}
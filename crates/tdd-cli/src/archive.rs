@@ -0,0 +1,132 @@
+//! Marks a finished kata as archived at `.tdd/state/archived.json`, so a
+//! long-since-completed workspace reads that way in `status` and refuses
+//! an accidental `run`/`step` instead of silently resuming.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// What `status`, `run`, and `doctor` need to know about an archived kata.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchiveRecord {
+    pub archived_at: chrono::DateTime<chrono::Utc>,
+    pub final_step_count: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl ArchiveRecord {
+    /// Renders the banner `status` leads with, e.g. "📦 Kata archived on
+    /// 2026-03-05 after 30 steps: shipped to prod".
+    pub fn format_banner(&self) -> String {
+        let note = self.note.as_deref().map(|note| format!(": {note}")).unwrap_or_default();
+        format!("📦 Kata archived on {} after {} steps{note}", self.archived_at.format("%Y-%m-%d"), self.final_step_count)
+    }
+}
+
+fn state_path(repo_root: &Path) -> PathBuf {
+    repo_root.join(".tdd").join("state").join("archived.json")
+}
+
+/// Writes the archive marker, creating `.tdd/state` if needed. Overwrites
+/// any marker already there, so re-archiving updates the note and step
+/// count instead of refusing.
+pub fn write(repo_root: &Path, final_step_count: u32, note: Option<String>) -> anyhow::Result<()> {
+    let path = state_path(repo_root);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let record = ArchiveRecord {
+        archived_at: chrono::Utc::now(),
+        final_step_count,
+        note,
+    };
+    std::fs::write(path, serde_json::to_string_pretty(&record)?)?;
+    Ok(())
+}
+
+/// Reads the archive marker, or `None` if this kata isn't archived.
+pub fn read(repo_root: &Path) -> anyhow::Result<Option<ArchiveRecord>> {
+    let path = state_path(repo_root);
+    if !path.exists() {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::from_str(&std::fs::read_to_string(path)?)?))
+}
+
+/// Removes the archive marker, restoring normal `run`/`step` behavior. A
+/// no-op if this kata isn't archived.
+pub fn clear(repo_root: &Path) -> anyhow::Result<()> {
+    let path = state_path(repo_root);
+    if path.exists() {
+        std::fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn an_unarchived_workspace_reads_as_none() {
+        let dir = tempdir().unwrap();
+        assert_eq!(read(dir.path()).unwrap(), None);
+    }
+
+    #[test]
+    fn a_written_marker_round_trips_through_read() {
+        let dir = tempdir().unwrap();
+        write(dir.path(), 30, Some("shipped to prod".to_string())).unwrap();
+
+        let record = read(dir.path()).unwrap().unwrap();
+        assert_eq!(record.final_step_count, 30);
+        assert_eq!(record.note, Some("shipped to prod".to_string()));
+    }
+
+    #[test]
+    fn clear_removes_the_marker() {
+        let dir = tempdir().unwrap();
+        write(dir.path(), 30, None).unwrap();
+        clear(dir.path()).unwrap();
+
+        assert_eq!(read(dir.path()).unwrap(), None);
+    }
+
+    #[test]
+    fn clear_is_a_no_op_when_there_is_nothing_archived() {
+        let dir = tempdir().unwrap();
+        assert!(clear(dir.path()).is_ok());
+    }
+
+    #[test]
+    fn re_archiving_overwrites_the_previous_marker() {
+        let dir = tempdir().unwrap();
+        write(dir.path(), 10, None).unwrap();
+        write(dir.path(), 30, Some("shipped to prod".to_string())).unwrap();
+
+        let record = read(dir.path()).unwrap().unwrap();
+        assert_eq!(record.final_step_count, 30);
+        assert_eq!(record.note, Some("shipped to prod".to_string()));
+    }
+
+    #[test]
+    fn the_banner_includes_the_note_when_present() {
+        let record = ArchiveRecord {
+            archived_at: chrono::DateTime::parse_from_rfc3339("2026-03-05T00:00:00Z").unwrap().with_timezone(&chrono::Utc),
+            final_step_count: 30,
+            note: Some("shipped to prod".to_string()),
+        };
+        assert_eq!(record.format_banner(), "📦 Kata archived on 2026-03-05 after 30 steps: shipped to prod");
+    }
+
+    #[test]
+    fn the_banner_omits_the_colon_when_there_is_no_note() {
+        let record = ArchiveRecord {
+            archived_at: chrono::DateTime::parse_from_rfc3339("2026-03-05T00:00:00Z").unwrap().with_timezone(&chrono::Utc),
+            final_step_count: 30,
+            note: None,
+        };
+        assert_eq!(record.format_banner(), "📦 Kata archived on 2026-03-05 after 30 steps");
+    }
+}
@@ -0,0 +1,142 @@
+//! Parses `.tdd/plan/step-{n}-{slug}.md` and `.tdd/logs/step-{n}-{slug}.json`
+//! filenames back into a step number and role. Thin wrapper around
+//! [`tdd_core::artifacts`], which also knows about the `r<run_id>-`
+//! segment a re-executed step's filename carries; kept here so callers
+//! that only care about role slugs (not which run they're looking at)
+//! don't need to reach into `tdd_core` directly. Centralized so progress
+//! detection never silently drops an artifact just because its role slug
+//! isn't a built-in [`Role`] — a slug from a future custom-roles feature
+//! still advances step numbering, and callers can surface a warning for
+//! it instead of losing the artifact entirely.
+
+use tdd_core::artifacts;
+use tdd_core::Role;
+
+/// A `step-{n}-{slug}` filename, parsed back into its step number, role,
+/// and run-id. `role` is `None` when `slug` is well-formed but doesn't
+/// map to a built-in [`Role`], in which case `slug` still holds the full
+/// role name so step numbering can advance correctly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedArtifactName {
+    pub step: u32,
+    pub role: Option<Role>,
+    pub slug: String,
+    pub run_id: u32,
+}
+
+/// Parses a `.tdd/plan/step-{n}-{slug}.md` filename.
+pub fn parse_plan_filename(name: &str) -> Option<ParsedArtifactName> {
+    parse_step_artifact(name, ".md")
+}
+
+/// Parses a `.tdd/logs/step-{n}-{slug}.json` filename.
+pub fn parse_log_filename(name: &str) -> Option<ParsedArtifactName> {
+    parse_step_artifact(name, ".json")
+}
+
+fn parse_step_artifact(name: &str, extension: &str) -> Option<ParsedArtifactName> {
+    let parsed = artifacts::parse_name(name, extension)?;
+    Some(ParsedArtifactName {
+        step: parsed.step,
+        role: parsed.role,
+        slug: parsed.slug,
+        run_id: parsed.run_id,
+    })
+}
+
+/// A warning for an artifact filename whose role slug doesn't map to a
+/// built-in [`Role`]. Collected rather than printed so callers (e.g.
+/// `status`) decide how and whether to surface it.
+pub fn unrecognized_role_warning(parsed: &ParsedArtifactName) -> Option<String> {
+    if parsed.role.is_some() {
+        return None;
+    }
+    Some(format!(
+        "step {} was taken by an unrecognized role \"{}\"",
+        parsed.step, parsed.slug
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_plan_filenames_for_built_in_roles() {
+        assert_eq!(
+            parse_plan_filename("step-1-tester.md"),
+            Some(ParsedArtifactName {
+                step: 1,
+                role: Some(Role::Tester),
+                slug: "tester".to_string(),
+                run_id: 0,
+            })
+        );
+        assert_eq!(
+            parse_plan_filename("step-12-refactorer.md").unwrap().role,
+            Some(Role::Refactorer)
+        );
+    }
+
+    #[test]
+    fn parses_log_filenames_for_built_in_roles() {
+        assert_eq!(
+            parse_log_filename("step-3-implementor.json"),
+            Some(ParsedArtifactName {
+                step: 3,
+                role: Some(Role::Implementor),
+                slug: "implementor".to_string(),
+                run_id: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn parses_a_run_id_when_the_filename_carries_one() {
+        let parsed = parse_plan_filename("step-00007-r3-implementor.md").unwrap();
+        assert_eq!(parsed.step, 7);
+        assert_eq!(parsed.run_id, 3);
+        assert_eq!(parsed.role, Some(Role::Implementor));
+    }
+
+    #[test]
+    fn a_hyphenated_custom_role_slug_still_advances_step_numbering() {
+        let parsed = parse_plan_filename("step-004-code-reviewer.md").unwrap();
+        assert_eq!(parsed.step, 4);
+        assert_eq!(parsed.role, None);
+        assert_eq!(parsed.slug, "code-reviewer");
+    }
+
+    #[test]
+    fn a_hyphenated_custom_role_slug_is_parsed_from_log_filenames_too() {
+        let parsed = parse_log_filename("step-004-code-reviewer.json").unwrap();
+        assert_eq!(parsed.step, 4);
+        assert_eq!(parsed.role, None);
+        assert_eq!(parsed.slug, "code-reviewer");
+    }
+
+    #[test]
+    fn malformed_filenames_are_rejected() {
+        assert_eq!(parse_plan_filename("step-1-tester.json"), None);
+        assert_eq!(parse_plan_filename("notastep-1-tester.md"), None);
+        assert_eq!(parse_plan_filename("step-tester.md"), None);
+        assert_eq!(parse_plan_filename("step-1-.md"), None);
+        assert_eq!(parse_plan_filename("step-1.md"), None);
+        assert_eq!(parse_log_filename("step-1-tester.md"), None);
+    }
+
+    #[test]
+    fn unrecognized_role_warning_is_none_for_built_in_roles() {
+        let parsed = parse_plan_filename("step-1-tester.md").unwrap();
+        assert_eq!(unrecognized_role_warning(&parsed), None);
+    }
+
+    #[test]
+    fn unrecognized_role_warning_names_the_step_and_slug() {
+        let parsed = parse_plan_filename("step-4-code-reviewer.md").unwrap();
+        assert_eq!(
+            unrecognized_role_warning(&parsed),
+            Some("step 4 was taken by an unrecognized role \"code-reviewer\"".to_string())
+        );
+    }
+}
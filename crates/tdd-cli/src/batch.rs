@@ -0,0 +1,439 @@
+//! Implements `tdd-cli batch`: runs `--cycles` worth of steps over every
+//! workspace listed in a manifest, up to `--parallel` at once, and
+//! aggregates how each one ended — for pre-running the first cycle on a
+//! pile of starter repos ahead of a dojo session instead of looping
+//! `tdd-cli run` over them one at a time.
+//!
+//! There's no rate limiter or request budget anywhere in this codebase to
+//! share across workspaces (a single `llm_endpoints` connection is just a
+//! `reqwest::Client` — see [`crate::llm_endpoints`]); the only cross-workspace
+//! coordination here is the `--parallel` concurrency cap, via a
+//! [`tokio::sync::Semaphore`] shared across the spawned tasks.
+
+use crate::orchestrator::LoopOrchestrator;
+use crate::run_log::{self, StopReason};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::Semaphore;
+
+/// A manifest of workspace roots for `tdd-cli batch`, e.g.:
+/// `workspaces: [katas/one, katas/two]`. Relative entries resolve against
+/// the manifest file's own directory, not the current working directory,
+/// so a manifest committed alongside its workspaces works from anywhere.
+#[derive(Debug, Clone, Deserialize)]
+struct BatchManifest {
+    workspaces: Vec<PathBuf>,
+}
+
+/// Reads `path` and resolves every listed workspace relative to its
+/// parent directory.
+fn load_manifest(path: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let raw = std::fs::read_to_string(path).map_err(|error| anyhow::anyhow!("could not read {}: {error}", path.display()))?;
+    let manifest: BatchManifest = serde_yaml::from_str(&raw).map_err(|error| anyhow::anyhow!("{} is not a valid batch manifest: {error}", path.display()))?;
+    let base = path.parent().unwrap_or_else(|| Path::new("."));
+    Ok(manifest.workspaces.into_iter().map(|workspace| base.join(workspace)).collect())
+}
+
+/// How one workspace's batch run ended.
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchEntryReport {
+    pub workspace: PathBuf,
+    pub steps_executed: u32,
+    pub stop_reason: StopReason,
+    pub duration_secs: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_error_line: Option<String>,
+}
+
+/// The aggregate result of a `tdd-cli batch` invocation, in manifest
+/// order regardless of which workspace actually finished first.
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchReport {
+    pub entries: Vec<BatchEntryReport>,
+}
+
+impl BatchReport {
+    /// `tdd-cli batch`'s exit code is non-zero exactly when this is true:
+    /// one workspace's failure never aborts the others, but the overall
+    /// invocation still reports that something needs attention.
+    pub fn any_failed(&self) -> bool {
+        self.entries.iter().any(|entry| entry.stop_reason != StopReason::Completed)
+    }
+
+    /// Renders the table printed to stdout: workspace, steps executed,
+    /// stop reason, duration, and the first line of the error (if any).
+    pub fn render_table(&self) -> String {
+        let mut lines = vec!["workspace                               steps  stop_reason            duration  first_error".to_string()];
+        for entry in &self.entries {
+            lines.push(format!(
+                "{:<40}{:<7}{:<23}{:>7.1}s  {}",
+                entry.workspace.display(),
+                entry.steps_executed,
+                entry.stop_reason.to_string(),
+                entry.duration_secs,
+                entry.first_error_line.as_deref().unwrap_or(""),
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+fn first_line(text: &str) -> String {
+    text.lines().next().unwrap_or(text).to_string()
+}
+
+/// Runs `steps` steps through an already-built `orchestrator`, records
+/// `.tdd/state/last-run.json` the same way a plain `run` would, and
+/// reduces the outcome to one [`BatchEntryReport`] row. Shared by the
+/// real `--manifest` path (which builds `orchestrator` via
+/// [`LoopOrchestrator::from_workspace`]) and by tests (which build one
+/// directly from [`LoopOrchestrator::new`] with stub agents, so batch's
+/// concurrency and aggregation can be exercised without a real LLM).
+pub async fn run_entry(workspace: PathBuf, mut orchestrator: LoopOrchestrator, steps: u32) -> BatchEntryReport {
+    let started = Instant::now();
+    let config_hash = run_log::config_hash(&workspace).unwrap_or_default();
+    let (record, error) = run_log::execute_steps(&mut orchestrator, steps, config_hash, None).await;
+    let _ = run_log::record(&workspace, &record);
+    BatchEntryReport {
+        workspace,
+        steps_executed: record.steps_executed,
+        stop_reason: record.stop_reason,
+        duration_secs: started.elapsed().as_secs_f64(),
+        first_error_line: error.as_ref().map(|error| first_line(&error.to_string())),
+    }
+}
+
+/// Builds and runs `cycles` worth of steps for `workspace`, for the real
+/// `--manifest` path. A workspace whose orchestrator never builds (a
+/// read-only checkout, a bad `tdd.yaml`, an archived kata, ...) reports
+/// [`StopReason::AbortedBeforeStart`] rather than propagating the error,
+/// since one workspace's setup failure must not abort the batch.
+async fn run_workspace(workspace: PathBuf, cycles: u32) -> BatchEntryReport {
+    let started = Instant::now();
+    let run_args = crate::cli::RunArgs {
+        path: workspace.clone(),
+        steps: 0,
+        plan_only: false,
+        no_preflight: false,
+        commit_prefix: None,
+        review_branch: false,
+        auto_merge: false,
+        no_ff: false,
+        allow_stacked: false,
+        ignore_max_steps: false,
+        debug_unredacted_logs: false,
+        pair: false,
+        no_ci_cache: false,
+        goal: Vec::new(),
+        unarchive: false,
+        deterministic: false,
+    };
+    let orchestrator = match LoopOrchestrator::from_workspace(&run_args).await {
+        Ok(orchestrator) => orchestrator,
+        Err(error) => {
+            return BatchEntryReport {
+                workspace,
+                steps_executed: 0,
+                stop_reason: StopReason::AbortedBeforeStart,
+                duration_secs: started.elapsed().as_secs_f64(),
+                first_error_line: Some(first_line(&error.to_string())),
+            };
+        }
+    };
+    let steps = cycles.saturating_mul(orchestrator.required_roles().len() as u32);
+    run_entry(workspace, orchestrator, steps).await
+}
+
+/// Runs every job concurrently, at most `parallel` at a time, via one
+/// tokio task per job guarded by a shared [`Semaphore`]. A job that
+/// panics is treated as a defect in `batch` itself, not a workspace
+/// failure, so it's propagated rather than folded into the report.
+async fn run_many<F>(jobs: Vec<F>, parallel: usize) -> Vec<BatchEntryReport>
+where
+    F: Future<Output = BatchEntryReport> + Send + 'static,
+{
+    let semaphore = Arc::new(Semaphore::new(parallel.max(1)));
+    let handles: Vec<_> = jobs
+        .into_iter()
+        .map(|job| {
+            let semaphore = semaphore.clone();
+            tokio::spawn(async move {
+                let _permit = semaphore.acquire().await.expect("the semaphore is never closed");
+                job.await
+            })
+        })
+        .collect();
+
+    let mut reports = Vec::with_capacity(handles.len());
+    for handle in handles {
+        reports.push(handle.await.expect("a batch task panicked"));
+    }
+    reports
+}
+
+/// The `tdd-cli batch` entrypoint: loads the manifest, runs every listed
+/// workspace concurrently (up to `--parallel`), prints the aggregate
+/// table, writes it as JSON to `--out` if given, and fails the process
+/// (after printing) if any workspace didn't complete.
+pub async fn run(args: crate::cli::BatchArgs) -> anyhow::Result<()> {
+    let workspaces = load_manifest(&args.manifest)?;
+    let jobs: Vec<_> = workspaces.into_iter().map(|workspace| run_workspace(workspace, args.cycles)).collect();
+    let report = BatchReport { entries: run_many(jobs, args.parallel).await };
+
+    println!("{}", report.render_table());
+    if let Some(out) = &args.out {
+        std::fs::write(out, serde_json::to_string_pretty(&report)?)?;
+    }
+
+    if report.any_failed() {
+        anyhow::bail!("{} of {} workspaces did not complete", report.entries.iter().filter(|e| e.stop_reason != StopReason::Completed).count(), report.entries.len());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::time::Duration;
+    use tdd_core::{Agent, Role, RunnerOutcome, StepResult, Vcs};
+
+    struct StubAgent(Role);
+
+    #[async_trait]
+    impl Agent for StubAgent {
+        fn role(&self) -> Role {
+            self.0
+        }
+
+        async fn plan(&self, _ctx: &tdd_core::StepContext) -> anyhow::Result<String> {
+            Ok(String::new())
+        }
+
+        async fn edit(&self, _ctx: &tdd_core::StepContext) -> anyhow::Result<StepResult> {
+            Ok(StepResult::default())
+        }
+    }
+
+    fn stub_agents() -> Vec<Box<dyn Agent>> {
+        [Role::Tester, Role::Implementor, Role::Refactorer].into_iter().map(|role| Box::new(StubAgent(role)) as Box<dyn Agent>).collect()
+    }
+
+    struct RecordingVcs;
+
+    impl Vcs for RecordingVcs {
+        fn init_if_needed(&self) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn read_state(&self) -> anyhow::Result<tdd_core::RepoState> {
+            Ok(tdd_core::RepoState::default())
+        }
+
+        fn stage_all(&self) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn commit(&self, _message: &str) -> anyhow::Result<String> {
+            Ok("deadbeef".to_string())
+        }
+
+        fn diff_against_head(&self, _paths: &[String]) -> anyhow::Result<String> {
+            Ok(String::new())
+        }
+
+        fn discard_paths(&self, _paths: &[String]) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn head_commit_timestamp(&self) -> anyhow::Result<Option<std::time::SystemTime>> {
+            Ok(None)
+        }
+
+        fn recently_changed_paths(&self, _n_commits: usize) -> anyhow::Result<Vec<String>> {
+            Ok(Vec::new())
+        }
+
+        fn diff_range(&self, _from: Option<&str>, _to: &str) -> anyhow::Result<String> {
+            Ok(String::new())
+        }
+
+        fn diff_range_stat(&self, _from: Option<&str>, _to: &str) -> anyhow::Result<String> {
+            Ok(String::new())
+        }
+
+        fn create_branch_from(&self, _name: &str, _commit: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn checkout(&self, _name: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn merge_ff(&self, _name: &str, _no_ff: bool) -> anyhow::Result<String> {
+            Ok(String::new())
+        }
+
+        fn is_detached(&self) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+
+        fn head_commit_id(&self) -> anyhow::Result<String> {
+            unreachable!("RecordingVcs never snapshots/resets")
+        }
+
+        fn reset_hard(&self, _commit: &str) -> anyhow::Result<()> {
+            unreachable!("RecordingVcs never snapshots/resets")
+        }
+
+        fn is_clean(&self) -> anyhow::Result<bool> {
+            unreachable!("RecordingVcs never snapshots/resets")
+        }
+    }
+
+    /// Green everywhere except `test()`, which is red on its very first
+    /// call and green after — just enough for a full Tester ->
+    /// Implementor -> Refactorer cycle to complete.
+    struct RedThenGreenRunner {
+        calls: std::sync::atomic::AtomicU32,
+    }
+
+    impl RedThenGreenRunner {
+        fn new() -> Self {
+            Self { calls: std::sync::atomic::AtomicU32::new(0) }
+        }
+    }
+
+    impl tdd_core::Runner for RedThenGreenRunner {
+        fn fmt_check(&self) -> anyhow::Result<RunnerOutcome> {
+            Ok(RunnerOutcome { ok: true, ..Default::default() })
+        }
+
+        fn fmt(&self) -> anyhow::Result<RunnerOutcome> {
+            Ok(RunnerOutcome { ok: true, ..Default::default() })
+        }
+
+        fn check(&self) -> anyhow::Result<RunnerOutcome> {
+            Ok(RunnerOutcome { ok: true, ..Default::default() })
+        }
+
+        fn test(&self) -> anyhow::Result<RunnerOutcome> {
+            let call = self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(RunnerOutcome { ok: call > 0, ..Default::default() })
+        }
+    }
+
+    /// Always fails `check()`, so no role can ever verify.
+    struct AlwaysFailingCheckRunner;
+
+    impl tdd_core::Runner for AlwaysFailingCheckRunner {
+        fn fmt_check(&self) -> anyhow::Result<RunnerOutcome> {
+            Ok(RunnerOutcome { ok: true, ..Default::default() })
+        }
+
+        fn fmt(&self) -> anyhow::Result<RunnerOutcome> {
+            Ok(RunnerOutcome { ok: true, ..Default::default() })
+        }
+
+        fn check(&self) -> anyhow::Result<RunnerOutcome> {
+            Ok(RunnerOutcome { ok: false, ..Default::default() })
+        }
+
+        fn test(&self) -> anyhow::Result<RunnerOutcome> {
+            Ok(RunnerOutcome { ok: false, ..Default::default() })
+        }
+    }
+
+    fn green_orchestrator(repo_root: PathBuf) -> LoopOrchestrator {
+        LoopOrchestrator::new(stub_agents(), Box::new(RecordingVcs), repo_root, String::new(), 3)
+            .unwrap()
+            .with_runner(Box::new(RedThenGreenRunner::new()))
+    }
+
+    fn failing_orchestrator(repo_root: PathBuf) -> LoopOrchestrator {
+        LoopOrchestrator::new(stub_agents(), Box::new(RecordingVcs), repo_root, String::new(), 1)
+            .unwrap()
+            .with_runner(Box::new(AlwaysFailingCheckRunner))
+    }
+
+    #[test]
+    fn a_manifest_resolves_workspaces_relative_to_its_own_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("katas.yaml"), "workspaces: [one, two]\n").unwrap();
+
+        let workspaces = load_manifest(&dir.path().join("katas.yaml")).unwrap();
+        assert_eq!(workspaces, vec![dir.path().join("one"), dir.path().join("two")]);
+    }
+
+    #[test]
+    fn a_missing_manifest_is_a_clear_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let error = load_manifest(&dir.path().join("missing.yaml")).unwrap_err();
+        assert!(error.to_string().contains("missing.yaml"));
+    }
+
+    #[test]
+    fn the_table_lists_one_row_per_entry() {
+        let report = BatchReport {
+            entries: vec![
+                BatchEntryReport { workspace: PathBuf::from("a"), steps_executed: 3, stop_reason: StopReason::Completed, duration_secs: 1.2, first_error_line: None },
+                BatchEntryReport { workspace: PathBuf::from("b"), steps_executed: 0, stop_reason: StopReason::Failed, duration_secs: 0.1, first_error_line: Some("boom".to_string()) },
+            ],
+        };
+        let table = report.render_table();
+        assert_eq!(table.lines().count(), 3);
+        assert!(table.contains("boom"));
+        assert!(!report.entries.is_empty() && report.any_failed());
+    }
+
+    #[test]
+    fn any_failed_is_false_when_every_entry_completed() {
+        let report = BatchReport {
+            entries: vec![BatchEntryReport { workspace: PathBuf::from("a"), steps_executed: 3, stop_reason: StopReason::Completed, duration_secs: 1.2, first_error_line: None }],
+        };
+        assert!(!report.any_failed());
+    }
+
+    #[tokio::test]
+    async fn three_workspaces_run_with_one_induced_failure_and_correct_stop_reasons() {
+        let dirs: Vec<_> = (0..3).map(|_| tempfile::tempdir().unwrap()).collect();
+        let jobs = vec![
+            run_entry(dirs[0].path().to_path_buf(), green_orchestrator(dirs[0].path().to_path_buf()), 3),
+            run_entry(dirs[1].path().to_path_buf(), green_orchestrator(dirs[1].path().to_path_buf()), 3),
+            run_entry(dirs[2].path().to_path_buf(), failing_orchestrator(dirs[2].path().to_path_buf()), 3),
+        ];
+
+        let reports = run_many(jobs, 2).await;
+
+        assert_eq!(reports.len(), 3);
+        assert_eq!(reports[0].stop_reason, StopReason::Completed);
+        assert_eq!(reports[0].steps_executed, 3);
+        assert_eq!(reports[1].stop_reason, StopReason::Completed);
+        assert_eq!(reports[2].stop_reason, StopReason::Failed);
+        assert!(reports[2].first_error_line.is_some());
+    }
+
+    #[tokio::test]
+    async fn parallelism_makes_wall_clock_closer_to_two_runs_than_three() {
+        let per_job = Duration::from_millis(150);
+        let jobs: Vec<_> = (0..3)
+            .map(|_| async move {
+                tokio::time::sleep(per_job).await;
+                BatchEntryReport { workspace: PathBuf::from("x"), steps_executed: 1, stop_reason: StopReason::Completed, duration_secs: 0.0, first_error_line: None }
+            })
+            .collect();
+
+        let started = Instant::now();
+        let reports = run_many(jobs, 2).await;
+        let elapsed = started.elapsed();
+
+        assert_eq!(reports.len(), 3);
+        // Three jobs of 150ms at parallelism 2 take two "waves": ~300ms,
+        // not ~450ms (serial) and not ~150ms (unbounded parallel).
+        assert!(elapsed >= per_job * 2, "expected at least two waves, took {elapsed:?}");
+        assert!(elapsed < per_job * 3, "expected less than three serial waves, took {elapsed:?}");
+    }
+}
@@ -0,0 +1,264 @@
+//! Appends a generated `CHANGELOG.md` entry for a verified step, so a
+//! kata's history also reads as a teaching artifact rather than just a
+//! commit log. See [`crate::config::WorkspaceConfig::changelog`] and
+//! [`crate::orchestrator::LoopOrchestrator::next`], which writes the
+//! entry before the step's files are staged so it lands in the same
+//! commit.
+
+use crate::config::ChangelogStyle;
+use std::collections::HashMap;
+use std::path::Path;
+use tdd_core::{template, Role};
+use tdd_llm::{LlmClient, Message};
+
+const HEADER: &str = "# Changelog\n";
+
+const ENTRY_TEMPLATE: &str = "\n### Cycle {{cycle}} — {{summary}}\n\n{{notes}}\n\nFiles changed: {{files}}\n";
+
+/// Whether a step in `role` gets its own entry under `style`. `PerCycle`
+/// writes once per cycle, at the Implementor step where the cycle's
+/// behavior actually lands; `PerStep` writes for every role.
+pub fn should_append(style: ChangelogStyle, role: Role) -> bool {
+    match style {
+        ChangelogStyle::PerCycle => role == Role::Implementor,
+        ChangelogStyle::PerStep => true,
+    }
+}
+
+/// The highest `### Cycle N` heading already in `changelog`, or `0` if
+/// there are none yet.
+fn last_cycle_number(changelog: &str) -> u32 {
+    changelog
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("### Cycle "))
+        .filter_map(|rest| rest.split_whitespace().next())
+        .filter_map(|number| number.parse::<u32>().ok())
+        .max()
+        .unwrap_or(0)
+}
+
+/// The cycle number a step in `role` belongs to, derived from the
+/// existing `changelog` text rather than any separately persisted
+/// counter: a Tester step always starts a new cycle (the last one plus
+/// one, or cycle 1 if this is the first entry); every other role
+/// continues the most recently started cycle.
+fn cycle_number(changelog: &str, role: Role) -> u32 {
+    let last = last_cycle_number(changelog);
+    if role == Role::Tester || last == 0 {
+        last + 1
+    } else {
+        last
+    }
+}
+
+fn render_entry(cycle: u32, summary: &str, notes: &str, files_changed: &[String]) -> String {
+    let mut vars = HashMap::new();
+    vars.insert("cycle", cycle.to_string());
+    vars.insert("summary", summary.to_string());
+    let notes = if notes.trim().is_empty() { "(no notes)".to_string() } else { notes.trim().to_string() };
+    vars.insert("notes", notes);
+    let files = if files_changed.is_empty() { "(none)".to_string() } else { files_changed.join(", ") };
+    vars.insert("files", files);
+    template::render(ENTRY_TEMPLATE, &vars)
+}
+
+/// Rephrases `notes` through `client` for a reader skimming the kata's
+/// history, falling back to `notes` verbatim if the call fails — the
+/// same fallback shape as [`crate::kata_summary::summarize`].
+async fn polish(notes: &str, client: &dyn LlmClient) -> String {
+    if notes.trim().is_empty() {
+        return notes.to_string();
+    }
+    let messages = vec![
+        Message::system(
+            "Rephrase this changelog note for a reader skimming a kata's history. \
+             Keep it to one or two sentences and don't invent detail that isn't here.",
+        ),
+        Message::user(notes),
+    ];
+    match client.chat(messages).await {
+        Ok(polished) => polished,
+        Err(_) => notes.to_string(),
+    }
+}
+
+/// The part of a commit message worth putting in a changelog heading: its
+/// first line, with a conventional-commit type prefix (`"test: "`,
+/// `"feat: "`, ...) stripped if present.
+pub fn short_summary(commit_message: &str) -> &str {
+    let first_line = commit_message.lines().next().unwrap_or(commit_message);
+    match first_line.split_once(": ") {
+        Some((_, rest)) => rest,
+        None => first_line,
+    }
+}
+
+/// The step content going into one changelog entry, gathered up so
+/// [`append_entry`] doesn't need a long parameter list of its own.
+pub struct StepEntry<'a> {
+    pub role: Role,
+    pub summary: &'a str,
+    pub notes: &'a str,
+    pub files_changed: &'a [String],
+}
+
+/// Appends one entry to `repo_root.join(path)` for `entry`, creating the
+/// file with a `# Changelog` header first if it doesn't exist yet.
+/// `llm_client` is consulted only when `llm_polish` is set; on any
+/// failure (or when unset), the entry's notes are used verbatim. Must run
+/// before `Vcs::stage_all`, so the write lands inside the same commit as
+/// the step it documents.
+pub async fn append_entry(repo_root: &Path, path: &str, entry: StepEntry<'_>, llm_polish: bool, llm_client: Option<&dyn LlmClient>) -> anyhow::Result<()> {
+    let full_path = repo_root.join(path);
+    let mut existing = std::fs::read_to_string(&full_path).unwrap_or_default();
+    if existing.is_empty() {
+        existing.push_str(HEADER);
+    }
+
+    let cycle = cycle_number(&existing, entry.role);
+    let notes = match (llm_polish, llm_client) {
+        (true, Some(client)) => polish(entry.notes, client).await,
+        _ => entry.notes.to_string(),
+    };
+    existing.push_str(&render_entry(cycle, entry.summary, &notes, entry.files_changed));
+
+    if let Some(parent) = full_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(full_path, existing)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tdd_fixtures::ScriptedLlmClient;
+    use tempfile::tempdir;
+
+    #[test]
+    fn per_cycle_only_appends_on_the_implementor_step() {
+        assert!(!should_append(ChangelogStyle::PerCycle, Role::Tester));
+        assert!(should_append(ChangelogStyle::PerCycle, Role::Implementor));
+        assert!(!should_append(ChangelogStyle::PerCycle, Role::Refactorer));
+    }
+
+    #[test]
+    fn short_summary_strips_a_conventional_commit_type_prefix() {
+        assert_eq!(short_summary("test: add failing test for addition"), "add failing test for addition");
+        assert_eq!(short_summary("no prefix here"), "no prefix here");
+        assert_eq!(short_summary("feat: add addition\n\n- Role: implementor"), "add addition");
+    }
+
+    #[test]
+    fn per_step_always_appends() {
+        for role in [Role::Tester, Role::Implementor, Role::Refactorer] {
+            assert!(should_append(ChangelogStyle::PerStep, role));
+        }
+    }
+
+    #[test]
+    fn a_tester_step_starts_a_new_cycle_on_top_of_the_highest_existing_one() {
+        assert_eq!(cycle_number("", Role::Tester), 1);
+        assert_eq!(cycle_number("### Cycle 3 — done\n", Role::Tester), 4);
+    }
+
+    #[test]
+    fn an_implementor_or_refactorer_step_continues_the_latest_cycle() {
+        assert_eq!(cycle_number("### Cycle 2 — added a test\n", Role::Implementor), 2);
+        assert_eq!(cycle_number("", Role::Refactorer), 1);
+    }
+
+    #[tokio::test]
+    async fn a_first_entry_creates_the_file_with_a_header_and_the_entry() {
+        let dir = tempdir().unwrap();
+        append_entry(
+            dir.path(),
+            "CHANGELOG.md",
+            StepEntry { role: Role::Implementor, summary: "handles custom delimiters", notes: "added a regex split", files_changed: &["src/lib.rs".to_string()] },
+            false,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let written = std::fs::read_to_string(dir.path().join("CHANGELOG.md")).unwrap();
+        assert!(written.starts_with("# Changelog\n"));
+        assert!(written.contains("### Cycle 1 — handles custom delimiters"));
+        assert!(written.contains("added a regex split"));
+        assert!(written.contains("Files changed: src/lib.rs"));
+    }
+
+    #[tokio::test]
+    async fn a_later_entry_is_appended_after_the_existing_ones() {
+        let dir = tempdir().unwrap();
+        append_entry(
+            dir.path(),
+            "CHANGELOG.md",
+            StepEntry { role: Role::Implementor, summary: "first", notes: "", files_changed: &[] },
+            false,
+            None,
+        ).await.unwrap();
+        append_entry(
+            dir.path(),
+            "CHANGELOG.md",
+            StepEntry { role: Role::Tester, summary: "second", notes: "", files_changed: &[] },
+            false,
+            None,
+        ).await.unwrap();
+
+        let written = std::fs::read_to_string(dir.path().join("CHANGELOG.md")).unwrap();
+        assert!(written.contains("### Cycle 1 — first"));
+        assert!(written.contains("### Cycle 2 — second"));
+        assert!(written.find("Cycle 1").unwrap() < written.find("Cycle 2").unwrap());
+    }
+
+    #[tokio::test]
+    async fn notes_are_not_polished_when_llm_polish_is_off() {
+        let dir = tempdir().unwrap();
+        let client = ScriptedLlmClient::new(["polished version".to_string()]);
+        append_entry(
+            dir.path(),
+            "CHANGELOG.md",
+            StepEntry { role: Role::Implementor, summary: "x", notes: "raw notes", files_changed: &[] },
+            false,
+            Some(&client),
+        ).await.unwrap();
+
+        let written = std::fs::read_to_string(dir.path().join("CHANGELOG.md")).unwrap();
+        assert!(written.contains("raw notes"));
+        assert!(client.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn notes_are_polished_through_the_client_when_llm_polish_is_on() {
+        let dir = tempdir().unwrap();
+        let client = ScriptedLlmClient::new(["polished version".to_string()]);
+        append_entry(
+            dir.path(),
+            "CHANGELOG.md",
+            StepEntry { role: Role::Implementor, summary: "x", notes: "raw notes", files_changed: &[] },
+            true,
+            Some(&client),
+        ).await.unwrap();
+
+        let written = std::fs::read_to_string(dir.path().join("CHANGELOG.md")).unwrap();
+        assert!(written.contains("polished version"));
+        assert!(!written.contains("raw notes"));
+    }
+
+    #[tokio::test]
+    async fn a_failed_polish_falls_back_to_the_verbatim_notes() {
+        let dir = tempdir().unwrap();
+        let client = ScriptedLlmClient::new(Vec::<String>::new());
+        append_entry(
+            dir.path(),
+            "CHANGELOG.md",
+            StepEntry { role: Role::Implementor, summary: "x", notes: "raw notes", files_changed: &[] },
+            true,
+            Some(&client),
+        ).await.unwrap();
+
+        let written = std::fs::read_to_string(dir.path().join("CHANGELOG.md")).unwrap();
+        assert!(written.contains("raw notes"));
+    }
+}
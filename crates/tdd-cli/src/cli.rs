@@ -0,0 +1,504 @@
+//! The `tdd-cli` argument surface.
+
+use crate::completions::{CompletionKind, Shell};
+use crate::init::InitArgs;
+use crate::kata::KataRefreshArgs;
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+#[command(name = "tdd-cli", about = "An autonomous, multi-agent TDD machine for code katas")]
+pub struct Cli {
+    /// Increases trace verbosity; repeatable (`-v` for debug, `-vv` for
+    /// trace). Overridden by `TDD_LOG` when set.
+    #[arg(short = 'v', long = "verbose", action = clap::ArgAction::Count, global = true)]
+    pub verbose: u8,
+    /// Silences info-level trace output; errors still surface.
+    #[arg(short = 'q', long = "quiet", global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Initialize a new kata workspace and git repo.
+    Init(InitArgs),
+    /// Manage the kata description.
+    Kata {
+        #[command(subcommand)]
+        command: KataCommand,
+    },
+    /// Run N full TDD steps.
+    Run(RunArgs),
+    /// Run forever, waking on a timer to run a configured number of
+    /// cycles unattended.
+    Schedule(ScheduleArgs),
+    /// Run a single agent step (debug).
+    Step(StepArgs),
+    /// Undo the most recent step's commit, archiving its plan, log, and
+    /// files for a later `redo`.
+    Undo(WorkspaceArgs),
+    /// Re-apply the most recently undone step, re-verify it, and commit it
+    /// again with a `(redone)` marker.
+    Redo(WorkspaceArgs),
+    /// Run steps in an interactive terminal dashboard.
+    #[cfg(feature = "tui")]
+    Tui(RunArgs),
+    /// Show current agent, step counter, last commit summary.
+    Status(StatusArgs),
+    /// Verify tools, versions, environment.
+    Doctor(WorkspaceArgs),
+    /// Mark a finished kata as archived: `status` leads with a banner
+    /// instead of the next-step line, and `run`/`step` refuse until
+    /// `unarchive` clears the marker.
+    Archive(ArchiveArgs),
+    /// Clear an `archive` marker, restoring normal `run`/`step` behavior.
+    Unarchive(WorkspaceArgs),
+    /// Run `--cycles` worth of steps over every workspace listed in a
+    /// manifest, up to `--parallel` at once, and print an aggregate
+    /// table of how each one ended.
+    Batch(BatchArgs),
+    /// Rewrite stale absolute paths in `tdd.yaml` (left over from a moved
+    /// or renamed workspace root) back to the relative form.
+    RepairPaths(RepairPathsArgs),
+    /// Inspect and decide steps awaiting review under `review_mode: file`.
+    Review {
+        #[command(subcommand)]
+        command: ReviewCommand,
+    },
+    /// Inspect `tdd.yaml`.
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    /// Render an aggregate diff across part or all of a kata session,
+    /// instead of one patch per step.
+    Diff(DiffArgs),
+    /// Report `.tdd/`'s disk usage by category, optionally reclaiming
+    /// space from the transient ones.
+    Size(SizeArgs),
+    /// Removes abandoned scratch entries under `.tdd/` left behind by a
+    /// crashed or interrupted run.
+    Clean(CleanArgs),
+    /// Print a shell completion script to stdout.
+    Completions(CompletionsArgs),
+    /// Prints dynamic completion candidates (role slugs, logged step
+    /// indices) for the scripts `completions` generates. Not meant to be
+    /// run by hand.
+    #[command(hide = true)]
+    Complete(CompleteArgs),
+    /// Compares Tester prompt variants head to head: runs `--trials`
+    /// worth of isolated attempts at `--cycles` cycles under each
+    /// `--variants` prompt template, restoring a workspace snapshot
+    /// between attempts, then prints a comparison table.
+    Experiment(ExperimentArgs),
+    /// Export the kata session as a self-contained static HTML bundle —
+    /// a cycle timeline, one page per step, and a summary — for sharing
+    /// with stakeholders who'd rather not read raw JSON and diffs.
+    Export(ExportArgs),
+}
+
+impl Command {
+    /// The workspace path every subcommand operates on, used to load
+    /// `tdd.yaml` before dispatching so the tracing subscriber can be set
+    /// up ahead of any subcommand-specific logic.
+    pub fn workspace_path(&self) -> &std::path::Path {
+        match self {
+            Command::Init(args) => &args.path,
+            Command::Kata { command: KataCommand::Refresh(args) } => &args.path,
+            Command::Run(args) => &args.path,
+            Command::Schedule(args) => &args.path,
+            Command::Step(args) => &args.path,
+            Command::Undo(args) => &args.path,
+            Command::Redo(args) => &args.path,
+            #[cfg(feature = "tui")]
+            Command::Tui(args) => &args.path,
+            Command::Status(args) => &args.path,
+            Command::Doctor(args) => &args.path,
+            Command::Archive(args) => &args.path,
+            Command::Unarchive(args) => &args.path,
+            Command::Batch(args) => args.manifest.parent().unwrap_or(std::path::Path::new(".")),
+            Command::RepairPaths(args) => &args.path,
+            Command::Review { command: ReviewCommand::List(args) } => &args.path,
+            Command::Review { command: ReviewCommand::Decide(args) } => &args.path,
+            Command::Config { command: ConfigCommand::Show(args) } => &args.path,
+            Command::Diff(args) => &args.path,
+            Command::Size(args) => &args.path,
+            Command::Clean(args) => &args.path,
+            Command::Completions(args) => &args.path,
+            Command::Complete(args) => &args.path,
+            Command::Experiment(args) => &args.path,
+            Command::Export(args) => &args.path,
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum KataCommand {
+    /// Re-fetch the kata description from its recorded source URL.
+    Refresh(KataRefreshArgs),
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ReviewCommand {
+    /// List steps currently waiting on a review decision.
+    List(WorkspaceArgs),
+    /// Record a decision for a pending step review: "accept",
+    /// "reject: <reason>", or "edit-message: <new summary>".
+    Decide(ReviewDecideArgs),
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    /// Prints `tdd.yaml` as loaded, with every `extends:` fragment merged
+    /// in and every default filled in.
+    Show(ConfigShowArgs),
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct ConfigShowArgs {
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+    /// The only mode today: merges `extends:` fragments and fills in
+    /// defaults before printing. Reserved so a future `--raw` (the file
+    /// exactly as written) has something to contrast with.
+    #[arg(long)]
+    pub effective: bool,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct ReviewDecideArgs {
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+    pub step: u32,
+    pub decision: String,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct RunArgs {
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+    #[arg(long)]
+    pub steps: u32,
+    /// Only runs the planning phase for each step and writes the proposals
+    /// under `.tdd/plan/proposals/`, without editing, committing, or
+    /// running CI. A later real `run` ignores these proposals entirely.
+    #[arg(long)]
+    pub plan_only: bool,
+    /// Skips the preflight check that pings every configured model before
+    /// the first step, overriding `workspace.preflight` in `tdd.yaml`.
+    #[arg(long)]
+    pub no_preflight: bool,
+    /// A ticket reference (e.g. `KATA-123`) prepended to every commit
+    /// summary this run makes, overriding `commit.summary_prefix` in
+    /// `tdd.yaml`.
+    #[arg(long)]
+    pub commit_prefix: Option<String>,
+    /// Commits each red-green-refactor cycle to its own `tdd/cycle-{n}`
+    /// branch cut from the branch `run` started on, instead of committing
+    /// directly to it. See [`crate::cycle_branch`].
+    #[arg(long)]
+    pub review_branch: bool,
+    /// With `--review-branch`, merges each cycle branch back once it
+    /// completes instead of leaving it for manual review.
+    #[arg(long)]
+    pub auto_merge: bool,
+    /// With `--review-branch --auto-merge`, always creates a merge commit
+    /// instead of fast-forwarding.
+    #[arg(long)]
+    pub no_ff: bool,
+    /// With `--review-branch`, allows starting a new cycle branch while
+    /// the previous one is still awaiting review, instead of refusing.
+    #[arg(long)]
+    pub allow_stacked: bool,
+    /// Runs anyway when `workspace.max_steps` has already been reached or
+    /// exceeded, instead of refusing with a `MaxStepsReached` error. The
+    /// override is logged and recorded in the run summary.
+    #[arg(long)]
+    pub ignore_max_steps: bool,
+    /// Leaves CI output, step logs, and failure messages unredacted, for
+    /// debugging a credential or provider issue. Off by default: resolved
+    /// LLM credentials are scrubbed from everything this run writes.
+    #[arg(long)]
+    pub debug_unredacted_logs: bool,
+    /// Pauses after the Tester step instead of running the Implementor
+    /// agent, so a human can implement by hand, overriding
+    /// `workspace.pair_mode` in `tdd.yaml`.
+    #[arg(long)]
+    pub pair: bool,
+    /// Reruns every verification stage on every retry attempt instead of
+    /// reusing a passing stage's outcome when its inputs haven't
+    /// changed, overriding `ci.ci_cache` in `tdd.yaml`.
+    #[arg(long)]
+    pub no_ci_cache: bool,
+    /// Steers the very next step without editing `kata.md`, e.g.
+    /// `--goal "handle negative numbers"`. Repeatable; multiple flags
+    /// concatenate as bullets. Stored in `.tdd/state/next-goal.txt` and
+    /// consumed (the file deleted) once that step commits. See
+    /// [`crate::operator_goal`].
+    #[arg(long)]
+    pub goal: Vec<String>,
+    /// Clears an existing `.tdd/state/archived.json` marker instead of
+    /// refusing with a `KataArchived` error. See [`crate::archive`].
+    #[arg(long)]
+    pub unarchive: bool,
+    /// Forces `roles.<role>.retry_temperature_bump` to `0.0` for every
+    /// role regardless of `tdd.yaml`, so a retry resends the identical
+    /// request instead of escalating the sampling temperature.
+    #[arg(long)]
+    pub deterministic: bool,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct ScheduleArgs {
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+    /// Runs this long after the last scheduled run, e.g. `"24h"`,
+    /// `"90m"`, `"45s"`. Mutually exclusive with `--at`.
+    #[arg(long, conflicts_with = "at")]
+    pub every: Option<String>,
+    /// Runs at this UTC time every day, e.g. `"02:30"`. Mutually
+    /// exclusive with `--every`.
+    #[arg(long, conflicts_with = "every")]
+    pub at: Option<String>,
+    /// How many full red-green-refactor cycles to run at each tick.
+    #[arg(long, default_value_t = 1)]
+    pub cycles: u32,
+    /// Runs a single tick and exits, instead of running forever.
+    #[arg(long)]
+    pub once: bool,
+    /// Prints when the next tick would fire and exits without touching
+    /// the workspace, to sanity-check `--every`/`--at` before leaving
+    /// this running unattended. Requires `--once`.
+    #[arg(long, requires = "once")]
+    pub dry_run: bool,
+    /// Skips the preflight check that pings every configured model
+    /// before the orchestrator is built, overriding `workspace.preflight`
+    /// in `tdd.yaml`. See [`RunArgs::no_preflight`].
+    #[arg(long)]
+    pub no_preflight: bool,
+    /// A ticket reference (e.g. `KATA-123`) prepended to every commit
+    /// summary each scheduled run makes, overriding
+    /// `commit.summary_prefix` in `tdd.yaml`.
+    #[arg(long)]
+    pub commit_prefix: Option<String>,
+    /// Forces `roles.<role>.retry_temperature_bump` to `0.0` for every
+    /// scheduled tick. See [`RunArgs::deterministic`].
+    #[arg(long)]
+    pub deterministic: bool,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct WorkspaceArgs {
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct StatusArgs {
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+    /// Prints the locale-stable JSON snapshot (see
+    /// [`crate::status::StatusSnapshot`]) instead of the human-readable
+    /// text `status` normally prints.
+    #[arg(long)]
+    pub json: bool,
+    /// Keeps polling and, combined with `--json`, streams one NDJSON
+    /// line per refresh in which the snapshot actually changed, instead
+    /// of printing once and exiting. Requires `--json`, since there's no
+    /// sensible way to re-render the human text in place on every poll.
+    #[arg(long, requires = "json")]
+    pub watch: bool,
+    /// With `--watch`, also emits the current (possibly unchanged)
+    /// snapshot at least this often, in seconds, so a consumer watching
+    /// the stream for liveness doesn't mistake a quiet workspace for a
+    /// hung one. Unset means only changed snapshots are emitted.
+    #[arg(long, requires = "watch")]
+    pub heartbeat: Option<u64>,
+    /// How often `--watch` re-reads the workspace, in seconds. Defaults
+    /// to 2s; lower values cost more CPU for no real benefit, since
+    /// nothing in a kata workspace changes faster than a step commits.
+    #[arg(long, default_value = "2", requires = "watch")]
+    pub poll_interval: u64,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct RepairPathsArgs {
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+    /// Show what would change without writing `tdd.yaml`.
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct DiffArgs {
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+    /// Diffs from the parent of the first bot-authored commit to `HEAD`:
+    /// the net result of the whole session, even across refactors that
+    /// rewrote earlier steps. Mutually exclusive with `--from-step`/`--to-step`.
+    #[arg(long, conflicts_with_all = ["from_step", "to_step"])]
+    pub session: bool,
+    /// The step to start the range at (inclusive), resolved to a commit
+    /// id via its `"<type>: step <n>"` commit summary. Requires `--to-step`.
+    #[arg(long, requires = "to_step")]
+    pub from_step: Option<u32>,
+    /// The step to end the range at (inclusive). Requires `--from-step`.
+    #[arg(long, requires = "from_step")]
+    pub to_step: Option<u32>,
+    /// Prints only the `--stat`-style summary table instead of the full patch.
+    #[arg(long)]
+    pub stat: bool,
+    /// Writes the diff to a file instead of stdout.
+    #[arg(long)]
+    pub out: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct ExportArgs {
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+    /// Directory to write the static HTML bundle into. Created if it
+    /// doesn't exist; files already there with the same names are
+    /// overwritten.
+    #[arg(long)]
+    pub html: PathBuf,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct SizeArgs {
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+    /// Reclaims space from transient categories (oldest file first) down
+    /// to `workspace.max_tdd_dir_mb`, instead of only reporting.
+    #[arg(long)]
+    pub clean: bool,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct CleanArgs {
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+    /// Removes stale, unreferenced entries under `.tdd/tmp/`,
+    /// `.tdd/cache/staging/`, and `.tdd/logs/raw/` — the same pass `run`
+    /// makes at startup. The only mode today; required so a future
+    /// `clean` variant doesn't silently widen what gets deleted.
+    #[arg(long, required = true)]
+    pub transient: bool,
+    /// How old an unreferenced entry must be before it's considered
+    /// abandoned. Accepts the same duration syntax as `--every`, e.g.
+    /// `"6h"`, `"30m"`. Defaults to 24h.
+    #[arg(long)]
+    pub max_age: Option<String>,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct CompletionsArgs {
+    /// Unused by script generation itself; present so every subcommand
+    /// has a workspace path to load `tdd.yaml` from before dispatch.
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+    /// The shell to generate a completion script for.
+    pub shell: Shell,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct CompleteArgs {
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+    /// What kind of candidate to list: `role` or `step`.
+    pub kind: CompletionKind,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct StepArgs {
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+    /// A ticket reference (e.g. `KATA-123`) prepended to every commit
+    /// summary this step makes, overriding `commit.summary_prefix` in
+    /// `tdd.yaml`.
+    #[arg(long)]
+    pub commit_prefix: Option<String>,
+    /// Skips the Tester agent and commits this file as the next red step
+    /// instead, for when you already know exactly which test you want
+    /// next. Only valid on a Tester turn; the file must fail against the
+    /// current implementation.
+    #[arg(long)]
+    pub inject_test: Option<PathBuf>,
+    /// Where `--inject-test`'s file is written, relative to the workspace
+    /// root. Defaults to `tests/<file name>`.
+    #[arg(long, requires = "inject_test")]
+    pub dest: Option<PathBuf>,
+    /// Leaves CI output, step logs, and failure messages unredacted, for
+    /// debugging a credential or provider issue. Off by default: resolved
+    /// LLM credentials are scrubbed from everything this run writes.
+    #[arg(long)]
+    pub debug_unredacted_logs: bool,
+    /// Steers this step without editing `kata.md`. See [`RunArgs::goal`].
+    #[arg(long)]
+    pub goal: Vec<String>,
+    /// Clears an existing `.tdd/state/archived.json` marker instead of
+    /// refusing with a `KataArchived` error. See [`RunArgs::unarchive`].
+    #[arg(long)]
+    pub unarchive: bool,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct ArchiveArgs {
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+    /// A short free-text note recorded alongside the archive marker, e.g.
+    /// "shipped to prod".
+    #[arg(long)]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct BatchArgs {
+    /// A YAML file listing the workspace roots to run, e.g.:
+    /// `workspaces: [katas/one, katas/two]`. Each entry has its own
+    /// `tdd.yaml`, resolved relative to the manifest's own directory.
+    #[arg(long)]
+    pub manifest: PathBuf,
+    /// How many full red-green-refactor cycles to run per workspace.
+    #[arg(long, default_value_t = 1)]
+    pub cycles: u32,
+    /// How many workspaces to run at once; the rest queue until a slot
+    /// frees up.
+    #[arg(long, default_value_t = 1)]
+    pub parallel: usize,
+    /// Writes the aggregate [`crate::batch::BatchReport`] as JSON to this
+    /// path, in addition to the table printed to stdout.
+    #[arg(long)]
+    pub out: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct ExperimentArgs {
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+    /// Paths to the Tester prompt template files to compare, comma
+    /// separated, e.g. `--variants prompts/testerA.tmpl,prompts/testerB.tmpl`.
+    /// Each variant's file stem (`testerA`, `testerB`) labels its rows in
+    /// the comparison table and its entries in the raw trial log.
+    #[arg(long, value_delimiter = ',', required = true)]
+    pub variants: Vec<PathBuf>,
+    /// How many full red-green-refactor cycles to run per trial.
+    #[arg(long, default_value_t = 1)]
+    pub cycles: u32,
+    /// How many isolated attempts to run per variant. Trials are
+    /// interleaved across variants (trial 1 of every variant, then trial
+    /// 2 of every variant, ...) to spread out any drift over the course
+    /// of the experiment evenly across variants rather than
+    /// concentrating it on whichever variant runs last.
+    #[arg(long, default_value_t = 1)]
+    pub trials: u32,
+    /// Proceed even if the workspace has uncommitted changes. Every trial
+    /// (including the first) resets the workspace to `HEAD` and deletes
+    /// untracked files, so without this flag a dirty working tree is
+    /// refused rather than silently discarded.
+    #[arg(long)]
+    pub force: bool,
+}
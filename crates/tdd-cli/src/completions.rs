@@ -0,0 +1,200 @@
+//! Shell completion scripts for `tdd-cli`, plus the hidden `complete`
+//! subcommand those scripts shell back out to for candidates that can't be
+//! baked in statically: role slugs, and the step indices already logged
+//! in a workspace. There's no kata registry or `--kata`/`--profile` flag
+//! in this CLI to complete against, so dynamic hints are scoped to what
+//! actually exists rather than invented for this feature.
+//!
+//! Generation is hand-rolled instead of pulled from a `clap_complete`-style
+//! crate: the scripts only need to list subcommand names and shell out to
+//! [`print_candidates`], which doesn't need a templating dependency.
+
+use clap::ValueEnum;
+use std::path::Path;
+
+/// A shell to render a completion script for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "lower")]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    PowerShell,
+}
+
+/// What a hidden `complete` invocation is asked to list candidates for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "lower")]
+pub enum CompletionKind {
+    Role,
+    Step,
+}
+
+/// Role slugs recognized by `tdd_core::Role::from_slug`, for `--role`-style
+/// completion.
+pub fn role_candidates() -> Vec<String> {
+    ["tester", "implementor", "refactorer"].into_iter().map(str::to_string).collect()
+}
+
+/// Step indices with a log already recorded under `<path>/.tdd/logs`,
+/// ascending and deduplicated (a step logs once per attempt, not once per
+/// file). Returns an empty list rather than erroring when the directory
+/// is missing or unreadable — completion must never error.
+pub fn step_candidates(path: &Path) -> Vec<String> {
+    let dir = path.join(".tdd").join("logs");
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+
+    let mut steps: Vec<u32> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .filter_map(|name| name.strip_prefix("step-").and_then(|rest| rest.split('-').next()).and_then(|n| n.parse().ok()))
+        .collect();
+    steps.sort_unstable();
+    steps.dedup();
+    steps.into_iter().map(|n| n.to_string()).collect()
+}
+
+/// Prints one candidate per line for `kind`. Never errors: an unreadable
+/// or nonexistent workspace just yields no candidates, so a broken
+/// workspace never breaks the shell's tab completion.
+pub fn print_candidates(kind: CompletionKind, path: &Path) {
+    let candidates = match kind {
+        CompletionKind::Role => role_candidates(),
+        CompletionKind::Step => step_candidates(path),
+    };
+    for candidate in candidates {
+        println!("{candidate}");
+    }
+}
+
+/// Renders the completion script for `shell`, listing every top-level
+/// subcommand name from `command` and wiring `--role`/step-index
+/// arguments to call back into `tdd-cli complete <kind>`.
+pub fn render(shell: Shell, command: &clap::Command) -> String {
+    let subcommands: Vec<&str> = command.get_subcommands().map(clap::Command::get_name).collect();
+    match shell {
+        Shell::Bash => render_bash(&subcommands),
+        Shell::Zsh => render_zsh(&subcommands),
+        Shell::Fish => render_fish(&subcommands),
+        Shell::PowerShell => render_powershell(&subcommands),
+    }
+}
+
+fn render_bash(subcommands: &[&str]) -> String {
+    format!(
+        r#"_tdd_cli() {{
+    local cur prev
+    cur="${{COMP_WORDS[COMP_CWORD]}}"
+    prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+
+    case "$prev" in
+        --role)
+            COMPREPLY=($(compgen -W "$(tdd-cli complete role)" -- "$cur"))
+            return
+            ;;
+        show|replay|why)
+            COMPREPLY=($(compgen -W "$(tdd-cli complete step)" -- "$cur"))
+            return
+            ;;
+    esac
+
+    COMPREPLY=($(compgen -W "{subcommands}" -- "$cur"))
+}}
+complete -F _tdd_cli tdd-cli
+"#,
+        subcommands = subcommands.join(" ")
+    )
+}
+
+fn render_zsh(subcommands: &[&str]) -> String {
+    format!(
+        r#"#compdef tdd-cli
+
+_tdd_cli() {{
+    local -a subcommands
+    subcommands=({subcommands})
+
+    case "$words[2]" in
+        show|replay|why)
+            compadd -- $(tdd-cli complete step)
+            ;;
+        *)
+            if [[ "$words[CURRENT-1]" == "--role" ]]; then
+                compadd -- $(tdd-cli complete role)
+            else
+                compadd -- $subcommands
+            fi
+            ;;
+    esac
+}}
+compdef _tdd_cli tdd-cli
+"#,
+        subcommands = subcommands.join(" ")
+    )
+}
+
+fn render_fish(subcommands: &[&str]) -> String {
+    let mut script = String::new();
+    for subcommand in subcommands {
+        script.push_str(&format!("complete -c tdd-cli -f -n '__fish_use_subcommand' -a {subcommand}\n"));
+    }
+    script.push_str("complete -c tdd-cli -f -l role -a '(tdd-cli complete role)'\n");
+    script.push_str("complete -c tdd-cli -f -n '__fish_seen_subcommand_from show replay why' -a '(tdd-cli complete step)'\n");
+    script
+}
+
+fn render_powershell(subcommands: &[&str]) -> String {
+    format!(
+        r#"Register-ArgumentCompleter -Native -CommandName tdd-cli -ScriptBlock {{
+    param($wordToComplete, $commandAst, $cursorPosition)
+    $subcommands = @({subcommands})
+    $subcommands | Where-Object {{ $_ -like "$wordToComplete*" }} | ForEach-Object {{
+        [System.Management.Automation.CompletionResult]::new($_, $_, 'ParameterValue', $_)
+    }}
+}}
+"#,
+        subcommands = subcommands.iter().map(|s| format!("'{s}'")).collect::<Vec<_>>().join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Cli;
+    use clap::CommandFactory;
+
+    #[test]
+    fn every_shell_renders_without_panicking() {
+        let command = Cli::command();
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish, Shell::PowerShell] {
+            let script = render(shell, &command);
+            assert!(!script.is_empty());
+            assert!(script.contains("tdd-cli"));
+        }
+    }
+
+    #[test]
+    fn role_candidates_lists_the_three_builtin_roles() {
+        assert_eq!(role_candidates(), vec!["tester", "implementor", "refactorer"]);
+    }
+
+    #[test]
+    fn step_candidates_lists_logged_steps_ascending_and_deduplicated() {
+        let dir = tempfile::tempdir().unwrap();
+        let logs = dir.path().join(".tdd").join("logs");
+        std::fs::create_dir_all(&logs).unwrap();
+        std::fs::write(logs.join("step-2-implementor.json"), "{}").unwrap();
+        std::fs::write(logs.join("step-0-tester.json"), "{}").unwrap();
+        std::fs::write(logs.join("step-0-refactorer.json"), "{}").unwrap();
+
+        assert_eq!(step_candidates(dir.path()), vec!["0".to_string(), "2".to_string()]);
+    }
+
+    #[test]
+    fn step_candidates_is_empty_for_a_workspace_with_no_logs_directory_at_all() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(step_candidates(dir.path()), Vec::<String>::new());
+    }
+}
@@ -0,0 +1,769 @@
+//! Loads and saves `tdd.yaml`, the per-workspace configuration file.
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use crate::review::ReviewMode;
+use tdd_core::{CommandSpec, LargeBlobPolicy, ManifestPolicy, SecretScanMode, UnicodePolicy};
+use tdd_llm::{HttpConfig, LlmConnection, RoleModelConfig};
+
+/// Commands used to verify a step's edits, keyed by stage. Any stage may
+/// be set to `skip` (e.g. `check_cmd: skip`) to opt out entirely, rather
+/// than working around an empty command with a no-op like `["true"]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CiConfig {
+    #[serde(default = "CiConfig::default_test_cmd")]
+    pub test_cmd: CommandSpec,
+    #[serde(default = "CiConfig::default_check_cmd")]
+    pub check_cmd: CommandSpec,
+    #[serde(default = "CiConfig::default_fmt_cmd")]
+    pub fmt_cmd: CommandSpec,
+    /// When the test stage fails, re-run it up to this many more times and
+    /// treat it as passed if a re-run goes green, as long as none of the
+    /// failing tests belong to a file the step touched. 0 disables this.
+    #[serde(default)]
+    pub test_flaky_retries: u32,
+    /// How much of each stage's stdout/stderr stays resident before the
+    /// rest spills to `.tdd/logs/raw/`. Defaults to
+    /// [`tdd_core::DEFAULT_CAPTURE_LIMIT_BYTES`].
+    #[serde(default = "CiConfig::default_output_capture_limit_bytes")]
+    pub output_capture_limit_bytes: usize,
+    /// Between retry attempts at the same step, reuse a stage's previous
+    /// outcome instead of rerunning it when a content hash of its
+    /// inputs (the step's changed files, plus `Cargo.toml`/`Cargo.lock`
+    /// for check/test) matches the attempt it last passed on. Never
+    /// reuses a stage that previously failed. Overridden off by
+    /// `--no-ci-cache`. Defaults to on. See
+    /// [`tdd_exec::hash_stage_inputs`].
+    #[serde(default = "CiConfig::default_ci_cache")]
+    pub ci_cache: bool,
+}
+
+impl CiConfig {
+    fn default_test_cmd() -> CommandSpec {
+        CommandSpec::Command(vec!["cargo".into(), "test".into(), "--all".into()])
+    }
+
+    fn default_check_cmd() -> CommandSpec {
+        CommandSpec::Command(vec!["cargo".into(), "clippy".into(), "--all".into(), "--".into(), "-D".into(), "warnings".into()])
+    }
+
+    fn default_fmt_cmd() -> CommandSpec {
+        CommandSpec::Command(vec!["cargo".into(), "fmt".into()])
+    }
+
+    fn default_output_capture_limit_bytes() -> usize {
+        tdd_core::DEFAULT_CAPTURE_LIMIT_BYTES
+    }
+
+    fn default_ci_cache() -> bool {
+        true
+    }
+
+    /// Rejects a configuration that skips every stage — a kata with no
+    /// verification left isn't "fast", it's unverified.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if !self.test_cmd.is_enabled() && !self.check_cmd.is_enabled() && !self.fmt_cmd.is_enabled() {
+            anyhow::bail!("ci: at least one of test_cmd, check_cmd, fmt_cmd must stay enabled");
+        }
+        Ok(())
+    }
+}
+
+impl Default for CiConfig {
+    fn default() -> Self {
+        Self {
+            test_cmd: Self::default_test_cmd(),
+            check_cmd: Self::default_check_cmd(),
+            fmt_cmd: Self::default_fmt_cmd(),
+            test_flaky_retries: 0,
+            output_capture_limit_bytes: Self::default_output_capture_limit_bytes(),
+            ci_cache: Self::default_ci_cache(),
+        }
+    }
+}
+
+/// How often [`crate::changelog`] appends an entry as steps land.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangelogStyle {
+    /// One entry per red-green-refactor cycle, written when the
+    /// Implementor's step commits — the step where a cycle's behavior
+    /// actually lands.
+    #[default]
+    PerCycle,
+    /// One entry for every role's commit: Tester, Implementor, and
+    /// Refactorer alike.
+    PerStep,
+}
+
+/// A machine-written `CHANGELOG.md` that grows one entry per green step,
+/// so a kata's history reads as a teaching artifact rather than just a
+/// commit log. See [`crate::changelog`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangelogConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "ChangelogConfig::default_path")]
+    pub path: String,
+    #[serde(default)]
+    pub style: ChangelogStyle,
+    /// Runs the entry's notes through the refactorer model for phrasing
+    /// before appending, rather than using them verbatim. Falls back to
+    /// the verbatim notes if the call fails, the same way
+    /// [`crate::kata_summary::summarize`] falls back to its deterministic
+    /// extract. Defaults to off, since the plain notes are already
+    /// deterministic and reviewable.
+    #[serde(default)]
+    pub llm_polish: bool,
+}
+
+impl ChangelogConfig {
+    fn default_path() -> String {
+        "CHANGELOG.md".to_string()
+    }
+}
+
+impl Default for ChangelogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: Self::default_path(),
+            style: ChangelogStyle::default(),
+            llm_polish: false,
+        }
+    }
+}
+
+/// The default path (relative to the workspace root) of the standing-
+/// instructions file `tdd-cli init` scaffolds and every step's prompt
+/// includes when non-empty.
+pub const DEFAULT_CONTEXT_FILE: &str = ".tdd/context.md";
+
+/// Settings that shape how the orchestrator treats the working tree,
+/// independent of any single role.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceConfig {
+    /// When set, the Refactorer step is rejected as retryable if it
+    /// removes or reshapes a public item in `src/`.
+    #[serde(default)]
+    pub protect_public_api: bool,
+    /// A file (relative to the workspace root) whose contents are included
+    /// in every step's prompt, ahead of the kata description, for standing
+    /// instructions that shouldn't live in `kata.md` (naming conventions,
+    /// house style, and the like). Ignored when missing or empty.
+    #[serde(default = "default_context_file")]
+    pub context_file: String,
+    /// A file (relative to the workspace root) to additionally receive
+    /// JSON-lines trace output, rotated by size. Unset means trace output
+    /// only goes to stderr.
+    #[serde(default)]
+    pub log_file: Option<String>,
+    /// Rejects a Tester edit plan that `use`s the library crate under the
+    /// wrong name as a retryable error instead of letting it reach
+    /// `cargo test` as a compile error. Defaults to on, since it only
+    /// fires when the crate name was actually resolved.
+    #[serde(default = "default_lint_imports")]
+    pub lint_imports: bool,
+    /// Pings every distinct configured model with a minimal chat request
+    /// before the first step, aborting fast if any is unreachable instead
+    /// of discovering it after the baseline CI has already run. Defaults
+    /// to on; overridden off by `--no-preflight`.
+    #[serde(default = "default_preflight")]
+    pub preflight: bool,
+    /// Severity for secret-shaped tokens (API keys, private key headers, ...)
+    /// found in an agent-generated edit plan or its notes before they're
+    /// written and committed. Defaults to rejecting the step as retryable.
+    #[serde(default = "default_secret_scan")]
+    pub secret_scan: SecretScanMode,
+    /// Severity for bidirectional override characters, zero-width
+    /// characters, and identifiers mixing Unicode normalization forms
+    /// found in an agent-generated edit plan before it's written and
+    /// committed. Defaults to rejecting the step as retryable for a bidi
+    /// control and warning for a zero-width character or a mixed-
+    /// normalization identifier.
+    #[serde(default)]
+    pub unicode_policy: UnicodePolicy,
+    /// Lets the Tester's very first step (step 0) commit a test that
+    /// doesn't compile yet, as long as the check stage's only diagnostics
+    /// are unresolved-name errors (`E0425`/`E0433`) pointing at the test
+    /// file it just wrote — the shape of a type-driven kata that
+    /// intentionally starts from a compile error rather than a test
+    /// failure. fmt is still required to pass. Every other step keeps the
+    /// usual "check must pass" rule. Defaults to off.
+    #[serde(default)]
+    pub allow_initial_compile_failure: bool,
+    /// When set to `file`, a verified step doesn't commit immediately;
+    /// instead its proposed commit is written under `.tdd/review/` and the
+    /// orchestrator polls for a decision file, for asynchronous review by
+    /// someone not at the terminal when the machine runs. Defaults to off.
+    #[serde(default)]
+    pub review_mode: ReviewMode,
+    /// How long to poll for a review decision before aborting the step
+    /// cleanly and rolling back its edits. Ignored under `review_mode: off`.
+    #[serde(default = "default_review_timeout_secs")]
+    pub review_timeout_secs: u64,
+    /// When the kata description exceeds
+    /// [`crate::kata_summary::SUMMARIZE_THRESHOLD`], generate a structured
+    /// summary with the tester model and cache it at
+    /// `.tdd/state/kata-summary.md` instead of handing every prompt a
+    /// mid-sentence truncation of `kata.md`. Falls back to a deterministic
+    /// heading-and-list-items extract when off or when the summarization
+    /// call fails. Defaults to on.
+    #[serde(default = "default_summarize_long_kata")]
+    pub summarize_long_kata: bool,
+    /// An overall wall-clock ceiling on a single step, covering planning
+    /// through commit. When set and exceeded, the orchestrator discards
+    /// whatever uncommitted edits it knows about and fails the step
+    /// instead of letting it run unbounded. Checked at phase boundaries
+    /// and before each retry attempt, not enforced mid-phase. Unset (the
+    /// default) means no ceiling.
+    #[serde(default)]
+    pub max_step_duration_secs: Option<u64>,
+    /// The maximum number of paths the "Tracked files" prompt section
+    /// lists (ordered by category, then by recency) before collapsing the
+    /// rest into a trailing "...and N more" count.
+    #[serde(default = "default_file_list_limit")]
+    pub file_list_limit: usize,
+    /// A `.tdd/` size ceiling, in megabytes, above which `doctor` warns
+    /// and `tdd-cli size --clean` has something to reclaim. Unset (the
+    /// default) means no ceiling is enforced. See
+    /// [`crate::disk_usage`].
+    #[serde(default)]
+    pub max_tdd_dir_mb: Option<u64>,
+    /// Globs (see [`tdd_core::path_glob`]) naming paths an agent must
+    /// never write to — a provided interface the kata says is off
+    /// limits. Enforced at the edit-plan validation layer for every
+    /// role (see [`tdd_agents::readonly_guard`]) and listed under a "Do
+    /// not modify" heading in every step's prompt. Empty by default. A
+    /// glob matching nothing in the workspace is flagged by `doctor` as
+    /// a likely typo.
+    #[serde(default)]
+    pub readonly_paths: Vec<String>,
+    /// A ceiling, in KB, on any single file an edit plan writes or
+    /// modifies — past this, a generated fixture is more likely to be a
+    /// one-off the agent should produce at test time than something
+    /// worth paying for in every future clone. Deleted/renamed entries
+    /// have nothing to check, since an edit plan only ever upserts.
+    /// Defaults to 1024 (1 MB).
+    #[serde(default = "default_max_blob_kb")]
+    pub max_blob_kb: u64,
+    /// What happens when a file exceeds `max_blob_kb`: `reject` (the
+    /// default) fails the step as retryable, naming the offending paths
+    /// and sizes; `warn` commits anyway but flags it loudly in the commit
+    /// body and step log.
+    #[serde(default = "default_large_files")]
+    pub large_files: LargeBlobPolicy,
+    /// A lifetime ceiling on completed steps for this workspace, checked
+    /// against [`crate::status::read_status`]'s step count before a `run`
+    /// starts its first step. Unset (the default) means no ceiling.
+    /// Lowering this below what's already completed makes `run` refuse
+    /// to start (see [`crate::orchestrator::LoopOrchestrator::from_workspace`])
+    /// unless `--ignore-max-steps` is passed.
+    #[serde(default)]
+    pub max_steps: Option<u32>,
+    /// When set, an Implementor turn is never handed to the bot agent:
+    /// the orchestrator stops the run cleanly and waits for a human to
+    /// edit the tree by hand, committing (or acknowledging an existing
+    /// manual commit of) their change on the next `run` once check and
+    /// test are both green. Overridden on by `--pair`. Defaults to off.
+    /// See [`crate::orchestrator::LoopOrchestrator::resolve_pair_mode_implementor`].
+    #[serde(default)]
+    pub pair_mode: bool,
+    /// A generated `CHANGELOG.md` of the kata's history. See
+    /// [`crate::changelog`]. Defaults to disabled: existing workspaces
+    /// keep committing exactly what they do today until this is opted in.
+    #[serde(default)]
+    pub changelog: ChangelogConfig,
+    /// What happens when an edit plan's `Cargo.toml` flips the crate
+    /// edition or a `[profile.*]` setting. Defaults to rejecting both as
+    /// retryable; a dependency or package metadata change always passes
+    /// through. See [`tdd_core::manifest_guard`].
+    #[serde(default)]
+    pub manifest_policy: ManifestPolicy,
+}
+
+impl Default for WorkspaceConfig {
+    fn default() -> Self {
+        Self {
+            protect_public_api: false,
+            context_file: default_context_file(),
+            log_file: None,
+            lint_imports: default_lint_imports(),
+            preflight: default_preflight(),
+            secret_scan: default_secret_scan(),
+            unicode_policy: UnicodePolicy::default(),
+            allow_initial_compile_failure: false,
+            review_mode: ReviewMode::default(),
+            review_timeout_secs: default_review_timeout_secs(),
+            summarize_long_kata: default_summarize_long_kata(),
+            max_step_duration_secs: None,
+            file_list_limit: default_file_list_limit(),
+            max_tdd_dir_mb: None,
+            readonly_paths: Vec::new(),
+            max_blob_kb: default_max_blob_kb(),
+            large_files: default_large_files(),
+            max_steps: None,
+            pair_mode: false,
+            changelog: ChangelogConfig::default(),
+            manifest_policy: ManifestPolicy::default(),
+        }
+    }
+}
+
+/// The default for [`WorkspaceConfig::file_list_limit`], also used by
+/// [`crate::orchestrator::LoopOrchestrator`] when built directly rather
+/// than via [`crate::orchestrator::LoopOrchestrator::from_workspace`].
+pub const DEFAULT_FILE_LIST_LIMIT: usize = 30;
+
+fn default_file_list_limit() -> usize {
+    DEFAULT_FILE_LIST_LIMIT
+}
+
+fn default_context_file() -> String {
+    DEFAULT_CONTEXT_FILE.to_string()
+}
+
+fn default_lint_imports() -> bool {
+    true
+}
+
+fn default_preflight() -> bool {
+    true
+}
+
+fn default_secret_scan() -> SecretScanMode {
+    SecretScanMode::Error
+}
+
+fn default_max_blob_kb() -> u64 {
+    tdd_core::DEFAULT_MAX_BLOB_BYTES / 1024
+}
+
+fn default_large_files() -> LargeBlobPolicy {
+    LargeBlobPolicy::Reject
+}
+
+fn default_review_timeout_secs() -> u64 {
+    3600
+}
+
+fn default_summarize_long_kata() -> bool {
+    true
+}
+
+/// Author identity recorded on every bot commit, and how those commits
+/// are split.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitConfig {
+    pub author_name: String,
+    pub author_email: String,
+    /// When a step's fmt check fails and gets auto-fixed, record the fix as
+    /// its own `style: apply rustfmt` commit instead of folding it into the
+    /// step's commit.
+    #[serde(default)]
+    pub separate_fmt_commits: bool,
+    /// A ticket reference (e.g. `"KATA-123"`) prepended to every commit
+    /// summary, right after the conventional-commit type, so commits made
+    /// while practicing a kata comply with a host repo's commit-message
+    /// policy. Overridden per run by `--commit-prefix`. Unset by default.
+    #[serde(default)]
+    pub summary_prefix: Option<String>,
+    /// A `Co-authored-by: Name <email>` trailer appended to the commit
+    /// `tdd-cli step --inject-test` makes, crediting the human who wrote
+    /// the injected test. Unset (the default) leaves the commit without
+    /// a trailer.
+    #[serde(default)]
+    pub human_co_author: Option<String>,
+}
+
+impl Default for CommitConfig {
+    fn default() -> Self {
+        Self {
+            author_name: "TDD Machine".to_string(),
+            author_email: "tdd@local".to_string(),
+            separate_fmt_commits: false,
+            summary_prefix: None,
+            human_co_author: None,
+        }
+    }
+}
+
+/// A command that provisions the environment a kata needs — installing a
+/// toolchain version, pulling dependencies not vendored in the repo — run
+/// at most once per environment definition rather than on every run. See
+/// [`tdd_exec::BootstrapRunner`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BootstrapConfig {
+    /// The command to run, e.g. `["scripts/bootstrap.sh"]`. `skip` (the
+    /// default) means there's no provisioning step at all.
+    #[serde(default = "default_bootstrap_command")]
+    pub command: CommandSpec,
+    /// Where the marker recording the last successful run's environment
+    /// hash is written, relative to the workspace root.
+    #[serde(default = "default_bootstrap_marker_path")]
+    pub marker_path: String,
+}
+
+impl Default for BootstrapConfig {
+    fn default() -> Self {
+        Self {
+            command: CommandSpec::Skip,
+            marker_path: default_bootstrap_marker_path(),
+        }
+    }
+}
+
+fn default_bootstrap_command() -> CommandSpec {
+    CommandSpec::Skip
+}
+
+fn default_bootstrap_marker_path() -> String {
+    ".tdd/state/bootstrap-marker".to_string()
+}
+
+/// How the orchestrator treats `HEAD` and branches.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GitConfig {
+    /// What to do when `HEAD` is detached (e.g. a CI checkout at a PR
+    /// merge commit) before the first step. Defaults to refusing, since a
+    /// commit made there is unreachable as soon as the checkout ends.
+    #[serde(default)]
+    pub detached_head: crate::detached_head::DetachedHeadPolicy,
+    /// The branch `detached_head: branch` creates and checks out, e.g.
+    /// `ci/tdd-run`. Unset (the default) falls back to a generated
+    /// `tdd/run-<timestamp>` name, so every run lands on its own branch.
+    #[serde(default)]
+    pub detached_head_branch: Option<String>,
+    /// Whether a bot commit runs the repository's `pre-commit`/`commit-msg`
+    /// hooks first. Defaults to `bypass`, matching the behavior before
+    /// this existed (`GitVcs::commit` is `git2`-based and never ran
+    /// hooks on its own) — now explicit and audited rather than silent.
+    /// See [`crate::git_hooks`].
+    #[serde(default)]
+    pub hooks: crate::git_hooks::HooksPolicy,
+}
+
+/// The full contents of `tdd.yaml`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TddConfig {
+    pub kata_description: String,
+    #[serde(default = "default_language")]
+    pub language: String,
+    #[serde(default = "default_steps")]
+    pub steps: u32,
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts_per_agent: u32,
+    pub roles: HashMap<String, RoleModelConfig>,
+    pub llm: LlmConnection,
+    /// Named additional connections a role's `roles.<role>.endpoint` can
+    /// point at, for heterogeneous setups (e.g. a cheap local model for
+    /// the Tester and a hosted one for the Implementor). Empty by default,
+    /// in which case every role uses the legacy single `llm` block.
+    #[serde(default)]
+    pub llm_endpoints: HashMap<String, LlmConnection>,
+    /// The `llm_endpoints` entry a role with no `endpoint` set falls back
+    /// to, before falling back further to the legacy `llm` block.
+    #[serde(default)]
+    pub default_endpoint: Option<String>,
+    #[serde(default)]
+    pub ci: CiConfig,
+    #[serde(default)]
+    pub commit: CommitConfig,
+    #[serde(default)]
+    pub workspace: WorkspaceConfig,
+    #[serde(default)]
+    pub bootstrap: BootstrapConfig,
+    #[serde(default)]
+    pub git: GitConfig,
+}
+
+fn default_language() -> String {
+    "rust".to_string()
+}
+
+fn default_steps() -> u32 {
+    20
+}
+
+fn default_max_attempts() -> u32 {
+    5
+}
+
+impl Default for TddConfig {
+    fn default() -> Self {
+        let mut roles = HashMap::new();
+        roles.insert(
+            "tester".to_string(),
+            RoleModelConfig {
+                model: "openai:gpt-4.1-mini".to_string(),
+                temperature: 0.4,
+                endpoint: None,
+                retry_temperature_bump: 0.0,
+            },
+        );
+        roles.insert(
+            "implementor".to_string(),
+            RoleModelConfig {
+                model: "deepseek:coder-v2".to_string(),
+                temperature: 0.2,
+                endpoint: None,
+                retry_temperature_bump: 0.0,
+            },
+        );
+        roles.insert(
+            "refactorer".to_string(),
+            RoleModelConfig {
+                model: "glm:glm-4-air".to_string(),
+                temperature: 0.3,
+                endpoint: None,
+                retry_temperature_bump: 0.0,
+            },
+        );
+
+        Self {
+            kata_description: "kata.md".to_string(),
+            language: default_language(),
+            steps: default_steps(),
+            max_attempts_per_agent: default_max_attempts(),
+            roles,
+            llm: LlmConnection {
+                provider: "ollama".to_string(),
+                base_url: "http://localhost:11434/v1".to_string(),
+                api_key_env: "LLM_API_KEY".to_string(),
+                request_timeout_secs: 120,
+                connect_timeout_secs: 10,
+                allow_file_requests: false,
+                http: HttpConfig::default(),
+            },
+            llm_endpoints: HashMap::new(),
+            default_endpoint: None,
+            ci: CiConfig::default(),
+            commit: CommitConfig::default(),
+            workspace: WorkspaceConfig::default(),
+            bootstrap: BootstrapConfig::default(),
+            git: GitConfig::default(),
+        }
+    }
+}
+
+impl TddConfig {
+    /// Loads `path`, deep-merging any `extends:` fragments it names ahead
+    /// of its own contents before deserializing. See
+    /// [`load_effective_value`] for the merge semantics.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let merged = load_effective_value(path, &mut HashSet::new(), 0)?;
+        serde_yaml::from_value(merged).with_context(|| format!("{} (after merging any `extends` fragments) does not match the expected shape", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let raw = serde_yaml::to_string(self)?;
+        std::fs::write(path, raw)?;
+        Ok(())
+    }
+}
+
+/// How many `extends:` fragments deep [`load_effective_value`] will follow
+/// before giving up, as a backstop against a cycle slipping past
+/// [`load_effective_value`]'s own detection.
+const MAX_EXTENDS_DEPTH: usize = 8;
+
+/// Reads `path` and deep-merges every fragment its `extends:` key names
+/// (relative to `path`'s own directory) ahead of its own contents — a
+/// fragment's fields are overridden by anything `path` sets directly, a
+/// nested mapping (e.g. `ci`, `commit`) merges key by key rather than
+/// being replaced wholesale, and a sequence is replaced rather than
+/// appended. `extends` may be a single path or a list, resolved in order
+/// so a later fragment overrides an earlier one. `visited` tracks the
+/// canonical paths on the current include chain to reject a cycle; it is
+/// backtracked on return so the same fragment can still be reached via
+/// two different chains (a diamond include).
+fn load_effective_value(path: &Path, visited: &mut HashSet<PathBuf>, depth: usize) -> anyhow::Result<serde_yaml::Value> {
+    if depth > MAX_EXTENDS_DEPTH {
+        anyhow::bail!("extends chain through {} is more than {MAX_EXTENDS_DEPTH} fragments deep; check for a cycle", path.display());
+    }
+    let canonical = path.canonicalize().with_context(|| format!("could not find config fragment {}", path.display()))?;
+    if !visited.insert(canonical.clone()) {
+        anyhow::bail!("extends cycle detected: {} includes itself, directly or indirectly", path.display());
+    }
+
+    let raw = std::fs::read_to_string(path).with_context(|| format!("could not read config fragment {}", path.display()))?;
+    let mut value: serde_yaml::Value = serde_yaml::from_str(&raw).with_context(|| format!("{} is not valid YAML", path.display()))?;
+    let fragment_paths = take_extends(&mut value).with_context(|| format!("{} has an invalid `extends` key", path.display()))?;
+
+    let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let mut merged = serde_yaml::Value::Mapping(Default::default());
+    for fragment_path in fragment_paths {
+        let fragment = load_effective_value(&base_dir.join(&fragment_path), visited, depth + 1)?;
+        merged = merge_yaml(merged, fragment);
+    }
+    merged = merge_yaml(merged, value);
+
+    visited.remove(&canonical);
+    Ok(merged)
+}
+
+/// Removes and parses `value`'s top-level `extends` key, leaving `value`
+/// without it. Accepts a single string or a list of strings; anything
+/// else is an error.
+fn take_extends(value: &mut serde_yaml::Value) -> anyhow::Result<Vec<String>> {
+    let serde_yaml::Value::Mapping(map) = value else {
+        return Ok(Vec::new());
+    };
+    let Some(extends) = map.remove("extends") else {
+        return Ok(Vec::new());
+    };
+    match extends {
+        serde_yaml::Value::String(path) => Ok(vec![path]),
+        serde_yaml::Value::Sequence(paths) => paths
+            .into_iter()
+            .map(|entry| match entry {
+                serde_yaml::Value::String(path) => Ok(path),
+                other => anyhow::bail!("extends: expected a string path, found {other:?}"),
+            })
+            .collect(),
+        other => anyhow::bail!("extends: expected a string or a list of strings, found {other:?}"),
+    }
+}
+
+/// Merges `overlay` onto `base`: a mapping key present in both merges
+/// recursively when both sides are themselves mappings, and otherwise
+/// `overlay`'s value wins outright — this is what makes a sequence
+/// replace rather than append, and a scalar override rather than combine.
+fn merge_yaml(base: serde_yaml::Value, overlay: serde_yaml::Value) -> serde_yaml::Value {
+    match (base, overlay) {
+        (serde_yaml::Value::Mapping(mut base_map), serde_yaml::Value::Mapping(overlay_map)) => {
+            for (key, overlay_value) in overlay_map {
+                let merged_value = match base_map.remove(&key) {
+                    Some(base_value) => merge_yaml(base_value, overlay_value),
+                    None => overlay_value,
+                };
+                base_map.insert(key, merged_value);
+            }
+            serde_yaml::Value::Mapping(base_map)
+        }
+        (_, overlay) => overlay,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_skip_marker_parses_as_a_disabled_stage() {
+        let ci: CiConfig = serde_yaml::from_str("check_cmd: skip").unwrap();
+        assert_eq!(ci.check_cmd, CommandSpec::Skip);
+        assert!(!ci.check_cmd.is_enabled());
+        // Omitted stages still fall back to their defaults.
+        assert!(ci.test_cmd.is_enabled());
+        assert!(ci.fmt_cmd.is_enabled());
+    }
+
+    #[test]
+    fn an_explicit_command_list_parses_as_an_enabled_stage() {
+        let ci: CiConfig = serde_yaml::from_str("fmt_cmd: [cargo, fmt, --check]").unwrap();
+        assert_eq!(ci.fmt_cmd, CommandSpec::Command(vec!["cargo".into(), "fmt".into(), "--check".into()]));
+    }
+
+    #[test]
+    fn validate_passes_when_at_least_one_stage_is_enabled() {
+        let ci: CiConfig = serde_yaml::from_str("check_cmd: skip\nfmt_cmd: skip").unwrap();
+        assert!(ci.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_every_stage_skipped() {
+        let ci: CiConfig = serde_yaml::from_str("test_cmd: skip\ncheck_cmd: skip\nfmt_cmd: skip").unwrap();
+        assert!(ci.validate().is_err());
+    }
+
+    #[test]
+    fn a_config_with_no_llm_endpoints_section_defaults_to_none_configured() {
+        let config = TddConfig::default();
+        assert!(config.llm_endpoints.is_empty());
+        assert!(config.default_endpoint.is_none());
+    }
+
+    #[test]
+    fn llm_endpoints_and_a_per_role_endpoint_round_trip_through_yaml() {
+        let mut config = TddConfig::default();
+        config.llm_endpoints.insert(
+            "fast".to_string(),
+            LlmConnection {
+                provider: "openai".to_string(),
+                base_url: "https://api.openai.com/v1".to_string(),
+                api_key_env: "OPENAI_API_KEY".to_string(),
+                request_timeout_secs: 120,
+                connect_timeout_secs: 10,
+                allow_file_requests: false,
+                http: HttpConfig::default(),
+            },
+        );
+        config.default_endpoint = Some("fast".to_string());
+        config.roles.get_mut("tester").unwrap().endpoint = Some("fast".to_string());
+
+        let round_tripped: TddConfig = serde_yaml::from_str(&serde_yaml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(round_tripped.default_endpoint.as_deref(), Some("fast"));
+        assert_eq!(round_tripped.llm_endpoints["fast"].provider, "openai");
+        assert_eq!(round_tripped.roles["tester"].endpoint.as_deref(), Some("fast"));
+    }
+
+    #[test]
+    fn a_fragment_plus_an_override_produce_the_expected_merged_config() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("base.yaml"),
+            "kata_description: kata.md\nroles: {}\nllm: {provider: ollama, base_url: 'http://x', api_key_env: K}\nci:\n  test_flaky_retries: 3\ncommit:\n  author_name: Base Bot\n  author_email: base@local\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("tdd.yaml"),
+            "extends: base.yaml\nkata_description: kata.md\nroles: {}\nllm: {provider: ollama, base_url: 'http://x', api_key_env: K}\nci:\n  test_flaky_retries: 5\n",
+        )
+        .unwrap();
+
+        let config = TddConfig::load(&dir.path().join("tdd.yaml")).unwrap();
+
+        assert_eq!(config.ci.test_flaky_retries, 5);
+        assert_eq!(config.commit.author_name, "Base Bot");
+    }
+
+    #[test]
+    fn a_list_of_fragments_is_merged_in_order_with_the_main_file_winning() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.yaml"), "commit:\n  author_name: A\n  author_email: a@local\n").unwrap();
+        std::fs::write(dir.path().join("b.yaml"), "commit:\n  author_name: B\n  author_email: b@local\n").unwrap();
+        std::fs::write(
+            dir.path().join("tdd.yaml"),
+            "extends: [a.yaml, b.yaml]\nkata_description: kata.md\nroles: {}\nllm: {provider: ollama, base_url: 'http://x', api_key_env: K}\n",
+        )
+        .unwrap();
+
+        let config = TddConfig::load(&dir.path().join("tdd.yaml")).unwrap();
+
+        assert_eq!(config.commit.author_name, "B");
+    }
+
+    #[test]
+    fn an_extends_cycle_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.yaml"), "extends: b.yaml\n").unwrap();
+        std::fs::write(dir.path().join("b.yaml"), "extends: a.yaml\n").unwrap();
+
+        let error = TddConfig::load(&dir.path().join("a.yaml")).unwrap_err();
+
+        assert!(error.to_string().contains("cycle"), "expected a cycle error, got {error}");
+    }
+
+    #[test]
+    fn a_missing_fragment_names_itself_in_the_error() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("tdd.yaml"), "extends: missing.yaml\n").unwrap();
+
+        let error = TddConfig::load(&dir.path().join("tdd.yaml")).unwrap_err();
+
+        assert!(error.to_string().contains("missing.yaml"), "expected the missing fragment's name, got {error}");
+    }
+}
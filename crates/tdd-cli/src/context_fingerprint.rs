@@ -0,0 +1,197 @@
+//! Persists a compact fingerprint of each step's context under
+//! `.tdd/state/context/`, so the next time the same role takes a turn it
+//! can be told what changed since its own last turn rather than only
+//! since `HEAD`. Fingerprints hold only paths and hashes — never file
+//! contents — to keep them small.
+
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use tdd_core::Role;
+
+/// One step's fingerprint: the kata's hash plus a hash per tracked file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextFingerprint {
+    pub kata_hash: String,
+    pub files: Vec<FileFingerprint>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileFingerprint {
+    pub path: String,
+    pub hash: String,
+}
+
+fn hash_str(content: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// Builds the fingerprint for the current step from its kata description
+/// and tracked files, reading each file's content fresh off disk.
+pub fn fingerprint(kata_description: &str, repo_root: &Path, paths: &[String]) -> ContextFingerprint {
+    let files = paths
+        .iter()
+        .map(|path| {
+            let hash = std::fs::read_to_string(repo_root.join(path)).map(|content| hash_str(&content)).unwrap_or_default();
+            FileFingerprint { path: path.clone(), hash }
+        })
+        .collect();
+    ContextFingerprint { kata_hash: hash_str(kata_description), files }
+}
+
+fn context_dir(repo_root: &Path) -> std::path::PathBuf {
+    repo_root.join(".tdd").join("state").join("context")
+}
+
+/// Writes `fingerprint` to `.tdd/state/context/step-{step}-{role}.json`.
+pub fn write(repo_root: &Path, step: u32, role: Role, fingerprint: &ContextFingerprint) -> anyhow::Result<()> {
+    let dir = context_dir(repo_root);
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("step-{step}-{role}.json"));
+    std::fs::write(path, serde_json::to_string_pretty(fingerprint)?)?;
+    Ok(())
+}
+
+/// The most recently recorded fingerprint for `role` at a step strictly
+/// before `before_step`, or `None` if this is `role`'s first turn, or its
+/// earlier fingerprints were removed (e.g. by an undo or a cleaned
+/// `.tdd/state`).
+pub fn previous_for_role(repo_root: &Path, role: Role, before_step: u32) -> Option<ContextFingerprint> {
+    let entries = std::fs::read_dir(context_dir(repo_root)).ok()?;
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let step = parse_step(&name, role)?;
+            (step < before_step).then_some((step, entry.path()))
+        })
+        .max_by_key(|(step, _)| *step)
+        .and_then(|(_, path)| std::fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+fn parse_step(file_name: &str, role: Role) -> Option<u32> {
+    let suffix = format!("-{role}.json");
+    file_name.strip_prefix("step-")?.strip_suffix(&suffix)?.parse().ok()
+}
+
+/// Renders a one-line "since your last turn" summary from an earlier
+/// fingerprint to the current one: which tracked files were added,
+/// modified, or removed, and whether the kata description changed.
+pub fn describe_delta(previous: &ContextFingerprint, current: &ContextFingerprint) -> String {
+    let prev_hashes: HashMap<&str, &str> = previous.files.iter().map(|f| (f.path.as_str(), f.hash.as_str())).collect();
+    let curr_hashes: HashMap<&str, &str> = current.files.iter().map(|f| (f.path.as_str(), f.hash.as_str())).collect();
+
+    let mut added: Vec<&str> = curr_hashes.keys().filter(|path| !prev_hashes.contains_key(*path)).copied().collect();
+    let mut removed: Vec<&str> = prev_hashes.keys().filter(|path| !curr_hashes.contains_key(*path)).copied().collect();
+    let mut modified: Vec<&str> = curr_hashes
+        .iter()
+        .filter(|(path, hash)| prev_hashes.get(*path).is_some_and(|previous_hash| previous_hash != *hash))
+        .map(|(path, _)| *path)
+        .collect();
+    added.sort_unstable();
+    removed.sort_unstable();
+    modified.sort_unstable();
+
+    let kata_summary = if previous.kata_hash == current.kata_hash { "kata unchanged" } else { "kata changed" };
+
+    format!(
+        "files added: {}; files modified: {}; files removed: {}; {kata_summary}",
+        describe_paths(&added),
+        describe_paths(&modified),
+        describe_paths(&removed),
+    )
+}
+
+fn describe_paths(paths: &[&str]) -> String {
+    if paths.is_empty() {
+        "none".to_string()
+    } else {
+        paths.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn no_previous_fingerprint_is_found_on_a_fresh_workspace() {
+        let dir = tempdir().unwrap();
+        assert!(previous_for_role(dir.path(), Role::Tester, 3).is_none());
+    }
+
+    #[test]
+    fn a_fingerprint_round_trips_and_is_found_by_a_later_step_of_the_same_role() {
+        let dir = tempdir().unwrap();
+        let fp = fingerprint("kata", dir.path(), &[]);
+        write(dir.path(), 0, Role::Tester, &fp).unwrap();
+
+        let found = previous_for_role(dir.path(), Role::Tester, 3).unwrap();
+        assert_eq!(found.kata_hash, fp.kata_hash);
+    }
+
+    #[test]
+    fn a_fingerprint_for_a_different_role_is_not_matched() {
+        let dir = tempdir().unwrap();
+        let fp = fingerprint("kata", dir.path(), &[]);
+        write(dir.path(), 0, Role::Implementor, &fp).unwrap();
+
+        assert!(previous_for_role(dir.path(), Role::Tester, 3).is_none());
+    }
+
+    #[test]
+    fn only_a_step_strictly_before_the_current_one_is_considered() {
+        let dir = tempdir().unwrap();
+        let fp = fingerprint("kata", dir.path(), &[]);
+        write(dir.path(), 3, Role::Tester, &fp).unwrap();
+
+        assert!(previous_for_role(dir.path(), Role::Tester, 3).is_none());
+        assert!(previous_for_role(dir.path(), Role::Tester, 4).is_some());
+    }
+
+    #[test]
+    fn the_most_recent_matching_step_wins() {
+        let dir = tempdir().unwrap();
+        write(dir.path(), 0, Role::Tester, &fingerprint("kata", dir.path(), &[])).unwrap();
+        write(dir.path(), 3, Role::Tester, &fingerprint("kata v2", dir.path(), &[])).unwrap();
+
+        let found = previous_for_role(dir.path(), Role::Tester, 6).unwrap();
+        assert_eq!(found.kata_hash, hash_str("kata v2"));
+    }
+
+    #[test]
+    fn describe_delta_reports_added_modified_removed_and_kata_status() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("kept.rs"), "fn a() {}").unwrap();
+        std::fs::write(dir.path().join("removed.rs"), "fn b() {}").unwrap();
+        let previous = fingerprint("kata", dir.path(), &["kept.rs".to_string(), "removed.rs".to_string()]);
+
+        std::fs::write(dir.path().join("kept.rs"), "fn a() { /* changed */ }").unwrap();
+        std::fs::remove_file(dir.path().join("removed.rs")).unwrap();
+        std::fs::write(dir.path().join("added.rs"), "fn c() {}").unwrap();
+        let current = fingerprint("kata v2", dir.path(), &["kept.rs".to_string(), "added.rs".to_string()]);
+
+        let summary = describe_delta(&previous, &current);
+        assert!(summary.contains("files added: added.rs"));
+        assert!(summary.contains("files modified: kept.rs"));
+        assert!(summary.contains("files removed: removed.rs"));
+        assert!(summary.contains("kata changed"));
+    }
+
+    #[test]
+    fn describe_delta_reports_no_changes_when_nothing_moved() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("kept.rs"), "fn a() {}").unwrap();
+        let fp = fingerprint("kata", dir.path(), &["kept.rs".to_string()]);
+
+        let summary = describe_delta(&fp, &fp);
+        assert_eq!(summary, "files added: none; files modified: none; files removed: none; kata unchanged");
+    }
+}
@@ -0,0 +1,210 @@
+//! `--review-branch` mode: commits each red-green-refactor cycle to its
+//! own `tdd/cycle-{n}` branch cut from the integration branch, so a team
+//! can review a whole cycle as a unit instead of one step's diff at a
+//! time. State (which cycle is active, and whether it's been merged) is
+//! persisted at `.tdd/state/cycle-branches.json` so a later invocation
+//! can tell an unmerged cycle apart from one that already landed.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tdd_core::Vcs;
+
+/// One cycle's branch, in the order cycles were started.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CycleRecord {
+    pub index: u32,
+    pub branch: String,
+    pub merged: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CycleBranchState {
+    cycles: Vec<CycleRecord>,
+}
+
+impl CycleBranchState {
+    fn load(repo_root: &Path) -> Self {
+        std::fs::read_to_string(state_path(repo_root))
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, repo_root: &Path) -> anyhow::Result<()> {
+        let path = state_path(repo_root);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+fn state_path(repo_root: &Path) -> PathBuf {
+    repo_root.join(".tdd").join("state").join("cycle-branches.json")
+}
+
+/// What happened at the end of a cycle: either it was merged (or
+/// fast-forwarded) back onto the integration branch, or it was left on
+/// its own branch awaiting manual review.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CycleOutcome {
+    Merged { branch: String, commit: String },
+    AwaitingReview { branch: String },
+}
+
+/// Starts the next cycle: creates `tdd/cycle-{index:03}` off
+/// `integration_branch`'s tip and checks it out. Refuses to start a new
+/// cycle while the previous one is unmerged, unless `allow_stacked` is
+/// set.
+pub fn begin_cycle(repo_root: &Path, vcs: &dyn Vcs, integration_branch: &str, allow_stacked: bool) -> anyhow::Result<CycleRecord> {
+    let mut state = CycleBranchState::load(repo_root);
+
+    if let Some(previous) = state.cycles.last() {
+        if !previous.merged && !allow_stacked {
+            anyhow::bail!(
+                "cycle {} (branch {}) is unmerged; merge or review it, or pass --allow-stacked to start another cycle on top of it",
+                previous.index,
+                previous.branch
+            );
+        }
+    }
+
+    let index = state.cycles.last().map(|c| c.index + 1).unwrap_or(1);
+    let branch = format!("tdd/cycle-{index:03}");
+
+    vcs.checkout(integration_branch)?;
+    vcs.create_branch_from(&branch, integration_branch)?;
+    vcs.checkout(&branch)?;
+
+    let record = CycleRecord { index, branch, merged: false };
+    state.cycles.push(record.clone());
+    state.save(repo_root)?;
+    Ok(record)
+}
+
+/// Ends the active cycle: with `auto_merge`, checks out
+/// `integration_branch` and merges the cycle branch in (fast-forward
+/// unless `no_ff`), then records it as merged; without it, leaves the
+/// working tree on the cycle branch for manual review.
+pub fn end_cycle(repo_root: &Path, vcs: &dyn Vcs, integration_branch: &str, auto_merge: bool, no_ff: bool) -> anyhow::Result<CycleOutcome> {
+    let mut state = CycleBranchState::load(repo_root);
+    let Some(active) = state.cycles.last_mut() else {
+        anyhow::bail!("no cycle is active; call begin_cycle first");
+    };
+
+    if !auto_merge {
+        return Ok(CycleOutcome::AwaitingReview { branch: active.branch.clone() });
+    }
+
+    vcs.checkout(integration_branch)?;
+    let commit = vcs.merge_ff(&active.branch, no_ff)?;
+    active.merged = true;
+    let outcome = CycleOutcome::Merged { branch: active.branch.clone(), commit };
+    state.save(repo_root)?;
+    Ok(outcome)
+}
+
+/// `--review-branch` settings threaded through
+/// [`crate::run_log::execute_steps`], bundled together since they're only
+/// ever meaningful as a group.
+pub struct ReviewBranchOptions<'a> {
+    pub vcs: &'a dyn Vcs,
+    pub integration_branch: String,
+    pub auto_merge: bool,
+    pub no_ff: bool,
+    pub allow_stacked: bool,
+}
+
+/// Marks the most recently started cycle as merged without touching any
+/// branch, for when a reviewer merged it by hand (e.g. via a pull
+/// request) rather than through [`end_cycle`]'s `auto_merge`.
+pub fn mark_merged(repo_root: &Path, branch: &str) -> anyhow::Result<()> {
+    let mut state = CycleBranchState::load(repo_root);
+    let Some(record) = state.cycles.iter_mut().find(|c| c.branch == branch) else {
+        anyhow::bail!("no recorded cycle has branch {branch}");
+    };
+    record.merged = true;
+    state.save(repo_root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tdd_exec::{CommitAuthor, GitVcs};
+    use tempfile::tempdir;
+
+    fn commit_file(vcs: &GitVcs, repo_root: &Path, name: &str, contents: &str) {
+        std::fs::write(repo_root.join(name), contents).unwrap();
+        vcs.stage_all().unwrap();
+        vcs.commit(&format!("test: write {name}")).unwrap();
+    }
+
+    fn init_repo(repo_root: &Path) -> (GitVcs, String) {
+        let vcs = GitVcs::new(repo_root, CommitAuthor::default());
+        vcs.init_if_needed().unwrap();
+        crate::ignore_policy::apply(repo_root).unwrap();
+        commit_file(&vcs, repo_root, "kata.md", "# Kata\n");
+        let integration_branch = git2::Repository::open(repo_root).unwrap().head().unwrap().shorthand().unwrap().to_string();
+        (vcs, integration_branch)
+    }
+
+    #[test]
+    fn two_auto_merged_cycles_land_six_commits_on_the_integration_branch() {
+        let dir = tempdir().unwrap();
+        let (vcs, integration_branch) = init_repo(dir.path());
+
+        for cycle in 0..2 {
+            let record = begin_cycle(dir.path(), &vcs, &integration_branch, false).unwrap();
+            assert_eq!(record.branch, format!("tdd/cycle-{:03}", cycle + 1));
+            for step in 0..3 {
+                commit_file(&vcs, dir.path(), &format!("cycle-{cycle}-step-{step}.txt"), "x");
+            }
+            let outcome = end_cycle(dir.path(), &vcs, &integration_branch, true, false).unwrap();
+            assert!(matches!(outcome, CycleOutcome::Merged { .. }));
+        }
+
+        vcs.checkout(&integration_branch).unwrap();
+        let repo = git2::Repository::open(dir.path()).unwrap();
+        let mut revwalk = repo.revwalk().unwrap();
+        revwalk.push_head().unwrap();
+        let count = revwalk.count();
+        assert_eq!(count, 1 + 6);
+
+        let state = CycleBranchState::load(dir.path());
+        assert_eq!(state.cycles.len(), 2);
+        assert!(state.cycles.iter().all(|c| c.merged));
+    }
+
+    #[test]
+    fn a_second_cycle_is_refused_while_the_first_is_unmerged() {
+        let dir = tempdir().unwrap();
+        let (vcs, integration_branch) = init_repo(dir.path());
+
+        begin_cycle(dir.path(), &vcs, &integration_branch, false).unwrap();
+        commit_file(&vcs, dir.path(), "step.txt", "x");
+        let outcome = end_cycle(dir.path(), &vcs, &integration_branch, false, false).unwrap();
+        assert!(matches!(outcome, CycleOutcome::AwaitingReview { .. }));
+
+        let refused = begin_cycle(dir.path(), &vcs, &integration_branch, false);
+        assert!(refused.is_err());
+        assert!(refused.unwrap_err().to_string().contains("unmerged"));
+
+        mark_merged(dir.path(), "tdd/cycle-001").unwrap();
+        let second = begin_cycle(dir.path(), &vcs, &integration_branch, false).unwrap();
+        assert_eq!(second.branch, "tdd/cycle-002");
+    }
+
+    #[test]
+    fn allow_stacked_permits_starting_a_cycle_while_the_prior_one_is_unmerged() {
+        let dir = tempdir().unwrap();
+        let (vcs, integration_branch) = init_repo(dir.path());
+
+        begin_cycle(dir.path(), &vcs, &integration_branch, false).unwrap();
+        commit_file(&vcs, dir.path(), "step.txt", "x");
+        end_cycle(dir.path(), &vcs, &integration_branch, false, false).unwrap();
+
+        let stacked = begin_cycle(dir.path(), &vcs, &integration_branch, true).unwrap();
+        assert_eq!(stacked.branch, "tdd/cycle-002");
+    }
+}
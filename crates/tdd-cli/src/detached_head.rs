@@ -0,0 +1,127 @@
+//! Handles a `HEAD` that's detached (e.g. a CI checkout at a PR merge
+//! commit) before the orchestrator's first step. A commit made onto a
+//! detached `HEAD` is unreachable as soon as the checkout ends — nothing
+//! references it and it's eventually garbage-collected — so the default
+//! is to refuse outright rather than let a run "work" and quietly lose
+//! its results.
+
+use serde::{Deserialize, Serialize};
+use tdd_core::Vcs;
+
+/// How `run`/`step` responds to a detached `HEAD` at startup. See
+/// [`crate::config::GitConfig::detached_head`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DetachedHeadPolicy {
+    /// Refuse to start, with guidance on the fix.
+    #[default]
+    Refuse,
+    /// Create and check out a deterministic branch before the first step,
+    /// off the branch-creation primitives [`crate::cycle_branch`] also
+    /// uses, so the run's commits stay reachable and pushable by whatever
+    /// workflow step runs after it.
+    Branch,
+}
+
+/// The exact guidance text [`ensure_usable`] returns when refusing a
+/// detached `HEAD`, pulled out as its own function so a test can assert
+/// on it without duplicating the string.
+pub fn refuse_message() -> String {
+    "HEAD is detached; commits made here would be unreachable once this checkout ends. \
+Set `git.detached_head: branch` in tdd.yaml to create and check out a branch first, \
+or check out a branch yourself before running."
+        .to_string()
+}
+
+/// Checked once by
+/// [`crate::orchestrator::LoopOrchestrator::from_workspace`], before any
+/// step runs. Returns the branch name it checked out onto, if any —
+/// `None` when `HEAD` was already on a branch, so there was nothing to
+/// do.
+pub fn ensure_usable(vcs: &dyn Vcs, policy: DetachedHeadPolicy, configured_branch: Option<&str>, now: chrono::DateTime<chrono::Utc>) -> anyhow::Result<Option<String>> {
+    if !vcs.is_detached()? {
+        return Ok(None);
+    }
+
+    if policy == DetachedHeadPolicy::Refuse {
+        anyhow::bail!(refuse_message());
+    }
+
+    let branch = configured_branch.map(str::to_string).unwrap_or_else(|| default_branch_name(now));
+    vcs.create_branch_from(&branch, "HEAD")?;
+    vcs.checkout(&branch)?;
+    Ok(Some(branch))
+}
+
+fn default_branch_name(now: chrono::DateTime<chrono::Utc>) -> String {
+    format!("tdd/run-{}", now.format("%Y%m%d%H%M%S"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tdd_exec::{CommitAuthor, GitVcs};
+    use tempfile::tempdir;
+
+    fn init_repo(repo_root: &std::path::Path) -> GitVcs {
+        let vcs = GitVcs::new(repo_root, CommitAuthor::default());
+        vcs.init_if_needed().unwrap();
+        std::fs::write(repo_root.join("kata.md"), "# Kata\n").unwrap();
+        vcs.stage_all().unwrap();
+        vcs.commit("chore: initial scaffold").unwrap();
+        vcs
+    }
+
+    fn detach(repo_root: &std::path::Path) {
+        let repo = git2::Repository::open(repo_root).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap().id();
+        repo.set_head_detached(head).unwrap();
+    }
+
+    fn now() -> chrono::DateTime<chrono::Utc> {
+        "2026-08-08T10:00:00Z".parse().unwrap()
+    }
+
+    #[test]
+    fn a_branch_checkout_is_left_untouched() {
+        let dir = tempdir().unwrap();
+        let vcs = init_repo(dir.path());
+
+        let result = ensure_usable(&vcs, DetachedHeadPolicy::Refuse, None, now()).unwrap();
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn a_detached_head_is_refused_by_default_with_the_exact_guidance_text() {
+        let dir = tempdir().unwrap();
+        let vcs = init_repo(dir.path());
+        detach(dir.path());
+
+        let error = ensure_usable(&vcs, DetachedHeadPolicy::Refuse, None, now()).unwrap_err();
+        assert_eq!(error.to_string(), refuse_message());
+    }
+
+    #[test]
+    fn branch_policy_creates_and_checks_out_a_timestamped_branch_by_default() {
+        let dir = tempdir().unwrap();
+        let vcs = init_repo(dir.path());
+        detach(dir.path());
+
+        let branch = ensure_usable(&vcs, DetachedHeadPolicy::Branch, None, now()).unwrap();
+        assert_eq!(branch, Some("tdd/run-20260808100000".to_string()));
+        assert!(!vcs.is_detached().unwrap());
+
+        let repo = git2::Repository::open(dir.path()).unwrap();
+        assert_eq!(repo.head().unwrap().shorthand(), Some("tdd/run-20260808100000"));
+    }
+
+    #[test]
+    fn branch_policy_honors_a_configured_branch_name() {
+        let dir = tempdir().unwrap();
+        let vcs = init_repo(dir.path());
+        detach(dir.path());
+
+        let branch = ensure_usable(&vcs, DetachedHeadPolicy::Branch, Some("ci/tdd-run"), now()).unwrap();
+        assert_eq!(branch, Some("ci/tdd-run".to_string()));
+    }
+}
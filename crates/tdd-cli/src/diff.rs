@@ -0,0 +1,227 @@
+//! Implements `tdd-cli diff`: an aggregate view of the changes made across
+//! part or all of a kata session, as a single diff instead of one patch
+//! per step.
+//!
+//! Unlike [`tdd_core::Vcs::diff_against_head`], which diffs an in-progress
+//! step's uncommitted edits, this operates purely on already-committed
+//! history via [`tdd_core::Vcs::diff_range`].
+
+use crate::cli::DiffArgs;
+use std::path::Path;
+use tdd_core::Vcs;
+use tdd_exec::GitVcs;
+
+/// Runs `tdd-cli diff` end to end: resolves the range named by `args`,
+/// renders it (falling back to `--stat` past the size guard, same as
+/// `--stat` requested outright), and prepends a header naming the
+/// boundary commits.
+pub fn run(args: &DiffArgs, bot_author_email: &str) -> anyhow::Result<String> {
+    anyhow::ensure!(
+        args.session || (args.from_step.is_some() && args.to_step.is_some()),
+        "pass either --session or both --from-step and --to-step"
+    );
+
+    let (from, to) = if args.session {
+        session_range(&args.path, bot_author_email)?
+    } else {
+        step_range(&args.path, args.from_step.unwrap(), args.to_step.unwrap())?
+    };
+
+    let vcs = GitVcs::open_existing(&args.path)?;
+    let body = render(&vcs, from.as_deref(), &to, args.stat)?;
+    Ok(format!("{}\n\n{body}", header(from.as_deref(), &to)))
+}
+
+/// Resolves `--session`'s range: from the parent of the first
+/// bot-authored commit — identified by `bot_author_email`, falling back
+/// to the first commit whose message is step 0 — to `HEAD`. `from` of
+/// `None` means the session's first commit is the repository's root
+/// commit, with nothing before it to exclude.
+fn session_range(repo_root: &Path, bot_author_email: &str) -> anyhow::Result<(Option<String>, String)> {
+    let repo = git2::Repository::open(repo_root)?;
+    let head = repo.head()?.peel_to_commit()?;
+
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push_head()?;
+    revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+
+    let mut first_bot_commit = None;
+    for oid in revwalk {
+        let commit = repo.find_commit(oid?)?;
+        let is_bot_commit = commit.author().email() == Some(bot_author_email)
+            || matches!(crate::undo::parse_step_commit(commit.message().unwrap_or_default()), Some((_, 0)));
+        if is_bot_commit {
+            first_bot_commit = Some(commit);
+            break;
+        }
+    }
+
+    let first_bot_commit =
+        first_bot_commit.ok_or_else(|| anyhow::anyhow!("no bot-authored commit (author email {bot_author_email}) found in this history"))?;
+    let from = first_bot_commit.parent_id(0).ok().map(|id| id.to_string());
+    Ok((from, head.id().to_string()))
+}
+
+/// Resolves `--from-step N --to-step M`'s range by mapping each step index
+/// to its commit id through the `"<type>: step <n>"` commit summary
+/// [`crate::undo::parse_step_commit`] recovers.
+fn step_range(repo_root: &Path, from_step: u32, to_step: u32) -> anyhow::Result<(Option<String>, String)> {
+    anyhow::ensure!(from_step <= to_step, "--from-step {from_step} is after --to-step {to_step}");
+
+    let repo = git2::Repository::open(repo_root)?;
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push_head()?;
+    revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+
+    let mut by_step = std::collections::HashMap::new();
+    for oid in revwalk {
+        let commit = repo.find_commit(oid?)?;
+        if let Some((_, step)) = crate::undo::parse_step_commit(commit.message().unwrap_or_default()) {
+            by_step.entry(step).or_insert_with(|| commit.id());
+        }
+    }
+
+    let from_commit = *by_step.get(&from_step).ok_or_else(|| anyhow::anyhow!("no commit found for step {from_step}"))?;
+    let to_commit = *by_step.get(&to_step).ok_or_else(|| anyhow::anyhow!("no commit found for step {to_step}"))?;
+
+    let from = repo.find_commit(from_commit)?.parent_id(0).ok().map(|id| id.to_string());
+    Ok((from, to_commit.to_string()))
+}
+
+/// Renders `from..to` as a full patch, or its `--stat` summary when
+/// `stat_only` is set or the patch would exceed
+/// [`tdd_core::DEFAULT_CAPTURE_LIMIT_BYTES`] — the same capture limit
+/// [`tdd_exec::process`] applies to command output, so an oversized
+/// session diff degrades the same way an oversized test log does.
+fn render(vcs: &dyn Vcs, from: Option<&str>, to: &str, stat_only: bool) -> anyhow::Result<String> {
+    if stat_only {
+        return vcs.diff_range_stat(from, to);
+    }
+
+    let patch = vcs.diff_range(from, to)?;
+    if patch.len() > tdd_core::DEFAULT_CAPTURE_LIMIT_BYTES {
+        let stat = vcs.diff_range_stat(from, to)?;
+        return Ok(format!(
+            "diff omitted: {} bytes exceeds the {}-byte limit; showing --stat instead\n\n{stat}",
+            patch.len(),
+            tdd_core::DEFAULT_CAPTURE_LIMIT_BYTES
+        ));
+    }
+    Ok(patch)
+}
+
+fn header(from: Option<&str>, to: &str) -> String {
+    match from {
+        Some(from) => format!("--- boundary (excluded): {}\n+++ boundary (included): {}", short(from), short(to)),
+        None => format!("--- boundary (excluded): repository root\n+++ boundary (included): {}", short(to)),
+    }
+}
+
+fn short(oid: &str) -> &str {
+    &oid[..oid.len().min(10)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tdd_exec::CommitAuthor;
+
+    fn commit_as(repo: &git2::Repository, name: &str, email: &str, message: &str) -> git2::Oid {
+        let mut index = repo.index().unwrap();
+        index.add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None).unwrap();
+        index.write().unwrap();
+        let tree_id = index.write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let signature = git2::Signature::now(name, email).unwrap();
+        let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &parents).unwrap()
+    }
+
+    fn bot_vcs(dir: &Path) -> GitVcs {
+        GitVcs::new(dir, CommitAuthor::default())
+    }
+
+    #[test]
+    fn session_range_excludes_a_manual_commit_before_the_first_bot_commit() {
+        let dir = tempfile::tempdir().unwrap();
+        crate::init::run(&crate::init::InitArgs { path: dir.path().to_path_buf(), kata_url: None }).unwrap();
+        let repo = git2::Repository::open(dir.path()).unwrap();
+
+        std::fs::write(dir.path().join("README.md"), "manual notes\n").unwrap();
+        let manual_commit = commit_as(&repo, "Human", "human@example.com", "chore: human setup");
+
+        let vcs = bot_vcs(dir.path());
+        std::fs::write(dir.path().join("tests/api.rs"), "#[test]\nfn it_fails() {}\n").unwrap();
+        vcs.stage_all().unwrap();
+        vcs.commit("test: step 0").unwrap();
+
+        std::fs::write(dir.path().join("src/lib.rs"), "pub fn add() {}\n").unwrap();
+        vcs.stage_all().unwrap();
+        vcs.commit("feat: step 1").unwrap();
+
+        std::fs::write(dir.path().join("src/lib.rs"), "pub fn add() -> i32 { 0 }\n").unwrap();
+        vcs.stage_all().unwrap();
+        let last_commit = vcs.commit("refactor: step 2").unwrap();
+
+        let (from, to) = session_range(dir.path(), "tdd@local").unwrap();
+        assert_eq!(from, Some(manual_commit.to_string()));
+        assert_eq!(to, last_commit);
+
+        let stat = render(&vcs, from.as_deref(), &to, true).unwrap();
+        assert!(stat.contains("api.rs"));
+        assert!(stat.contains("lib.rs"));
+        assert!(!stat.contains("README.md"));
+    }
+
+    #[test]
+    fn session_range_is_the_root_commit_when_the_very_first_commit_is_bot_authored() {
+        let dir = tempfile::tempdir().unwrap();
+        crate::init::run(&crate::init::InitArgs { path: dir.path().to_path_buf(), kata_url: None }).unwrap();
+        let vcs = bot_vcs(dir.path());
+
+        std::fs::write(dir.path().join("tests/api.rs"), "#[test]\nfn it_fails() {}\n").unwrap();
+        vcs.stage_all().unwrap();
+        let first_commit = vcs.commit("test: step 0").unwrap();
+
+        let (from, to) = session_range(dir.path(), "tdd@local").unwrap();
+        assert_eq!(from, None);
+        assert_eq!(to, first_commit);
+    }
+
+    #[test]
+    fn step_range_maps_step_indices_to_their_commit_ids() {
+        let dir = tempfile::tempdir().unwrap();
+        crate::init::run(&crate::init::InitArgs { path: dir.path().to_path_buf(), kata_url: None }).unwrap();
+        let repo = git2::Repository::open(dir.path()).unwrap();
+
+        std::fs::write(dir.path().join("README.md"), "manual notes\n").unwrap();
+        let manual_commit = commit_as(&repo, "Human", "human@example.com", "chore: human setup");
+
+        let vcs = bot_vcs(dir.path());
+        std::fs::write(dir.path().join("tests/api.rs"), "#[test]\nfn it_fails() {}\n").unwrap();
+        vcs.stage_all().unwrap();
+        vcs.commit("test: step 0").unwrap();
+
+        std::fs::write(dir.path().join("src/lib.rs"), "pub fn add() {}\n").unwrap();
+        vcs.stage_all().unwrap();
+        vcs.commit("feat: step 1").unwrap();
+
+        std::fs::write(dir.path().join("src/lib.rs"), "pub fn add() -> i32 { 0 }\n").unwrap();
+        vcs.stage_all().unwrap();
+        let step_2_commit = vcs.commit("refactor: step 2").unwrap();
+
+        let (from, to) = step_range(dir.path(), 0, 2).unwrap();
+        assert_eq!(from, Some(manual_commit.to_string()));
+        assert_eq!(to, step_2_commit);
+    }
+
+    #[test]
+    fn an_out_of_order_step_range_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        crate::init::run(&crate::init::InitArgs { path: dir.path().to_path_buf(), kata_url: None }).unwrap();
+
+        let error = step_range(dir.path(), 3, 1).unwrap_err();
+        assert!(error.to_string().contains("is after"));
+    }
+}
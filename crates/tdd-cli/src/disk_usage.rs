@@ -0,0 +1,206 @@
+//! Accounts for the size of `.tdd/` by subdirectory, and reclaims space
+//! from the transient ones (LLM transcripts, raw capture logs, the
+//! summarization cache, scratch files) when it grows past a configured
+//! budget — never touching `plan/` or the step logs under `logs/`
+//! themselves, since those are the session's record.
+
+use crate::workspace_access;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// One `.tdd` subdirectory's total size, as reported by [`report`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CategorySize {
+    pub category: String,
+    pub bytes: u64,
+}
+
+/// The result of walking `.tdd/`: one entry per category plus the sum.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DiskUsageReport {
+    pub categories: Vec<CategorySize>,
+    pub total_bytes: u64,
+}
+
+/// `.tdd` subdirectories accounted for individually, in report order.
+/// `logs/llm` is reported separately from the rest of `logs/` since it's
+/// almost always the biggest line item.
+const CATEGORIES: &[&str] = &["plan", "logs", "logs/llm", "cache", "state", "tmp"];
+
+/// The subset of [`CATEGORIES`] the retention pass is allowed to delete
+/// from, in the order it reclaims from them: raw and LLM transcripts
+/// first (purely diagnostic), then the summarization cache, then scratch
+/// files — `plan/`, `logs/` (the step logs themselves), and `state/` are
+/// never touched.
+const TRANSIENT_CATEGORIES: &[&str] = &["logs/llm", "cache", "tmp"];
+
+/// Walks `.tdd/` under `repo_root` and sums file sizes per
+/// [`CATEGORIES`] entry. Skips a subdirectory it can't list (permission
+/// denied) rather than failing the whole report, matching the lenient
+/// listing `status`/`doctor` already use elsewhere.
+pub fn report(repo_root: &Path) -> DiskUsageReport {
+    let tdd_dir = repo_root.join(".tdd");
+    let mut categories = Vec::new();
+    let mut total_bytes = 0;
+
+    for &category in CATEGORIES {
+        let bytes = if category == "logs" {
+            dir_size(&tdd_dir.join("logs")).saturating_sub(dir_size(&tdd_dir.join("logs").join("llm")))
+        } else {
+            dir_size(&tdd_dir.join(category))
+        };
+        total_bytes += bytes;
+        categories.push(CategorySize {
+            category: category.to_string(),
+            bytes,
+        });
+    }
+
+    DiskUsageReport { categories, total_bytes }
+}
+
+/// The total size of every file under `.tdd/`, categorized or not — used
+/// by `doctor`'s budget check since an uncategorized directory (e.g. a
+/// future addition) should still count against the limit.
+pub fn total_tdd_dir_bytes(repo_root: &Path) -> u64 {
+    dir_size(&repo_root.join(".tdd"))
+}
+
+fn dir_size(dir: &Path) -> u64 {
+    if !dir.exists() || workspace_access::is_unreadable(dir) {
+        return 0;
+    }
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// A file removed by [`reclaim`], for a `--report`-style log of what was
+/// deleted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReclaimedFile {
+    pub path: PathBuf,
+    pub bytes: u64,
+}
+
+/// Deletes files from [`TRANSIENT_CATEGORIES`] oldest-first (by mtime)
+/// until `.tdd/`'s total size is at or under `target_bytes`, or there's
+/// nothing left to delete. Returns every file removed, in deletion order.
+pub fn reclaim(repo_root: &Path, target_bytes: u64) -> anyhow::Result<Vec<ReclaimedFile>> {
+    let tdd_dir = repo_root.join(".tdd");
+    let mut current = dir_size(&tdd_dir);
+    let mut removed = Vec::new();
+
+    for category in TRANSIENT_CATEGORIES {
+        if current <= target_bytes {
+            break;
+        }
+        let dir = tdd_dir.join(category);
+        if !dir.exists() || workspace_access::is_unreadable(&dir) {
+            continue;
+        }
+
+        let mut files: Vec<(PathBuf, u64, std::time::SystemTime)> = WalkDir::new(&dir)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .filter_map(|entry| {
+                let metadata = entry.metadata().ok()?;
+                let modified = metadata.modified().ok()?;
+                Some((entry.into_path(), metadata.len(), modified))
+            })
+            .collect();
+        files.sort_by_key(|(_, _, modified)| *modified);
+
+        for (path, bytes, _) in files {
+            if current <= target_bytes {
+                break;
+            }
+            std::fs::remove_file(&path)?;
+            current = current.saturating_sub(bytes);
+            removed.push(ReclaimedFile { path, bytes });
+        }
+    }
+
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write(path: &Path, bytes: usize) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, vec![b'x'; bytes]).unwrap();
+    }
+
+    fn set_mtime(path: &Path, seconds_ago: u64) {
+        let modified = std::time::SystemTime::now() - std::time::Duration::from_secs(seconds_ago);
+        std::fs::File::open(path).unwrap().set_modified(modified).unwrap();
+    }
+
+    #[test]
+    fn report_sums_each_category_and_separates_llm_logs_from_the_rest() {
+        let dir = tempdir().unwrap();
+        write(&dir.path().join(".tdd/plan/step-0-tester.md"), 10);
+        write(&dir.path().join(".tdd/logs/step-0-tester.json"), 20);
+        write(&dir.path().join(".tdd/logs/llm/step-0-tester.json"), 30);
+        write(&dir.path().join(".tdd/cache/kata-summary.md"), 40);
+        write(&dir.path().join(".tdd/state/provider.json"), 5);
+        write(&dir.path().join(".tdd/tmp/scratch.txt"), 7);
+
+        let report = report(dir.path());
+
+        let by_category: std::collections::HashMap<_, _> = report.categories.iter().map(|c| (c.category.as_str(), c.bytes)).collect();
+        assert_eq!(by_category["plan"], 10);
+        assert_eq!(by_category["logs"], 20);
+        assert_eq!(by_category["logs/llm"], 30);
+        assert_eq!(by_category["cache"], 40);
+        assert_eq!(by_category["state"], 5);
+        assert_eq!(by_category["tmp"], 7);
+        assert_eq!(report.total_bytes, 10 + 20 + 30 + 40 + 5 + 7);
+    }
+
+    #[test]
+    fn reclaim_deletes_only_transient_categories_oldest_first() {
+        let dir = tempdir().unwrap();
+        write(&dir.path().join(".tdd/plan/step-0-tester.md"), 10);
+        write(&dir.path().join(".tdd/logs/step-0-tester.json"), 10);
+
+        let old = dir.path().join(".tdd/logs/llm/old.json");
+        write(&old, 50);
+        let newer = dir.path().join(".tdd/cache/newer.md");
+        write(&newer, 50);
+        set_mtime(&old, 200);
+        set_mtime(&newer, 100);
+
+        let removed = reclaim(dir.path(), 70).unwrap();
+
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].path, old);
+        assert!(dir.path().join(".tdd/plan/step-0-tester.md").exists());
+        assert!(dir.path().join(".tdd/logs/step-0-tester.json").exists());
+        assert!(newer.exists());
+        assert!(!old.exists());
+    }
+
+    #[test]
+    fn reclaim_stops_once_the_target_is_reached() {
+        let dir = tempdir().unwrap();
+        for i in 0..5 {
+            let path = dir.path().join(format!(".tdd/tmp/scratch-{i}.txt"));
+            write(&path, 10);
+            set_mtime(&path, 500 - i);
+        }
+
+        let removed = reclaim(dir.path(), 20).unwrap();
+
+        assert_eq!(removed.len(), 3);
+        assert!(total_tdd_dir_bytes(dir.path()) <= 20);
+    }
+}
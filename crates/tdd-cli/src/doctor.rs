@@ -0,0 +1,402 @@
+//! Implements `tdd-cli doctor`: verifies the tools and environment the
+//! orchestrator depends on before a run is attempted.
+
+use crate::config::{CiConfig, TddConfig};
+use crate::ignore_policy;
+use crate::workspace_access;
+use crate::workspace_paths;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tdd_core::CommandSpec;
+
+/// The result of checking a single required tool.
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolCheck {
+    pub name: String,
+    pub found: bool,
+}
+
+/// Checks that `cargo` and `git` are on `PATH`, along with `rustfmt` and
+/// `cargo-clippy` unless `ci` skips the stage that needs them — a
+/// documentation-only kata that skips `check` has no reason to fail
+/// `doctor` over a missing `cargo-clippy`.
+pub fn run_checks(ci: &CiConfig) -> Vec<ToolCheck> {
+    let mut tools = vec!["cargo"];
+    if ci.fmt_cmd.is_enabled() {
+        tools.push("rustfmt");
+    }
+    if ci.check_cmd.is_enabled() {
+        tools.push("cargo-clippy");
+    }
+    tools.push("git");
+
+    tools
+        .into_iter()
+        .map(|name| ToolCheck {
+            name: name.to_string(),
+            found: which(name),
+        })
+        .collect()
+}
+
+/// Same as [`run_checks`], but returns no checks at all once a kata is
+/// archived — there's no run left to get ready for, so `doctor` shouldn't
+/// flag a missing `cargo-clippy` on a finished workspace.
+pub fn run_checks_unless_archived(ci: &CiConfig, archived: bool) -> Vec<ToolCheck> {
+    if archived {
+        Vec::new()
+    } else {
+        run_checks(ci)
+    }
+}
+
+fn which(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .map(|paths| {
+            std::env::split_paths(&paths).any(|dir| {
+                let candidate = dir.join(program);
+                candidate.is_file()
+            })
+        })
+        .unwrap_or(false)
+}
+
+/// Above this size, a standing-instructions context file is probably
+/// wasting prompt budget rather than conveying house style.
+const CONTEXT_FILE_SANITY_BYTES: u64 = 4096;
+
+/// Returns a warning when the workspace's standing-instructions file
+/// exceeds a sanity size, since its full contents go into every step's
+/// prompt. `None` when the file is missing or within budget.
+pub fn oversized_context_file(repo_root: &Path, context_file: &str) -> Option<String> {
+    let size = std::fs::metadata(repo_root.join(context_file)).ok()?.len();
+    if size > CONTEXT_FILE_SANITY_BYTES {
+        Some(format!("{context_file} is {size} bytes; it's included in every step's prompt, consider trimming it"))
+    } else {
+        None
+    }
+}
+
+/// Returns a warning per configured path (`kata_description`,
+/// `workspace.context_file`) that's a stale absolute path left over from a
+/// workspace root that's since moved or been renamed — the kind of thing
+/// [`crate::workspace_paths::resolve`] already works around at run time,
+/// but still worth surfacing rather than leaving silently masked.
+/// `repair-paths` rewrites these back to the relative form.
+pub fn stale_absolute_paths(repo_root: &Path, config: &TddConfig) -> Vec<String> {
+    [("kata_description", &config.kata_description), ("context_file", &config.workspace.context_file)]
+        .into_iter()
+        .filter(|(_, configured)| workspace_paths::is_stale_absolute(repo_root, configured))
+        .map(|(key, configured)| format!("{key}: {configured} doesn't exist under this root; run `tdd-cli repair-paths` to make it relative"))
+        .collect()
+}
+
+/// Returns a warning when `.tdd/` exceeds `workspace.max_tdd_dir_mb`, so a
+/// long-lived repo's transcripts and caches don't quietly fill the disk
+/// before anyone notices. Silent when the budget is unset.
+pub fn oversized_tdd_dir(repo_root: &Path, config: &TddConfig) -> Option<String> {
+    let max_mb = config.workspace.max_tdd_dir_mb?;
+    let bytes = crate::disk_usage::total_tdd_dir_bytes(repo_root);
+    let max_bytes = max_mb.saturating_mul(1024 * 1024);
+    if bytes > max_bytes {
+        Some(format!(
+            ".tdd is {} MB, over the {max_mb} MB budget; run `tdd-cli size --clean` to reclaim transient categories",
+            bytes / (1024 * 1024)
+        ))
+    } else {
+        None
+    }
+}
+
+/// Returns a warning when the bootstrap marker's stored hash no longer
+/// matches the configured command (or the script it invokes), meaning
+/// `tdd_exec::BootstrapRunner::run` will re-provision on the next `run`
+/// rather than skip. Silent when bootstrap is unconfigured (`CommandSpec::Skip`)
+/// or when there's nothing yet to compare (missing or legacy empty marker).
+pub fn stale_bootstrap_marker(repo_root: &Path, config: &TddConfig) -> Option<String> {
+    let CommandSpec::Command(command) = &config.bootstrap.command else {
+        return None;
+    };
+    let runner = tdd_exec::BootstrapRunner::new(repo_root, command.clone(), &config.bootstrap.marker_path);
+    runner.is_stale().filter(|stale| *stale).map(|_| {
+        format!("{} is stale; the next run will re-provision the environment", config.bootstrap.marker_path)
+    })
+}
+
+/// Returns a specific warning when `repo_root` itself isn't writable,
+/// instead of letting a real `run` discover that mid-step when it tries
+/// to write `.tdd/` artifacts or stage a commit.
+pub fn read_only_workspace(repo_root: &Path) -> Option<String> {
+    workspace_access::is_read_only(repo_root).then(|| format!("{} is read-only; `run` will fail before it can commit a step", repo_root.display()))
+}
+
+/// Returns a warning per `workspace.readonly_paths` glob that matches no
+/// file currently tracked by git — almost always a typo, since a glob
+/// that's supposed to protect a real directory should match something.
+pub fn unmatched_readonly_globs(repo_root: &Path, config: &TddConfig) -> anyhow::Result<Vec<String>> {
+    if config.workspace.readonly_paths.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let repo = git2::Repository::open(repo_root)?;
+    let index = repo.index()?;
+    let tracked: Vec<String> = index.iter().filter_map(|entry| String::from_utf8(entry.path).ok()).collect();
+
+    Ok(config
+        .workspace
+        .readonly_paths
+        .iter()
+        .filter(|glob| !tracked.iter().any(|path| tdd_core::path_glob::matches(glob, path)))
+        .map(|glob| format!("readonly_paths: `{glob}` matches no tracked file; check for a typo"))
+        .collect())
+}
+
+/// Returns a warning listing the evidence (see [`crate::testscan`]) when
+/// the workspace already has test code before the TDD loop has run a
+/// single step, since a kata is meant to start from a clean red slate.
+pub fn existing_tests_before_first_step(repo_root: &Path) -> Option<String> {
+    let evidence = crate::testscan::scan(repo_root);
+    if evidence.is_empty() {
+        return None;
+    }
+    let files = evidence.iter().map(|item| item.path.as_str()).collect::<Vec<_>>().join(", ");
+    Some(format!("found existing test code before the first step: {files}"))
+}
+
+/// Returns the repo-relative paths of any tracked file that lives under a
+/// directory the ignore policy declares sensitive, e.g. a committed LLM
+/// transcript or `.tdd/state/kata-source.json`.
+pub fn tracked_sensitive_paths(repo_root: &Path) -> anyhow::Result<Vec<String>> {
+    let repo = git2::Repository::open(repo_root)?;
+    let index = repo.index()?;
+
+    let flagged = index
+        .iter()
+        .filter_map(|entry| String::from_utf8(entry.path).ok())
+        .filter(|path| ignore_policy::sensitive_dirs().iter().any(|dir| path.starts_with(dir)))
+        .collect();
+
+    Ok(flagged)
+}
+
+/// Returns an informational line naming the main repository when
+/// `repo_root` is a linked git worktree rather than a normal checkout —
+/// `git2::Repository::open` already follows the `.git` *file* a worktree
+/// leaves behind to find the real gitdir, so this is routine, not a
+/// problem; `doctor` just says so instead of leaving it unremarked.
+pub fn worktree_notice(repo_root: &Path) -> Option<String> {
+    let repo = git2::Repository::open(repo_root).ok()?;
+    if !repo.is_worktree() {
+        return None;
+    }
+    match main_repo_workdir(&repo) {
+        Some(path) => Some(format!("git worktree detected (main repo at {})", path.display())),
+        None => Some("git worktree detected".to_string()),
+    }
+}
+
+/// A linked worktree's gitdir is `<main>/.git/worktrees/<name>`; that
+/// directory's `commondir` file names the shared `.git` whose parent is
+/// the main repository's working directory.
+fn main_repo_workdir(repo: &git2::Repository) -> Option<PathBuf> {
+    let commondir = std::fs::read_to_string(repo.path().join("commondir")).ok()?;
+    let common_git_dir = repo.path().join(commondir.trim()).canonicalize().ok()?;
+    common_git_dir.parent().map(Path::to_path_buf)
+}
+
+/// Returns a warning when sparse-checkout is enabled and the configured
+/// `kata_description` is tracked but missing from disk, meaning it sits
+/// outside this checkout's sparse cone — a clear diagnosis instead of the
+/// plain I/O error `run` would otherwise fail with the first time it
+/// tries to read it.
+pub fn kata_file_outside_sparse_cone(repo_root: &Path, config: &TddConfig) -> anyhow::Result<Option<String>> {
+    let repo = git2::Repository::open(repo_root)?;
+    if !repo.config()?.get_bool("core.sparseCheckout").unwrap_or(false) {
+        return Ok(None);
+    }
+    if repo_root.join(&config.kata_description).exists() {
+        return Ok(None);
+    }
+
+    let index = repo.index()?;
+    let tracked = index.iter().any(|entry| String::from_utf8_lossy(&entry.path) == config.kata_description);
+    Ok(tracked.then(|| format!("{} is tracked but missing from disk; it's outside this sparse checkout's cone", config.kata_description)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tdd_core::Vcs;
+    use tdd_exec::{CommitAuthor, GitVcs};
+    use tempfile::tempdir;
+
+    #[test]
+    fn flags_a_tracked_file_under_a_sensitive_directory() {
+        let dir = tempdir().unwrap();
+        let vcs = GitVcs::new(dir.path(), CommitAuthor::default());
+        vcs.init_if_needed().unwrap();
+
+        std::fs::create_dir_all(dir.path().join(".tdd/state")).unwrap();
+        std::fs::write(dir.path().join(".tdd/state/kata-source.json"), "{}").unwrap();
+        vcs.stage_all().unwrap();
+
+        let flagged = tracked_sensitive_paths(dir.path()).unwrap();
+        assert_eq!(flagged, vec![".tdd/state/kata-source.json".to_string()]);
+    }
+
+    #[test]
+    fn flags_a_context_file_over_the_sanity_size() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".tdd")).unwrap();
+        std::fs::write(dir.path().join(".tdd/context.md"), "x".repeat(CONTEXT_FILE_SANITY_BYTES as usize + 1)).unwrap();
+
+        let warning = oversized_context_file(dir.path(), ".tdd/context.md").unwrap();
+        assert!(warning.contains(".tdd/context.md"));
+    }
+
+    #[test]
+    fn run_checks_covers_every_stage_binary_by_default() {
+        let names: Vec<_> = run_checks(&CiConfig::default()).into_iter().map(|check| check.name).collect();
+        assert_eq!(names, vec!["cargo", "rustfmt", "cargo-clippy", "git"]);
+    }
+
+    #[test]
+    fn run_checks_exempts_binaries_for_skipped_stages() {
+        let ci = CiConfig {
+            check_cmd: tdd_core::CommandSpec::Skip,
+            ..CiConfig::default()
+        };
+        let names: Vec<_> = run_checks(&ci).into_iter().map(|check| check.name).collect();
+        assert_eq!(names, vec!["cargo", "rustfmt", "git"]);
+    }
+
+    #[test]
+    fn run_checks_unless_archived_matches_run_checks_when_active() {
+        let ci = CiConfig::default();
+        let names: Vec<_> = run_checks_unless_archived(&ci, false).into_iter().map(|check| check.name).collect();
+        assert_eq!(names, vec!["cargo", "rustfmt", "cargo-clippy", "git"]);
+    }
+
+    #[test]
+    fn run_checks_unless_archived_is_empty_once_archived() {
+        assert!(run_checks_unless_archived(&CiConfig::default(), true).is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn a_read_only_workspace_is_flagged_by_name() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempdir().unwrap();
+        std::fs::set_permissions(dir.path(), std::fs::Permissions::from_mode(0o555)).unwrap();
+
+        let warning = read_only_workspace(dir.path());
+        std::fs::set_permissions(dir.path(), std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        assert!(warning.unwrap().contains("read-only"));
+    }
+
+    #[test]
+    fn a_writable_workspace_is_not_flagged() {
+        let dir = tempdir().unwrap();
+        assert_eq!(read_only_workspace(dir.path()), None);
+    }
+
+    #[test]
+    fn a_small_context_file_is_not_flagged() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".tdd")).unwrap();
+        std::fs::write(dir.path().join(".tdd/context.md"), "be nice").unwrap();
+
+        assert!(oversized_context_file(dir.path(), ".tdd/context.md").is_none());
+    }
+
+    #[test]
+    fn a_readonly_glob_matching_nothing_tracked_is_flagged_as_a_likely_typo() {
+        let dir = tempdir().unwrap();
+        let vcs = GitVcs::new(dir.path(), CommitAuthor::default());
+        vcs.init_if_needed().unwrap();
+
+        std::fs::create_dir_all(dir.path().join("contracts")).unwrap();
+        std::fs::write(dir.path().join("contracts/billing.rs"), "pub trait Billing {}\n").unwrap();
+        vcs.stage_all().unwrap();
+
+        let mut config = TddConfig::default();
+        config.workspace.readonly_paths = vec!["contarcts/**".to_string()];
+
+        let warnings = unmatched_readonly_globs(dir.path(), &config).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("contarcts/**"));
+    }
+
+    #[test]
+    fn a_normal_checkout_gets_no_worktree_notice() {
+        let dir = tempdir().unwrap();
+        let vcs = GitVcs::new(dir.path(), CommitAuthor::default());
+        vcs.init_if_needed().unwrap();
+
+        assert_eq!(worktree_notice(dir.path()), None);
+    }
+
+    #[test]
+    fn a_linked_worktree_is_named_by_its_main_repo() {
+        let main_dir = tempdir().unwrap();
+        let vcs = GitVcs::new(main_dir.path(), CommitAuthor::default());
+        vcs.init_if_needed().unwrap();
+        std::fs::write(main_dir.path().join("README.md"), "kata").unwrap();
+        vcs.stage_all().unwrap();
+        vcs.commit("chore: scaffold").unwrap();
+
+        let repo = git2::Repository::open(main_dir.path()).unwrap();
+        let worktree_dir = tempdir().unwrap();
+        let worktree_path = worktree_dir.path().join("linked");
+        repo.worktree("linked", &worktree_path, None).unwrap();
+
+        let notice = worktree_notice(&worktree_path).unwrap();
+        assert!(notice.starts_with("git worktree detected (main repo at "));
+        assert!(notice.contains(&main_dir.path().canonicalize().unwrap().display().to_string()));
+    }
+
+    #[test]
+    fn sparse_checkout_is_not_flagged_when_disabled() {
+        let dir = tempdir().unwrap();
+        let vcs = GitVcs::new(dir.path(), CommitAuthor::default());
+        vcs.init_if_needed().unwrap();
+
+        assert_eq!(kata_file_outside_sparse_cone(dir.path(), &TddConfig::default()).unwrap(), None);
+    }
+
+    #[test]
+    fn a_tracked_kata_file_missing_under_sparse_checkout_is_flagged() {
+        let dir = tempdir().unwrap();
+        let vcs = GitVcs::new(dir.path(), CommitAuthor::default());
+        vcs.init_if_needed().unwrap();
+
+        std::fs::write(dir.path().join("kata.md"), "solve it").unwrap();
+        vcs.stage_all().unwrap();
+        vcs.commit("chore: scaffold").unwrap();
+
+        let repo = git2::Repository::open(dir.path()).unwrap();
+        repo.config().unwrap().set_bool("core.sparseCheckout", true).unwrap();
+        std::fs::remove_file(dir.path().join("kata.md")).unwrap();
+
+        let warning = kata_file_outside_sparse_cone(dir.path(), &TddConfig::default()).unwrap().unwrap();
+        assert!(warning.contains("kata.md"));
+        assert!(warning.contains("sparse checkout"));
+    }
+
+    #[test]
+    fn a_readonly_glob_matching_a_tracked_file_is_not_flagged() {
+        let dir = tempdir().unwrap();
+        let vcs = GitVcs::new(dir.path(), CommitAuthor::default());
+        vcs.init_if_needed().unwrap();
+
+        std::fs::create_dir_all(dir.path().join("contracts")).unwrap();
+        std::fs::write(dir.path().join("contracts/billing.rs"), "pub trait Billing {}\n").unwrap();
+        vcs.stage_all().unwrap();
+
+        let mut config = TddConfig::default();
+        config.workspace.readonly_paths = vec!["contracts/**".to_string()];
+
+        assert!(unmatched_readonly_globs(dir.path(), &config).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,25 @@
+//! Typed errors surfaced while assembling a [`crate::orchestrator::LoopOrchestrator`].
+
+use std::path::PathBuf;
+use tdd_core::Role;
+use thiserror::Error;
+
+/// Errors raised while validating the agents handed to
+/// [`crate::orchestrator::LoopOrchestrator::new`], before any step runs.
+#[derive(Debug, Error)]
+pub enum OrchestratorBuildError {
+    #[error("duplicate agents registered for role(s): {0}")]
+    DuplicateRoles(String),
+
+    #[error("no agent registered for required role: {0}")]
+    MissingRole(Role),
+
+    #[error("agent registered for role {0}, which is outside the configured role cycle (pass allow_extra_agents(true) to permit this)")]
+    UnexpectedRole(Role),
+
+    #[error("workspace at {0} is not writable; `run` needs to write `.tdd/` artifacts and commit each step")]
+    WorkspaceNotWritable(PathBuf),
+
+    #[error("kata archived on {} after {} steps; pass --unarchive (or run `tdd-cli unarchive`) to resume", .0.archived_at.format("%Y-%m-%d"), .0.final_step_count)]
+    KataArchived(crate::archive::ArchiveRecord),
+}
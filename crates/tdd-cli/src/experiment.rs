@@ -0,0 +1,578 @@
+//! Implements `tdd-cli experiment`: runs isolated trials of `--cycles`
+//! worth of steps under each of `--variants`' Tester prompt templates,
+//! restoring a workspace snapshot between trials so one variant's edits
+//! never leak into the next, and prints a comparison table of retries,
+//! scope violations, and whether CI went green.
+//!
+//! Token usage isn't in the table: [`tdd_llm::LlmClient::chat`] never
+//! surfaces usage back to its caller (see its tracing-only `ChatUsage`),
+//! so there's nothing honest to report here without a wider change to
+//! that trait.
+
+use crate::orchestrator::LoopOrchestrator;
+use crate::run_log::{self, StopReason};
+use crate::tui::events::StepEvent;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+use tdd_core::{StepFailureDetail, Vcs};
+
+/// One variant's prompt template, read up front so a trial's isolation
+/// window (snapshot restore through the next restore) never includes
+/// file I/O for something that doesn't change between trials.
+pub(crate) struct Variant {
+    label: String,
+    prompt: String,
+}
+
+fn load_variants(paths: &[PathBuf]) -> anyhow::Result<Vec<Variant>> {
+    paths
+        .iter()
+        .map(|path| {
+            let prompt = std::fs::read_to_string(path).map_err(|error| anyhow::anyhow!("could not read variant {}: {error}", path.display()))?;
+            let label = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default().to_string();
+            Ok(Variant { label, prompt })
+        })
+        .collect()
+}
+
+/// How one isolated trial ended.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrialOutcome {
+    pub variant: String,
+    pub trial: u32,
+    pub steps_executed: u32,
+    /// Number of [`StepEvent::AttemptStarted`] events beyond each step's
+    /// first attempt — an unverified attempt that didn't exhaust the
+    /// step's budget leaves no other trace in the event stream, so this
+    /// (not [`StepEvent::AttemptFailed`], which only fires on an outright
+    /// error or the step's very last attempt) is what actually counts
+    /// every retry.
+    pub retries: u32,
+    /// Number of [`StepEvent::AttemptFailed`] events whose failure text
+    /// matched [`tdd_agents::readonly_guard::ReadonlyViolation`]'s
+    /// message — there's no dedicated [`StepFailureDetail`] variant for
+    /// a scope violation, so it's recognized the same way a human
+    /// reading the step log would.
+    pub scope_violations: u32,
+    pub ci_green: bool,
+    pub wall_time_secs: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_reason: Option<StopReason>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// The raw result of a `tdd-cli experiment` invocation, one entry per
+/// trial in the order it ran (variants interleaved across trials, not
+/// grouped).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExperimentReport {
+    pub trials: Vec<TrialOutcome>,
+}
+
+/// One variant's aggregated row in [`ExperimentReport::render_table`].
+struct VariantTotals {
+    variant: String,
+    trials: u32,
+    successes: u32,
+    total_retries: u32,
+    total_scope_violations: u32,
+    total_wall_time_secs: f64,
+}
+
+impl ExperimentReport {
+    /// Aggregates [`Self::trials`] by variant, in first-seen order, and
+    /// renders one row per variant: trial count, how many went green,
+    /// and the per-trial average of retries, scope violations, and wall
+    /// time.
+    pub fn render_table(&self) -> String {
+        let mut totals: Vec<VariantTotals> = Vec::new();
+        for trial in &self.trials {
+            let entry = match totals.iter_mut().find(|totals| totals.variant == trial.variant) {
+                Some(entry) => entry,
+                None => {
+                    totals.push(VariantTotals { variant: trial.variant.clone(), trials: 0, successes: 0, total_retries: 0, total_scope_violations: 0, total_wall_time_secs: 0.0 });
+                    totals.last_mut().expect("just pushed")
+                }
+            };
+            entry.trials += 1;
+            entry.successes += u32::from(trial.ci_green);
+            entry.total_retries += trial.retries;
+            entry.total_scope_violations += trial.scope_violations;
+            entry.total_wall_time_secs += trial.wall_time_secs;
+        }
+
+        let mut lines = vec!["variant                                trials  green  avg_retries  avg_scope_violations  avg_wall_time".to_string()];
+        for entry in &totals {
+            let trials = entry.trials.max(1) as f64;
+            lines.push(format!(
+                "{:<40}{:<8}{:<7}{:<13.1}{:<23.1}{:>6.1}s",
+                entry.variant,
+                entry.trials,
+                entry.successes,
+                entry.total_retries as f64 / trials,
+                entry.total_scope_violations as f64 / trials,
+                entry.total_wall_time_secs / trials,
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Whether `detail` is the one shape a readonly-path rejection takes once
+/// it crosses the `anyhow` boundary: [`StepFailureDetail::Other`] with a
+/// message matching [`tdd_agents::readonly_guard::ReadonlyViolation`]'s
+/// `Display`.
+fn is_scope_violation(detail: &StepFailureDetail) -> bool {
+    matches!(detail, StepFailureDetail::Other { message } if message.contains("protected by readonly_paths"))
+}
+
+/// Runs one isolated trial: builds an orchestrator with `variant`'s
+/// prompt substituted for the Tester's, counts retries and scope
+/// violations off its [`StepEvent`] stream, and runs `cycles` worth of
+/// steps. The workspace is expected to already be at the experiment's
+/// baseline snapshot; callers are responsible for restoring it before
+/// calling this and again before the next trial.
+async fn run_trial(workspace: &Path, variant: &Variant, trial: u32, cycles: u32) -> TrialOutcome {
+    let started = Instant::now();
+    let run_args = crate::cli::RunArgs {
+        path: workspace.to_path_buf(),
+        steps: 0,
+        plan_only: false,
+        no_preflight: false,
+        commit_prefix: None,
+        review_branch: false,
+        auto_merge: false,
+        no_ff: false,
+        allow_stacked: false,
+        ignore_max_steps: false,
+        debug_unredacted_logs: false,
+        pair: false,
+        no_ci_cache: false,
+        goal: Vec::new(),
+        unarchive: false,
+        deterministic: false,
+    };
+
+    let mut orchestrator = match LoopOrchestrator::from_workspace_with_tester_prompt_override(&run_args, Some(variant.prompt.clone())).await {
+        Ok(orchestrator) => orchestrator,
+        Err(error) => {
+            return TrialOutcome {
+                variant: variant.label.clone(),
+                trial,
+                steps_executed: 0,
+                retries: 0,
+                scope_violations: 0,
+                ci_green: false,
+                wall_time_secs: started.elapsed().as_secs_f64(),
+                stop_reason: None,
+                error: Some(error.to_string()),
+            };
+        }
+    };
+
+    let retries = Arc::new(AtomicU32::new(0));
+    let scope_violations = Arc::new(AtomicU32::new(0));
+    let retries_for_observer = retries.clone();
+    let scope_violations_for_observer = scope_violations.clone();
+    orchestrator = orchestrator.add_observer(Box::new(move |event| match &event {
+        StepEvent::AttemptStarted { attempt, .. } if *attempt > 1 => {
+            retries_for_observer.fetch_add(1, Ordering::SeqCst);
+        }
+        StepEvent::AttemptFailed { detail, .. } if is_scope_violation(detail) => {
+            scope_violations_for_observer.fetch_add(1, Ordering::SeqCst);
+        }
+        _ => {}
+    }));
+
+    let steps = cycles.saturating_mul(orchestrator.required_roles().len() as u32);
+    let config_hash = run_log::config_hash(workspace).unwrap_or_default();
+    let (record, error) = run_log::execute_steps(&mut orchestrator, steps, config_hash, None).await;
+
+    TrialOutcome {
+        variant: variant.label.clone(),
+        trial,
+        steps_executed: record.steps_executed,
+        retries: retries.load(Ordering::SeqCst),
+        scope_violations: scope_violations.load(Ordering::SeqCst),
+        ci_green: record.stop_reason == StopReason::Completed,
+        wall_time_secs: started.elapsed().as_secs_f64(),
+        stop_reason: Some(record.stop_reason),
+        error: error.map(|error| error.to_string()),
+    }
+}
+
+/// Runs every variant `trials` times, interleaved (trial 0 of every
+/// variant, then trial 1 of every variant, ...) so drift over the
+/// experiment's wall-clock span lands evenly across variants rather than
+/// piling onto whichever one runs last. `vcs` is reset to `baseline`
+/// immediately before every trial, including the first, so a trial never
+/// inherits a previous one's edits; `on_restore` is notified once per
+/// reset, for counting how many isolated attempts actually ran.
+pub(crate) async fn run_trials(workspace: &Path, variants: &[Variant], cycles: u32, trials: u32, vcs: &dyn Vcs, baseline: &str, mut on_restore: impl FnMut()) -> anyhow::Result<ExperimentReport> {
+    let mut report = ExperimentReport { trials: Vec::new() };
+    for trial in 0..trials {
+        for variant in variants {
+            vcs.reset_hard(baseline)?;
+            on_restore();
+            report.trials.push(run_trial(workspace, variant, trial, cycles).await);
+        }
+    }
+    vcs.reset_hard(baseline)?;
+    Ok(report)
+}
+
+/// The `tdd-cli experiment` entrypoint: loads the variant prompt files,
+/// snapshots the workspace at its current `HEAD`, runs every trial via
+/// [`run_trials`], prints the comparison table, writes the raw per-trial
+/// data to `.tdd/logs/experiments/<timestamp>.json`, and restores the
+/// workspace to its pre-experiment state.
+pub async fn run(args: crate::cli::ExperimentArgs) -> anyhow::Result<()> {
+    let variants = load_variants(&args.variants)?;
+    let vcs = tdd_exec::GitVcs::open_existing(&args.path)?;
+
+    if !args.force && !vcs.is_clean()? {
+        anyhow::bail!(
+            "workspace has uncommitted changes; every trial resets it to HEAD and deletes \
+untracked files, which would discard them permanently. Commit or stash your changes first, \
+or pass --force to proceed anyway."
+        );
+    }
+
+    let baseline = vcs.head_commit_id()?;
+
+    let report = run_trials(&args.path, &variants, args.cycles, args.trials, &vcs, &baseline, || {}).await?;
+
+    println!("{}", report.render_table());
+
+    let dir = args.path.join(".tdd").join("logs").join("experiments");
+    std::fs::create_dir_all(&dir)?;
+    let out = dir.join(format!("{}.json", chrono::Utc::now().format("%Y%m%d%H%M%S")));
+    std::fs::write(&out, serde_json::to_string_pretty(&report)?)?;
+    println!("raw trial data written to {}", out.display());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use tdd_core::{Agent, RepoState, Role, RunnerOutcome, StepContext, StepResult};
+
+    struct StubAgent(Role);
+
+    #[async_trait]
+    impl Agent for StubAgent {
+        fn role(&self) -> Role {
+            self.0
+        }
+
+        async fn plan(&self, _ctx: &StepContext) -> anyhow::Result<String> {
+            Ok(String::new())
+        }
+
+        async fn edit(&self, _ctx: &StepContext) -> anyhow::Result<StepResult> {
+            Ok(StepResult::default())
+        }
+    }
+
+    fn stub_agents() -> Vec<Box<dyn Agent>> {
+        [Role::Tester, Role::Implementor, Role::Refactorer].into_iter().map(|role| Box::new(StubAgent(role)) as Box<dyn Agent>).collect()
+    }
+
+    struct RecordingVcs;
+
+    impl Vcs for RecordingVcs {
+        fn init_if_needed(&self) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn read_state(&self) -> anyhow::Result<RepoState> {
+            Ok(RepoState::default())
+        }
+
+        fn stage_all(&self) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn commit(&self, _message: &str) -> anyhow::Result<String> {
+            Ok("deadbeef".to_string())
+        }
+
+        fn diff_against_head(&self, _paths: &[String]) -> anyhow::Result<String> {
+            Ok(String::new())
+        }
+
+        fn discard_paths(&self, _paths: &[String]) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn head_commit_timestamp(&self) -> anyhow::Result<Option<std::time::SystemTime>> {
+            Ok(None)
+        }
+
+        fn recently_changed_paths(&self, _n_commits: usize) -> anyhow::Result<Vec<String>> {
+            Ok(Vec::new())
+        }
+
+        fn diff_range(&self, _from: Option<&str>, _to: &str) -> anyhow::Result<String> {
+            Ok(String::new())
+        }
+
+        fn diff_range_stat(&self, _from: Option<&str>, _to: &str) -> anyhow::Result<String> {
+            Ok(String::new())
+        }
+
+        fn create_branch_from(&self, _name: &str, _commit: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn checkout(&self, _name: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn merge_ff(&self, _name: &str, _no_ff: bool) -> anyhow::Result<String> {
+            Ok(String::new())
+        }
+
+        fn is_detached(&self) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+
+        fn head_commit_id(&self) -> anyhow::Result<String> {
+            Ok("deadbeef".to_string())
+        }
+
+        fn reset_hard(&self, _commit: &str) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn is_clean(&self) -> anyhow::Result<bool> {
+            Ok(true)
+        }
+    }
+
+    /// `check()` fails on its first call and passes from then on; `test()`
+    /// always fails. For a Tester step (which needs `check.ok && !test.ok`
+    /// to verify) this fails attempt 1 on the `check` stage and then
+    /// verifies on attempt 2 — exactly one retry.
+    struct RedThenGreenRunner {
+        calls: std::sync::atomic::AtomicU32,
+    }
+
+    impl RedThenGreenRunner {
+        fn new() -> Self {
+            Self { calls: std::sync::atomic::AtomicU32::new(0) }
+        }
+    }
+
+    impl tdd_core::Runner for RedThenGreenRunner {
+        fn fmt_check(&self) -> anyhow::Result<RunnerOutcome> {
+            Ok(RunnerOutcome { ok: true, ..Default::default() })
+        }
+
+        fn fmt(&self) -> anyhow::Result<RunnerOutcome> {
+            Ok(RunnerOutcome { ok: true, ..Default::default() })
+        }
+
+        fn check(&self) -> anyhow::Result<RunnerOutcome> {
+            let call = self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(RunnerOutcome { ok: call > 0, ..Default::default() })
+        }
+
+        fn test(&self) -> anyhow::Result<RunnerOutcome> {
+            Ok(RunnerOutcome { ok: false, ..Default::default() })
+        }
+    }
+
+    struct AlwaysFailingCheckRunner;
+
+    impl tdd_core::Runner for AlwaysFailingCheckRunner {
+        fn fmt_check(&self) -> anyhow::Result<RunnerOutcome> {
+            Ok(RunnerOutcome { ok: true, ..Default::default() })
+        }
+
+        fn fmt(&self) -> anyhow::Result<RunnerOutcome> {
+            Ok(RunnerOutcome { ok: true, ..Default::default() })
+        }
+
+        fn check(&self) -> anyhow::Result<RunnerOutcome> {
+            Ok(RunnerOutcome { ok: false, ..Default::default() })
+        }
+
+        fn test(&self) -> anyhow::Result<RunnerOutcome> {
+            Ok(RunnerOutcome { ok: false, ..Default::default() })
+        }
+    }
+
+    #[test]
+    fn render_table_averages_each_variant_independently() {
+        let report = ExperimentReport {
+            trials: vec![
+                TrialOutcome { variant: "a".to_string(), trial: 0, steps_executed: 3, retries: 2, scope_violations: 0, ci_green: true, wall_time_secs: 1.0, stop_reason: Some(StopReason::Completed), error: None },
+                TrialOutcome { variant: "a".to_string(), trial: 1, steps_executed: 3, retries: 4, scope_violations: 2, ci_green: false, wall_time_secs: 3.0, stop_reason: Some(StopReason::Failed), error: None },
+                TrialOutcome { variant: "b".to_string(), trial: 0, steps_executed: 3, retries: 0, scope_violations: 0, ci_green: true, wall_time_secs: 2.0, stop_reason: Some(StopReason::Completed), error: None },
+            ],
+        };
+
+        let table = report.render_table();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with("a"));
+        assert!(lines[1].contains(" 1 ")); // one of two trials went green
+        assert!(lines[2].starts_with("b"));
+    }
+
+    #[test]
+    fn is_scope_violation_matches_a_readonly_rejection_and_nothing_else() {
+        assert!(is_scope_violation(&StepFailureDetail::Other { message: "contracts/api.rs is protected by readonly_paths (matches `contracts/**`)".to_string() }));
+        assert!(!is_scope_violation(&StepFailureDetail::Other { message: "something else entirely".to_string() }));
+        assert!(!is_scope_violation(&StepFailureDetail::CiFailure { stage: "test".to_string(), stderr_tail: String::new() }));
+    }
+
+    #[tokio::test]
+    async fn two_variants_times_two_trials_restore_the_snapshot_exactly_four_times() {
+        let dir = tempfile::tempdir().unwrap();
+        let variants = vec![Variant { label: "a".to_string(), prompt: "prompt a".to_string() }, Variant { label: "b".to_string(), prompt: "prompt b".to_string() }];
+        let restores = Arc::new(AtomicU32::new(0));
+        let restores_for_hook = restores.clone();
+
+        // The orchestrator build itself fails fast (no `tdd.yaml` in this
+        // bare tempdir), which is fine here: the isolation guarantee under
+        // test is restore-per-trial, independent of whether a trial's
+        // orchestrator build succeeds.
+        let report = run_trials(dir.path(), &variants, 1, 2, &RecordingVcs, "deadbeef", || {
+            restores_for_hook.fetch_add(1, Ordering::SeqCst);
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(restores.load(Ordering::SeqCst), 4);
+        assert_eq!(report.trials.len(), 4);
+        assert_eq!(report.trials.iter().map(|trial| trial.variant.clone()).collect::<Vec<_>>(), vec!["a", "b", "a", "b"]);
+        assert!(report.trials.iter().all(|trial| trial.error.is_some()), "expected every trial to fail fast on orchestrator build");
+    }
+
+    #[tokio::test]
+    async fn a_failing_trial_is_recorded_without_aborting_the_experiment() {
+        let dir = tempfile::tempdir().unwrap();
+        tdd_core::Vcs::init_if_needed(&tdd_exec::GitVcs::new(dir.path(), tdd_exec::CommitAuthor::default())).unwrap();
+
+        std::fs::write(dir.path().join("kata.md"), "Build a thing.").unwrap();
+        std::fs::write(
+            dir.path().join("tdd.yaml"),
+            "kata_description: kata.md\nroles:\n  tester: {model: stub}\n  implementor: {model: stub}\n  refactorer: {model: stub}\n",
+        )
+        .unwrap();
+
+        let variants = vec![Variant { label: "broken".to_string(), prompt: "irrelevant, the config itself is incomplete".to_string() }];
+        let report = run_trials(dir.path(), &variants, 1, 1, &RecordingVcs, "deadbeef", || {}).await.unwrap();
+
+        assert_eq!(report.trials.len(), 1);
+        assert!(report.trials[0].error.is_some());
+        assert!(!report.trials[0].ci_green);
+    }
+
+    fn experiment_args(path: PathBuf, variant: PathBuf, force: bool) -> crate::cli::ExperimentArgs {
+        crate::cli::ExperimentArgs { path, variants: vec![variant], cycles: 1, trials: 1, force }
+    }
+
+    #[tokio::test]
+    async fn uncommitted_changes_are_refused_without_force() {
+        let dir = tempfile::tempdir().unwrap();
+        let vcs = tdd_exec::GitVcs::new(dir.path(), tdd_exec::CommitAuthor::default());
+        tdd_core::Vcs::init_if_needed(&vcs).unwrap();
+        std::fs::write(dir.path().join("kata.md"), "Build a thing.").unwrap();
+        tdd_core::Vcs::stage_all(&vcs).unwrap();
+        tdd_core::Vcs::commit(&vcs, "chore: initial scaffold").unwrap();
+
+        // An uncommitted, untracked file: exactly what `reset_hard` would
+        // silently delete.
+        std::fs::write(dir.path().join("scratch.txt"), "not yet committed").unwrap();
+
+        let variant_path = dir.path().join("variant.tmpl");
+        std::fs::write(&variant_path, "prompt").unwrap();
+
+        let error = run(experiment_args(dir.path().to_path_buf(), variant_path, false)).await.unwrap_err();
+        assert!(error.to_string().contains("uncommitted changes"), "unexpected error: {error}");
+        assert!(dir.path().join("scratch.txt").exists(), "the guard must not touch the working tree before refusing");
+    }
+
+    #[tokio::test]
+    async fn force_skips_the_clean_check_and_proceeds_to_the_first_reset() {
+        let dir = tempfile::tempdir().unwrap();
+        let vcs = tdd_exec::GitVcs::new(dir.path(), tdd_exec::CommitAuthor::default());
+        tdd_core::Vcs::init_if_needed(&vcs).unwrap();
+        std::fs::write(dir.path().join("kata.md"), "Build a thing.").unwrap();
+        tdd_core::Vcs::stage_all(&vcs).unwrap();
+        tdd_core::Vcs::commit(&vcs, "chore: initial scaffold").unwrap();
+
+        std::fs::write(dir.path().join("scratch.txt"), "not yet committed").unwrap();
+
+        let variant_path = dir.path().join("variant.tmpl");
+        std::fs::write(&variant_path, "prompt").unwrap();
+
+        // No `tdd.yaml`, so the run still fails once it gets to building the
+        // orchestrator — but that's well past the clean-tree guard, and the
+        // untracked file is gone, proving `--force` let the reset through.
+        run(experiment_args(dir.path().to_path_buf(), variant_path, true)).await.unwrap();
+        assert!(!dir.path().join("scratch.txt").exists());
+    }
+
+    fn stub_orchestrator(repo_root: PathBuf, runner: Box<dyn tdd_core::Runner + Send>, max_attempts: u32) -> LoopOrchestrator {
+        LoopOrchestrator::new(stub_agents(), Box::new(RecordingVcs), repo_root, String::new(), max_attempts).unwrap().with_runner(runner)
+    }
+
+    #[tokio::test]
+    async fn attempt_failed_events_are_counted_as_retries_and_scope_violations_separately() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut orchestrator = stub_orchestrator(dir.path().to_path_buf(), Box::new(RedThenGreenRunner::new()), 3);
+
+        let retries = Arc::new(AtomicU32::new(0));
+        let scope_violations = Arc::new(AtomicU32::new(0));
+        let retries_for_observer = retries.clone();
+        let scope_violations_for_observer = scope_violations.clone();
+        orchestrator = orchestrator.add_observer(Box::new(move |event| match &event {
+            StepEvent::AttemptStarted { attempt, .. } if *attempt > 1 => {
+                retries_for_observer.fetch_add(1, Ordering::SeqCst);
+            }
+            StepEvent::AttemptFailed { detail, .. } if is_scope_violation(detail) => {
+                scope_violations_for_observer.fetch_add(1, Ordering::SeqCst);
+            }
+            _ => {}
+        }));
+
+        let (record, error) = run_log::execute_steps(&mut orchestrator, 1, String::new(), None).await;
+
+        assert!(error.is_none());
+        assert_eq!(record.stop_reason, StopReason::Completed);
+        assert_eq!(retries.load(Ordering::SeqCst), 1, "the Tester's first check() call fails, triggering exactly one retry");
+        assert_eq!(scope_violations.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn an_exhausted_step_still_reports_its_retries() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut orchestrator = stub_orchestrator(dir.path().to_path_buf(), Box::new(AlwaysFailingCheckRunner), 2);
+
+        let retries = Arc::new(AtomicU32::new(0));
+        let retries_for_observer = retries.clone();
+        orchestrator = orchestrator.add_observer(Box::new(move |event| {
+            if let StepEvent::AttemptStarted { attempt, .. } = &event {
+                if *attempt > 1 {
+                    retries_for_observer.fetch_add(1, Ordering::SeqCst);
+                }
+            }
+        }));
+
+        let (record, error) = run_log::execute_steps(&mut orchestrator, 1, String::new(), None).await;
+
+        assert!(error.is_some());
+        assert_eq!(record.stop_reason, StopReason::Failed);
+        assert_eq!(retries.load(Ordering::SeqCst), 1, "one retry attempt ran before the step's budget was exhausted");
+    }
+}
@@ -0,0 +1,416 @@
+//! Implements `tdd-cli export --html <dir>`: renders a kata session as a
+//! self-contained static HTML bundle for sharing with non-technical
+//! stakeholders — an index with the cycle timeline, one page per step
+//! (plan, commit message, patch, CI outcome), and a summary page. Every
+//! asset is inline or generated locally (no CDN), and the page shell is
+//! embedded via `include_str!` and filled in with
+//! [`tdd_core::template::render`], so the bundle renders correctly opened
+//! straight off disk via `file://`.
+//!
+//! Steps are identified the same way [`crate::diff`] and [`crate::undo`]
+//! do: by walking `HEAD`'s history for commits matching
+//! [`crate::undo::parse_step_commit`]'s `"<type>: step <n>"` convention.
+//! A step's plan and log artifacts are looked up separately via
+//! [`tdd_core::artifacts::resolve_step`], since neither is addressable
+//! from the commit alone.
+
+use crate::step_log::StepLog;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tdd_core::{artifacts, template, Role, Vcs};
+use tdd_exec::GitVcs;
+
+const PAGE_TEMPLATE: &str = include_str!("../templates/export/page.html");
+
+/// One committed step gathered for export, in commit order.
+struct StepRecord {
+    step: u32,
+    role: Role,
+    commit_id: String,
+    commit_message: String,
+    plan: Option<String>,
+    patch: Option<String>,
+    log: Option<StepLog>,
+}
+
+/// A red-green-refactor cycle: a Tester step and the Implementor/
+/// Refactorer steps that complete it, grouped the same way
+/// [`crate::changelog::cycle_number`] groups changelog entries.
+struct CycleSummary {
+    number: u32,
+    steps: Vec<StepRecord>,
+}
+
+/// Writes `repo_root`'s session as a static HTML bundle under `out_dir`:
+/// `index.html`, one `step-{n}.html` per recorded step, and
+/// `summary.html`. Creates `out_dir` if it doesn't exist; overwrites
+/// anything already there under the same names. Returns the paths
+/// written, for the caller to report.
+pub fn export_html(repo_root: &Path, out_dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let vcs = GitVcs::open_existing(repo_root)?;
+    let cycles = group_into_cycles(gather_steps(repo_root, &vcs)?);
+
+    std::fs::create_dir_all(out_dir)?;
+    let mut written = Vec::new();
+
+    let index_path = out_dir.join("index.html");
+    std::fs::write(&index_path, render_index(&cycles))?;
+    written.push(index_path);
+
+    for cycle in &cycles {
+        for step in &cycle.steps {
+            let step_path = out_dir.join(format!("step-{}.html", step.step));
+            std::fs::write(&step_path, render_step(step))?;
+            written.push(step_path);
+        }
+    }
+
+    let summary_path = out_dir.join("summary.html");
+    std::fs::write(&summary_path, render_summary(&cycles))?;
+    written.push(summary_path);
+
+    Ok(written)
+}
+
+/// Walks `HEAD`'s history for step commits, oldest first, pairing each
+/// with its patch (via [`tdd_core::Vcs::diff_range`] against its parent)
+/// and its current plan/log artifacts. A step with no recognizable
+/// commit contributes nothing; a step whose plan or log was since
+/// archived (e.g. by `undo`) degrades to `None` rather than failing the
+/// whole export.
+fn gather_steps(repo_root: &Path, vcs: &dyn Vcs) -> anyhow::Result<Vec<StepRecord>> {
+    let repo = git2::Repository::open(repo_root)?;
+    let mut revwalk = repo.revwalk()?;
+    if revwalk.push_head().is_err() {
+        // An unborn HEAD (a freshly initialized workspace with no commits
+        // yet) has nothing to export.
+        return Ok(Vec::new());
+    }
+    revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+
+    let plan_names = artifact_names(&repo_root.join(".tdd").join("plan"));
+    let log_names = artifact_names(&repo_root.join(".tdd").join("logs"));
+
+    let mut steps = Vec::new();
+    for oid in revwalk {
+        let commit = repo.find_commit(oid?)?;
+        let message = commit.message().unwrap_or_default().to_string();
+        let Some((role, step)) = crate::undo::parse_step_commit(&message) else {
+            continue;
+        };
+
+        let parent = commit.parent_id(0).ok().map(|id| id.to_string());
+        let patch = vcs.diff_range(parent.as_deref(), &commit.id().to_string()).ok();
+
+        let plan = artifacts::resolve_step(plan_names.iter().map(String::as_str), ".md", step, None)
+            .and_then(|name| std::fs::read_to_string(repo_root.join(".tdd").join("plan").join(name)).ok());
+        let log = artifacts::resolve_step(log_names.iter().map(String::as_str), ".json", step, None)
+            .and_then(|name| std::fs::read_to_string(repo_root.join(".tdd").join("logs").join(name)).ok())
+            .and_then(|content| serde_json::from_str(&content).ok());
+
+        steps.push(StepRecord { step, role, commit_id: commit.id().to_string(), commit_message: message, plan, patch, log });
+    }
+    Ok(steps)
+}
+
+fn artifact_names(dir: &Path) -> Vec<String> {
+    std::fs::read_dir(dir)
+        .map(|entries| entries.filter_map(|entry| entry.ok()).filter_map(|entry| entry.file_name().to_str().map(str::to_string)).collect())
+        .unwrap_or_default()
+}
+
+/// Groups `steps` into cycles using the same rule
+/// [`crate::changelog::cycle_number`] applies: a Tester step starts a new
+/// cycle; every other role continues the most recently started one.
+fn group_into_cycles(steps: Vec<StepRecord>) -> Vec<CycleSummary> {
+    let mut cycles: Vec<CycleSummary> = Vec::new();
+    for step in steps {
+        if cycles.is_empty() || step.role == Role::Tester {
+            cycles.push(CycleSummary { number: cycles.len() as u32 + 1, steps: Vec::new() });
+        }
+        cycles.last_mut().expect("just pushed if empty").steps.push(step);
+    }
+    cycles
+}
+
+fn page(title: &str, nav: &str, body: &str) -> String {
+    let mut vars = HashMap::new();
+    vars.insert("title", escape_html(title));
+    vars.insert("nav", nav.to_string());
+    vars.insert("body", body.to_string());
+    template::render(PAGE_TEMPLATE, &vars)
+}
+
+fn nav() -> &'static str {
+    r#"<a href="index.html">Index</a><a href="summary.html">Summary</a>"#
+}
+
+fn render_index(cycles: &[CycleSummary]) -> String {
+    let mut body = String::from("<h1>Kata session</h1>\n");
+    if cycles.is_empty() {
+        body.push_str(r#"<p class="placeholder">No recorded steps yet.</p>"#);
+        return page("Kata session", nav(), &body);
+    }
+    for cycle in cycles {
+        body.push_str(&format!("<h2>Cycle {}</h2>\n<ul>\n", cycle.number));
+        for step in &cycle.steps {
+            body.push_str(&format!(
+                r#"<li><a href="step-{step}.html">step {step} — {role}</a>: {summary}</li>{nl}"#,
+                step = step.step,
+                role = escape_html(&step.role.to_string()),
+                summary = escape_html(first_line(&step.commit_message)),
+                nl = "\n",
+            ));
+        }
+        body.push_str("</ul>\n");
+    }
+    page("Kata session", nav(), &body)
+}
+
+fn render_step(step: &StepRecord) -> String {
+    let title = format!("Step {} — {}", step.step, step.role);
+    let mut body = format!("<h1>{}</h1>\n", escape_html(&title));
+    body.push_str(&format!("<p><strong>Commit:</strong> {}</p>\n", escape_html(&step.commit_id)));
+    body.push_str(&format!("<h2>Commit message</h2>\n<pre>{}</pre>\n", escape_html(&step.commit_message)));
+
+    body.push_str("<h2>Plan</h2>\n");
+    match &step.plan {
+        Some(plan) => body.push_str(&format!("<pre>{}</pre>\n", escape_html(plan))),
+        None => body.push_str(r#"<p class="placeholder">No plan recorded for this step.</p>"#),
+    }
+
+    body.push_str("<h2>Patch</h2>\n");
+    match &step.patch {
+        Some(patch) if !patch.is_empty() => body.push_str(&format!("<pre>{}</pre>\n", highlight_patch(patch))),
+        _ => body.push_str(r#"<p class="placeholder">No patch available for this step.</p>"#),
+    }
+
+    body.push_str("<h2>CI outcome</h2>\n");
+    match &step.log {
+        Some(log) => {
+            body.push_str(&format!("<p>{}</p>\n", escape_html(&log.format_verification())));
+            if let Some(failure) = &log.failure {
+                body.push_str(&format!("<p><strong>Failure:</strong> {}</p>\n", escape_html(&format!("{failure:?}"))));
+            }
+            if log.hooks_bypassed {
+                body.push_str(r#"<p><em>Commit hooks were bypassed for this step.</em></p>"#);
+            }
+        }
+        None => body.push_str(r#"<p class="placeholder">No verification log recorded for this step.</p>"#),
+    }
+
+    page(&title, nav(), &body)
+}
+
+fn render_summary(cycles: &[CycleSummary]) -> String {
+    let steps: Vec<&StepRecord> = cycles.iter().flat_map(|cycle| &cycle.steps).collect();
+    let mut by_role: HashMap<Role, u32> = HashMap::new();
+    let mut hooks_bypassed = 0u32;
+    for step in &steps {
+        *by_role.entry(step.role).or_default() += 1;
+        if step.log.as_ref().is_some_and(|log| log.hooks_bypassed) {
+            hooks_bypassed += 1;
+        }
+    }
+
+    let mut body = String::from("<h1>Summary</h1>\n<table>\n");
+    body.push_str(&format!("<tr><th>Cycles</th><td>{}</td></tr>\n", cycles.len()));
+    body.push_str(&format!("<tr><th>Steps</th><td>{}</td></tr>\n", steps.len()));
+    for role in [Role::Tester, Role::Implementor, Role::Refactorer] {
+        body.push_str(&format!("<tr><th>{} steps</th><td>{}</td></tr>\n", escape_html(&role.to_string()), by_role.get(&role).copied().unwrap_or(0)));
+    }
+    body.push_str(&format!("<tr><th>Steps committed with hooks bypassed</th><td>{hooks_bypassed}</td></tr>\n"));
+    body.push_str("</table>\n");
+
+    page("Summary", nav(), &body)
+}
+
+fn first_line(text: &str) -> &str {
+    text.lines().next().unwrap_or(text)
+}
+
+/// Escapes the five characters that matter for safe inline placement in
+/// HTML text and attribute content. No markdown or HTML is otherwise
+/// interpreted — a plan, commit message, or patch renders as literal
+/// text inside a `<pre>`, never as markup.
+fn escape_html(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// A minimal diff highlighter: classifies each line by its leading
+/// character and wraps it in a `<span>` the page's inline stylesheet
+/// colors, after escaping. Deliberately scoped to unified-diff syntax
+/// rather than full Rust highlighting — the patch is the one thing in
+/// this bundle dense enough to need it.
+fn highlight_patch(patch: &str) -> String {
+    patch
+        .lines()
+        .map(|line| {
+            let class = if line.starts_with("+++") || line.starts_with("---") {
+                "diff-meta"
+            } else if line.starts_with('+') {
+                "diff-add"
+            } else if line.starts_with('-') {
+                "diff-del"
+            } else if line.starts_with("@@") {
+                "diff-hunk"
+            } else {
+                "diff-ctx"
+            };
+            format!(r#"<span class="{class}">{}</span>"#, escape_html(line))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tdd_exec::CommitAuthor;
+    use tempfile::tempdir;
+
+    fn bot_vcs(dir: &Path) -> GitVcs {
+        GitVcs::new(dir, CommitAuthor::default())
+    }
+
+    fn commit_step(vcs: &GitVcs, path: &Path, file: &str, contents: &str, message: &str) -> String {
+        std::fs::write(path.join(file), contents).unwrap();
+        vcs.stage_all().unwrap();
+        vcs.commit(message).unwrap()
+    }
+
+    fn write_plan(path: &Path, step: u32, slug: &str, contents: &str) {
+        let dir = path.join(".tdd").join("plan");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(format!("step-{step}-{slug}.md")), contents).unwrap();
+    }
+
+    fn write_log(path: &Path, step: u32, slug: &str, log: &StepLog) {
+        let dir = path.join(".tdd").join("logs");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(format!("step-{step}-{slug}.json")), serde_json::to_string(log).unwrap()).unwrap();
+    }
+
+    fn two_cycle_session(dir: &Path) {
+        crate::init::run(&crate::init::InitArgs { path: dir.to_path_buf(), kata_url: None }).unwrap();
+        let vcs = bot_vcs(dir);
+
+        commit_step(&vcs, dir, "tests/api.rs", "#[test]\nfn it_fails() {}\n", "test: step 0");
+        write_plan(dir, 0, "tester", "Plan: write a failing test.");
+        write_log(dir, 0, "tester", &StepLog::default());
+
+        commit_step(&vcs, dir, "src/lib.rs", "pub fn add() -> i32 { 0 }\n", "feat: step 1");
+        write_plan(dir, 1, "implementor", "Plan: make it pass.");
+        write_log(dir, 1, "implementor", &StepLog { hooks_bypassed: true, ..Default::default() });
+
+        commit_step(&vcs, dir, "tests/api2.rs", "#[test]\nfn it_fails_again() {}\n", "test: step 2");
+        write_plan(dir, 2, "tester", "Plan: add another failing test.");
+        write_log(dir, 2, "tester", &StepLog::default());
+
+        commit_step(&vcs, dir, "src/lib.rs", "pub fn add() -> i32 { 1 }\n", "feat: step 3");
+        write_plan(dir, 3, "implementor", "Plan: make the second test pass.");
+        write_log(dir, 3, "implementor", &StepLog::default());
+    }
+
+    #[test]
+    fn a_two_cycle_session_exports_the_expected_file_set() {
+        let dir = tempdir().unwrap();
+        two_cycle_session(dir.path());
+        let out = dir.path().join("export");
+
+        let written = export_html(dir.path(), &out).unwrap();
+
+        let names: Vec<String> = written.iter().map(|path| path.file_name().unwrap().to_string_lossy().to_string()).collect();
+        assert!(names.contains(&"index.html".to_string()));
+        assert!(names.contains(&"summary.html".to_string()));
+        for step in 0..=3 {
+            assert!(names.contains(&format!("step-{step}.html")), "missing step-{step}.html in {names:?}");
+        }
+        assert_eq!(written.len(), 6);
+    }
+
+    #[test]
+    fn the_index_lists_both_cycles_with_links_that_resolve() {
+        let dir = tempdir().unwrap();
+        two_cycle_session(dir.path());
+        let out = dir.path().join("export");
+        export_html(dir.path(), &out).unwrap();
+
+        let index = std::fs::read_to_string(out.join("index.html")).unwrap();
+        assert!(index.contains("Cycle 1"));
+        assert!(index.contains("Cycle 2"));
+        for step in 0..=3 {
+            let href = format!(r#"href="step-{step}.html""#);
+            assert!(index.contains(&href), "index is missing a link for step {step}");
+            assert!(out.join(format!("step-{step}.html")).exists());
+        }
+    }
+
+    #[test]
+    fn a_step_page_contains_the_escaped_patch_content() {
+        let dir = tempdir().unwrap();
+        two_cycle_session(dir.path());
+        let out = dir.path().join("export");
+        export_html(dir.path(), &out).unwrap();
+
+        let step_page = std::fs::read_to_string(out.join("step-1.html")).unwrap();
+        assert!(step_page.contains("lib.rs"));
+        assert!(step_page.contains("diff-add"));
+        assert!(step_page.contains("Plan: make it pass."));
+        assert!(step_page.contains("bypassed"), "expected the hooks-bypassed note on step 1's page");
+    }
+
+    #[test]
+    fn html_special_characters_in_a_commit_message_are_escaped() {
+        let dir = tempdir().unwrap();
+        crate::init::run(&crate::init::InitArgs { path: dir.path().to_path_buf(), kata_url: None }).unwrap();
+        let vcs = bot_vcs(dir.path());
+        commit_step(&vcs, dir.path(), "tests/api.rs", "#[test]\nfn it_fails() {}\n", "test: step 0\n\n<script>alert(1)</script>");
+
+        let out = dir.path().join("export");
+        export_html(dir.path(), &out).unwrap();
+
+        let step_page = std::fs::read_to_string(out.join("step-0.html")).unwrap();
+        assert!(!step_page.contains("<script>alert(1)</script>"));
+        assert!(step_page.contains("&lt;script&gt;"));
+    }
+
+    #[test]
+    fn a_session_with_no_step_commits_still_exports_an_index_with_a_placeholder() {
+        let dir = tempdir().unwrap();
+        crate::init::run(&crate::init::InitArgs { path: dir.path().to_path_buf(), kata_url: None }).unwrap();
+
+        let out = dir.path().join("export");
+        let written = export_html(dir.path(), &out).unwrap();
+
+        assert_eq!(written.len(), 2);
+        let index = std::fs::read_to_string(out.join("index.html")).unwrap();
+        assert!(index.contains("No recorded steps yet."));
+    }
+
+    #[test]
+    fn a_missing_plan_or_log_degrades_to_a_placeholder_instead_of_failing() {
+        let dir = tempdir().unwrap();
+        crate::init::run(&crate::init::InitArgs { path: dir.path().to_path_buf(), kata_url: None }).unwrap();
+        let vcs = bot_vcs(dir.path());
+        commit_step(&vcs, dir.path(), "tests/api.rs", "#[test]\nfn it_fails() {}\n", "test: step 0");
+
+        let out = dir.path().join("export");
+        export_html(dir.path(), &out).unwrap();
+
+        let step_page = std::fs::read_to_string(out.join("step-0.html")).unwrap();
+        assert!(step_page.contains("No plan recorded for this step."));
+        assert!(step_page.contains("No verification log recorded for this step."));
+    }
+}
@@ -0,0 +1,204 @@
+//! Runs the repository's own `pre-commit`/`commit-msg` hooks ahead of a
+//! bot commit, or records that they were skipped. `GitVcs::commit` is
+//! `git2`-based and never touches `.git/hooks` on its own, which is
+//! surprising for teams that rely on hooks (a file-size guard, a secret
+//! scanner) as policy rather than convention. See
+//! [`crate::config::GitConfig::hooks`].
+
+use std::path::{Path, PathBuf};
+use tdd_exec::{CaptureConfig, ExecError};
+
+/// Whether a bot commit runs the repository's hooks or skips them
+/// outright. See [`crate::config::GitConfig::hooks`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HooksPolicy {
+    /// Run `pre-commit` and `commit-msg` before every commit. A non-zero
+    /// exit surfaces as `StepFailureDetail::HookRejected`, which the
+    /// orchestrator retries like any other failed attempt.
+    Run,
+    /// Skip hook execution entirely — the behavior before this existed,
+    /// now explicit and recorded as `hooks_bypassed: true` in the step
+    /// log and commit body.
+    #[default]
+    Bypass,
+}
+
+/// The directory hooks are resolved from: `core.hooksPath` if the
+/// repository sets one, else its own `.git/hooks` (which, for a linked
+/// worktree, `git2` already resolves to the shared `.git` directory's
+/// `hooks/`).
+fn hooks_dir(repo_root: &Path) -> anyhow::Result<PathBuf> {
+    let repo = git2::Repository::open(repo_root)?;
+    if let Ok(configured) = repo.config()?.get_path("core.hooksPath") {
+        return Ok(if configured.is_absolute() { configured } else { repo_root.join(configured) });
+    }
+    Ok(repo.path().join("hooks"))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path).map(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0).unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// Runs `name` (`"pre-commit"` or `"commit-msg"`) from the resolved hooks
+/// directory with `args`, reusing [`tdd_exec::run_command_captured`]. A
+/// missing or non-executable hook is not an error, matching git's own
+/// behavior when no hook is installed.
+fn run_hook(repo_root: &Path, name: &str, args: &[&str]) -> anyhow::Result<()> {
+    let hook_path = hooks_dir(repo_root)?.join(name);
+    if !is_executable(&hook_path) {
+        return Ok(());
+    }
+    let outcome = tdd_exec::run_command_captured(
+        &hook_path.to_string_lossy(),
+        args,
+        repo_root,
+        &CaptureConfig {
+            label: name.to_string(),
+            ..Default::default()
+        },
+    )?;
+    if !outcome.ok {
+        return Err(ExecError::HookRejected {
+            hook: name.to_string(),
+            stderr: outcome.stderr.inline,
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Runs the repository's `pre-commit` hook, then its `commit-msg` hook
+/// (with `message` written to a scratch file under `.tdd/tmp/`, per
+/// githooks(5)), ahead of a bot commit — unless `policy` is
+/// [`HooksPolicy::Bypass`], in which case this is a no-op. Returns
+/// whether the hooks were bypassed, for
+/// [`crate::step_log::StepLog::hooks_bypassed`] and the commit body
+/// trailer.
+pub fn run_hooks(repo_root: &Path, policy: HooksPolicy, message: &str) -> anyhow::Result<bool> {
+    if policy == HooksPolicy::Bypass {
+        return Ok(true);
+    }
+
+    run_hook(repo_root, "pre-commit", &[])?;
+
+    let message_path = repo_root.join(".tdd/tmp/commit-msg.txt");
+    if let Some(parent) = message_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&message_path, message)?;
+    let result = run_hook(repo_root, "commit-msg", &[&message_path.to_string_lossy()]);
+    let _ = std::fs::remove_file(&message_path);
+    result?;
+
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tdd_core::Vcs;
+    use tdd_exec::{CommitAuthor, GitVcs};
+    use tempfile::tempdir;
+
+    fn init_repo(repo_root: &Path) -> GitVcs {
+        let vcs = GitVcs::new(repo_root, CommitAuthor::default());
+        vcs.init_if_needed().unwrap();
+        std::fs::write(repo_root.join("kata.md"), "# Kata\n").unwrap();
+        vcs.stage_all().unwrap();
+        vcs.commit("chore: initial scaffold").unwrap();
+        vcs
+    }
+
+    #[cfg(unix)]
+    fn install_hook(repo_root: &Path, name: &str, script: &str) {
+        use std::os::unix::fs::PermissionsExt;
+        let path = repo_root.join(".git/hooks").join(name);
+        std::fs::write(&path, script).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[test]
+    fn bypass_policy_never_touches_the_hooks_directory_and_reports_bypassed() {
+        let dir = tempdir().unwrap();
+        init_repo(dir.path());
+        #[cfg(unix)]
+        install_hook(dir.path(), "pre-commit", "#!/bin/sh\nexit 1\n");
+
+        let bypassed = run_hooks(dir.path(), HooksPolicy::Bypass, "feat: add thing").unwrap();
+
+        assert!(bypassed);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_policy_with_no_hooks_installed_is_a_no_op() {
+        let dir = tempdir().unwrap();
+        init_repo(dir.path());
+
+        let bypassed = run_hooks(dir.path(), HooksPolicy::Run, "feat: add thing").unwrap();
+
+        assert!(!bypassed);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_policy_surfaces_a_pre_commit_rejection_with_its_stderr() {
+        let dir = tempdir().unwrap();
+        init_repo(dir.path());
+        install_hook(dir.path(), "pre-commit", "#!/bin/sh\necho 'marker.bin exceeds the 1 MB limit' >&2\nexit 1\n");
+
+        let error = run_hooks(dir.path(), HooksPolicy::Run, "feat: add thing").unwrap_err();
+
+        let exec_error = error.downcast_ref::<ExecError>().expect("expected an ExecError");
+        match exec_error {
+            ExecError::HookRejected { hook, stderr } => {
+                assert_eq!(hook, "pre-commit");
+                assert!(stderr.contains("marker.bin exceeds the 1 MB limit"));
+            }
+            other => panic!("expected HookRejected, got {other:?}"),
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_policy_surfaces_a_commit_msg_rejection_with_the_hooks_message() {
+        let dir = tempdir().unwrap();
+        init_repo(dir.path());
+        install_hook(dir.path(), "commit-msg", "#!/bin/sh\necho 'commit messages must reference a ticket' >&2\nexit 1\n");
+
+        let error = run_hooks(dir.path(), HooksPolicy::Run, "feat: add thing").unwrap_err();
+
+        let exec_error = error.downcast_ref::<ExecError>().expect("expected an ExecError");
+        match exec_error {
+            ExecError::HookRejected { hook, stderr } => {
+                assert_eq!(hook, "commit-msg");
+                assert!(stderr.contains("commit messages must reference a ticket"));
+            }
+            other => panic!("expected HookRejected, got {other:?}"),
+        }
+        assert!(!dir.path().join(".tdd/tmp/commit-msg.txt").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn core_hooks_path_is_resolved_relative_to_the_repo_root() {
+        let dir = tempdir().unwrap();
+        init_repo(dir.path());
+        std::fs::create_dir_all(dir.path().join("custom-hooks")).unwrap();
+        install_hook(dir.path(), "pre-commit", "#!/bin/sh\nexit 0\n");
+        std::fs::rename(dir.path().join(".git/hooks/pre-commit"), dir.path().join("custom-hooks/pre-commit")).unwrap();
+        git2::Repository::open(dir.path()).unwrap().config().unwrap().set_str("core.hooksPath", "custom-hooks").unwrap();
+
+        let bypassed = run_hooks(dir.path(), HooksPolicy::Run, "feat: add thing").unwrap();
+
+        assert!(!bypassed);
+    }
+}
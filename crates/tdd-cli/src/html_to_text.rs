@@ -0,0 +1,107 @@
+//! A minimal HTML-to-markdown-ish converter for kata pages fetched with
+//! `--kata-url`. This deliberately avoids a full HTML parser: kata sites
+//! are simple prose pages, and we only need headings, lists, code blocks,
+//! and paragraph text to survive the trip.
+
+/// Converts `html` into a plain/markdown-ish text approximation, stripping
+/// tags we don't understand and preserving the handful we do.
+pub fn html_to_text(html: &str) -> String {
+    let mut out = String::new();
+    let mut in_tag = false;
+    let mut tag_name = String::new();
+    let mut in_pre = false;
+
+    for c in html.chars() {
+        if c == '<' {
+            in_tag = true;
+            tag_name.clear();
+            continue;
+        }
+        if in_tag {
+            if c == '>' {
+                in_tag = false;
+                apply_tag(&tag_name, &mut out, &mut in_pre);
+            } else {
+                tag_name.push(c);
+            }
+            continue;
+        }
+        out.push(c);
+    }
+
+    decode_entities(&collapse_blank_lines(&out))
+}
+
+fn apply_tag(tag: &str, out: &mut String, in_pre: &mut bool) {
+    let lower = tag.trim().to_ascii_lowercase();
+    let name = lower.trim_start_matches('/').split_whitespace().next().unwrap_or("");
+    let closing = lower.starts_with('/');
+
+    match name {
+        "h1" | "h2" | "h3" | "h4" | "p" | "div" | "li" | "br" | "tr" if !out.ends_with('\n') => {
+            out.push('\n');
+        }
+        "pre" | "code" => *in_pre = !closing,
+        _ => {}
+    }
+
+    if name == "li" && !closing {
+        out.push_str("- ");
+    }
+}
+
+fn collapse_blank_lines(input: &str) -> String {
+    let mut collapsed = String::new();
+    let mut last_was_blank = false;
+    for line in input.lines() {
+        let trimmed = line.trim_end();
+        let is_blank = trimmed.trim().is_empty();
+        if is_blank && last_was_blank {
+            continue;
+        }
+        collapsed.push_str(trimmed);
+        collapsed.push('\n');
+        last_was_blank = is_blank;
+    }
+    collapsed.trim().to_string() + "\n"
+}
+
+fn decode_entities(input: &str) -> String {
+    input
+        .replace("&amp;", "&")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_tags_and_keeps_headings() {
+        let html = "<html><body><h1>Bowling Game</h1><p>Score a game.</p></body></html>";
+        let text = html_to_text(html);
+        assert!(text.contains("Bowling Game"));
+        assert!(text.contains("Score a game."));
+        assert!(!text.contains('<'));
+    }
+
+    #[test]
+    fn converts_list_items_to_markdown_bullets() {
+        let html = "<ul><li>First rule</li><li>Second rule</li></ul>";
+        let text = html_to_text(html);
+        assert!(text.contains("- First rule"));
+        assert!(text.contains("- Second rule"));
+    }
+
+    #[test]
+    fn decodes_common_entities() {
+        let html = "<p>Tom &amp; Jerry &mdash; &quot;fun&quot;</p>";
+        let text = html_to_text(html);
+        assert!(text.contains("Tom & Jerry"));
+        assert!(text.contains("\"fun\""));
+    }
+}
@@ -0,0 +1,132 @@
+//! Manages the block of `.gitignore` entries that keep `.tdd`'s sensitive,
+//! transient state out of commits, without taking over the whole file.
+
+use std::path::Path;
+
+const BEGIN_MARKER: &str = "# BEGIN tdd-cli managed ignores (do not edit by hand)";
+const END_MARKER: &str = "# END tdd-cli managed ignores";
+
+/// Paths the machine can regenerate or that may contain command output
+/// and LLM transcripts; `.tdd/plan/` and `.tdd/logs/*.json` are
+/// intentionally left out so they stay committable for traceability.
+const MANAGED_ENTRIES: &[&str] = &[".tdd/state/", ".tdd/logs/llm/", ".tdd/logs/raw/", ".tdd/cache/", ".tdd/tmp/"];
+
+/// Rewrites `.gitignore` at `repo_root` so it contains exactly one managed
+/// block covering [`MANAGED_ENTRIES`], skipping any entry an existing
+/// user rule already covers. Safe to call repeatedly: re-running replaces
+/// the previous managed block instead of appending another one.
+pub fn apply(repo_root: &Path) -> anyhow::Result<()> {
+    let path = repo_root.join(".gitignore");
+    let existing = std::fs::read_to_string(&path).unwrap_or_default();
+    let user_lines = strip_managed_block(&existing);
+
+    let needed: Vec<&str> = MANAGED_ENTRIES
+        .iter()
+        .filter(|entry| !is_already_covered(&user_lines, entry))
+        .copied()
+        .collect();
+
+    let mut out = user_lines.trim_end().to_string();
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    if !needed.is_empty() {
+        out.push_str(BEGIN_MARKER);
+        out.push('\n');
+        for entry in needed {
+            out.push_str(entry);
+            out.push('\n');
+        }
+        out.push_str(END_MARKER);
+        out.push('\n');
+    }
+
+    std::fs::write(path, out)?;
+    Ok(())
+}
+
+/// Returns `content` with any previously managed block removed, leaving
+/// the user's own rules untouched.
+fn strip_managed_block(content: &str) -> String {
+    let mut out = String::new();
+    let mut in_block = false;
+    for line in content.lines() {
+        if line == BEGIN_MARKER {
+            in_block = true;
+            continue;
+        }
+        if line == END_MARKER {
+            in_block = false;
+            continue;
+        }
+        if !in_block {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// A user rule "covers" a managed entry when it is that exact path, or a
+/// prefix of it ending in `/` (e.g. `.tdd/` covers `.tdd/state/`).
+fn is_already_covered(user_lines: &str, entry: &str) -> bool {
+    user_lines.lines().map(str::trim).any(|line| {
+        if line.is_empty() || line.starts_with('#') {
+            return false;
+        }
+        line == entry || (line.ends_with('/') && entry.starts_with(line))
+    })
+}
+
+/// Sensitive directories under `.tdd` that must never be tracked by git,
+/// used by `tdd-cli doctor` to flag policy violations.
+pub fn sensitive_dirs() -> &'static [&'static str] {
+    MANAGED_ENTRIES
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn fresh_init_writes_the_managed_block() {
+        let dir = tempdir().unwrap();
+        apply(dir.path()).unwrap();
+
+        let content = std::fs::read_to_string(dir.path().join(".gitignore")).unwrap();
+        assert!(content.contains(".tdd/state/"));
+        assert!(content.contains(BEGIN_MARKER));
+    }
+
+    #[test]
+    fn fresh_init_ignores_spilled_raw_output() {
+        let dir = tempdir().unwrap();
+        apply(dir.path()).unwrap();
+
+        let content = std::fs::read_to_string(dir.path().join(".gitignore")).unwrap();
+        assert!(content.contains(".tdd/logs/raw/"));
+    }
+
+    #[test]
+    fn re_applying_does_not_duplicate_the_block() {
+        let dir = tempdir().unwrap();
+        apply(dir.path()).unwrap();
+        apply(dir.path()).unwrap();
+
+        let content = std::fs::read_to_string(dir.path().join(".gitignore")).unwrap();
+        assert_eq!(content.matches(BEGIN_MARKER).count(), 1);
+    }
+
+    #[test]
+    fn a_broader_user_rule_suppresses_the_redundant_entry() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join(".gitignore"), ".tdd/\n").unwrap();
+
+        apply(dir.path()).unwrap();
+
+        let content = std::fs::read_to_string(dir.path().join(".gitignore")).unwrap();
+        assert!(!content.contains(".tdd/state/"));
+        assert!(content.contains(".tdd/\n"));
+    }
+}
@@ -0,0 +1,108 @@
+//! Implements `tdd-cli init`: scaffolds a new kata workspace.
+
+use crate::config::TddConfig;
+use crate::kata_source::{fetch_kata_markdown, KataSource};
+use std::path::{Path, PathBuf};
+use tdd_exec::{CommitAuthor, GitVcs};
+use tdd_core::Vcs;
+
+/// Arguments accepted by `tdd-cli init`.
+#[derive(Debug, Clone, clap::Args)]
+pub struct InitArgs {
+    /// Directory to initialize. Defaults to the current directory.
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+
+    /// Fetch the kata description from a URL instead of writing a
+    /// placeholder `kata.md`.
+    #[arg(long)]
+    pub kata_url: Option<String>,
+}
+
+const KATA_PLACEHOLDER: &str = "# Kata\n\nDescribe the kata here.\n";
+
+const GITIGNORE: &str = "/target\n";
+
+const CONTEXT_FILE_PLACEHOLDER: &str =
+    "<!-- Standing instructions included in every step's prompt, ahead of the kata description. Leave empty to omit. -->\n";
+
+/// Runs `tdd-cli init`, creating the cargo scaffold, `kata.md`, `tdd.yaml`,
+/// and the git repository if one doesn't already exist.
+pub fn run(args: &InitArgs) -> anyhow::Result<()> {
+    let root = &args.path;
+    std::fs::create_dir_all(root)?;
+    std::fs::create_dir_all(root.join("src"))?;
+    std::fs::create_dir_all(root.join("tests"))?;
+
+    write_if_absent(&root.join("Cargo.toml"), DEFAULT_CARGO_TOML)?;
+    write_if_absent(&root.join("src/lib.rs"), "")?;
+    write_if_absent(&root.join(".gitignore"), GITIGNORE)?;
+    write_if_absent(&root.join("rust-toolchain.toml"), crate::RUST_TOOLCHAIN_TOML)?;
+    crate::ignore_policy::apply(root)?;
+
+    write_kata_description(root, args.kata_url.as_deref())?;
+
+    std::fs::create_dir_all(root.join(".tdd"))?;
+    write_if_absent(&root.join(crate::config::DEFAULT_CONTEXT_FILE), CONTEXT_FILE_PLACEHOLDER)?;
+
+    if !root.join("tdd.yaml").exists() {
+        TddConfig::default().save(&root.join("tdd.yaml"))?;
+    }
+
+    let vcs = GitVcs::new(root, CommitAuthor::default());
+    vcs.init_if_needed()?;
+
+    Ok(())
+}
+
+/// Writes `kata.md`, either from `kata_url` (leaving existing files
+/// untouched on network failure) or as a placeholder if no URL was given.
+fn write_kata_description(root: &Path, kata_url: Option<&str>) -> anyhow::Result<()> {
+    let kata_path = root.join("kata.md");
+
+    let Some(url) = kata_url else {
+        write_if_absent(&kata_path, KATA_PLACEHOLDER)?;
+        return Ok(());
+    };
+
+    let (markdown, fetched_at) = fetch_kata_markdown(url)?;
+    std::fs::write(&kata_path, markdown)?;
+    KataSource {
+        url: url.to_string(),
+        fetched_at,
+    }
+    .save(root)?;
+
+    Ok(())
+}
+
+fn write_if_absent(path: &Path, content: &str) -> anyhow::Result<()> {
+    if !path.exists() {
+        std::fs::write(path, content)?;
+    }
+    Ok(())
+}
+
+const DEFAULT_CARGO_TOML: &str = "[package]\nname = \"kata\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\n";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn init_creates_placeholder_kata_when_no_url_given() {
+        let dir = tempdir().unwrap();
+        let args = InitArgs {
+            path: dir.path().to_path_buf(),
+            kata_url: None,
+        };
+
+        run(&args).unwrap();
+
+        let kata = std::fs::read_to_string(dir.path().join("kata.md")).unwrap();
+        assert!(kata.contains("Describe the kata here."));
+        assert!(dir.path().join(".git").exists());
+        assert!(dir.path().join("tdd.yaml").exists());
+    }
+}
@@ -0,0 +1,223 @@
+//! Cleans up the transient scratch entries under `.tdd/` that a crashed
+//! or abandoned run leaves behind — `.tdd/tmp/`, `.tdd/cache/staging/`,
+//! and `.tdd/logs/raw/` spill files — since the size-budget retention
+//! pass (see [`crate::disk_usage`]) only reclaims when a limit is
+//! exceeded and never looks at age. Never touches `plan/`, `logs/` (the
+//! step logs themselves), or `state/`. See [`clean`] and the `clean
+//! --transient` CLI command.
+
+use crate::{progress, step_log};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use walkdir::WalkDir;
+
+/// `.tdd` subdirectories [`clean`] is allowed to remove entries from.
+const TRANSIENT_DIRS: &[&str] = &["tmp", "cache/staging", "logs/raw"];
+
+/// How old an unreferenced transient entry must be before [`clean`]
+/// treats it as abandoned rather than belonging to a run still in its
+/// first minutes. The request's "configurable age (default 24h)".
+pub const DEFAULT_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// One entry [`clean`] removed, for the summary line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemovedEntry {
+    pub path: PathBuf,
+    pub bytes: u64,
+}
+
+/// What one [`clean`] pass did.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CleanupReport {
+    pub removed: Vec<RemovedEntry>,
+    /// Entries `clean` tried to delete but couldn't (permission denied,
+    /// removed out from under it, ...). A failure here is a warning, not
+    /// a reason to fail the whole pass.
+    pub failures: Vec<String>,
+    /// Set, with nothing removed, when another process appears to hold
+    /// the workspace — an unfinished progress snapshot with a live PID.
+    /// Racing that run's own scratch writes would be unsafe, so `clean`
+    /// skips entirely rather than guessing which entries are its.
+    pub skipped: Option<String>,
+}
+
+impl CleanupReport {
+    pub fn total_bytes(&self) -> u64 {
+        self.removed.iter().map(|entry| entry.bytes).sum()
+    }
+
+    /// `"cleaned 37 stale transient entries, 120.5 MB"`, or the skip
+    /// note, for the startup log line and `clean --transient`'s stdout.
+    pub fn format_summary(&self) -> String {
+        if let Some(reason) = &self.skipped {
+            return format!("skipped transient cleanup: {reason}");
+        }
+        let count = self.removed.len();
+        format!(
+            "cleaned {count} stale transient entr{}, {:.1} MB",
+            if count == 1 { "y" } else { "ies" },
+            self.total_bytes() as f64 / (1024.0 * 1024.0)
+        )
+    }
+}
+
+/// Removes every entry under [`TRANSIENT_DIRS`] whose mtime is older
+/// than `max_age`, skipping whatever is still referenced by a retained
+/// step log's `output_spills` (see [`referenced_spill_paths`]). Returns
+/// immediately, without deleting anything, if [`progress::read`] finds
+/// an unfinished run whose PID is still alive — another process may
+/// still be writing these same scratch entries.
+pub fn clean(repo_root: &Path, max_age: Duration) -> anyhow::Result<CleanupReport> {
+    if let Some(snapshot) = progress::read(repo_root)? {
+        if !snapshot.finished && pid_is_alive(snapshot.pid) {
+            return Ok(CleanupReport {
+                skipped: Some(format!("run {} (pid {}) is still in progress", snapshot.run_id, snapshot.pid)),
+                ..Default::default()
+            });
+        }
+    }
+
+    let referenced = referenced_spill_paths(repo_root);
+    let tdd_dir = repo_root.join(".tdd");
+    let cutoff = SystemTime::now().checked_sub(max_age).unwrap_or(SystemTime::UNIX_EPOCH);
+
+    let mut report = CleanupReport::default();
+    for dir in TRANSIENT_DIRS {
+        let dir_path = tdd_dir.join(dir);
+        let Ok(entries) = std::fs::read_dir(&dir_path) else { continue };
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let path = entry.path();
+            if referenced.contains(&path) {
+                continue;
+            }
+            let Ok(metadata) = entry.metadata() else { continue };
+            let Ok(modified) = metadata.modified() else { continue };
+            if modified >= cutoff {
+                continue;
+            }
+
+            let bytes = entry_size(&path);
+            let result = if metadata.is_dir() { std::fs::remove_dir_all(&path) } else { std::fs::remove_file(&path) };
+            match result {
+                Ok(()) => report.removed.push(RemovedEntry { path, bytes }),
+                Err(error) => report.failures.push(format!("{}: {error}", path.display())),
+            }
+        }
+    }
+    Ok(report)
+}
+
+fn entry_size(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// The `output_spills` every step log under `.tdd/logs/` still points
+/// at — the only entries under `.tdd/logs/raw/` a retained log cares
+/// about, so [`clean`] must never remove them regardless of age, even
+/// across a `--debug-unredacted-logs` run whose spills long outlive the
+/// default max age.
+fn referenced_spill_paths(repo_root: &Path) -> HashSet<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(repo_root.join(".tdd").join("logs")) else {
+        return HashSet::new();
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|entry| std::fs::read_to_string(entry.path()).ok())
+        .filter_map(|contents| serde_json::from_str::<step_log::StepLog>(&contents).ok())
+        .flat_map(|log| log.output_spills)
+        .map(PathBuf::from)
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn pid_is_alive(pid: u32) -> bool {
+    Path::new(&format!("/proc/{pid}")).exists()
+}
+
+/// No portable liveness check without a new dependency on a platform
+/// without `/proc`; treats every PID as alive so `clean` skips instead
+/// of risking a race with another process's scratch writes.
+#[cfg(not(target_os = "linux"))]
+fn pid_is_alive(_pid: u32) -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tdd_core::Role;
+    use tempfile::tempdir;
+
+    fn write(path: &Path, bytes: usize) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, vec![b'x'; bytes]).unwrap();
+    }
+
+    fn set_mtime(path: &Path, seconds_ago: u64) {
+        let modified = SystemTime::now() - Duration::from_secs(seconds_ago);
+        std::fs::File::open(path).unwrap().set_modified(modified).unwrap();
+    }
+
+    #[test]
+    fn cleans_only_the_stale_unreferenced_entries() {
+        let dir = tempdir().unwrap();
+
+        let stale = dir.path().join(".tdd/tmp/attempt-1");
+        write(&stale.join("scratch.txt"), 10);
+        set_mtime(&stale.join("scratch.txt"), 48 * 60 * 60);
+        set_mtime(&stale, 48 * 60 * 60);
+
+        let fresh = dir.path().join(".tdd/tmp/attempt-2");
+        write(&fresh.join("scratch.txt"), 10);
+        set_mtime(&fresh.join("scratch.txt"), 60);
+        set_mtime(&fresh, 60);
+
+        let referenced = dir.path().join(".tdd/logs/raw/step-0-implementor.stderr");
+        write(&referenced, 10);
+        set_mtime(&referenced, 48 * 60 * 60);
+        std::fs::create_dir_all(dir.path().join(".tdd/logs")).unwrap();
+        std::fs::write(
+            dir.path().join(".tdd/logs/step-0-implementor.json"),
+            serde_json::to_string(&step_log::StepLog {
+                output_spills: vec![referenced.display().to_string()],
+                ..Default::default()
+            })
+            .unwrap(),
+        )
+        .unwrap();
+
+        let report = clean(dir.path(), Duration::from_secs(24 * 60 * 60)).unwrap();
+
+        assert_eq!(report.removed.len(), 1);
+        assert_eq!(report.removed[0].path, stale);
+        assert!(report.skipped.is_none());
+        assert!(!stale.exists());
+        assert!(fresh.exists());
+        assert!(referenced.exists());
+    }
+
+    #[test]
+    fn skips_cleanly_when_another_run_still_holds_the_workspace() {
+        let dir = tempdir().unwrap();
+        let stale = dir.path().join(".tdd/tmp/attempt-1/scratch.txt");
+        write(&stale, 10);
+        set_mtime(&stale, 48 * 60 * 60);
+
+        let mut writer = crate::progress::ProgressWriter::start(dir.path(), 1, 1, Role::Implementor).unwrap();
+        let _ = &mut writer;
+
+        let report = clean(dir.path(), Duration::from_secs(24 * 60 * 60)).unwrap();
+
+        assert!(report.removed.is_empty());
+        assert!(report.skipped.as_ref().unwrap().contains(&std::process::id().to_string()));
+        assert!(stale.exists());
+    }
+}
@@ -0,0 +1,85 @@
+//! Implements `tdd-cli kata refresh`: re-fetches a kata description that
+//! was originally pulled in via `tdd-cli init --kata-url`.
+
+use crate::kata_source::{fetch_kata_markdown, KataSource};
+use std::path::PathBuf;
+
+/// Arguments accepted by `tdd-cli kata refresh`.
+#[derive(Debug, Clone, clap::Args)]
+pub struct KataRefreshArgs {
+    /// Workspace to refresh. Defaults to the current directory.
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+
+    /// Write the re-fetched description instead of only showing a diff.
+    #[arg(long)]
+    pub apply: bool,
+}
+
+/// Runs `tdd-cli kata refresh`. Always shows a diff between the current
+/// `kata.md` and the freshly fetched version; only writes it when
+/// `--apply` is passed. A network failure leaves `kata.md` untouched.
+pub fn refresh(args: &KataRefreshArgs) -> anyhow::Result<String> {
+    let source = KataSource::load(&args.path)?
+        .ok_or_else(|| anyhow::anyhow!("no kata source recorded; run `tdd-cli init --kata-url` first"))?;
+
+    let kata_path = args.path.join("kata.md");
+    let current = std::fs::read_to_string(&kata_path).unwrap_or_default();
+
+    let (updated, fetched_at) = fetch_kata_markdown(&source.url)?;
+    let diff = diff_lines(&current, &updated);
+
+    if args.apply {
+        std::fs::write(&kata_path, &updated)?;
+        KataSource {
+            url: source.url,
+            fetched_at,
+        }
+        .save(&args.path)?;
+    }
+
+    Ok(diff)
+}
+
+/// A minimal unified-style line diff: enough to show a reviewer what
+/// changed without pulling in a diff crate for a CLI convenience command.
+fn diff_lines(before: &str, after: &str) -> String {
+    if before == after {
+        return "no changes".to_string();
+    }
+
+    let before_lines: Vec<&str> = before.lines().collect();
+    let after_lines: Vec<&str> = after.lines().collect();
+    let mut out = String::new();
+
+    for line in &before_lines {
+        if !after_lines.contains(line) {
+            out.push_str(&format!("-{line}\n"));
+        }
+    }
+    for line in &after_lines {
+        if !before_lines.contains(line) {
+            out.push_str(&format!("+{line}\n"));
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_content_reports_no_changes() {
+        assert_eq!(diff_lines("same\n", "same\n"), "no changes");
+    }
+
+    #[test]
+    fn reports_added_and_removed_lines() {
+        let diff = diff_lines("keep\nold\n", "keep\nnew\n");
+        assert!(diff.contains("-old"));
+        assert!(diff.contains("+new"));
+        assert!(!diff.contains("-keep"));
+    }
+}
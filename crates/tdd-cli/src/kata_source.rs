@@ -0,0 +1,76 @@
+//! Tracks the remote source of a kata description fetched via
+//! `--kata-url`, so `tdd-cli kata refresh` knows what to re-fetch.
+
+use crate::html_to_text::html_to_text;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Persisted at `.tdd/state/kata-source.json` whenever `kata.md` was
+/// populated from a remote URL rather than authored by hand.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KataSource {
+    pub url: String,
+    pub fetched_at: DateTime<Utc>,
+}
+
+impl KataSource {
+    pub fn state_path(repo_root: &Path) -> std::path::PathBuf {
+        repo_root.join(".tdd").join("state").join("kata-source.json")
+    }
+
+    pub fn load(repo_root: &Path) -> anyhow::Result<Option<Self>> {
+        let path = Self::state_path(repo_root);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let raw = std::fs::read_to_string(path)?;
+        Ok(Some(serde_json::from_str(&raw)?))
+    }
+
+    pub fn save(&self, repo_root: &Path) -> anyhow::Result<()> {
+        let path = Self::state_path(repo_root);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Fetches `url`, converts the HTML body to markdown-ish text, and
+/// prefixes it with a provenance comment. Returns the full contents ready
+/// to be written to `kata.md`, and the fetch timestamp used in the
+/// provenance comment.
+pub fn fetch_kata_markdown(url: &str) -> anyhow::Result<(String, DateTime<Utc>)> {
+    let html = reqwest::blocking::get(url)?.error_for_status()?.text()?;
+    let fetched_at = Utc::now();
+    let body = html_to_text(&html);
+    let markdown = format!("<!-- source: {url}, fetched: {} -->\n\n{body}", fetched_at.to_rfc3339());
+    Ok((markdown, fetched_at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn round_trips_through_disk() {
+        let dir = tempdir().unwrap();
+        let source = KataSource {
+            url: "https://example.com/kata".to_string(),
+            fetched_at: Utc::now(),
+        };
+        source.save(dir.path()).unwrap();
+
+        let loaded = KataSource::load(dir.path()).unwrap().unwrap();
+        assert_eq!(loaded.url, source.url);
+    }
+
+    #[test]
+    fn load_returns_none_when_absent() {
+        let dir = tempdir().unwrap();
+        assert!(KataSource::load(dir.path()).unwrap().is_none());
+    }
+}
@@ -0,0 +1,233 @@
+//! Summarizes an oversized kata description once and caches the result
+//! at `.tdd/state/kata-summary.md`, so a multi-page `kata.md` doesn't get
+//! cut off mid-sentence by the excerpt every prompt is built under.
+
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use tdd_llm::{LlmClient, Message};
+
+/// Kata descriptions at or under this many characters are used verbatim;
+/// past it, a cached summary (or the deterministic fallback) takes over.
+pub const SUMMARIZE_THRESHOLD: usize = 1200;
+
+fn state_path(repo_root: &Path) -> PathBuf {
+    repo_root.join(".tdd").join("state").join("kata-summary.md")
+}
+
+fn content_hash(kata_markdown: &str) -> String {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    kata_markdown.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// Loads the cached summary for `kata_markdown`, or `None` if there's no
+/// cache yet or it was written for a different kata (content hash
+/// mismatch), so a changed `kata.md` regenerates instead of serving a
+/// stale summary.
+fn load_cached(repo_root: &Path, kata_markdown: &str) -> Option<String> {
+    let raw = std::fs::read_to_string(state_path(repo_root)).ok()?;
+    let (header, body) = raw.split_once("\n\n")?;
+    let hash = header.strip_prefix("<!-- kata-hash: ")?.strip_suffix(" -->")?;
+    if hash != content_hash(kata_markdown) {
+        return None;
+    }
+    Some(body.to_string())
+}
+
+fn save_cache(repo_root: &Path, kata_markdown: &str, summary: &str) -> anyhow::Result<()> {
+    let path = state_path(repo_root);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, format!("<!-- kata-hash: {} -->\n\n{summary}", content_hash(kata_markdown)))?;
+    Ok(())
+}
+
+/// A deterministic summary used when summarization is disabled or the LLM
+/// call fails: the first heading line plus every list item, joined up to
+/// `budget` characters. Never calls out to an LLM, so it's always
+/// available as a fallback.
+pub fn fallback_summary(kata_markdown: &str, budget: usize) -> String {
+    let mut lines = Vec::new();
+    let mut used = 0;
+
+    if let Some(heading) = kata_markdown.lines().find(|line| line.trim_start().starts_with('#')) {
+        used += heading.len() + 1;
+        lines.push(heading.to_string());
+    }
+
+    for line in kata_markdown.lines() {
+        if !is_list_item(line.trim_start()) {
+            continue;
+        }
+        if used + line.len() + 1 > budget {
+            break;
+        }
+        used += line.len() + 1;
+        lines.push(line.to_string());
+    }
+
+    lines.join("\n")
+}
+
+fn is_list_item(trimmed: &str) -> bool {
+    if trimmed.starts_with("- ") || trimmed.starts_with("* ") {
+        return true;
+    }
+    match trimmed.find(". ") {
+        Some(dot) if dot > 0 => trimmed[..dot].chars().all(|c| c.is_ascii_digit()),
+        _ => false,
+    }
+}
+
+async fn generate(kata_markdown: &str, tester: &dyn LlmClient) -> anyhow::Result<String> {
+    let messages = vec![
+        Message::system(
+            "Summarize this kata description for an agent that will only see the summary, \
+             not the full text. Respond with three sections: Goal, Constraints, Requirements \
+             (a list). Be concise but don't drop any requirement.",
+        ),
+        Message::user(kata_markdown),
+    ];
+    tester.chat(messages).await
+}
+
+/// Renders the text that goes in a [`tdd_core::StepContext::kata_description`]
+/// slot. Kata descriptions at or under [`SUMMARIZE_THRESHOLD`] pass
+/// through unchanged. Past it, returns the summary (generated via
+/// `tester`, cached at `.tdd/state/kata-summary.md` keyed by `kata_markdown`'s
+/// content hash, or the deterministic [`fallback_summary`] when
+/// `summarize_long_kata` is off or the LLM call fails) followed by an
+/// excerpt of the original, with a note that a summary is in use.
+pub async fn summarize(repo_root: &Path, kata_markdown: &str, summarize_long_kata: bool, tester: &dyn LlmClient) -> String {
+    if kata_markdown.len() <= SUMMARIZE_THRESHOLD {
+        return kata_markdown.to_string();
+    }
+
+    let summary = if summarize_long_kata {
+        match load_cached(repo_root, kata_markdown) {
+            Some(cached) => cached,
+            None => match generate(kata_markdown, tester).await {
+                Ok(generated) => {
+                    let _ = save_cache(repo_root, kata_markdown, &generated);
+                    generated
+                }
+                Err(_) => fallback_summary(kata_markdown, SUMMARIZE_THRESHOLD),
+            },
+        }
+    } else {
+        fallback_summary(kata_markdown, SUMMARIZE_THRESHOLD)
+    };
+
+    let excerpt: String = kata_markdown.chars().take(SUMMARIZE_THRESHOLD).collect();
+    format!(
+        "This kata description is long; a summary is in use below, followed by the first \
+         {SUMMARIZE_THRESHOLD} characters of the original.\n\nSummary:\n{summary}\n\nOriginal (truncated):\n{excerpt}"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tdd_fixtures::ScriptedLlmClient;
+    use tempfile::tempdir;
+
+    fn long_kata() -> String {
+        let mut markdown = "# String Calculator\n\n".to_string();
+        for i in 0..50 {
+            markdown.push_str(&format!("- requirement {i} goes here with some padding text\n"));
+        }
+        markdown
+    }
+
+    #[test]
+    fn a_short_kata_fallback_keeps_the_heading_and_every_list_item() {
+        let kata = "# Goal\n\nSome prose.\n\n- one\n- two\n* three\n1. four\n";
+        let summary = fallback_summary(kata, 1000);
+        assert!(summary.starts_with("# Goal"));
+        assert!(summary.contains("- one"));
+        assert!(summary.contains("- two"));
+        assert!(summary.contains("* three"));
+        assert!(summary.contains("1. four"));
+        assert!(!summary.contains("Some prose"));
+    }
+
+    #[test]
+    fn the_fallback_stops_adding_items_once_the_budget_is_spent() {
+        let kata = long_kata();
+        let summary = fallback_summary(&kata, 100);
+        assert!(summary.len() <= 100 + "# String Calculator".len() + 1);
+        assert!(!summary.contains("requirement 49"));
+    }
+
+    #[tokio::test]
+    async fn a_short_kata_passes_through_unchanged() {
+        let dir = tempdir().unwrap();
+        let client = ScriptedLlmClient::new(Vec::<String>::new());
+        let rendered = summarize(dir.path(), "# Small kata\n\nDo the thing.", true, &client).await;
+        assert_eq!(rendered, "# Small kata\n\nDo the thing.");
+    }
+
+    #[tokio::test]
+    async fn an_oversized_kata_is_summarized_and_the_prompt_notes_it() {
+        let dir = tempdir().unwrap();
+        let kata = long_kata();
+        let client = ScriptedLlmClient::new(["Goal: add requirements\nConstraints: none\nRequirements:\n- all of them".to_string()]);
+
+        let rendered = summarize(dir.path(), &kata, true, &client).await;
+
+        assert!(rendered.contains("a summary is in use"));
+        assert!(rendered.contains("Goal: add requirements"));
+        assert!(rendered.contains("Original (truncated):"));
+    }
+
+    #[tokio::test]
+    async fn a_cached_summary_is_reused_without_calling_the_llm_again() {
+        let dir = tempdir().unwrap();
+        let kata = long_kata();
+        let client = ScriptedLlmClient::new(["first summary".to_string()]);
+
+        summarize(dir.path(), &kata, true, &client).await;
+        let second = summarize(dir.path(), &kata, true, &client).await;
+
+        assert!(second.contains("first summary"));
+        assert_eq!(client.calls().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn changing_the_kata_invalidates_the_cache() {
+        let dir = tempdir().unwrap();
+        let kata = long_kata();
+        let client = ScriptedLlmClient::new(["first summary".to_string(), "second summary".to_string()]);
+
+        summarize(dir.path(), &kata, true, &client).await;
+        let changed_kata = format!("{kata}\n- one more requirement that pushes the hash to change");
+        let rendered = summarize(dir.path(), &changed_kata, true, &client).await;
+
+        assert!(rendered.contains("second summary"));
+        assert_eq!(client.calls().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn disabling_summarization_uses_the_deterministic_fallback_and_skips_the_llm() {
+        let dir = tempdir().unwrap();
+        let kata = long_kata();
+        let client = ScriptedLlmClient::new(Vec::<String>::new());
+
+        let rendered = summarize(dir.path(), &kata, false, &client).await;
+
+        assert!(rendered.contains("# String Calculator"));
+        assert!(client.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn a_failed_llm_call_falls_back_to_the_deterministic_summary() {
+        let dir = tempdir().unwrap();
+        let kata = long_kata();
+        let client = ScriptedLlmClient::new(Vec::<String>::new());
+
+        let rendered = summarize(dir.path(), &kata, true, &client).await;
+
+        assert!(rendered.contains("# String Calculator"));
+    }
+}
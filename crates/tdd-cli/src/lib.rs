@@ -0,0 +1,48 @@
+//! Library surface for `tdd-cli`, split out from `main.rs` so the
+//! subcommand implementations are unit-testable in isolation.
+
+pub mod archive;
+pub mod artifact_name;
+pub mod batch;
+pub mod changelog;
+pub mod cli;
+pub mod completions;
+pub mod config;
+pub mod context_fingerprint;
+pub mod cycle_branch;
+pub mod detached_head;
+pub mod diff;
+pub mod disk_usage;
+pub mod doctor;
+pub mod error;
+pub mod experiment;
+pub mod export;
+pub mod git_hooks;
+pub mod html_to_text;
+pub mod ignore_policy;
+pub mod init;
+pub mod janitor;
+pub mod kata;
+pub mod kata_source;
+pub mod kata_summary;
+pub mod llm_endpoints;
+pub mod operator_goal;
+pub mod orchestrator;
+pub mod preflight;
+pub mod progress;
+pub mod provider_state;
+pub mod review;
+pub mod run_log;
+pub mod run_sequence;
+pub mod schedule;
+pub mod status;
+pub mod step_log;
+pub mod testscan;
+pub mod tracing_setup;
+pub mod tui;
+pub mod undo;
+pub mod workspace_access;
+pub mod workspace_paths;
+
+/// The `rust-toolchain.toml` written by `tdd-cli init`.
+pub const RUST_TOOLCHAIN_TOML: &str = "[toolchain]\nchannel = \"stable\"\ncomponents = [\"rustfmt\", \"clippy\"]\n";
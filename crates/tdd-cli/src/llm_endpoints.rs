@@ -0,0 +1,188 @@
+//! Resolves a role's `llm_endpoints` routing into the
+//! [`tdd_llm::LlmConnection`] it should talk to, and builds (and shares)
+//! the [`tdd_llm::LlmClient`]s those connections need.
+//!
+//! A role's model config (`tdd.yaml`'s `roles.<role>`) names an endpoint by
+//! key, falling back to `default_endpoint`, and then to the legacy single
+//! `llm:` block when neither is set — so an existing `tdd.yaml` with no
+//! `llm_endpoints` section keeps working unchanged.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use tdd_core::Role;
+use tdd_llm::{CancellationToken, LlmClient, LlmConnection, OpenAiCompatibleClient, RoleModelConfig};
+
+/// The implicit endpoint name a role resolves to when it names no
+/// `endpoint`, no `default_endpoint` is configured, and the workspace has
+/// no `llm_endpoints` section at all — i.e. the legacy single `llm:` block.
+pub const LEGACY_ENDPOINT_NAME: &str = "default";
+
+/// The endpoint name a role with model config `role_config` resolves to,
+/// given the workspace's `default_endpoint`.
+pub fn endpoint_name_for(role_config: &RoleModelConfig, default_endpoint: Option<&str>) -> String {
+    role_config.endpoint.clone().or_else(|| default_endpoint.map(str::to_string)).unwrap_or_else(|| LEGACY_ENDPOINT_NAME.to_string())
+}
+
+/// Resolves every role's endpoint name to the [`LlmConnection`] it names,
+/// falling back to the legacy `llm:` block (under [`LEGACY_ENDPOINT_NAME`])
+/// when `llm_endpoints` doesn't define it.
+///
+/// Fails with a clear error naming the role and the missing endpoint if a
+/// role names an endpoint that isn't in `llm_endpoints` and isn't the
+/// legacy fallback.
+pub fn resolve_endpoints(
+    role_configs: &HashMap<Role, (&'static str, RoleModelConfig)>,
+    llm_endpoints: &HashMap<String, LlmConnection>,
+    default_endpoint: Option<&str>,
+    legacy_llm: &LlmConnection,
+) -> anyhow::Result<HashMap<Role, (String, LlmConnection)>> {
+    let mut resolved = HashMap::new();
+    for (role, (role_key, role_config)) in role_configs {
+        let endpoint_name = endpoint_name_for(role_config, default_endpoint);
+        let connection = if endpoint_name == LEGACY_ENDPOINT_NAME && !llm_endpoints.contains_key(LEGACY_ENDPOINT_NAME) {
+            legacy_llm.clone()
+        } else {
+            llm_endpoints
+                .get(&endpoint_name)
+                .ok_or_else(|| anyhow::anyhow!("roles.{role_key}.endpoint \"{endpoint_name}\" is not in tdd.yaml's llm_endpoints"))?
+                .clone()
+        };
+        resolved.insert(*role, (endpoint_name, connection));
+    }
+    Ok(resolved)
+}
+
+/// Validates every distinct [`LlmConnection`] a role resolved to. Run once
+/// up front so a bad timeout on a rarely-used endpoint is caught before
+/// any step starts, not the first time that role runs.
+pub fn validate(resolved: &HashMap<Role, (String, LlmConnection)>) -> anyhow::Result<()> {
+    let mut seen = std::collections::HashSet::new();
+    for (endpoint_name, connection) in resolved.values() {
+        if seen.insert(endpoint_name.clone()) {
+            connection.validate()?;
+        }
+    }
+    Ok(())
+}
+
+/// A role's resolved client, plus the file-request permission that came
+/// with its endpoint (each endpoint may allow or disallow it separately).
+pub struct RoleClient {
+    pub client: Arc<dyn LlmClient>,
+    pub allow_file_requests: bool,
+    pub provider: String,
+}
+
+/// Builds one [`OpenAiCompatibleClient`] per distinct `(endpoint, model,
+/// temperature)` combination and hands out an [`Arc`] to it for every role
+/// that resolved to that combination, so two roles deliberately configured
+/// identically on the same endpoint share a single client instance instead
+/// of opening a second connection. Every client shares `cancellation`, so
+/// a step abort (see `LoopOrchestrator::check_step_deadline`/`await_review`)
+/// stops an in-flight request instead of waiting it out.
+pub fn create_clients(
+    resolved: &HashMap<Role, (String, LlmConnection)>,
+    role_configs: &HashMap<Role, (&'static str, RoleModelConfig)>,
+    cancellation: &CancellationToken,
+) -> anyhow::Result<HashMap<Role, RoleClient>> {
+    let mut cache: HashMap<(String, String, String), Arc<dyn LlmClient>> = HashMap::new();
+    let mut clients = HashMap::new();
+
+    for (role, (endpoint_name, connection)) in resolved {
+        let (_, role_config) = &role_configs[role];
+        let cache_key = (endpoint_name.clone(), role_config.model.clone(), role_config.temperature.to_string());
+        let client = match cache.get(&cache_key) {
+            Some(client) => client.clone(),
+            None => {
+                let client: Arc<dyn LlmClient> =
+                    Arc::new(OpenAiCompatibleClient::new(connection.clone(), role_config.clone())?.with_cancellation(cancellation.clone()));
+                cache.insert(cache_key, client.clone());
+                client
+            }
+        };
+        clients.insert(*role, RoleClient { client, allow_file_requests: connection.allow_file_requests, provider: connection.provider.clone() });
+    }
+
+    Ok(clients)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn role_config(model: &str, endpoint: Option<&str>) -> RoleModelConfig {
+        RoleModelConfig { model: model.to_string(), temperature: 0.3, endpoint: endpoint.map(str::to_string), retry_temperature_bump: 0.0 }
+    }
+
+    fn connection(provider: &str) -> LlmConnection {
+        LlmConnection {
+            provider: provider.to_string(),
+            base_url: "http://localhost:11434/v1".to_string(),
+            api_key_env: "LLM_API_KEY".to_string(),
+            request_timeout_secs: 120,
+            connect_timeout_secs: 10,
+            allow_file_requests: false,
+            http: Default::default(),
+        }
+    }
+
+    #[test]
+    fn a_role_with_no_endpoint_falls_back_to_the_legacy_llm_block() {
+        let role_configs = HashMap::from([(Role::Tester, ("tester", role_config("gpt-4.1-mini", None)))]);
+        let resolved = resolve_endpoints(&role_configs, &HashMap::new(), None, &connection("ollama")).unwrap();
+        let (name, conn) = &resolved[&Role::Tester];
+        assert_eq!(name, LEGACY_ENDPOINT_NAME);
+        assert_eq!(conn.provider, "ollama");
+    }
+
+    #[test]
+    fn a_role_naming_an_endpoint_resolves_to_it() {
+        let role_configs = HashMap::from([(Role::Tester, ("tester", role_config("gpt-4.1-mini", Some("fast"))))]);
+        let llm_endpoints = HashMap::from([("fast".to_string(), connection("openai"))]);
+        let resolved = resolve_endpoints(&role_configs, &llm_endpoints, None, &connection("ollama")).unwrap();
+        let (name, conn) = &resolved[&Role::Tester];
+        assert_eq!(name, "fast");
+        assert_eq!(conn.provider, "openai");
+    }
+
+    #[test]
+    fn a_role_falls_back_to_default_endpoint_when_unset() {
+        let role_configs = HashMap::from([(Role::Tester, ("tester", role_config("gpt-4.1-mini", None)))]);
+        let llm_endpoints = HashMap::from([("fast".to_string(), connection("openai"))]);
+        let resolved = resolve_endpoints(&role_configs, &llm_endpoints, Some("fast"), &connection("ollama")).unwrap();
+        assert_eq!(resolved[&Role::Tester].0, "fast");
+    }
+
+    #[test]
+    fn an_unknown_endpoint_name_is_a_clear_error() {
+        let role_configs = HashMap::from([(Role::Tester, ("tester", role_config("gpt-4.1-mini", Some("missing"))))]);
+        let error = resolve_endpoints(&role_configs, &HashMap::new(), None, &connection("ollama")).unwrap_err();
+        assert!(error.to_string().contains("roles.tester.endpoint \"missing\""));
+    }
+
+    #[test]
+    fn two_roles_sharing_an_endpoint_model_and_temperature_share_one_client() {
+        let role_configs = HashMap::from([
+            (Role::Tester, ("tester", role_config("gpt-4.1-mini", Some("shared")))),
+            (Role::Implementor, ("implementor", role_config("gpt-4.1-mini", Some("shared")))),
+        ]);
+        let llm_endpoints = HashMap::from([("shared".to_string(), connection("openai"))]);
+        let resolved = resolve_endpoints(&role_configs, &llm_endpoints, None, &connection("ollama")).unwrap();
+        let clients = create_clients(&resolved, &role_configs, &CancellationToken::new()).unwrap();
+
+        assert!(Arc::ptr_eq(&clients[&Role::Tester].client, &clients[&Role::Implementor].client));
+    }
+
+    #[test]
+    fn two_roles_on_the_same_endpoint_with_different_models_get_distinct_clients() {
+        let role_configs = HashMap::from([
+            (Role::Tester, ("tester", role_config("gpt-4.1-mini", Some("shared")))),
+            (Role::Implementor, ("implementor", role_config("gpt-4o", Some("shared")))),
+        ]);
+        let llm_endpoints = HashMap::from([("shared".to_string(), connection("openai"))]);
+        let resolved = resolve_endpoints(&role_configs, &llm_endpoints, None, &connection("ollama")).unwrap();
+        let clients = create_clients(&resolved, &role_configs, &CancellationToken::new()).unwrap();
+
+        assert!(!Arc::ptr_eq(&clients[&Role::Tester].client, &clients[&Role::Implementor].client));
+    }
+}
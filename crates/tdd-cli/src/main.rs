@@ -0,0 +1,415 @@
+//! `tdd-cli`: the user-facing entrypoint for the TDD machine.
+
+use clap::{CommandFactory, Parser};
+use tdd_cli::cli::{ArchiveArgs, CleanArgs, Cli, Command, CompletionsArgs, ConfigCommand, ConfigShowArgs, DiffArgs, ExportArgs, KataCommand, ReviewCommand, ReviewDecideArgs, RunArgs, SizeArgs, StatusArgs, StepArgs, WorkspaceArgs};
+use tdd_cli::schedule;
+use tdd_cli::completions;
+use tdd_cli::config::{CiConfig, TddConfig};
+use tdd_cli::orchestrator::LoopOrchestrator;
+use tdd_cli::run_log::{RunRecord, StopReason};
+use tdd_cli::tracing_setup::{self, Verbosity};
+use tdd_cli::{archive, batch, doctor, experiment, init, kata, run_log, status, undo};
+use tdd_core::Orchestrator;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    let log_file = TddConfig::load(&cli.command.workspace_path().join("tdd.yaml"))
+        .ok()
+        .and_then(|config| config.workspace.log_file)
+        .map(|log_file| cli.command.workspace_path().join(log_file));
+    tracing_setup::init(Verbosity::from_flags(cli.verbose, cli.quiet), log_file.as_deref())?;
+
+    match cli.command {
+        Command::Init(args) => init::run(&args),
+        Command::Kata { command } => match command {
+            KataCommand::Refresh(args) => {
+                let diff = kata::refresh(&args)?;
+                println!("{diff}");
+                Ok(())
+            }
+        },
+        Command::Run(args) => run(args).await,
+        Command::Schedule(args) => schedule::run(args).await,
+        Command::Step(args) => match args.inject_test.clone() {
+            Some(source) => inject_test_step(args, source).await,
+            None => {
+                run(RunArgs {
+                    path: args.path,
+                    steps: 1,
+                    plan_only: false,
+                    no_preflight: false,
+                    commit_prefix: args.commit_prefix,
+                    review_branch: false,
+                    auto_merge: false,
+                    no_ff: false,
+                    allow_stacked: false,
+                    ignore_max_steps: false,
+                    debug_unredacted_logs: args.debug_unredacted_logs,
+                    pair: false,
+                    no_ci_cache: false,
+                    goal: args.goal,
+                    unarchive: args.unarchive,
+                    deterministic: false,
+                })
+                .await
+            }
+        },
+        #[cfg(feature = "tui")]
+        Command::Tui(args) => tdd_cli::tui::run(args).await,
+        Command::Status(args) => print_status(args).await,
+        Command::Doctor(args) => print_doctor(args),
+        Command::Archive(args) => archive_kata(args),
+        Command::Unarchive(args) => unarchive_kata(args),
+        Command::Batch(args) => batch::run(args).await,
+        Command::RepairPaths(args) => repair_paths(args),
+        Command::Undo(args) => undo_step(args),
+        Command::Redo(args) => redo_step(args),
+        Command::Review { command } => match command {
+            ReviewCommand::List(args) => print_pending_reviews(args),
+            ReviewCommand::Decide(args) => decide_review(args),
+        },
+        Command::Config { command } => match command {
+            ConfigCommand::Show(args) => print_config_show(args),
+        },
+        Command::Diff(args) => print_diff(args),
+        Command::Size(args) => print_size(args),
+        Command::Clean(args) => print_clean(args),
+        Command::Completions(args) => print_completions(args),
+        Command::Complete(args) => {
+            completions::print_candidates(args.kind, &args.path);
+            Ok(())
+        }
+        Command::Experiment(args) => experiment::run(args).await,
+        Command::Export(args) => export_session(args),
+    }
+}
+
+fn print_completions(args: CompletionsArgs) -> anyhow::Result<()> {
+    println!("{}", completions::render(args.shell, &Cli::command()));
+    Ok(())
+}
+
+async fn run(args: RunArgs) -> anyhow::Result<()> {
+    match tdd_cli::janitor::clean(&args.path, tdd_cli::janitor::DEFAULT_MAX_AGE) {
+        Ok(report) => tracing::info!("{}", report.format_summary()),
+        Err(error) => tracing::warn!(%error, "failed to clean stale transient entries under .tdd/"),
+    }
+
+    if args.plan_only {
+        return run_plan_only(args).await;
+    }
+
+    let started_at = chrono::Utc::now();
+    let config_hash = run_log::config_hash(&args.path).unwrap_or_default();
+
+    let mut orchestrator = match LoopOrchestrator::from_workspace(&args).await {
+        Ok(orchestrator) => orchestrator,
+        Err(error) => {
+            let stop_reason = if error.downcast_ref::<tdd_core::CoreError>().is_some_and(|e| matches!(e, tdd_core::CoreError::MaxStepsReached { .. })) {
+                StopReason::MaxStepsReached
+            } else {
+                StopReason::AbortedBeforeStart
+            };
+            run_log::record(&args.path, &RunRecord {
+                stop_reason,
+                steps_requested: args.steps,
+                steps_executed: 0,
+                started_at,
+                ended_at: chrono::Utc::now(),
+                config_hash,
+                final_step_index: 0,
+                final_role: None,
+                failure: None,
+                max_steps_overridden: false,
+                detached_head_branch: None,
+            })?;
+            return Err(error);
+        }
+    };
+
+    let progress_writer = tdd_cli::progress::ProgressWriter::start(&args.path, orchestrator.run_id(), args.steps, orchestrator.current_role())?;
+    orchestrator = orchestrator.add_observer(progress_writer.into_observer());
+
+    let review_branch_vcs = args.review_branch.then(|| tdd_exec::GitVcs::open_existing(&args.path)).transpose()?;
+    let integration_branch = review_branch_vcs
+        .as_ref()
+        .map(|_| tdd_exec::current_branch_name(&args.path))
+        .transpose()?;
+    let review_branch = review_branch_vcs.as_ref().map(|vcs| tdd_cli::cycle_branch::ReviewBranchOptions {
+        vcs: vcs as &dyn tdd_core::Vcs,
+        integration_branch: integration_branch.expect("set alongside review_branch_vcs"),
+        auto_merge: args.auto_merge,
+        no_ff: args.no_ff,
+        allow_stacked: args.allow_stacked,
+    });
+
+    let (run_record, error) = run_log::execute_steps(&mut orchestrator, args.steps, config_hash, review_branch).await;
+    run_log::record(&args.path, &run_record)?;
+    tdd_cli::progress::finish(&args.path, &run_record)?;
+
+    if run_record.steps_executed > 0 && undo::clear_redo_stack(&args.path)? {
+        println!("WARNING      a new step was taken after an undo; the redo stack has been cleared");
+    }
+
+    if run_record.stop_reason == StopReason::AwaitingHumanImplementor {
+        println!("your turn — implement until tests pass, then run `tdd-cli run` again");
+    }
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+async fn inject_test_step(args: StepArgs, source: std::path::PathBuf) -> anyhow::Result<()> {
+    let run_args = RunArgs {
+        path: args.path,
+        steps: 1,
+        plan_only: false,
+        no_preflight: false,
+        commit_prefix: args.commit_prefix,
+        review_branch: false,
+        auto_merge: false,
+        no_ff: false,
+        allow_stacked: false,
+        ignore_max_steps: false,
+        debug_unredacted_logs: args.debug_unredacted_logs,
+        pair: false,
+        no_ci_cache: false,
+        goal: args.goal,
+        unarchive: args.unarchive,
+        deterministic: false,
+    };
+    let mut orchestrator = LoopOrchestrator::from_workspace(&run_args).await?;
+    orchestrator.inject_test(&source, args.dest.as_deref()).await?;
+    println!("injected {} as the next red step", source.display());
+    Ok(())
+}
+
+async fn run_plan_only(args: RunArgs) -> anyhow::Result<()> {
+    let mut orchestrator = LoopOrchestrator::from_workspace(&args).await?;
+    let mut proposals = Vec::new();
+
+    for _ in 0..args.steps {
+        let proposal = orchestrator.plan_next(&mut proposals).await?;
+        let summary = proposal.plan.lines().next().unwrap_or("").trim();
+        println!("step {} ({}): {summary}", proposal.step, proposal.role);
+    }
+
+    println!("proposals written to {}", args.path.join(".tdd/plan/proposals").display());
+    Ok(())
+}
+
+fn undo_step(args: WorkspaceArgs) -> anyhow::Result<()> {
+    let record = undo::undo(&args.path)?;
+    println!("undid step {} ({}), reverting commit {}", record.step, record.role, record.reverted_commit);
+    Ok(())
+}
+
+fn redo_step(args: WorkspaceArgs) -> anyhow::Result<()> {
+    let runner = tdd_exec::CargoRunner::new(args.path.clone());
+    let record = undo::redo(&args.path, &runner)?;
+    println!("redid step {} ({}): {}", record.step, record.role, record.commit_message);
+    Ok(())
+}
+
+async fn print_status(args: StatusArgs) -> anyhow::Result<()> {
+    let max_steps = TddConfig::load(&args.path.join("tdd.yaml")).ok().and_then(|config| config.workspace.max_steps);
+
+    if args.watch {
+        let heartbeat = args.heartbeat.map(std::time::Duration::from_secs);
+        return status::run_watch(&args.path, std::time::Duration::from_secs(args.poll_interval), heartbeat).await;
+    }
+
+    let report = status::read_status(&args.path)?;
+    if args.json {
+        println!("{}", serde_json::to_string(&status::StatusSnapshot::from_report(&report, max_steps))?);
+        return Ok(());
+    }
+
+    for line in status::format_lines(&report, max_steps) {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+fn print_pending_reviews(args: WorkspaceArgs) -> anyhow::Result<()> {
+    let pending = tdd_cli::review::list_pending(&args.path)?;
+    if pending.is_empty() {
+        println!("no pending reviews");
+        return Ok(());
+    }
+    for review in pending {
+        let summary = review.commit_message.lines().next().unwrap_or("").trim();
+        println!("step {} ({}): {summary}", review.step, review.role);
+        for file in &review.files {
+            println!("  {file}");
+        }
+    }
+    Ok(())
+}
+
+fn decide_review(args: ReviewDecideArgs) -> anyhow::Result<()> {
+    tdd_cli::review::write_decision(&args.path, args.step, &args.decision)?;
+    println!("recorded decision for step {}: {}", args.step, args.decision);
+    Ok(())
+}
+
+fn print_config_show(args: ConfigShowArgs) -> anyhow::Result<()> {
+    let config = TddConfig::load(&args.path.join("tdd.yaml"))?;
+    print!("{}", serde_yaml::to_string(&config)?);
+    Ok(())
+}
+
+fn print_diff(args: DiffArgs) -> anyhow::Result<()> {
+    let config = TddConfig::load(&args.path.join("tdd.yaml")).unwrap_or_default();
+    let report = tdd_cli::diff::run(&args, &config.commit.author_email)?;
+    match &args.out {
+        Some(path) => std::fs::write(path, report)?,
+        None => println!("{report}"),
+    }
+    Ok(())
+}
+
+fn print_size(args: SizeArgs) -> anyhow::Result<()> {
+    let report = tdd_cli::disk_usage::report(&args.path);
+    for category in &report.categories {
+        println!("{:<10} {} KB", category.category, category.bytes / 1024);
+    }
+    println!("{:<10} {} KB", "total", report.total_bytes / 1024);
+
+    if args.clean {
+        let config = TddConfig::load(&args.path.join("tdd.yaml")).unwrap_or_default();
+        let Some(max_mb) = config.workspace.max_tdd_dir_mb else {
+            println!("workspace.max_tdd_dir_mb is unset; nothing to clean toward");
+            return Ok(());
+        };
+        let removed = tdd_cli::disk_usage::reclaim(&args.path, max_mb.saturating_mul(1024 * 1024))?;
+        for file in &removed {
+            println!("removed {} ({} KB)", file.path.display(), file.bytes / 1024);
+        }
+        println!("reclaimed {} KB across {} files", removed.iter().map(|f| f.bytes).sum::<u64>() / 1024, removed.len());
+    }
+    Ok(())
+}
+
+fn export_session(args: ExportArgs) -> anyhow::Result<()> {
+    let written = tdd_cli::export::export_html(&args.path, &args.html)?;
+    println!("wrote {} file(s) to {}", written.len(), args.html.display());
+    Ok(())
+}
+
+fn print_clean(args: CleanArgs) -> anyhow::Result<()> {
+    let max_age = match &args.max_age {
+        Some(raw) => tdd_cli::schedule::ScheduleSpec::parse_every(raw).map_err(|error| anyhow::anyhow!(error))?,
+        None => tdd_cli::janitor::DEFAULT_MAX_AGE,
+    };
+    let report = tdd_cli::janitor::clean(&args.path, max_age)?;
+    for entry in &report.removed {
+        println!("removed {} ({} KB)", entry.path.display(), entry.bytes / 1024);
+    }
+    for failure in &report.failures {
+        println!("WARNING      failed to remove {failure}");
+    }
+    println!("{}", report.format_summary());
+    Ok(())
+}
+
+fn archive_kata(args: ArchiveArgs) -> anyhow::Result<()> {
+    let step_count = status::read_status(&args.path)?.step_count;
+    archive::write(&args.path, step_count, args.note)?;
+    let record = archive::read(&args.path)?.expect("just wrote it");
+    println!("{}", record.format_banner());
+    Ok(())
+}
+
+fn unarchive_kata(args: WorkspaceArgs) -> anyhow::Result<()> {
+    archive::clear(&args.path)?;
+    println!("kata unarchived; `run`/`step` will resume normally");
+    Ok(())
+}
+
+fn print_doctor(args: WorkspaceArgs) -> anyhow::Result<()> {
+    let config = TddConfig::load(&args.path.join("tdd.yaml")).ok();
+    let archived = archive::read(&args.path).ok().flatten();
+
+    if let Some(archived) = &archived {
+        println!("INFO         {}; skipping run-readiness checks (`tdd-cli unarchive` to resume)", archived.format_banner());
+    }
+    let default_ci = CiConfig::default();
+    let ci = config.as_ref().map_or(&default_ci, |config| &config.ci);
+    for check in doctor::run_checks_unless_archived(ci, archived.is_some()) {
+        let mark = if check.found { "ok" } else { "MISSING" };
+        println!("{:<12} {}", check.name, mark);
+    }
+
+    if let Some(info) = doctor::worktree_notice(&args.path) {
+        println!("INFO         {info}");
+    }
+
+    if let Some(warning) = doctor::read_only_workspace(&args.path) {
+        println!("WARNING      {warning}");
+    }
+
+    if let Ok(flagged) = doctor::tracked_sensitive_paths(&args.path) {
+        for path in flagged {
+            println!("WARNING      {path} is tracked but matches the ignore policy's sensitive paths");
+        }
+    }
+
+    if let Some(config) = &config {
+        if let Some(warning) = doctor::oversized_context_file(&args.path, &config.workspace.context_file) {
+            println!("WARNING      {warning}");
+        }
+        for warning in doctor::stale_absolute_paths(&args.path, config) {
+            println!("WARNING      {warning}");
+        }
+        if let Some(warning) = doctor::stale_bootstrap_marker(&args.path, config) {
+            println!("WARNING      {warning}");
+        }
+        if let Some(warning) = doctor::oversized_tdd_dir(&args.path, config) {
+            println!("WARNING      {warning}");
+        }
+        if let Ok(unmatched) = doctor::unmatched_readonly_globs(&args.path, config) {
+            for warning in unmatched {
+                println!("WARNING      {warning}");
+            }
+        }
+        if let Ok(Some(warning)) = doctor::kata_file_outside_sparse_cone(&args.path, config) {
+            println!("WARNING      {warning}");
+        }
+    }
+
+    if status::read_status(&args.path).is_ok_and(|report| report.step_count == 0) {
+        if let Some(warning) = doctor::existing_tests_before_first_step(&args.path) {
+            println!("WARNING      {warning}");
+        }
+    }
+
+    Ok(())
+}
+
+fn repair_paths(args: tdd_cli::cli::RepairPathsArgs) -> anyhow::Result<()> {
+    let config_path = args.path.join("tdd.yaml");
+    let mut config = TddConfig::load(&config_path)?;
+
+    let repaired = tdd_cli::workspace_paths::repair(&args.path, &mut config);
+    if repaired.is_empty() {
+        println!("no stale absolute paths found");
+        return Ok(());
+    }
+
+    for path in &repaired {
+        println!("{}: {} -> {}", path.key, path.from, path.to);
+    }
+
+    if args.dry_run {
+        println!("dry run: tdd.yaml was not written");
+    } else {
+        config.save(&config_path)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+//! Lets a human nudge the next step without editing `kata.md`: `--goal`
+//! text is stashed under `.tdd/state/next-goal.txt` by
+//! [`write`], read back into [`tdd_core::StepContext::user_goal`] by
+//! [`crate::orchestrator::LoopOrchestrator::build_context`], and removed
+//! by [`clear`] once the step it steered actually commits, so a goal
+//! never silently outlives the step it was meant for.
+
+use std::path::{Path, PathBuf};
+
+fn path(repo_root: &Path) -> PathBuf {
+    repo_root.join(".tdd").join("state").join("next-goal.txt")
+}
+
+/// Stores `goals` as a bulleted list for the next step to pick up,
+/// overwriting any goal left over from a previous run. A no-op when
+/// `goals` is empty, so a run made without `--goal` never clears a goal
+/// still awaiting the step that will consume it.
+pub fn write(repo_root: &Path, goals: &[String]) -> anyhow::Result<()> {
+    if goals.is_empty() {
+        return Ok(());
+    }
+    let path = path(repo_root);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let bullets = goals.iter().map(|goal| format!("- {goal}")).collect::<Vec<_>>().join("\n");
+    std::fs::write(path, bullets)?;
+    Ok(())
+}
+
+/// The pending goal, if one was stored and hasn't been [`clear`]ed yet.
+pub fn read(repo_root: &Path) -> Option<String> {
+    std::fs::read_to_string(path(repo_root)).ok().map(|content| content.trim().to_string()).filter(|content| !content.is_empty())
+}
+
+/// Removes the pending goal once the step it steered has committed.
+pub fn clear(repo_root: &Path) -> anyhow::Result<()> {
+    let path = path(repo_root);
+    if path.exists() {
+        std::fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn write_then_read_round_trips_as_a_bulleted_list() {
+        let dir = tempdir().unwrap();
+        write(dir.path(), &["handle negative numbers".to_string(), "also handle zero".to_string()]).unwrap();
+
+        assert_eq!(read(dir.path()), Some("- handle negative numbers\n- also handle zero".to_string()));
+    }
+
+    #[test]
+    fn read_is_none_when_no_goal_has_been_written() {
+        let dir = tempdir().unwrap();
+        assert_eq!(read(dir.path()), None);
+    }
+
+    #[test]
+    fn writing_an_empty_goal_list_leaves_an_existing_pending_goal_untouched() {
+        let dir = tempdir().unwrap();
+        write(dir.path(), &["handle negative numbers".to_string()]).unwrap();
+        write(dir.path(), &[]).unwrap();
+
+        assert_eq!(read(dir.path()), Some("- handle negative numbers".to_string()));
+    }
+
+    #[test]
+    fn clear_removes_the_pending_goal() {
+        let dir = tempdir().unwrap();
+        write(dir.path(), &["handle negative numbers".to_string()]).unwrap();
+        clear(dir.path()).unwrap();
+
+        assert_eq!(read(dir.path()), None);
+    }
+
+    #[test]
+    fn clear_is_a_no_op_when_there_is_nothing_pending() {
+        let dir = tempdir().unwrap();
+        assert!(clear(dir.path()).is_ok());
+    }
+}
@@ -0,0 +1,90 @@
+//! A fail-fast check that every configured LLM connection actually works,
+//! run once before a real step starts. Without it, a bad API key or an
+//! unreachable endpoint is only discovered after the context has been
+//! built and the baseline CI has run — minutes into the first step
+//! instead of seconds into the command.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tdd_llm::{LlmClient, Message};
+use thiserror::Error;
+
+/// Raised by [`run`] when a model's preflight chat request fails.
+#[derive(Debug, Error)]
+#[error("preflight check failed for model `{model}`: {message}")]
+pub struct PreflightFailed {
+    pub model: String,
+    pub message: String,
+}
+
+/// Each distinct model's latency from a successful preflight check, keyed
+/// by model identifier.
+pub type PreflightReport = HashMap<String, Duration>;
+
+/// Sends a minimal chat request to each distinct model among `clients`
+/// (deduplicated across roles sharing a model) and returns every model's
+/// latency. Aborts on the first failure, naming the model and carrying
+/// the provider's error classification in [`PreflightFailed::message`].
+pub async fn run(clients: &[(String, Arc<dyn LlmClient>)]) -> Result<PreflightReport, PreflightFailed> {
+    let mut seen = HashSet::new();
+    let mut report = PreflightReport::new();
+
+    for (model, client) in clients {
+        if !seen.insert(model.clone()) {
+            continue;
+        }
+
+        let started = Instant::now();
+        client.chat(vec![Message::user("ping")]).await.map_err(|error| PreflightFailed {
+            model: model.clone(),
+            message: error.to_string(),
+        })?;
+        let elapsed = started.elapsed();
+
+        tracing::info!(model = %model, elapsed_ms = elapsed.as_millis(), "preflight ok");
+        report.insert(model.clone(), elapsed);
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tdd_fixtures::ScriptedLlmClient;
+
+    struct FailingClient;
+
+    #[async_trait::async_trait]
+    impl LlmClient for FailingClient {
+        async fn chat(&self, _messages: Vec<Message>) -> anyhow::Result<String> {
+            anyhow::bail!("connection refused")
+        }
+    }
+
+    #[tokio::test]
+    async fn every_distinct_model_gets_one_ping() {
+        let calls = std::sync::Arc::new(ScriptedLlmClient::new(["pong".to_string(), "pong".to_string()]));
+        let clients: Vec<(String, Arc<dyn LlmClient>)> = vec![
+            ("gpt-4o-mini".to_string(), calls.clone()),
+            ("gpt-4o-mini".to_string(), calls.clone()),
+            ("gpt-4o".to_string(), calls.clone()),
+        ];
+
+        let report = run(&clients).await.unwrap();
+
+        assert_eq!(report.len(), 2);
+        assert_eq!(calls.calls().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn a_failing_model_aborts_with_its_name_and_the_provider_message() {
+        let clients: Vec<(String, Arc<dyn LlmClient>)> = vec![("gpt-4o".to_string(), Arc::new(FailingClient))];
+
+        let error = run(&clients).await.unwrap_err();
+
+        assert_eq!(error.model, "gpt-4o");
+        assert!(error.message.contains("connection refused"));
+    }
+}
@@ -0,0 +1,283 @@
+//! Mirrors a run's live state to `.tdd/state/progress.json` so an
+//! external reader — a CI wrapper, an editor plugin — can render a
+//! progress bar without parsing stdout or waiting for the run to finish.
+//! Written atomically (temp file + rename) on every [`StepEvent`], so a
+//! concurrent reader never observes a torn write; see [`read`].
+
+use crate::run_log::RunRecord;
+use crate::tui::events::StepEvent;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tdd_core::Role;
+
+/// A snapshot of where a run currently stands, read back by
+/// [`crate::status::read_status`] for its "in progress" line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProgressSnapshot {
+    /// The monotonic id (see [`crate::run_sequence`]) of the step this
+    /// snapshot describes.
+    pub run_id: u32,
+    /// This process's PID, so a reader can tell a crashed run's leftover
+    /// file apart from one still being written — there's no liveness
+    /// check beyond that today.
+    pub pid: u32,
+    pub steps_requested: u32,
+    pub steps_executed: u32,
+    pub current_step: u32,
+    pub role: Role,
+    /// A best-effort label of what the current step is doing, derived
+    /// from whichever [`StepEvent`] last fired: `"planning"`, `"editing
+    /// attempt {n}"`, `"awaiting review"`, `"committing"`, or (only when
+    /// `workspace.max_step_duration_secs` is configured, since that's the
+    /// only thing that emits [`StepEvent::DeadlineChecked`]) the exact
+    /// phase name checked against the deadline.
+    pub phase: String,
+    pub run_started_at: chrono::DateTime<chrono::Utc>,
+    pub step_started_at: chrono::DateTime<chrono::Utc>,
+    pub finished: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stop_reason: Option<crate::run_log::StopReason>,
+}
+
+impl ProgressSnapshot {
+    /// Renders the line `status` prints for an unfinished run, e.g.
+    /// `"step 2 (implementor), editing attempt 2, started 3m ago"`.
+    pub fn format_summary(&self) -> String {
+        format!(
+            "step {} ({}), {}, started {}",
+            self.current_step,
+            self.role,
+            self.phase,
+            tdd_core::humanize_age(std::time::SystemTime::now().duration_since(std::time::SystemTime::from(self.step_started_at)).unwrap_or_default())
+        )
+    }
+}
+
+fn state_path(repo_root: &Path) -> PathBuf {
+    repo_root.join(".tdd").join("state").join("progress.json")
+}
+
+fn write_atomic(path: &Path, contents: &str) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp_path = path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, contents)?;
+    std::fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+/// Reads `.tdd/state/progress.json`, or `None` if no run is in progress
+/// (including one that finished and had its file removed or, today,
+/// left marked [`ProgressSnapshot::finished`]).
+pub fn read(repo_root: &Path) -> anyhow::Result<Option<ProgressSnapshot>> {
+    let path = state_path(repo_root);
+    if !path.exists() {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::from_str(&std::fs::read_to_string(path)?)?))
+}
+
+/// Marks `.tdd/state/progress.json` finished with `run`'s outcome,
+/// called once a [`crate::run_log::execute_steps`] run ends, success or
+/// failure. A no-op if no progress file was ever written (e.g. the run
+/// aborted before [`ProgressWriter::start`] ran).
+pub fn finish(repo_root: &Path, run: &RunRecord) -> anyhow::Result<()> {
+    let path = state_path(repo_root);
+    let mut snapshot = match read(repo_root)? {
+        Some(snapshot) => snapshot,
+        None => return Ok(()),
+    };
+    snapshot.steps_executed = run.steps_executed;
+    snapshot.current_step = run.final_step_index;
+    if let Some(role) = run.final_role {
+        snapshot.role = role;
+    }
+    snapshot.finished = true;
+    snapshot.stop_reason = Some(run.stop_reason);
+    write_atomic(&path, &serde_json::to_string_pretty(&snapshot)?)
+}
+
+/// Writes and keeps `.tdd/state/progress.json` up to date for one `run`
+/// invocation. [`Self::start`] writes the initial snapshot immediately,
+/// so a reader polling before the first step finishes still sees
+/// something; [`Self::into_observer`] then keeps it current as
+/// [`StepEvent`]s arrive.
+pub struct ProgressWriter {
+    path: PathBuf,
+    snapshot: ProgressSnapshot,
+    last_counted_step: Option<u32>,
+}
+
+impl ProgressWriter {
+    pub fn start(repo_root: &Path, run_id: u32, steps_requested: u32, role: Role) -> anyhow::Result<Self> {
+        let now = chrono::Utc::now();
+        let writer = Self {
+            path: state_path(repo_root),
+            snapshot: ProgressSnapshot {
+                run_id,
+                pid: std::process::id(),
+                steps_requested,
+                steps_executed: 0,
+                current_step: 0,
+                role,
+                phase: "planning".to_string(),
+                run_started_at: now,
+                step_started_at: now,
+                finished: false,
+                stop_reason: None,
+            },
+            last_counted_step: None,
+        };
+        writer.write()?;
+        Ok(writer)
+    }
+
+    /// Turns this writer into an [`crate::orchestrator::LoopOrchestrator::add_observer`]
+    /// callback. Write failures are logged and otherwise swallowed — a
+    /// reader that can't keep up with the progress file shouldn't fail
+    /// the run it's describing.
+    pub fn into_observer(mut self) -> Box<dyn FnMut(StepEvent) + Send> {
+        Box::new(move |event| {
+            self.apply(&event);
+            if let Err(error) = self.write() {
+                tracing::warn!(%error, "failed to update .tdd/state/progress.json");
+            }
+        })
+    }
+
+    fn apply(&mut self, event: &StepEvent) {
+        let (step, role) = event_step_and_role(event);
+        if Some(step) != self.last_counted_step && self.snapshot.current_step != step {
+            self.snapshot.step_started_at = chrono::Utc::now();
+        }
+        self.snapshot.current_step = step;
+        self.snapshot.role = role;
+
+        self.snapshot.phase = match event {
+            StepEvent::PlanWritten { .. } => "planning".to_string(),
+            StepEvent::AttemptStarted { attempt, .. } => format!("editing attempt {attempt}"),
+            StepEvent::AttemptFailed { .. } => self.snapshot.phase.clone(),
+            StepEvent::FlakyRerun { .. } => "ci stage test (flaky retry)".to_string(),
+            StepEvent::ReviewPending { .. } | StepEvent::ReviewWaiting { .. } => "awaiting review".to_string(),
+            StepEvent::ReviewDecided { .. } | StepEvent::StepCommitted { .. } => "committing".to_string(),
+            StepEvent::DeadlineChecked { phase, .. } => phase.clone(),
+        };
+
+        if let StepEvent::StepCommitted { step, .. } = event {
+            if self.last_counted_step != Some(*step) {
+                self.snapshot.steps_executed += 1;
+                self.last_counted_step = Some(*step);
+            }
+        }
+    }
+
+    fn write(&self) -> anyhow::Result<()> {
+        write_atomic(&self.path, &serde_json::to_string_pretty(&self.snapshot)?)
+    }
+}
+
+fn event_step_and_role(event: &StepEvent) -> (u32, Role) {
+    match *event {
+        StepEvent::PlanWritten { role, step, .. } => (step, role),
+        StepEvent::AttemptStarted { role, step, .. } => (step, role),
+        StepEvent::AttemptFailed { role, step, .. } => (step, role),
+        StepEvent::FlakyRerun { role, step, .. } => (step, role),
+        StepEvent::ReviewPending { role, step } => (step, role),
+        StepEvent::ReviewWaiting { role, step, .. } => (step, role),
+        StepEvent::ReviewDecided { role, step, .. } => (step, role),
+        StepEvent::StepCommitted { role, step, .. } => (step, role),
+        StepEvent::DeadlineChecked { role, step, .. } => (step, role),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_log::StopReason;
+    use tempfile::tempdir;
+
+    #[test]
+    fn starting_writes_an_unfinished_snapshot_immediately() {
+        let dir = tempdir().unwrap();
+        ProgressWriter::start(dir.path(), 0, 4, Role::Tester).unwrap();
+
+        let snapshot = read(dir.path()).unwrap().unwrap();
+        assert!(!snapshot.finished);
+        assert_eq!(snapshot.steps_requested, 4);
+        assert_eq!(snapshot.phase, "planning");
+        assert_eq!(snapshot.pid, std::process::id());
+    }
+
+    #[test]
+    fn events_advance_the_phase_and_step_counters() {
+        let dir = tempdir().unwrap();
+        let writer = ProgressWriter::start(dir.path(), 0, 4, Role::Tester).unwrap();
+        let mut observer = writer.into_observer();
+
+        observer(StepEvent::PlanWritten { role: Role::Tester, step: 0, plan: "plan".to_string() });
+        assert_eq!(read(dir.path()).unwrap().unwrap().phase, "planning");
+
+        observer(StepEvent::AttemptStarted { role: Role::Tester, step: 0, attempt: 1 });
+        assert_eq!(read(dir.path()).unwrap().unwrap().phase, "editing attempt 1");
+
+        observer(StepEvent::StepCommitted { role: Role::Tester, step: 0, commit_message: "test: add a failing test".to_string() });
+        let snapshot = read(dir.path()).unwrap().unwrap();
+        assert_eq!(snapshot.phase, "committing");
+        assert_eq!(snapshot.steps_executed, 1);
+
+        observer(StepEvent::AttemptStarted { role: Role::Implementor, step: 1, attempt: 1 });
+        let snapshot = read(dir.path()).unwrap().unwrap();
+        assert_eq!(snapshot.current_step, 1);
+        assert_eq!(snapshot.role, Role::Implementor);
+    }
+
+    #[test]
+    fn finishing_marks_the_snapshot_with_the_stop_reason() {
+        let dir = tempdir().unwrap();
+        ProgressWriter::start(dir.path(), 0, 4, Role::Tester).unwrap();
+
+        let now = chrono::Utc::now();
+        finish(dir.path(), &RunRecord {
+            stop_reason: StopReason::Completed,
+            steps_requested: 4,
+            steps_executed: 4,
+            started_at: now,
+            ended_at: now,
+            config_hash: "deadbeef".to_string(),
+            final_step_index: 4,
+            final_role: Some(Role::Tester),
+            failure: None,
+            max_steps_overridden: false,
+            detached_head_branch: None,
+        })
+        .unwrap();
+
+        let snapshot = read(dir.path()).unwrap().unwrap();
+        assert!(snapshot.finished);
+        assert_eq!(snapshot.stop_reason, Some(StopReason::Completed));
+        assert_eq!(snapshot.steps_executed, 4);
+    }
+
+    #[test]
+    fn finishing_without_a_progress_file_is_a_no_op() {
+        let dir = tempdir().unwrap();
+        let now = chrono::Utc::now();
+        finish(dir.path(), &RunRecord {
+            stop_reason: StopReason::AbortedBeforeStart,
+            steps_requested: 1,
+            steps_executed: 0,
+            started_at: now,
+            ended_at: now,
+            config_hash: String::new(),
+            final_step_index: 0,
+            final_role: None,
+            failure: None,
+            max_steps_overridden: false,
+            detached_head_branch: None,
+        })
+        .unwrap();
+
+        assert_eq!(read(dir.path()).unwrap(), None);
+    }
+}
@@ -0,0 +1,141 @@
+//! Tracks the provider+model fingerprint a step ran under, so switching
+//! `tdd.yaml`'s `llm` section mid-kata (e.g. after hitting a quota) is
+//! surfaced instead of silently mixing logs and usage accounting across
+//! providers. Read by [`crate::orchestrator::LoopOrchestrator`] at the
+//! start of every step.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// The provider+model combination a step ran under.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderFingerprint {
+    pub provider: String,
+    pub model: String,
+}
+
+impl ProviderFingerprint {
+    pub fn new(provider: impl Into<String>, model: impl Into<String>) -> Self {
+        Self {
+            provider: provider.into(),
+            model: model.into(),
+        }
+    }
+
+    /// The `provider/model` form used in switch notices, step log
+    /// markers, and usage keys.
+    pub fn label(&self) -> String {
+        format!("{}/{}", self.provider, self.model)
+    }
+
+    fn state_path(repo_root: &Path) -> PathBuf {
+        repo_root.join(".tdd").join("state").join("provider.json")
+    }
+
+    fn load(repo_root: &Path) -> anyhow::Result<Option<Self>> {
+        let path = Self::state_path(repo_root);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&std::fs::read_to_string(path)?)?))
+    }
+
+    fn save(&self, repo_root: &Path) -> anyhow::Result<()> {
+        let path = Self::state_path(repo_root);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Step counts partitioned by provider, persisted at
+/// `.tdd/state/usage.json`. A provider switch never merges one provider's
+/// counter into another's; `total_steps` gives the combined view `status`
+/// reports alongside the per-provider breakdown.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UsageLog {
+    #[serde(default)]
+    pub steps_by_provider: HashMap<String, u32>,
+}
+
+impl UsageLog {
+    fn state_path(repo_root: &Path) -> PathBuf {
+        repo_root.join(".tdd").join("state").join("usage.json")
+    }
+
+    pub fn load(repo_root: &Path) -> anyhow::Result<Self> {
+        let path = Self::state_path(repo_root);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    fn save(&self, repo_root: &Path) -> anyhow::Result<()> {
+        let path = Self::state_path(repo_root);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn total_steps(&self) -> u32 {
+        self.steps_by_provider.values().sum()
+    }
+}
+
+/// Records that a step ran under `fingerprint`: persists it as the
+/// current fingerprint, bumps that provider's step count in the usage
+/// log, and, if the previous step ran under a different fingerprint,
+/// returns it so the caller can print a switch notice and annotate the
+/// step log.
+pub fn record_step(repo_root: &Path, fingerprint: &ProviderFingerprint) -> anyhow::Result<Option<ProviderFingerprint>> {
+    let previous = ProviderFingerprint::load(repo_root)?;
+    fingerprint.save(repo_root)?;
+
+    let mut usage = UsageLog::load(repo_root)?;
+    *usage.steps_by_provider.entry(fingerprint.provider.clone()).or_insert(0) += 1;
+    usage.save(repo_root)?;
+
+    Ok(previous.filter(|previous| previous != fingerprint))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn the_first_step_has_no_previous_provider_to_switch_from() {
+        let dir = tempdir().unwrap();
+        let switch = record_step(dir.path(), &ProviderFingerprint::new("openai", "gpt-4o-mini")).unwrap();
+        assert!(switch.is_none());
+    }
+
+    #[test]
+    fn a_same_provider_step_is_not_reported_as_a_switch() {
+        let dir = tempdir().unwrap();
+        record_step(dir.path(), &ProviderFingerprint::new("openai", "gpt-4o-mini")).unwrap();
+        let switch = record_step(dir.path(), &ProviderFingerprint::new("openai", "gpt-4o-mini")).unwrap();
+        assert!(switch.is_none());
+    }
+
+    #[test]
+    fn a_provider_change_is_reported_and_usage_stays_partitioned() {
+        let dir = tempdir().unwrap();
+        record_step(dir.path(), &ProviderFingerprint::new("openai", "gpt-4o-mini")).unwrap();
+        record_step(dir.path(), &ProviderFingerprint::new("openai", "gpt-4o-mini")).unwrap();
+        let switch = record_step(dir.path(), &ProviderFingerprint::new("github_copilot", "gpt-4o")).unwrap();
+
+        assert_eq!(switch, Some(ProviderFingerprint::new("openai", "gpt-4o-mini")));
+
+        let usage = UsageLog::load(dir.path()).unwrap();
+        assert_eq!(usage.steps_by_provider.get("openai"), Some(&2));
+        assert_eq!(usage.steps_by_provider.get("github_copilot"), Some(&1));
+        assert_eq!(usage.total_steps(), 3);
+    }
+}
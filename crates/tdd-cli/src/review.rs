@@ -0,0 +1,239 @@
+//! The file-based asynchronous review gate used when
+//! `workspace.review_mode` is `file`: a verified step's proposed commit is
+//! written under `.tdd/review/` instead of being committed immediately,
+//! and [`crate::orchestrator::LoopOrchestrator`] polls for a decision file
+//! a reviewer (or the companion `tdd-cli review` command) writes back.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tdd_core::Role;
+
+/// Relative to the workspace root.
+pub const REVIEW_DIR: &str = ".tdd/review";
+
+/// How a step's verified edits reach a commit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewMode {
+    /// Commit as soon as a step verifies, today's behavior.
+    #[default]
+    Off,
+    /// Pause a verified step and wait for a decision file under
+    /// `.tdd/review/` before committing.
+    File,
+}
+
+/// What a pending review asks a reviewer to decide on, persisted as
+/// `.tdd/review/pending-step-{step:03}.json` alongside a human-readable
+/// `.md` rendering of the same data.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingReview {
+    pub step: u32,
+    pub role: Role,
+    pub commit_message: String,
+    pub files: Vec<String>,
+    pub patch: String,
+}
+
+/// A reviewer's decision, parsed from `.tdd/review/decision-step-{n}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReviewDecision {
+    Accept,
+    Reject(String),
+    EditMessage(String),
+}
+
+pub fn pending_json_name(step: u32) -> String {
+    format!("pending-step-{step:03}.json")
+}
+
+pub fn pending_markdown_name(step: u32) -> String {
+    format!("pending-step-{step:03}.md")
+}
+
+pub fn decision_file_name(step: u32) -> String {
+    format!("decision-step-{step:03}")
+}
+
+/// Writes `review`'s JSON record and markdown rendering, creating
+/// `.tdd/review/` if needed.
+pub fn write_pending(repo_root: &Path, review: &PendingReview) -> anyhow::Result<()> {
+    let dir = repo_root.join(REVIEW_DIR);
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(dir.join(pending_json_name(review.step)), serde_json::to_string_pretty(review)?)?;
+    std::fs::write(dir.join(pending_markdown_name(review.step)), render_markdown(review))?;
+    Ok(())
+}
+
+fn render_markdown(review: &PendingReview) -> String {
+    let mut out = format!("# Step {} ({})\n\n{}\n\n## Files\n\n", review.step, review.role, review.commit_message);
+    for file in &review.files {
+        out.push_str(&format!("- {file}\n"));
+    }
+    out.push_str("\n## Patch\n\n```diff\n");
+    out.push_str(&review.patch);
+    out.push_str("```\n");
+    out
+}
+
+/// Removes a step's pending review record once it's been decided.
+pub fn clear_pending(repo_root: &Path, step: u32) -> anyhow::Result<()> {
+    let dir = repo_root.join(REVIEW_DIR);
+    for name in [pending_json_name(step), pending_markdown_name(step), decision_file_name(step)] {
+        let path = dir.join(name);
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Every still-pending review under `.tdd/review/`, oldest step first, for
+/// `tdd-cli review` to list.
+pub fn list_pending(repo_root: &Path) -> anyhow::Result<Vec<PendingReview>> {
+    let dir = repo_root.join(REVIEW_DIR);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut reviews = Vec::new();
+    for entry in std::fs::read_dir(&dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) == Some("json") {
+            reviews.push(serde_json::from_str(&std::fs::read_to_string(&path)?)?);
+        }
+    }
+    reviews.sort_by_key(|review: &PendingReview| review.step);
+    Ok(reviews)
+}
+
+/// Reads and parses a step's decision file, if one has been written yet.
+pub fn read_decision(repo_root: &Path, step: u32) -> anyhow::Result<Option<ReviewDecision>> {
+    let path = repo_root.join(REVIEW_DIR).join(decision_file_name(step));
+    if !path.exists() {
+        return Ok(None);
+    }
+    Ok(Some(parse_decision(&std::fs::read_to_string(path)?)?))
+}
+
+fn parse_decision(raw: &str) -> anyhow::Result<ReviewDecision> {
+    let trimmed = raw.trim();
+    if trimmed == "accept" {
+        Ok(ReviewDecision::Accept)
+    } else if let Some(reason) = trimmed.strip_prefix("reject:") {
+        Ok(ReviewDecision::Reject(reason.trim().to_string()))
+    } else if let Some(message) = trimmed.strip_prefix("edit-message:") {
+        Ok(ReviewDecision::EditMessage(message.trim().to_string()))
+    } else {
+        anyhow::bail!("unrecognized review decision {trimmed:?}: expected \"accept\", \"reject: <reason>\", or \"edit-message: <new summary>\"")
+    }
+}
+
+/// Writes a step's decision file, validating its shape first so a typo
+/// fails at the `review` CLI rather than silently stalling the poller.
+pub fn write_decision(repo_root: &Path, step: u32, raw: &str) -> anyhow::Result<()> {
+    parse_decision(raw)?;
+    let dir = repo_root.join(REVIEW_DIR);
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(dir.join(decision_file_name(step)), raw)?;
+    Ok(())
+}
+
+pub fn review_dir(repo_root: &Path) -> PathBuf {
+    repo_root.join(REVIEW_DIR)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_review() -> PendingReview {
+        PendingReview {
+            step: 4,
+            role: Role::Implementor,
+            commit_message: "feat: add addition".to_string(),
+            files: vec!["src/lib.rs".to_string()],
+            patch: "+pub fn add(a: i32, b: i32) -> i32 { a + b }\n".to_string(),
+        }
+    }
+
+    #[test]
+    fn writing_a_pending_review_produces_a_json_record_and_a_markdown_rendering() {
+        let dir = tempfile::tempdir().unwrap();
+        let review = sample_review();
+
+        write_pending(dir.path(), &review).unwrap();
+
+        let json = std::fs::read_to_string(review_dir(dir.path()).join(pending_json_name(4))).unwrap();
+        assert_eq!(serde_json::from_str::<PendingReview>(&json).unwrap(), review);
+
+        let markdown = std::fs::read_to_string(review_dir(dir.path()).join(pending_markdown_name(4))).unwrap();
+        assert!(markdown.contains("feat: add addition"));
+        assert!(markdown.contains("src/lib.rs"));
+    }
+
+    #[test]
+    fn an_accept_decision_parses_with_no_payload() {
+        assert_eq!(parse_decision("accept\n").unwrap(), ReviewDecision::Accept);
+    }
+
+    #[test]
+    fn a_reject_decision_carries_its_reason() {
+        assert_eq!(parse_decision("reject: missing an edge case\n").unwrap(), ReviewDecision::Reject("missing an edge case".to_string()));
+    }
+
+    #[test]
+    fn an_edit_message_decision_carries_the_replacement_summary() {
+        assert_eq!(
+            parse_decision("edit-message: feat: add subtraction\n").unwrap(),
+            ReviewDecision::EditMessage("feat: add subtraction".to_string())
+        );
+    }
+
+    #[test]
+    fn an_unrecognized_decision_is_rejected() {
+        assert!(parse_decision("lgtm").is_err());
+    }
+
+    #[test]
+    fn read_decision_is_none_before_a_decision_file_exists() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(read_decision(dir.path(), 4).unwrap(), None);
+    }
+
+    #[test]
+    fn write_decision_then_read_decision_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        write_decision(dir.path(), 4, "reject: flaky assertion").unwrap();
+        assert_eq!(read_decision(dir.path(), 4).unwrap(), Some(ReviewDecision::Reject("flaky assertion".to_string())));
+    }
+
+    #[test]
+    fn write_decision_rejects_an_unrecognized_shape_before_touching_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(write_decision(dir.path(), 4, "maybe").is_err());
+        assert!(!review_dir(dir.path()).join(decision_file_name(4)).exists());
+    }
+
+    #[test]
+    fn list_pending_returns_every_review_sorted_by_step() {
+        let dir = tempfile::tempdir().unwrap();
+        write_pending(dir.path(), &PendingReview { step: 7, ..sample_review() }).unwrap();
+        write_pending(dir.path(), &PendingReview { step: 2, ..sample_review() }).unwrap();
+
+        let steps: Vec<u32> = list_pending(dir.path()).unwrap().iter().map(|review| review.step).collect();
+        assert_eq!(steps, vec![2, 7]);
+    }
+
+    #[test]
+    fn clear_pending_removes_every_file_for_a_step() {
+        let dir = tempfile::tempdir().unwrap();
+        write_pending(dir.path(), &sample_review()).unwrap();
+        write_decision(dir.path(), 4, "accept").unwrap();
+
+        clear_pending(dir.path(), 4).unwrap();
+
+        assert!(!review_dir(dir.path()).join(pending_json_name(4)).exists());
+        assert!(!review_dir(dir.path()).join(pending_markdown_name(4)).exists());
+        assert!(!review_dir(dir.path()).join(decision_file_name(4)).exists());
+    }
+}
@@ -0,0 +1,357 @@
+//! Records why the most recent `run` invocation ended, at
+//! `.tdd/state/last-run.json`, so [`crate::status::read_status`] can
+//! answer "why did the last run stop" without re-reading every step log,
+//! and a future aggregate `stats` command has somewhere to read from.
+
+use crate::config::TddConfig;
+use crate::cycle_branch::{self, ReviewBranchOptions};
+use crate::orchestrator::LoopOrchestrator;
+use serde::{Deserialize, Serialize};
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use tdd_core::{Orchestrator, Role, StepFailureDetail};
+
+/// Why a `run` invocation stopped. Only covers the ways a run can
+/// actually end today; a scheduling/budget layer would add variants here
+/// rather than inventing a parallel mechanism.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StopReason {
+    /// Every requested step ran and committed.
+    Completed,
+    /// A step's attempts were exhausted, or another step error aborted
+    /// the run partway through.
+    Failed,
+    /// The run never reached its first step: a read-only checkout,
+    /// missing/invalid `tdd.yaml`, or a failed preflight model check.
+    AbortedBeforeStart,
+    /// The run never reached its first step because `workspace.max_steps`
+    /// had already been reached or exceeded and `--ignore-max-steps`
+    /// wasn't passed. Distinct from [`Self::AbortedBeforeStart`] so
+    /// `status` can point straight at the fix instead of a generic abort.
+    MaxStepsReached,
+    /// Under `workspace.pair_mode`, the run stopped cleanly on an
+    /// Implementor turn because check and test weren't both green yet —
+    /// a human still has work to do. No step was consumed, and running
+    /// again once the suite passes resolves it.
+    AwaitingHumanImplementor,
+}
+
+impl std::fmt::Display for StopReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            StopReason::Completed => "completed",
+            StopReason::Failed => "failed",
+            StopReason::AbortedBeforeStart => "aborted before start",
+            StopReason::MaxStepsReached => "max_steps reached",
+            StopReason::AwaitingHumanImplementor => "awaiting human implementor",
+        };
+        f.write_str(label)
+    }
+}
+
+/// What happened during one `run` invocation, success or failure.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub stop_reason: StopReason,
+    pub steps_requested: u32,
+    pub steps_executed: u32,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub ended_at: chrono::DateTime<chrono::Utc>,
+    /// A hash of `tdd.yaml`'s raw contents at the start of the run, so
+    /// two runs can be compared without re-reading and re-parsing the
+    /// file. Empty when the config couldn't be read at all.
+    pub config_hash: String,
+    pub final_step_index: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub final_role: Option<Role>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure: Option<StepFailureDetail>,
+    /// Whether this run started past a configured
+    /// [`crate::config::WorkspaceConfig::max_steps`] ceiling because
+    /// `--ignore-max-steps` was passed. `false` for a run that never hit
+    /// the ceiling, including every run recorded before this field
+    /// existed.
+    #[serde(default)]
+    pub max_steps_overridden: bool,
+    /// The branch `run` created and checked out because `HEAD` was
+    /// detached and `git.detached_head` was `branch`. `None` for a run
+    /// that found `HEAD` already on a branch, including every run
+    /// recorded before this field existed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detached_head_branch: Option<String>,
+}
+
+impl RunRecord {
+    /// Renders the line `status` prints, e.g. `"stopped after 4 steps
+    /// (failed) at 2025-01-12 13:40"`.
+    pub fn format_summary(&self) -> String {
+        format!(
+            "stopped after {} steps ({}{}{}) at {}",
+            self.steps_executed,
+            self.stop_reason,
+            if self.max_steps_overridden { ", max_steps overridden" } else { "" },
+            self.detached_head_branch.as_deref().map(|branch| format!(", detached HEAD branched to {branch}")).unwrap_or_default(),
+            self.ended_at.format("%Y-%m-%d %H:%M")
+        )
+    }
+}
+
+/// Runs up to `steps` orchestrator steps, stopping at the first error,
+/// and returns both the [`RunRecord`] describing how the run ended and
+/// that error (if any), so a caller can persist the record and still
+/// propagate the failure. Never writes the record itself — see
+/// [`record`].
+///
+/// With `review_branch` set, begins a new `tdd/cycle-{n}` branch (see
+/// [`crate::cycle_branch`]) every time a full cycle's worth of steps is
+/// about to start, and ends it once that cycle's last step has
+/// committed, so each cycle lands on its own branch instead of directly
+/// on the branch `run` started from.
+pub async fn execute_steps(
+    orchestrator: &mut LoopOrchestrator,
+    steps: u32,
+    config_hash: String,
+    review_branch: Option<ReviewBranchOptions<'_>>,
+) -> (RunRecord, Option<anyhow::Error>) {
+    let started_at = chrono::Utc::now();
+    let mut steps_executed = 0;
+    let mut error = None;
+    let mut awaiting_human = false;
+    let cycle_len = orchestrator.required_roles().len() as u32;
+
+    while steps_executed < steps {
+        if let Some(opts) = &review_branch {
+            if steps_executed % cycle_len == 0 {
+                if let Err(e) = cycle_branch::begin_cycle(orchestrator.repo_root(), opts.vcs, &opts.integration_branch, opts.allow_stacked) {
+                    error = Some(e);
+                    break;
+                }
+            }
+        }
+
+        if orchestrator.pair_mode() && orchestrator.current_role() == Role::Implementor {
+            match orchestrator.resolve_pair_mode_implementor().await {
+                Ok(crate::orchestrator::PairModeOutcome::AwaitingHuman) => {
+                    awaiting_human = true;
+                    break;
+                }
+                Ok(crate::orchestrator::PairModeOutcome::HumanStepResolved) => {}
+                Err(e) => {
+                    error = Some(e);
+                    break;
+                }
+            }
+        } else if let Err(e) = orchestrator.next().await {
+            error = Some(e);
+            break;
+        }
+        steps_executed += 1;
+
+        if let Some(opts) = &review_branch {
+            if steps_executed % cycle_len == 0 {
+                if let Err(e) = cycle_branch::end_cycle(orchestrator.repo_root(), opts.vcs, &opts.integration_branch, opts.auto_merge, opts.no_ff) {
+                    error = Some(e);
+                    break;
+                }
+            }
+        }
+    }
+
+    let stop_reason = if awaiting_human {
+        StopReason::AwaitingHumanImplementor
+    } else if error.is_some() {
+        StopReason::Failed
+    } else {
+        StopReason::Completed
+    };
+    let run_record = RunRecord {
+        stop_reason,
+        steps_requested: steps,
+        steps_executed,
+        started_at,
+        ended_at: chrono::Utc::now(),
+        config_hash,
+        final_step_index: orchestrator.step_index(),
+        final_role: Some(orchestrator.current_role()),
+        failure: None,
+        max_steps_overridden: orchestrator.max_steps_overridden(),
+        detached_head_branch: orchestrator.detached_head_branch().map(str::to_string),
+    };
+    (run_record, error)
+}
+
+fn state_path(repo_root: &Path) -> PathBuf {
+    repo_root.join(".tdd").join("state").join("last-run.json")
+}
+
+/// Writes `record` to `.tdd/state/last-run.json`, creating the directory
+/// if needed. Called at the end of every `run` invocation, success or
+/// failure, including one that aborts before its first step.
+pub fn record(repo_root: &Path, record: &RunRecord) -> anyhow::Result<()> {
+    let path = state_path(repo_root);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(record)?)?;
+    Ok(())
+}
+
+/// Reads `.tdd/state/last-run.json`, or `None` if no run has completed
+/// yet.
+pub fn load(repo_root: &Path) -> anyhow::Result<Option<RunRecord>> {
+    let path = state_path(repo_root);
+    if !path.exists() {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::from_str(&std::fs::read_to_string(path)?)?))
+}
+
+/// A stable hash of the effective config — `tdd.yaml` with every
+/// `extends:` fragment merged in and every default applied — for telling
+/// two runs' configs apart without a real content-addressed store. A
+/// fragment-only edit changes this even when `tdd.yaml` itself doesn't.
+/// `None` if the config can't be loaded at all.
+pub fn config_hash(repo_root: &Path) -> Option<String> {
+    let config = TddConfig::load(&repo_root.join("tdd.yaml")).ok()?;
+    let raw = serde_yaml::to_string(&config).ok()?;
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    raw.hash(&mut hasher);
+    Some(format!("{:016x}", hasher.finish()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn sample(stop_reason: StopReason) -> RunRecord {
+        let now = chrono::Utc::now();
+        RunRecord {
+            stop_reason,
+            steps_requested: 4,
+            steps_executed: 4,
+            started_at: now,
+            ended_at: now,
+            config_hash: "deadbeef".to_string(),
+            final_step_index: 3,
+            final_role: Some(Role::Implementor),
+            failure: None,
+            max_steps_overridden: false,
+            detached_head_branch: None,
+        }
+    }
+
+    #[test]
+    fn no_run_recorded_yet_loads_as_none() {
+        let dir = tempdir().unwrap();
+        assert_eq!(load(dir.path()).unwrap(), None);
+    }
+
+    #[test]
+    fn a_recorded_run_round_trips_through_load() {
+        let dir = tempdir().unwrap();
+        let run = sample(StopReason::Completed);
+        record(dir.path(), &run).unwrap();
+
+        let loaded = load(dir.path()).unwrap().unwrap();
+        assert_eq!(loaded.stop_reason, StopReason::Completed);
+        assert_eq!(loaded.steps_executed, 4);
+        assert_eq!(loaded.final_role, Some(Role::Implementor));
+    }
+
+    #[test]
+    fn a_run_aborted_before_its_first_step_records_zero_steps_executed() {
+        let dir = tempdir().unwrap();
+        let now = chrono::Utc::now();
+        let run = RunRecord {
+            stop_reason: StopReason::AbortedBeforeStart,
+            steps_requested: 5,
+            steps_executed: 0,
+            started_at: now,
+            ended_at: now,
+            config_hash: String::new(),
+            final_step_index: 0,
+            final_role: None,
+            failure: None,
+            max_steps_overridden: false,
+            detached_head_branch: None,
+        };
+        record(dir.path(), &run).unwrap();
+
+        let loaded = load(dir.path()).unwrap().unwrap();
+        assert_eq!(loaded.stop_reason, StopReason::AbortedBeforeStart);
+        assert_eq!(loaded.steps_executed, 0);
+        assert_eq!(loaded.final_role, None);
+    }
+
+    #[test]
+    fn a_later_run_overwrites_the_previous_record() {
+        let dir = tempdir().unwrap();
+        record(dir.path(), &sample(StopReason::Completed)).unwrap();
+        record(dir.path(), &sample(StopReason::Failed)).unwrap();
+
+        assert_eq!(load(dir.path()).unwrap().unwrap().stop_reason, StopReason::Failed);
+    }
+
+    #[test]
+    fn format_summary_reads_like_a_status_line() {
+        let run = sample(StopReason::Failed);
+        assert!(run.format_summary().starts_with("stopped after 4 steps (failed) at "));
+    }
+
+    #[test]
+    fn format_summary_notes_an_overridden_max_steps_cap() {
+        let run = RunRecord { max_steps_overridden: true, ..sample(StopReason::Completed) };
+        assert!(run.format_summary().starts_with("stopped after 4 steps (completed, max_steps overridden) at "));
+    }
+
+    #[test]
+    fn format_summary_names_the_branch_a_detached_head_was_moved_to() {
+        let run = RunRecord { detached_head_branch: Some("tdd/run-20260808100000".to_string()), ..sample(StopReason::Completed) };
+        assert!(run.format_summary().starts_with("stopped after 4 steps (completed, detached HEAD branched to tdd/run-20260808100000) at "));
+    }
+
+    #[test]
+    fn config_hash_is_none_when_tdd_yaml_is_missing() {
+        let dir = tempdir().unwrap();
+        assert_eq!(config_hash(dir.path()), None);
+    }
+
+    #[test]
+    fn config_hash_changes_when_the_file_contents_change() {
+        let dir = tempdir().unwrap();
+        write_minimal_config(&dir.path().join("tdd.yaml"), 10);
+        let first = config_hash(dir.path()).unwrap();
+
+        write_minimal_config(&dir.path().join("tdd.yaml"), 20);
+        let second = config_hash(dir.path()).unwrap();
+
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn config_hash_changes_when_an_extended_fragment_changes_even_if_tdd_yaml_does_not() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("base.yaml"), "ci:\n  test_flaky_retries: 1\n").unwrap();
+        std::fs::write(
+            dir.path().join("tdd.yaml"),
+            "extends: base.yaml\nkata_description: kata.md\nroles: {}\nllm: {provider: ollama, base_url: 'http://x', api_key_env: K}\n",
+        )
+        .unwrap();
+        let first = config_hash(dir.path()).unwrap();
+
+        std::fs::write(dir.path().join("base.yaml"), "ci:\n  test_flaky_retries: 2\n").unwrap();
+        let second = config_hash(dir.path()).unwrap();
+
+        assert_ne!(first, second);
+    }
+
+    fn write_minimal_config(path: &Path, steps: u32) {
+        std::fs::write(
+            path,
+            format!("kata_description: kata.md\nsteps: {steps}\nroles: {{}}\nllm: {{provider: ollama, base_url: 'http://x', api_key_env: K}}\n"),
+        )
+        .unwrap();
+    }
+}
@@ -0,0 +1,41 @@
+//! Persists a monotonic counter at `.tdd/state/seq` handing out a fresh
+//! run-id each time a step executes, so a step's plan/log filenames
+//! (formatted via [`tdd_core::artifacts::format_stem`]) never collide
+//! with an earlier execution of the same step — undo, then re-run, used
+//! to overwrite the archived original because both runs wrote to the
+//! same path.
+
+use std::path::Path;
+
+const SEQ_FILE: &str = ".tdd/state/seq";
+
+/// Returns the next run-id, persisting the updated counter. A workspace
+/// that has never recorded one starts at 0.
+pub fn next_run_id(repo_root: &Path) -> anyhow::Result<u32> {
+    let path = repo_root.join(SEQ_FILE);
+    let current: u32 = std::fs::read_to_string(&path).ok().and_then(|raw| raw.trim().parse().ok()).unwrap_or(0);
+
+    std::fs::create_dir_all(path.parent().unwrap())?;
+    std::fs::write(&path, (current + 1).to_string())?;
+    Ok(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn the_first_call_on_a_fresh_workspace_returns_zero() {
+        let dir = tempdir().unwrap();
+        assert_eq!(next_run_id(dir.path()).unwrap(), 0);
+    }
+
+    #[test]
+    fn successive_calls_increment_and_persist() {
+        let dir = tempdir().unwrap();
+        assert_eq!(next_run_id(dir.path()).unwrap(), 0);
+        assert_eq!(next_run_id(dir.path()).unwrap(), 1);
+        assert_eq!(next_run_id(dir.path()).unwrap(), 2);
+    }
+}
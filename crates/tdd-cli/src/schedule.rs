@@ -0,0 +1,310 @@
+//! Implements `tdd-cli schedule`: a long-lived process that sleeps until
+//! the next tick, runs a configured number of cycles the same way `run`
+//! does, and goes back to sleep — for a kata repo that should advance by
+//! itself overnight instead of waiting on someone to type `tdd-cli run`.
+//!
+//! There's no notification-delivery channel (email, Slack, ...) anywhere
+//! in this codebase to plug a "notify on failure" step into, so a failed
+//! scheduled run is reported the same way any other `run` failure is:
+//! logged to stderr and recorded at `.tdd/state/last-run.json` (see
+//! [`crate::run_log`]), visible from `status`. That failure never stops
+//! the scheduler itself — the tick is skipped and the loop goes back to
+//! sleep for the next one.
+
+use crate::cli::{RunArgs, ScheduleArgs};
+use crate::orchestrator::LoopOrchestrator;
+use crate::run_log;
+use chrono::{DateTime, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// When the next scheduled run is due.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduleSpec {
+    /// This long after the last scheduled run (or immediately, if the
+    /// scheduler has never run yet).
+    Interval(Duration),
+    /// The next occurrence of this UTC time of day.
+    FixedTime(NaiveTime),
+}
+
+impl ScheduleSpec {
+    /// Parses `--every`, e.g. `"24h"`, `"90m"`, `"45s"`. There's no
+    /// existing duration-string parser in this codebase to reuse, so
+    /// this only supports a single `s`/`m`/`h` suffix.
+    pub fn parse_every(raw: &str) -> Result<Duration, String> {
+        let raw = raw.trim();
+        let split_at = raw.find(|c: char| !c.is_ascii_digit()).ok_or_else(|| format!("'{raw}' is missing a unit (s, m, or h)"))?;
+        let (digits, unit) = raw.split_at(split_at);
+        let amount: u64 = digits.parse().map_err(|_| format!("'{raw}' doesn't start with a whole number"))?;
+        let secs = match unit {
+            "s" => amount,
+            "m" => amount * 60,
+            "h" => amount * 3600,
+            other => return Err(format!("unknown duration unit '{other}' in '{raw}'; use s, m, or h")),
+        };
+        if secs == 0 {
+            return Err("--every must be greater than zero".to_string());
+        }
+        Ok(Duration::from_secs(secs))
+    }
+
+    /// Parses `--at`, e.g. `"02:30"`, as a 24-hour UTC time. This
+    /// codebase otherwise only ever deals in UTC (see [`run_log`]), so
+    /// `--at` doesn't read the host's local timezone.
+    pub fn parse_at(raw: &str) -> Result<NaiveTime, String> {
+        NaiveTime::parse_from_str(raw.trim(), "%H:%M").map_err(|_| format!("'{raw}' isn't a 24-hour UTC time like \"02:30\""))
+    }
+}
+
+/// Computes the next time a scheduled run is due, given when the last
+/// one happened (`None` if the scheduler has never run before) and the
+/// current time. A pure function so the interval and fixed-time math,
+/// including the single-catch-up rule, can be tested without a real
+/// clock or a real sleep.
+///
+/// For [`ScheduleSpec::Interval`], the next tick is always exactly one
+/// interval after the last run — even if several intervals' worth of
+/// time has passed (the scheduler was asleep), the next tick is still
+/// just one interval out, so a long gap catches up with a single run
+/// rather than one run per missed interval.
+///
+/// For [`ScheduleSpec::FixedTime`], the next tick is the soonest
+/// occurrence of `at` that's still after the last run (today's, if it
+/// hasn't happened yet and there's no last run to be after; otherwise
+/// the next day's), which gives the same single-catch-up behavior: a
+/// multi-day gap lands on the very next occurrence, not one per missed
+/// day.
+pub fn next_tick(spec: &ScheduleSpec, last_run: Option<DateTime<Utc>>, now: DateTime<Utc>) -> DateTime<Utc> {
+    match spec {
+        ScheduleSpec::Interval(interval) => match last_run {
+            Some(last) => last + chrono::Duration::from_std(*interval).expect("--every fits in a chrono::Duration"),
+            None => now,
+        },
+        ScheduleSpec::FixedTime(at) => next_occurrence_of(*at, last_run.unwrap_or(now)),
+    }
+}
+
+/// The next occurrence of `at` (UTC time of day) that's strictly after
+/// `after`: today's, unless it's already passed, in which case tomorrow's.
+fn next_occurrence_of(at: NaiveTime, after: DateTime<Utc>) -> DateTime<Utc> {
+    let today = after.date_naive().and_time(at).and_utc();
+    if today > after {
+        today
+    } else {
+        today + chrono::Duration::days(1)
+    }
+}
+
+/// The scheduler's persisted state, at `.tdd/state/schedule.json`, so a
+/// restarted scheduler picks up from the last run it actually made
+/// instead of double-running a tick it already served.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScheduleState {
+    pub last_scheduled_run: Option<DateTime<Utc>>,
+}
+
+fn state_path(repo_root: &Path) -> PathBuf {
+    repo_root.join(".tdd").join("state").join("schedule.json")
+}
+
+/// Reads `.tdd/state/schedule.json`, defaulting to a scheduler that's
+/// never run before if the file doesn't exist yet.
+pub fn load_state(repo_root: &Path) -> anyhow::Result<ScheduleState> {
+    let path = state_path(repo_root);
+    if !path.exists() {
+        return Ok(ScheduleState::default());
+    }
+    Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+}
+
+/// Writes `state` to `.tdd/state/schedule.json`, creating the directory
+/// if needed.
+pub fn save_state(repo_root: &Path, state: &ScheduleState) -> anyhow::Result<()> {
+    let path = state_path(repo_root);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(state)?)?;
+    Ok(())
+}
+
+/// Sleeps until the next tick is due (skipped if it's already due),
+/// runs `cycles` cycles through `orchestrator`, and records the result,
+/// repeating forever unless `once` is set. A failed run is logged and
+/// recorded but never stops the loop.
+///
+/// Runs [`crate::run_log::execute_steps`] directly rather than going
+/// through a subprocess, so a scheduled run shares the same
+/// already-built orchestrator (and its preflight check) across every
+/// tick instead of paying that cost again each time.
+pub async fn run_loop(
+    repo_root: &Path,
+    orchestrator: &mut LoopOrchestrator,
+    config_hash: String,
+    spec: &ScheduleSpec,
+    cycles: u32,
+    once: bool,
+) -> anyhow::Result<()> {
+    loop {
+        let state = load_state(repo_root)?;
+        let now = Utc::now();
+        let due = next_tick(spec, state.last_scheduled_run, now);
+        if due > now {
+            tokio::time::sleep((due - now).to_std().unwrap_or_default()).await;
+        }
+
+        let steps = cycles.saturating_mul(orchestrator.required_roles().len() as u32);
+        let (run_record, error) = run_log::execute_steps(orchestrator, steps, config_hash.clone(), None).await;
+        run_log::record(repo_root, &run_record)?;
+        if let Some(error) = &error {
+            eprintln!("WARNING      scheduled run at {} failed: {error}; the next tick will still proceed", Utc::now().format("%Y-%m-%d %H:%M:%S UTC"));
+        } else {
+            println!("scheduled run at {}: {}", Utc::now().format("%Y-%m-%d %H:%M:%S UTC"), run_record.format_summary());
+        }
+
+        save_state(repo_root, &ScheduleState { last_scheduled_run: Some(Utc::now()) })?;
+
+        if once {
+            return Ok(());
+        }
+    }
+}
+
+/// The `tdd-cli schedule` entrypoint: builds the orchestrator the same
+/// way `run` would, then hands off to [`run_loop`], or with `--dry-run`
+/// (which requires `--once`) just prints the next due tick and exits
+/// without touching the workspace.
+pub async fn run(args: ScheduleArgs) -> anyhow::Result<()> {
+    let spec = match (&args.every, &args.at) {
+        (Some(every), None) => ScheduleSpec::Interval(ScheduleSpec::parse_every(every).map_err(|error| anyhow::anyhow!(error))?),
+        (None, Some(at)) => ScheduleSpec::FixedTime(ScheduleSpec::parse_at(at).map_err(|error| anyhow::anyhow!(error))?),
+        (None, None) => anyhow::bail!("schedule needs exactly one of --every or --at"),
+        (Some(_), Some(_)) => unreachable!("--every and --at are mutually exclusive"),
+    };
+
+    if args.dry_run {
+        let state = load_state(&args.path)?;
+        let due = next_tick(&spec, state.last_scheduled_run, Utc::now());
+        println!("next scheduled run: {}", due.format("%Y-%m-%d %H:%M:%S UTC"));
+        return Ok(());
+    }
+
+    let config_hash = run_log::config_hash(&args.path).unwrap_or_default();
+    let run_args = RunArgs {
+        path: args.path.clone(),
+        steps: 0,
+        plan_only: false,
+        no_preflight: args.no_preflight,
+        commit_prefix: args.commit_prefix,
+        review_branch: false,
+        auto_merge: false,
+        no_ff: false,
+        allow_stacked: false,
+        ignore_max_steps: false,
+        debug_unredacted_logs: false,
+        pair: false,
+        no_ci_cache: false,
+        goal: Vec::new(),
+        unarchive: false,
+        deterministic: args.deterministic,
+    };
+    let mut orchestrator = LoopOrchestrator::from_workspace(&run_args).await?;
+
+    run_loop(&args.path, &mut orchestrator, config_hash, &spec, args.cycles, args.once).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ymd_hms(y: i32, m: u32, d: u32, h: u32, min: u32, s: u32) -> DateTime<Utc> {
+        chrono::NaiveDate::from_ymd_opt(y, m, d).unwrap().and_hms_opt(h, min, s).unwrap().and_utc()
+    }
+
+    #[test]
+    fn parse_every_accepts_hours_minutes_and_seconds() {
+        assert_eq!(ScheduleSpec::parse_every("24h").unwrap(), Duration::from_secs(24 * 3600));
+        assert_eq!(ScheduleSpec::parse_every("90m").unwrap(), Duration::from_secs(90 * 60));
+        assert_eq!(ScheduleSpec::parse_every("45s").unwrap(), Duration::from_secs(45));
+    }
+
+    #[test]
+    fn parse_every_rejects_zero_and_unknown_units() {
+        assert!(ScheduleSpec::parse_every("0h").is_err());
+        assert!(ScheduleSpec::parse_every("3d").is_err());
+        assert!(ScheduleSpec::parse_every("abc").is_err());
+    }
+
+    #[test]
+    fn parse_at_accepts_a_24_hour_time() {
+        assert_eq!(ScheduleSpec::parse_at("02:30").unwrap(), NaiveTime::from_hms_opt(2, 30, 0).unwrap());
+        assert!(ScheduleSpec::parse_at("2:30pm").is_err());
+    }
+
+    #[test]
+    fn an_interval_schedule_with_no_last_run_is_due_immediately() {
+        let now = ymd_hms(2026, 8, 8, 10, 0, 0);
+        assert_eq!(next_tick(&ScheduleSpec::Interval(Duration::from_secs(3600)), None, now), now);
+    }
+
+    #[test]
+    fn an_interval_schedule_ticks_exactly_one_interval_after_the_last_run() {
+        let last_run = ymd_hms(2026, 8, 8, 10, 0, 0);
+        let now = ymd_hms(2026, 8, 8, 10, 30, 0);
+        let due = next_tick(&ScheduleSpec::Interval(Duration::from_secs(3600)), Some(last_run), now);
+        assert_eq!(due, ymd_hms(2026, 8, 8, 11, 0, 0));
+    }
+
+    #[test]
+    fn an_interval_schedule_catches_up_exactly_once_after_a_long_gap() {
+        let last_run = ymd_hms(2026, 8, 8, 0, 0, 0);
+        let now = ymd_hms(2026, 8, 8, 5, 0, 0);
+        let due = next_tick(&ScheduleSpec::Interval(Duration::from_secs(3600)), Some(last_run), now);
+        assert!(due <= now, "a 5-hour gap on an hourly schedule should already be due");
+
+        let next_due = next_tick(&ScheduleSpec::Interval(Duration::from_secs(3600)), Some(now), now);
+        assert_eq!(next_due, now + chrono::Duration::hours(1), "catching up shouldn't queue up the other missed ticks");
+    }
+
+    #[test]
+    fn a_fixed_time_schedule_with_no_last_run_waits_for_todays_occurrence() {
+        let now = ymd_hms(2026, 8, 8, 1, 0, 0);
+        let at = NaiveTime::from_hms_opt(2, 30, 0).unwrap();
+        assert_eq!(next_tick(&ScheduleSpec::FixedTime(at), None, now), ymd_hms(2026, 8, 8, 2, 30, 0));
+    }
+
+    #[test]
+    fn a_fixed_time_schedule_rolls_over_to_tomorrow_once_todays_time_has_passed() {
+        let now = ymd_hms(2026, 8, 8, 3, 0, 0);
+        let at = NaiveTime::from_hms_opt(2, 30, 0).unwrap();
+        assert_eq!(next_tick(&ScheduleSpec::FixedTime(at), None, now), ymd_hms(2026, 8, 9, 2, 30, 0));
+    }
+
+    #[test]
+    fn a_fixed_time_schedule_catches_up_exactly_once_after_missing_several_days() {
+        let last_run = ymd_hms(2026, 8, 1, 2, 30, 0);
+        let now = ymd_hms(2026, 8, 8, 12, 0, 0);
+        let at = NaiveTime::from_hms_opt(2, 30, 0).unwrap();
+
+        let due = next_tick(&ScheduleSpec::FixedTime(at), Some(last_run), now);
+        assert_eq!(due, ymd_hms(2026, 8, 2, 2, 30, 0), "should only advance to the day right after the last run, not skip ahead to today");
+    }
+
+    #[test]
+    fn schedule_state_round_trips_through_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let run_at = ymd_hms(2026, 8, 8, 2, 30, 0);
+        save_state(dir.path(), &ScheduleState { last_scheduled_run: Some(run_at) }).unwrap();
+
+        let loaded = load_state(dir.path()).unwrap();
+        assert_eq!(loaded.last_scheduled_run, Some(run_at));
+    }
+
+    #[test]
+    fn no_schedule_state_recorded_yet_loads_as_never_run() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(load_state(dir.path()).unwrap().last_scheduled_run, None);
+    }
+}
@@ -0,0 +1,782 @@
+//! Implements `tdd-cli status`: reports the current role, step counter,
+//! last commit summary, and a per-provider usage breakdown, either as
+//! human-readable text ([`format_lines`]) or as a locale-stable JSON
+//! snapshot ([`StatusSnapshot`]) for `--json`/`--watch` tooling
+//! integrations.
+
+use crate::artifact_name::{parse_log_filename, parse_plan_filename, unrecognized_role_warning};
+use crate::provider_state::UsageLog;
+use crate::run_log;
+use crate::step_log::StepLog;
+use crate::workspace_access;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+use tdd_core::Vcs;
+use tdd_exec::GitVcs;
+
+/// A snapshot of where a run currently stands.
+#[derive(Debug, Clone)]
+pub struct StatusReport {
+    pub last_commit_message: String,
+    pub step_count: u32,
+    /// Step counts by provider, combined across every provider a kata has
+    /// run under (see [`crate::provider_state`]). Empty if no step has
+    /// recorded a provider fingerprint yet.
+    pub usage_by_provider: HashMap<String, u32>,
+    /// One entry per `.tdd/plan` artifact whose role slug didn't map to a
+    /// built-in [`tdd_core::Role`] (see [`crate::artifact_name`]), so a
+    /// custom-roles artifact is surfaced rather than silently uncounted.
+    pub warnings: Vec<String>,
+    /// The highest-numbered step log in `.tdd/logs`, rendered for humans
+    /// via [`StepLog::format_verification`] and carried whole for
+    /// [`StatusSnapshot`]. `None` if no step log exists yet.
+    pub last_verification: Option<StepLog>,
+    /// The most recent `run` invocation recorded at
+    /// `.tdd/state/last-run.json`, rendered for humans via
+    /// [`run_log::RunRecord::format_summary`] and carried whole for
+    /// [`StatusSnapshot`]. `None` if no run has ever completed.
+    pub last_run: Option<run_log::RunRecord>,
+    /// [`tdd_core::humanize_age`] of `HEAD`, e.g. `"2h ago"`. `None` if
+    /// there's no commit yet.
+    pub last_commit_age: Option<String>,
+    /// The raw timestamp [`Self::last_commit_age`] was humanized from, so
+    /// [`StatusSnapshot`] can report it without baking in English
+    /// phrasing.
+    pub last_commit_timestamp: Option<DateTime<Utc>>,
+    /// `.tdd/state/progress.json`, if a `run` is currently in flight,
+    /// rendered for humans via
+    /// [`crate::progress::ProgressSnapshot::format_summary`] and carried
+    /// whole for [`StatusSnapshot`]. `None` once that run finishes, since
+    /// [`crate::progress::finish`] marks the file `finished` rather than
+    /// removing it.
+    pub in_progress: Option<crate::progress::ProgressSnapshot>,
+    /// [`crate::archive::ArchiveRecord`] from `.tdd/state/archived.json`,
+    /// if this kata has been archived. `None` for an active kata.
+    pub archived: Option<crate::archive::ArchiveRecord>,
+}
+
+/// Reads `.tdd/plan` to infer how many steps have completed, reads git
+/// for the last commit message, and `.tdd/state/usage.json` for the
+/// per-provider step breakdown. Never writes to `repo_root`, so it works
+/// on a read-only checkout; a `.tdd` subdirectory the caller can't read
+/// degrades to a warning instead of an error.
+pub fn read_status(repo_root: &Path) -> anyhow::Result<StatusReport> {
+    let vcs = GitVcs::open_existing(repo_root)?;
+    read_status_with_vcs(repo_root, &vcs)
+}
+
+/// Renders the "steps completed" status line, accounting for
+/// [`crate::config::WorkspaceConfig::max_steps`]. Over budget (the cap was
+/// lowered below `step_count`) renders guidance pointing at the fix
+/// instead of a confusing `"step 15 of 10"`.
+pub fn step_budget_line(step_count: u32, max_steps: Option<u32>) -> String {
+    match max_steps {
+        Some(max) if step_count > max => {
+            format!("Completed {step_count} steps (configured max is {max}) — raise workspace.max_steps or archive this kata")
+        }
+        Some(max) => format!("steps completed: {step_count} of {max}"),
+        None => format!("steps completed: {step_count}"),
+    }
+}
+
+/// Renders `report` as the lines `tdd-cli status` prints to stdout, one
+/// string per line (no trailing newline on any of them — the caller
+/// decides how they're joined). Free to change wording, add emoji, or
+/// localize in a future `workspace.locale` feature, since nothing reads
+/// this output except a human; [`StatusSnapshot`] is the stable contract
+/// for tooling.
+pub fn format_lines(report: &StatusReport, max_steps: Option<u32>) -> Vec<String> {
+    let mut lines = Vec::new();
+    if let Some(archived) = &report.archived {
+        lines.push(archived.format_banner());
+    } else {
+        lines.push(step_budget_line(report.step_count, max_steps));
+    }
+    match &report.last_commit_age {
+        Some(age) => lines.push(format!("last commit: {} ({age})", report.last_commit_message.trim())),
+        None => lines.push(format!("last commit: {}", report.last_commit_message.trim())),
+    }
+    if !report.usage_by_provider.is_empty() {
+        let mut providers: Vec<_> = report.usage_by_provider.iter().collect();
+        providers.sort_by_key(|(provider, _)| provider.as_str());
+        for (provider, steps) in providers {
+            lines.push(format!("  {provider}: {steps} steps"));
+        }
+    }
+    if let Some(verification) = &report.last_verification {
+        lines.push(format!("last verification: {}", verification.format_verification()));
+    }
+    if let Some(in_progress) = &report.in_progress {
+        lines.push(format!("In progress: {}", in_progress.format_summary()));
+    }
+    if let Some(last_run) = &report.last_run {
+        lines.push(format!("Last run: {}", last_run.format_summary()));
+    }
+    for warning in &report.warnings {
+        lines.push(format!("WARNING      {warning}"));
+    }
+    lines
+}
+
+/// The schema version [`StatusSnapshot`] serializes with today. Bump
+/// this, rather than silently repurposing a field, whenever a change to
+/// `StatusSnapshot` would break a `jq`-based consumer pinned to the old
+/// shape.
+pub const STATUS_SCHEMA_VERSION: u32 = 1;
+
+/// The `--json` rendering of a [`StatusReport`]: every field a tooling
+/// integration cares about, using only structured sub-types that already
+/// derive their own locale-independent `Serialize` (snake_case enum
+/// variants, plain struct fields) rather than the English prose
+/// [`format_lines`] produces — so a non-English wrapper script can match
+/// on `stop_reason` instead of scraping `"stopped after 4 steps
+/// (failed)"`. [`STATUS_SCHEMA_VERSION`] is the only field expected to
+/// change shape across releases; everything else is additive.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusSnapshot {
+    pub schema_version: u32,
+    pub step_count: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_steps: Option<u32>,
+    pub last_commit_message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_commit_timestamp: Option<DateTime<Utc>>,
+    pub usage_by_provider: HashMap<String, u32>,
+    pub warnings: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_verification: Option<StepLog>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_run: Option<run_log::RunRecord>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_progress: Option<crate::progress::ProgressSnapshot>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archived: Option<crate::archive::ArchiveRecord>,
+}
+
+impl StatusSnapshot {
+    pub fn from_report(report: &StatusReport, max_steps: Option<u32>) -> Self {
+        Self {
+            schema_version: STATUS_SCHEMA_VERSION,
+            step_count: report.step_count,
+            max_steps,
+            last_commit_message: report.last_commit_message.clone(),
+            last_commit_timestamp: report.last_commit_timestamp,
+            usage_by_provider: report.usage_by_provider.clone(),
+            warnings: report.warnings.clone(),
+            last_verification: report.last_verification.clone(),
+            last_run: report.last_run.clone(),
+            in_progress: report.in_progress.clone(),
+            archived: report.archived.clone(),
+        }
+    }
+}
+
+/// What one `--watch` poll should emit, if anything. See [`next_watch_event`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum WatchEvent {
+    /// `current` differs from the previously emitted snapshot.
+    Changed(StatusSnapshot),
+    /// `current` is unchanged, but `--heartbeat` is due, so it's emitted
+    /// anyway to prove the stream is still alive.
+    Heartbeat(StatusSnapshot),
+}
+
+impl WatchEvent {
+    pub fn snapshot(&self) -> &StatusSnapshot {
+        match self {
+            WatchEvent::Changed(snapshot) | WatchEvent::Heartbeat(snapshot) => snapshot,
+        }
+    }
+}
+
+/// Decides what `status --json --watch` should do with the latest poll:
+/// emit `current` if it differs from `previous`, emit it anyway as a
+/// heartbeat if `heartbeat` is set and at least that long has passed
+/// since `last_emitted_at`, or emit nothing. A pure function, taking
+/// `now` and `last_emitted_at` as plain values rather than reading the
+/// clock itself, so the polling loop's decision can be driven by a
+/// scripted sequence of snapshots and a mocked clock instead of a real
+/// sleep (see `run_watch`'s tests).
+pub fn next_watch_event(
+    previous: Option<&StatusSnapshot>,
+    current: &StatusSnapshot,
+    now: DateTime<Utc>,
+    last_emitted_at: Option<DateTime<Utc>>,
+    heartbeat: Option<Duration>,
+) -> Option<WatchEvent> {
+    if previous != Some(current) {
+        return Some(WatchEvent::Changed(current.clone()));
+    }
+    let heartbeat = chrono::Duration::from_std(heartbeat?).ok()?;
+    let last_emitted_at = last_emitted_at?;
+    if now - last_emitted_at >= heartbeat {
+        return Some(WatchEvent::Heartbeat(current.clone()));
+    }
+    None
+}
+
+/// Polls `repo_root` every `poll_interval`, printing one NDJSON line per
+/// [`WatchEvent`] `next_watch_event` decides to emit, forever. The actual
+/// `status --json --watch` entrypoint; see `next_watch_event` for the
+/// decision logic this just drives with a real clock and a real sleep.
+pub async fn run_watch(repo_root: &Path, poll_interval: Duration, heartbeat: Option<Duration>) -> anyhow::Result<()> {
+    let mut previous: Option<StatusSnapshot> = None;
+    let mut last_emitted_at: Option<DateTime<Utc>> = None;
+    loop {
+        let report = read_status(repo_root)?;
+        let max_steps = crate::config::TddConfig::load(&repo_root.join("tdd.yaml")).ok().and_then(|config| config.workspace.max_steps);
+        let current = StatusSnapshot::from_report(&report, max_steps);
+
+        if let Some(event) = next_watch_event(previous.as_ref(), &current, Utc::now(), last_emitted_at, heartbeat) {
+            println!("{}", serde_json::to_string(event.snapshot())?);
+            last_emitted_at = Some(Utc::now());
+            previous = Some(current);
+        }
+
+        tokio::time::sleep(poll_interval).await;
+    }
+}
+
+/// The testable core of [`read_status`], taking a [`Vcs`] so a fake can
+/// drive the commit-age line with a fixed timestamp instead of real wall
+/// clock time.
+fn read_status_with_vcs(repo_root: &Path, vcs: &dyn Vcs) -> anyhow::Result<StatusReport> {
+    let state = vcs.read_state()?;
+    let head_commit_timestamp = vcs.head_commit_timestamp()?;
+    let last_commit_age =
+        head_commit_timestamp.map(|commit_time| tdd_core::humanize_age(std::time::SystemTime::now().duration_since(commit_time).unwrap_or_default()));
+    let last_commit_timestamp = head_commit_timestamp.map(DateTime::<Utc>::from);
+
+    let (step_count, mut warnings) = read_plan_artifacts(&repo_root.join(".tdd").join("plan"))?;
+
+    let usage_by_provider = UsageLog::load(repo_root)?.steps_by_provider;
+    let (last_verification, logs_warning) = read_last_verification(&repo_root.join(".tdd").join("logs"))?;
+    warnings.extend(logs_warning);
+    let last_run = run_log::load(repo_root)?;
+    let in_progress = crate::progress::read(repo_root)?.filter(|snapshot| !snapshot.finished);
+    let archived = crate::archive::read(repo_root)?;
+
+    Ok(StatusReport {
+        last_commit_message: state.last_commit_message,
+        step_count,
+        usage_by_provider,
+        warnings,
+        last_verification,
+        last_run,
+        last_commit_age,
+        last_commit_timestamp,
+        in_progress,
+        archived,
+    })
+}
+
+/// Finds the highest-numbered `.tdd/logs` step log (breaking ties on the
+/// highest run-id, so a re-run after undo is preferred over the run it
+/// replaced) and renders its verification summary, along with a warning
+/// if `logs_dir` exists but can't be listed.
+fn read_last_verification(logs_dir: &Path) -> anyhow::Result<(Option<StepLog>, Option<String>)> {
+    if !logs_dir.exists() {
+        return Ok((None, None));
+    }
+    if workspace_access::is_unreadable(logs_dir) {
+        return Ok((None, Some("logs unavailable: permission denied".to_string())));
+    }
+
+    let mut latest: Option<(u32, u32, std::path::PathBuf)> = None;
+    for entry in std::fs::read_dir(logs_dir)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let Some(parsed) = name.to_str().and_then(parse_log_filename) else {
+            continue;
+        };
+        if latest.as_ref().is_none_or(|(step, run_id, _)| (parsed.step, parsed.run_id) > (*step, *run_id)) {
+            latest = Some((parsed.step, parsed.run_id, entry.path()));
+        }
+    }
+
+    let Some((_, _, path)) = latest else {
+        return Ok((None, None));
+    };
+    let log: StepLog = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+    Ok((Some(log), None))
+}
+
+/// Counts the plan artifacts in `plan_dir` and collects a warning for each
+/// one whose role slug isn't a built-in role, or a single warning if
+/// `plan_dir` exists but can't be listed.
+fn read_plan_artifacts(plan_dir: &Path) -> anyhow::Result<(u32, Vec<String>)> {
+    if !plan_dir.exists() {
+        return Ok((0, Vec::new()));
+    }
+    if workspace_access::is_unreadable(plan_dir) {
+        return Ok((0, vec!["plan unavailable: permission denied".to_string()]));
+    }
+
+    let mut step_count = 0u32;
+    let mut warnings = Vec::new();
+    for entry in std::fs::read_dir(plan_dir)? {
+        let name = entry?.file_name();
+        let Some(parsed) = name.to_str().and_then(parse_plan_filename) else {
+            continue;
+        };
+        step_count += 1;
+        if let Some(warning) = unrecognized_role_warning(&parsed) {
+            warnings.push(warning);
+        }
+    }
+    Ok((step_count, warnings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn step_budget_line_shows_the_plain_count_when_max_steps_is_unset() {
+        assert_eq!(step_budget_line(4, None), "steps completed: 4");
+    }
+
+    #[test]
+    fn step_budget_line_shows_the_fraction_when_under_the_cap() {
+        assert_eq!(step_budget_line(4, Some(10)), "steps completed: 4 of 10");
+    }
+
+    #[test]
+    fn step_budget_line_renders_guidance_once_over_the_lowered_cap() {
+        let line = step_budget_line(14, Some(10));
+        assert_eq!(line, "Completed 14 steps (configured max is 10) — raise workspace.max_steps or archive this kata");
+    }
+
+    fn snapshot(step_count: u32) -> StatusSnapshot {
+        StatusSnapshot {
+            schema_version: STATUS_SCHEMA_VERSION,
+            step_count,
+            max_steps: None,
+            last_commit_message: "chore: scaffold".to_string(),
+            last_commit_timestamp: None,
+            usage_by_provider: HashMap::new(),
+            warnings: Vec::new(),
+            last_verification: None,
+            last_run: None,
+            in_progress: None,
+            archived: None,
+        }
+    }
+
+    fn utc(seconds: i64) -> DateTime<Utc> {
+        DateTime::<Utc>::from_timestamp(seconds, 0).unwrap()
+    }
+
+    #[test]
+    fn a_changed_snapshot_is_always_emitted_regardless_of_heartbeat() {
+        let event = next_watch_event(Some(&snapshot(1)), &snapshot(2), utc(100), Some(utc(99)), Some(Duration::from_secs(3600)));
+        assert_eq!(event, Some(WatchEvent::Changed(snapshot(2))));
+    }
+
+    #[test]
+    fn an_unchanged_snapshot_with_no_heartbeat_configured_emits_nothing() {
+        let event = next_watch_event(Some(&snapshot(1)), &snapshot(1), utc(100), Some(utc(0)), None);
+        assert_eq!(event, None);
+    }
+
+    #[test]
+    fn an_unchanged_snapshot_emits_a_heartbeat_once_the_interval_elapses() {
+        let too_soon = next_watch_event(Some(&snapshot(1)), &snapshot(1), utc(50), Some(utc(0)), Some(Duration::from_secs(60)));
+        assert_eq!(too_soon, None);
+
+        let due = next_watch_event(Some(&snapshot(1)), &snapshot(1), utc(60), Some(utc(0)), Some(Duration::from_secs(60)));
+        assert_eq!(due, Some(WatchEvent::Heartbeat(snapshot(1))));
+    }
+
+    #[test]
+    fn the_first_poll_with_no_previous_snapshot_is_always_a_change() {
+        let event = next_watch_event(None, &snapshot(0), utc(0), None, Some(Duration::from_secs(60)));
+        assert_eq!(event, Some(WatchEvent::Changed(snapshot(0))));
+    }
+
+    /// Drives [`next_watch_event`] over a scripted sequence of polls
+    /// standing in for workspace mutations (a step committing, then
+    /// nothing changing for a while), with every clock reading supplied
+    /// by the script rather than read for real, and checks the emitted
+    /// stream is exactly the changed snapshots plus the heartbeats due
+    /// by then — nothing emitted for a poll that changed nothing before
+    /// its heartbeat was due.
+    #[test]
+    fn a_scripted_poll_sequence_emits_exactly_the_changes_and_due_heartbeats() {
+        let polls = [
+            (utc(0), snapshot(0)),
+            (utc(10), snapshot(1)),
+            (utc(20), snapshot(1)),
+            (utc(70), snapshot(1)),
+            (utc(75), snapshot(2)),
+            (utc(135), snapshot(2)),
+        ];
+        let heartbeat = Some(Duration::from_secs(60));
+
+        let mut previous = None;
+        let mut last_emitted_at = None;
+        let mut emitted = Vec::new();
+        for (now, current) in polls {
+            if let Some(event) = next_watch_event(previous.as_ref(), &current, now, last_emitted_at, heartbeat) {
+                last_emitted_at = Some(now);
+                emitted.push(event);
+            }
+            previous = Some(current);
+        }
+
+        assert_eq!(
+            emitted,
+            vec![
+                WatchEvent::Changed(snapshot(0)),
+                WatchEvent::Changed(snapshot(1)),
+                WatchEvent::Heartbeat(snapshot(1)),
+                WatchEvent::Changed(snapshot(2)),
+                WatchEvent::Heartbeat(snapshot(2)),
+            ]
+        );
+    }
+
+    /// Pins `StatusSnapshot`'s JSON shape against a literal fixture: a
+    /// deliberate field rename, added enum variant rename, or dropped
+    /// field shows up here as a diff instead of silently breaking a
+    /// `jq`-based consumer pinned to field names.
+    #[test]
+    fn status_snapshot_serializes_to_the_documented_locale_stable_schema() {
+        let snapshot = StatusSnapshot {
+            schema_version: STATUS_SCHEMA_VERSION,
+            step_count: 4,
+            max_steps: Some(10),
+            last_commit_message: "feat: step 4".to_string(),
+            last_commit_timestamp: Some(utc(1_700_000_000)),
+            usage_by_provider: HashMap::from([("anthropic".to_string(), 4)]),
+            warnings: vec!["step 4 was taken by an unrecognized role \"code-reviewer\"".to_string()],
+            last_verification: None,
+            last_run: Some(run_log::RunRecord {
+                stop_reason: run_log::StopReason::Failed,
+                steps_requested: 5,
+                steps_executed: 4,
+                started_at: utc(1_700_000_000),
+                ended_at: utc(1_700_000_100),
+                config_hash: "deadbeef".to_string(),
+                final_step_index: 3,
+                final_role: Some(tdd_core::Role::Implementor),
+                failure: None,
+                max_steps_overridden: false,
+                detached_head_branch: None,
+            }),
+            in_progress: None,
+            archived: None,
+        };
+
+        let value: serde_json::Value = serde_json::to_value(&snapshot).unwrap();
+        let expected = serde_json::json!({
+            "schema_version": 1,
+            "step_count": 4,
+            "max_steps": 10,
+            "last_commit_message": "feat: step 4",
+            "last_commit_timestamp": "2023-11-14T22:13:20Z",
+            "usage_by_provider": {"anthropic": 4},
+            "warnings": ["step 4 was taken by an unrecognized role \"code-reviewer\""],
+            "last_run": {
+                "stop_reason": "failed",
+                "steps_requested": 5,
+                "steps_executed": 4,
+                "started_at": "2023-11-14T22:13:20Z",
+                "ended_at": "2023-11-14T22:15:00Z",
+                "config_hash": "deadbeef",
+                "final_step_index": 3,
+                "final_role": "implementor",
+                "max_steps_overridden": false,
+            },
+        });
+        assert_eq!(value, expected);
+
+        let round_tripped: StatusSnapshot = serde_json::from_value(value).unwrap();
+        assert_eq!(round_tripped, snapshot);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn status_succeeds_on_a_read_only_copy_of_a_previously_run_workspace() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        crate::init::run(&crate::init::InitArgs {
+            path: dir.path().to_path_buf(),
+            kata_url: None,
+        })
+        .unwrap();
+        let vcs = GitVcs::new(dir.path(), tdd_exec::CommitAuthor::default());
+        vcs.stage_all().unwrap();
+        vcs.commit("chore: initial scaffold").unwrap();
+
+        std::fs::set_permissions(dir.path(), std::fs::Permissions::from_mode(0o555)).unwrap();
+        let result = read_status(dir.path());
+        std::fs::set_permissions(dir.path(), std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let report = result.unwrap();
+        assert_eq!(report.step_count, 0);
+        assert!(report.last_commit_message.contains("chore: initial scaffold"));
+    }
+
+    #[test]
+    fn an_archived_kata_surfaces_the_archive_record() {
+        let dir = tempdir().unwrap();
+        crate::init::run(&crate::init::InitArgs {
+            path: dir.path().to_path_buf(),
+            kata_url: None,
+        })
+        .unwrap();
+        let vcs = GitVcs::new(dir.path(), tdd_exec::CommitAuthor::default());
+        vcs.stage_all().unwrap();
+        vcs.commit("chore: initial scaffold").unwrap();
+
+        assert!(read_status(dir.path()).unwrap().archived.is_none());
+
+        crate::archive::write(dir.path(), 7, Some("kata complete".to_string())).unwrap();
+        let report = read_status(dir.path()).unwrap();
+        let record = report.archived.expect("expected an archive record");
+        assert_eq!(record.final_step_count, 7);
+    }
+
+    #[test]
+    fn a_recorded_run_surfaces_as_a_summary_line() {
+        let dir = tempdir().unwrap();
+        crate::init::run(&crate::init::InitArgs {
+            path: dir.path().to_path_buf(),
+            kata_url: None,
+        })
+        .unwrap();
+        let vcs = GitVcs::new(dir.path(), tdd_exec::CommitAuthor::default());
+        vcs.stage_all().unwrap();
+        vcs.commit("chore: initial scaffold").unwrap();
+
+        let now = chrono::Utc::now();
+        run_log::record(
+            dir.path(),
+            &run_log::RunRecord {
+                stop_reason: run_log::StopReason::Failed,
+                steps_requested: 5,
+                steps_executed: 4,
+                started_at: now,
+                ended_at: now,
+                config_hash: "deadbeef".to_string(),
+                final_step_index: 3,
+                final_role: Some(tdd_core::Role::Implementor),
+                failure: None,
+                max_steps_overridden: false,
+                detached_head_branch: None,
+            },
+        )
+        .unwrap();
+
+        let report = read_status(dir.path()).unwrap();
+        assert_eq!(report.last_run.unwrap().format_summary(), format!("stopped after 4 steps (failed) at {}", now.format("%Y-%m-%d %H:%M")));
+    }
+
+    struct FakeVcs {
+        last_commit_message: String,
+        commit_time: Option<std::time::SystemTime>,
+    }
+
+    impl Vcs for FakeVcs {
+        fn init_if_needed(&self) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn read_state(&self) -> anyhow::Result<tdd_core::RepoState> {
+            Ok(tdd_core::RepoState {
+                last_commit_message: self.last_commit_message.clone(),
+                ..Default::default()
+            })
+        }
+
+        fn stage_all(&self) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn commit(&self, _message: &str) -> anyhow::Result<String> {
+            unreachable!("status never commits")
+        }
+
+        fn diff_against_head(&self, _paths: &[String]) -> anyhow::Result<String> {
+            unreachable!("status never diffs")
+        }
+
+        fn discard_paths(&self, _paths: &[String]) -> anyhow::Result<()> {
+            unreachable!("status never discards")
+        }
+
+        fn head_commit_timestamp(&self) -> anyhow::Result<Option<std::time::SystemTime>> {
+            Ok(self.commit_time)
+        }
+
+        fn recently_changed_paths(&self, _n_commits: usize) -> anyhow::Result<Vec<String>> {
+            unreachable!("status never asks for recently changed paths")
+        }
+
+        fn diff_range(&self, _from: Option<&str>, _to: &str) -> anyhow::Result<String> {
+            unreachable!("status never diffs a range")
+        }
+
+        fn diff_range_stat(&self, _from: Option<&str>, _to: &str) -> anyhow::Result<String> {
+            unreachable!("status never diffs a range")
+        }
+
+        fn create_branch_from(&self, _name: &str, _commit: &str) -> anyhow::Result<()> {
+            unreachable!("status never creates branches")
+        }
+
+        fn checkout(&self, _name: &str) -> anyhow::Result<()> {
+            unreachable!("status never checks out branches")
+        }
+
+        fn merge_ff(&self, _name: &str, _no_ff: bool) -> anyhow::Result<String> {
+            unreachable!("status never merges branches")
+        }
+
+        fn is_detached(&self) -> anyhow::Result<bool> {
+            unreachable!("status never checks detached HEAD")
+        }
+
+        fn head_commit_id(&self) -> anyhow::Result<String> {
+            unreachable!("status never snapshots/resets")
+        }
+
+        fn reset_hard(&self, _commit: &str) -> anyhow::Result<()> {
+            unreachable!("status never snapshots/resets")
+        }
+
+        fn is_clean(&self) -> anyhow::Result<bool> {
+            unreachable!("status never snapshots/resets")
+        }
+    }
+
+    #[test]
+    fn a_commit_an_hour_old_surfaces_as_a_humanized_age() {
+        let dir = tempdir().unwrap();
+        let vcs = FakeVcs {
+            last_commit_message: "feat: add things".to_string(),
+            commit_time: Some(std::time::SystemTime::now() - std::time::Duration::from_secs(3700)),
+        };
+
+        let report = read_status_with_vcs(dir.path(), &vcs).unwrap();
+        assert_eq!(report.last_commit_age, Some("1h ago".to_string()));
+    }
+
+    #[test]
+    fn no_commit_yet_has_no_commit_age() {
+        let dir = tempdir().unwrap();
+        let vcs = FakeVcs {
+            last_commit_message: String::new(),
+            commit_time: None,
+        };
+
+        let report = read_status_with_vcs(dir.path(), &vcs).unwrap();
+        assert_eq!(report.last_commit_age, None);
+    }
+
+    #[test]
+    fn an_absent_plan_dir_has_no_steps_and_no_warnings() {
+        let dir = tempdir().unwrap();
+        let (step_count, warnings) = read_plan_artifacts(&dir.path().join(".tdd").join("plan")).unwrap();
+        assert_eq!(step_count, 0);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn built_in_role_artifacts_are_counted_without_warnings() {
+        let dir = tempdir().unwrap();
+        let plan_dir = dir.path().join(".tdd").join("plan");
+        std::fs::create_dir_all(&plan_dir).unwrap();
+        std::fs::write(plan_dir.join("step-1-tester.md"), "").unwrap();
+        std::fs::write(plan_dir.join("step-2-implementor.md"), "").unwrap();
+
+        let (step_count, warnings) = read_plan_artifacts(&plan_dir).unwrap();
+        assert_eq!(step_count, 2);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn a_custom_role_artifact_is_counted_and_warned_about() {
+        let dir = tempdir().unwrap();
+        let plan_dir = dir.path().join(".tdd").join("plan");
+        std::fs::create_dir_all(&plan_dir).unwrap();
+        std::fs::write(plan_dir.join("step-4-code-reviewer.md"), "").unwrap();
+
+        let (step_count, warnings) = read_plan_artifacts(&plan_dir).unwrap();
+        assert_eq!(step_count, 1);
+        assert_eq!(warnings, vec!["step 4 was taken by an unrecognized role \"code-reviewer\"".to_string()]);
+    }
+
+    #[test]
+    fn stray_non_artifact_files_are_ignored() {
+        let dir = tempdir().unwrap();
+        let plan_dir = dir.path().join(".tdd").join("plan");
+        std::fs::create_dir_all(&plan_dir).unwrap();
+        std::fs::write(plan_dir.join(".gitkeep"), "").unwrap();
+
+        let (step_count, warnings) = read_plan_artifacts(&plan_dir).unwrap();
+        assert_eq!(step_count, 0);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn an_absent_logs_dir_has_no_last_verification() {
+        let dir = tempdir().unwrap();
+        assert_eq!(read_last_verification(&dir.path().join(".tdd").join("logs")).unwrap(), (None, None));
+    }
+
+    #[test]
+    fn last_verification_picks_the_highest_numbered_log() {
+        let dir = tempdir().unwrap();
+        let logs_dir = dir.path().join(".tdd").join("logs");
+        std::fs::create_dir_all(&logs_dir).unwrap();
+        std::fs::write(logs_dir.join("step-1-tester.json"), serde_json::to_string(&StepLog::default()).unwrap()).unwrap();
+        let skipped_check = StepLog {
+            check_skipped: true,
+            ..Default::default()
+        };
+        std::fs::write(logs_dir.join("step-2-implementor.json"), serde_json::to_string(&skipped_check).unwrap()).unwrap();
+
+        let (verification, warning) = read_last_verification(&logs_dir).unwrap();
+        assert_eq!(verification.unwrap().format_verification(), "fmt: ran, check: skipped (per config), test: ran");
+        assert_eq!(warning, None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn an_unreadable_logs_dir_degrades_to_a_warning_instead_of_an_error() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempdir().unwrap();
+        let logs_dir = dir.path().join(".tdd").join("logs");
+        std::fs::create_dir_all(&logs_dir).unwrap();
+        std::fs::set_permissions(&logs_dir, std::fs::Permissions::from_mode(0o000)).unwrap();
+
+        let (verification, warning) = read_last_verification(&logs_dir).unwrap();
+        std::fs::set_permissions(&logs_dir, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        assert_eq!(verification, None);
+        assert_eq!(warning, Some("logs unavailable: permission denied".to_string()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn an_unreadable_plan_dir_degrades_to_a_warning_instead_of_an_error() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempdir().unwrap();
+        let plan_dir = dir.path().join(".tdd").join("plan");
+        std::fs::create_dir_all(&plan_dir).unwrap();
+        std::fs::set_permissions(&plan_dir, std::fs::Permissions::from_mode(0o000)).unwrap();
+
+        let (step_count, warnings) = read_plan_artifacts(&plan_dir).unwrap();
+        std::fs::set_permissions(&plan_dir, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        assert_eq!(step_count, 0);
+        assert_eq!(warnings, vec!["plan unavailable: permission denied".to_string()]);
+    }
+}
@@ -0,0 +1,206 @@
+//! Persists a small JSON record of what happened during a step's
+//! verification, alongside the human-readable plan in `.tdd/plan`.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tdd_core::{Role, RunnerOutcome, StepFailureDetail};
+
+/// What the orchestrator observed while verifying a single step.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StepLog {
+    /// Whether the step's edits failed a formatting check and were
+    /// reformatted automatically, rather than sent back to the agent.
+    pub fmt_autofixed: bool,
+    /// The repo-relative paths the automatic formatting fix touched.
+    pub fmt_touched_files: Vec<String>,
+    /// How many extra times the test stage was re-run after an initial
+    /// failure that didn't touch any of the step's changed files.
+    #[serde(default)]
+    pub flaky_reruns: u32,
+    /// The failing test names a passing re-run flaked away, kept visible
+    /// even though the stage ultimately passed.
+    #[serde(default)]
+    pub flaky_tests: Vec<String>,
+    /// Set when the step did not verify; structured detail behind the
+    /// failure, independent of whichever error type produced it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure: Option<StepFailureDetail>,
+    /// Set on the first step log after the role's provider/model
+    /// fingerprint changed from the previous step's, so usage accounting
+    /// doesn't silently merge incompatible providers.
+    #[serde(default)]
+    pub provider_changed: bool,
+    /// The `provider/model` label the previous step ran under, present
+    /// only when `provider_changed` is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_provider: Option<String>,
+    /// Whether the fmt stage was configured with `CommandSpec::Skip`
+    /// rather than actually run.
+    #[serde(default)]
+    pub fmt_skipped: bool,
+    /// Whether the check stage was configured with `CommandSpec::Skip`
+    /// rather than actually run.
+    #[serde(default)]
+    pub check_skipped: bool,
+    /// Whether the test stage was configured with `CommandSpec::Skip`
+    /// rather than actually run.
+    #[serde(default)]
+    pub test_skipped: bool,
+    /// Set on a committed step whose check stage actually failed but was
+    /// tolerated under `workspace.allow_initial_compile_failure` — the
+    /// step still verified and committed, but this keeps the real check
+    /// failure visible rather than silently recording a clean run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tolerated_check_failure: Option<StepFailureDetail>,
+    /// Repo-relative paths any stage's stdout/stderr spilled to under
+    /// `.tdd/logs/raw/` because it ran past the capture limit.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub output_spills: Vec<String>,
+    /// Names of the [`tdd_core::StepPostProcessor`]s applied to this
+    /// step's result, in the order they ran.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub post_processors: Vec<String>,
+    /// Repo-relative paths a post-processor added to `files_changed`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub post_processor_added_files: Vec<String>,
+    /// When the edit plan split this step into ordered sub-commits (see
+    /// [`tdd_core::SubCommit`]), their ids (`"{step}a"`, `"{step}b"`, ...)
+    /// in the order they were committed. Empty for the common single-commit
+    /// step.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sub_commit_ids: Vec<String>,
+    /// How many of a Tester step's newly added `#[test]` functions
+    /// actually ran in the test stage's output. `0` for a non-Tester
+    /// step, including every step log recorded before this field
+    /// existed. See [`tdd_core::StepFailureDetail::VacuousTest`].
+    #[serde(default)]
+    pub added_tests_executed: u32,
+    /// Set when this step's commit (or acknowledged pre-existing commit)
+    /// was written by a human during `workspace.pair_mode` rather than
+    /// by the Implementor agent. `false` for every step log recorded
+    /// before this field existed.
+    #[serde(default)]
+    pub human_authored: bool,
+    /// Stages whose outcome was reused from an earlier attempt at this
+    /// step rather than rerun, because `workspace.ci_cache` found the
+    /// same content hash as when that attempt's stage last passed. Empty
+    /// when the cache is disabled (`--no-ci-cache`), on the first attempt,
+    /// or when every stage's inputs actually changed. See
+    /// [`tdd_exec::hash_stage_inputs`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reused_ci_stages: Vec<ReusedCiStage>,
+    /// The `--goal` text this step was steered by, if `.tdd/state/next-goal.txt`
+    /// held one when the step's context was built. `None` for every step
+    /// log recorded before this field existed, and for a step taken
+    /// without a pending goal.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operator_goal: Option<String>,
+    /// How this step's `Cargo.toml` edit (if any) was classified by
+    /// `workspace.manifest_policy`, rendered for display. Empty when the
+    /// step didn't touch `Cargo.toml`, including every step log recorded
+    /// before this field existed. See [`tdd_core::manifest_guard`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub manifest_changes: Vec<String>,
+    /// The role's configured base temperature and this attempt's actual
+    /// (possibly retry-escalated) temperature, for auditing
+    /// `roles.<role>.retry_temperature_bump`. `None` for every step log
+    /// recorded before this field existed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<StepTemperature>,
+    /// Set when this step's commit was made under `git.hooks: bypass`
+    /// (the default), so a team that relies on its `pre-commit`/
+    /// `commit-msg` hooks as policy can see, from the log alone, that
+    /// this commit never ran them. `false` when `git.hooks: run` ran
+    /// them successfully, and for every step log recorded before this
+    /// field existed.
+    #[serde(default)]
+    pub hooks_bypassed: bool,
+}
+
+/// The two temperature values [`StepLog::temperature`] records: what the
+/// role was configured with, and what this attempt's chat calls actually
+/// used after escalation and clamping.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StepTemperature {
+    pub base: f32,
+    pub effective: f32,
+}
+
+/// One stage [`StepLog::reused_ci_stages`] skipped re-running.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReusedCiStage {
+    pub stage: String,
+    pub reused_from_attempt: u32,
+}
+
+impl StepLog {
+    /// Renders each stage's outcome as `"<stage>: skipped (per config)"`,
+    /// `"<stage>: reused from attempt N"`, or `"<stage>: ran"`, so a
+    /// skipped or reused stage reads honestly instead of looking like it
+    /// ran fresh and passed.
+    pub fn format_verification(&self) -> String {
+        [("fmt", self.fmt_skipped), ("check", self.check_skipped), ("test", self.test_skipped)]
+            .into_iter()
+            .map(|(stage, skipped)| {
+                if skipped {
+                    format!("{stage}: skipped (per config)")
+                } else if let Some(reused) = self.reused_ci_stages.iter().find(|r| r.stage == stage) {
+                    format!("{stage}: reused from attempt {}", reused.reused_from_attempt)
+                } else {
+                    format!("{stage}: ran")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Collects the spill paths, as display strings, of every stage outcome
+/// whose stdout or stderr ran past the capture limit and spilled to disk.
+pub fn collect_output_spills(outcomes: &[&RunnerOutcome]) -> Vec<String> {
+    outcomes
+        .iter()
+        .flat_map(|outcome| [&outcome.stdout.spill_path, &outcome.stderr.spill_path])
+        .filter_map(|path| path.as_ref())
+        .map(|path| path.display().to_string())
+        .collect()
+}
+
+/// Writes `log` to `.tdd/logs/step-{step}-{role}.json`, creating the
+/// directory if needed.
+pub fn write_step_log(repo_root: &Path, step: u32, run_id: u32, role: Role, log: &StepLog) -> anyhow::Result<()> {
+    let dir = repo_root.join(".tdd").join("logs");
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{}.json", tdd_core::artifacts::format_stem(step, run_id, &role.to_string())));
+    std::fs::write(path, serde_json::to_string_pretty(log)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_verification_reports_ran_by_default() {
+        let log = StepLog::default();
+        assert_eq!(log.format_verification(), "fmt: ran, check: ran, test: ran");
+    }
+
+    #[test]
+    fn format_verification_reports_skipped_stages_honestly() {
+        let log = StepLog {
+            check_skipped: true,
+            ..Default::default()
+        };
+        assert_eq!(log.format_verification(), "fmt: ran, check: skipped (per config), test: ran");
+    }
+
+    #[test]
+    fn format_verification_reports_a_reused_stage_with_the_attempt_it_came_from() {
+        let log = StepLog {
+            reused_ci_stages: vec![ReusedCiStage { stage: "check".to_string(), reused_from_attempt: 1 }],
+            ..Default::default()
+        };
+        assert_eq!(log.format_verification(), "fmt: ran, check: reused from attempt 1, test: ran");
+    }
+}
@@ -0,0 +1,244 @@
+//! Detects whether a workspace already has test code, so `doctor` can
+//! flag a kata that didn't start from a clean red-green-refactor slate.
+//!
+//! A naive `grep -r "#\[test\]" src/` has two failure modes: it fires on
+//! a commented-out test or a doc-comment example that merely mentions
+//! the attribute, and it misses real tests that live anywhere other
+//! than the top level of `tests/` (a nested `tests/integration/foo.rs`
+//! is still a test binary as far as cargo is concerned). This scans
+//! `src/` with a comment- and string-literal-aware tokenizer so quoted
+//! or commented-out attributes don't count, and walks `tests/` and
+//! `benches/` recursively so every `.rs` file there counts as evidence
+//! regardless of nesting.
+
+use std::path::{Path, PathBuf};
+
+/// A file that makes `scan` conclude the workspace already has tests,
+/// along with why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestEvidence {
+    pub path: String,
+    pub reason: &'static str,
+}
+
+/// Scans `repo_root` for evidence of existing tests: any `.rs` file
+/// under `tests/` or `benches/` (recursively), plus any file under
+/// `src/` whose code (comments and string literals stripped) contains a
+/// `#[test]` or `#[cfg(test)]` attribute. Logs its verdict and the
+/// evidence behind it at debug level.
+pub fn scan(repo_root: &Path) -> Vec<TestEvidence> {
+    let mut evidence = Vec::new();
+
+    for dir in ["tests", "benches"] {
+        for path in walk_rust_files(&repo_root.join(dir)) {
+            evidence.push(TestEvidence { path: display_relative(repo_root, &path), reason: "lives under tests/ or benches/" });
+        }
+    }
+
+    for path in walk_rust_files(&repo_root.join("src")) {
+        let Ok(content) = std::fs::read_to_string(&path) else { continue };
+        if code_contains_test_attribute(&content) {
+            evidence.push(TestEvidence { path: display_relative(repo_root, &path), reason: "contains #[test] or #[cfg(test)] outside a comment or string" });
+        }
+    }
+
+    if evidence.is_empty() {
+        tracing::debug!("no existing tests found");
+    } else {
+        for item in &evidence {
+            tracing::debug!(path = %item.path, reason = item.reason, "existing test evidence");
+        }
+    }
+
+    evidence
+}
+
+fn display_relative(repo_root: &Path, path: &Path) -> String {
+    path.strip_prefix(repo_root).unwrap_or(path).display().to_string()
+}
+
+fn walk_rust_files(dir: &Path) -> Vec<PathBuf> {
+    walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file() && entry.path().extension().is_some_and(|ext| ext == "rs"))
+        .map(|entry| entry.into_path())
+        .collect()
+}
+
+/// Strips `//` line comments, `/* */` block comments, and string/char
+/// literals from `source`, then checks what's left for `#[test]` or
+/// `#[cfg(test)]`. Doesn't need to be a real Rust tokenizer — just good
+/// enough to stop a doc example or a commented-out test from counting.
+fn code_contains_test_attribute(source: &str) -> bool {
+    strip_comments_and_literals(source).contains("#[test]") || strip_comments_and_literals(source).contains("#[cfg(test)]")
+}
+
+fn strip_comments_and_literals(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '/' if chars.peek() == Some(&'/') => {
+                while let Some(&next) = chars.peek() {
+                    if next == '\n' {
+                        break;
+                    }
+                    chars.next();
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut depth = 1;
+                while depth > 0 {
+                    match chars.next() {
+                        Some('*') if chars.peek() == Some(&'/') => {
+                            chars.next();
+                            depth -= 1;
+                        }
+                        Some('/') if chars.peek() == Some(&'*') => {
+                            chars.next();
+                            depth += 1;
+                        }
+                        Some(_) => {}
+                        None => break,
+                    }
+                }
+            }
+            '"' => {
+                while let Some(next) = chars.next() {
+                    if next == '\\' {
+                        chars.next();
+                    } else if next == '"' {
+                        break;
+                    }
+                }
+            }
+            '\'' => {
+                // Lone lifetimes (`'a`) and char literals (`'a'`) both
+                // start with a quote; only consume as a literal if it
+                // actually closes, so `'a: loop {` isn't eaten.
+                let mut lookahead = String::new();
+                let mut closed = false;
+                for next in chars.clone().take(4) {
+                    lookahead.push(next);
+                    if next == '\'' {
+                        closed = true;
+                        break;
+                    }
+                }
+                if closed {
+                    for next in lookahead.chars() {
+                        chars.next();
+                        if next == '\'' {
+                            break;
+                        }
+                    }
+                } else {
+                    out.push(c);
+                }
+            }
+            other => out.push(other),
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn an_empty_project_has_no_evidence() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), "pub fn add(a: i32, b: i32) -> i32 { a + b }\n").unwrap();
+
+        assert!(scan(dir.path()).is_empty());
+    }
+
+    #[test]
+    fn a_commented_out_test_attribute_is_not_evidence() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), "// #[test]\n// fn old_test() {}\npub fn add(a: i32, b: i32) -> i32 { a + b }\n").unwrap();
+
+        assert!(scan(dir.path()).is_empty());
+    }
+
+    #[test]
+    fn a_block_commented_out_test_attribute_is_not_evidence() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), "/*\n#[test]\nfn old_test() {}\n*/\npub fn add(a: i32, b: i32) -> i32 { a + b }\n").unwrap();
+
+        assert!(scan(dir.path()).is_empty());
+    }
+
+    #[test]
+    fn a_doc_comment_example_mentioning_test_is_not_evidence() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/lib.rs"),
+            "/// ```\n/// #[test]\n/// fn example() {}\n/// ```\npub fn add(a: i32, b: i32) -> i32 { a + b }\n",
+        )
+        .unwrap();
+
+        assert!(scan(dir.path()).is_empty());
+    }
+
+    #[test]
+    fn a_string_literal_mentioning_test_is_not_evidence() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), "pub fn sample() -> &'static str { \"#[test]\" }\n").unwrap();
+
+        assert!(scan(dir.path()).is_empty());
+    }
+
+    #[test]
+    fn a_real_inline_test_module_is_evidence() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), "pub fn add(a: i32, b: i32) -> i32 { a + b }\n\n#[cfg(test)]\nmod tests {\n    #[test]\n    fn it_adds() {}\n}\n").unwrap();
+
+        let evidence = scan(dir.path());
+        assert_eq!(evidence.len(), 1);
+        assert_eq!(evidence[0].path, "src/lib.rs");
+    }
+
+    #[test]
+    fn a_nested_integration_test_file_is_evidence() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("tests/integration")).unwrap();
+        std::fs::write(dir.path().join("tests/integration/nested.rs"), "#[test]\nfn it_works() {}\n").unwrap();
+
+        let evidence = scan(dir.path());
+        assert_eq!(evidence.len(), 1);
+        assert_eq!(evidence[0].path, "tests/integration/nested.rs");
+    }
+
+    #[test]
+    fn a_bench_file_is_evidence_even_without_a_test_attribute() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("benches")).unwrap();
+        std::fs::write(dir.path().join("benches/throughput.rs"), "fn main() {}\n").unwrap();
+
+        let evidence = scan(dir.path());
+        assert_eq!(evidence.len(), 1);
+        assert_eq!(evidence[0].path, "benches/throughput.rs");
+    }
+
+    #[test]
+    fn a_lifetime_quote_is_not_mistaken_for_a_char_literal() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), "pub fn first<'a>(items: &'a [i32]) -> i32 { items[0] }\n").unwrap();
+
+        assert!(scan(dir.path()).is_empty());
+    }
+}
@@ -0,0 +1,215 @@
+//! Installs the process-wide `tracing` subscriber: human-readable output
+//! to stderr, leveled by `-v`/`-vv`/`-q` or the `TDD_LOG` env var, and,
+//! when `workspace.log_file` is configured, JSON lines to a size-rotated
+//! file. None of this touches stdout, which stays reserved for the
+//! existing user-facing `println!` output.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::Layer;
+
+/// Above this size, the JSON log file is rotated: it's renamed with a
+/// `.1` suffix (overwriting any previous rotation) and a fresh file is
+/// started.
+const LOG_ROTATION_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Verbosity as parsed from the CLI: `-q` maps to `Quiet`, no flags to
+/// `Default`, and each repeated `-v` to the next tier.
+#[derive(Debug, Clone, Copy)]
+pub enum Verbosity {
+    Quiet,
+    Default,
+    Verbose,
+    VeryVerbose,
+}
+
+impl Verbosity {
+    pub fn from_flags(verbose: u8, quiet: bool) -> Self {
+        if quiet {
+            Verbosity::Quiet
+        } else {
+            match verbose {
+                0 => Verbosity::Default,
+                1 => Verbosity::Verbose,
+                _ => Verbosity::VeryVerbose,
+            }
+        }
+    }
+
+    fn level_filter(self) -> LevelFilter {
+        match self {
+            Verbosity::Quiet => LevelFilter::ERROR,
+            Verbosity::Default => LevelFilter::INFO,
+            Verbosity::Verbose => LevelFilter::DEBUG,
+            Verbosity::VeryVerbose => LevelFilter::TRACE,
+        }
+    }
+}
+
+fn stderr_filter(verbosity: Verbosity) -> EnvFilter {
+    match std::env::var("TDD_LOG") {
+        Ok(directive) => EnvFilter::new(directive),
+        Err(_) => EnvFilter::new(verbosity.level_filter().to_string()),
+    }
+}
+
+/// Installs the subscriber for the process. `log_file`, when given, is
+/// resolved relative to the workspace root and always receives JSON
+/// lines at trace level, independent of the stderr verbosity.
+pub fn init(verbosity: Verbosity, log_file: Option<&Path>) -> anyhow::Result<()> {
+    let stderr_layer = tracing_subscriber::fmt::layer().with_writer(std::io::stderr).with_filter(stderr_filter(verbosity));
+
+    let file_layer = match log_file {
+        Some(path) => Some(
+            tracing_subscriber::fmt::layer()
+                .json()
+                .with_writer(RotatingFileWriter::new(path.to_path_buf())?)
+                .with_filter(LevelFilter::TRACE),
+        ),
+        None => None,
+    };
+
+    tracing_subscriber::registry().with(stderr_layer).with(file_layer).try_init()?;
+    Ok(())
+}
+
+/// A [`Write`] implementation that rotates the underlying file by size,
+/// cheap to clone since the open file and byte counter are shared.
+#[derive(Clone)]
+struct RotatingFileWriter {
+    inner: Arc<Mutex<RotatingFile>>,
+}
+
+struct RotatingFile {
+    path: PathBuf,
+    file: std::fs::File,
+    written: u64,
+}
+
+impl RotatingFileWriter {
+    fn new(path: PathBuf) -> anyhow::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = std::fs::OpenOptions::new().create(true).append(true).open(&path)?;
+        let written = file.metadata()?.len();
+        Ok(Self {
+            inner: Arc::new(Mutex::new(RotatingFile { path, file, written })),
+        })
+    }
+}
+
+impl RotatingFile {
+    fn rotate(&mut self) -> std::io::Result<()> {
+        std::fs::rename(&self.path, rotated_path(&self.path))?;
+        self.file = std::fs::OpenOptions::new().create(true).write(true).truncate(true).open(&self.path)?;
+        self.written = 0;
+        Ok(())
+    }
+}
+
+fn rotated_path(path: &Path) -> PathBuf {
+    match path.extension() {
+        Some(ext) => path.with_extension(format!("1.{}", ext.to_string_lossy())),
+        None => path.with_extension("1"),
+    }
+}
+
+impl Write for RotatingFileWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let mut inner = self.inner.lock().expect("rotating log writer mutex poisoned");
+        if inner.written + buf.len() as u64 > LOG_ROTATION_BYTES {
+            inner.rotate()?;
+        }
+        let written = inner.file.write(buf)?;
+        inner.written += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.lock().expect("rotating log writer mutex poisoned").file.flush()
+    }
+}
+
+impl<'a> tracing_subscriber::fmt::MakeWriter<'a> for RotatingFileWriter {
+    type Writer = RotatingFileWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotates_once_the_size_threshold_is_crossed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("trace.jsonl");
+        let mut writer = RotatingFileWriter::new(path.clone()).unwrap();
+
+        writer.inner.lock().unwrap().written = LOG_ROTATION_BYTES;
+        writer.write_all(b"one more line\n").unwrap();
+
+        assert!(rotated_path(&path).exists());
+        assert!(path.exists());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "one more line\n");
+    }
+
+    #[test]
+    fn resumes_the_byte_count_from_an_existing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("trace.jsonl");
+        std::fs::write(&path, "existing content\n").unwrap();
+
+        let writer = RotatingFileWriter::new(path).unwrap();
+        assert_eq!(writer.inner.lock().unwrap().written, "existing content\n".len() as u64);
+    }
+
+    #[derive(Clone, Default)]
+    struct BufWriter(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for BufWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> tracing_subscriber::fmt::MakeWriter<'a> for BufWriter {
+        type Writer = BufWriter;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[test]
+    fn quiet_silences_info_events_but_not_errors() {
+        let buf = BufWriter::default();
+        let layer = tracing_subscriber::fmt::layer()
+            .with_writer(buf.clone())
+            .with_ansi(false)
+            .with_filter(EnvFilter::new(Verbosity::Quiet.level_filter().to_string()));
+        let subscriber = tracing_subscriber::registry().with(layer);
+
+        let _guard = tracing::subscriber::set_default(subscriber);
+        tracing::info!("should not appear");
+        tracing::error!("should appear");
+        drop(_guard);
+
+        let output = String::from_utf8(buf.0.lock().unwrap().clone()).unwrap();
+        assert!(!output.contains("should not appear"));
+        assert!(output.contains("should appear"));
+    }
+}
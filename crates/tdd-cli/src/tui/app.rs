@@ -0,0 +1,124 @@
+//! The `tdd-cli tui` rendering loop. This module owns *presentation* only —
+//! every value it draws comes from [`DashboardState`], which is folded
+//! purely from the same [`StepEvent`]s the non-interactive `run` command
+//! ignores. Not unit tested (see [`crate::tui::viewmodel`] for the tested
+//! reducer); a terminal-driving loop isn't worth mocking a backend for.
+
+use crate::cli::RunArgs;
+use crate::orchestrator::LoopOrchestrator;
+use crate::tui::events::StepEvent;
+use crate::tui::viewmodel::{reduce, DashboardState, PhaseStatus};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use std::time::Duration;
+use tdd_core::Orchestrator;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Runs `args.steps` steps against a real workspace, same as `run`, while
+/// rendering a live dashboard. `p` pauses after the current step, `q`
+/// requests graceful cancellation after the current step, `l` toggles a
+/// full-screen view of the CI log tail.
+pub async fn run(args: RunArgs) -> anyhow::Result<()> {
+    let (tx, rx) = std::sync::mpsc::channel::<StepEvent>();
+    let mut orchestrator = LoopOrchestrator::from_workspace(&args).await?.add_observer(Box::new(move |event| {
+        let _ = tx.send(event);
+    }));
+
+    let mut terminal = ratatui::try_init()?;
+    let mut state = DashboardState::default();
+    let mut show_full_log = false;
+    let mut paused = false;
+    let mut steps_done = 0;
+
+    let outcome = loop {
+        while let Ok(event) = rx.try_recv() {
+            reduce(&mut state, event);
+        }
+        terminal.draw(|frame| draw(frame, &state, show_full_log))?;
+
+        if let Some(key) = poll_keypress(POLL_INTERVAL)? {
+            match key {
+                KeyCode::Char('q') => break Ok(()),
+                KeyCode::Char('p') => paused = !paused,
+                KeyCode::Char('l') => show_full_log = !show_full_log,
+                _ => {}
+            }
+        }
+
+        if steps_done >= args.steps {
+            break Ok(());
+        }
+        if paused {
+            continue;
+        }
+
+        if let Err(error) = orchestrator.next().await {
+            break Err(error);
+        }
+        steps_done += 1;
+    };
+
+    ratatui::restore();
+    outcome
+}
+
+fn poll_keypress(timeout: Duration) -> anyhow::Result<Option<KeyCode>> {
+    if !event::poll(timeout)? {
+        return Ok(None);
+    }
+    match event::read()? {
+        Event::Key(key) if key.kind == KeyEventKind::Press => Ok(Some(key.code)),
+        _ => Ok(None),
+    }
+}
+
+fn draw(frame: &mut ratatui::Frame, state: &DashboardState, show_full_log: bool) {
+    if show_full_log {
+        frame.render_widget(ci_log_pane(state), frame.area());
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(3), Constraint::Percentage(50)])
+        .split(frame.area());
+
+    frame.render_widget(progress_pane(state), chunks[0]);
+    frame.render_widget(commit_or_plan_pane(state), chunks[1]);
+    frame.render_widget(ci_log_pane(state), chunks[2]);
+}
+
+fn progress_pane(state: &DashboardState) -> Paragraph<'static> {
+    let role = state.current_role.map(|role| role.to_string()).unwrap_or_else(|| "-".to_string());
+    let status = match state.phase_status {
+        PhaseStatus::Pending => "pending".to_string(),
+        PhaseStatus::Running => "running".to_string(),
+        PhaseStatus::Retrying { attempt } => format!("retrying (attempt {attempt})"),
+        PhaseStatus::Failed => "failed".to_string(),
+        PhaseStatus::AwaitingReview => "awaiting review".to_string(),
+        PhaseStatus::Committed => "committed".to_string(),
+    };
+    let mut text = format!("step {} · {role} · {status}", state.current_step);
+    if let Some(notice) = &state.flaky_notice {
+        text.push_str(&format!(" · {notice}"));
+    }
+    Paragraph::new(text).block(Block::default().borders(Borders::ALL).title("cycle"))
+}
+
+fn commit_or_plan_pane(state: &DashboardState) -> Paragraph<'static> {
+    let body = if !state.last_commit_message.is_empty() {
+        state.last_commit_message.clone()
+    } else {
+        state.plan_excerpt.clone()
+    };
+    let title = if state.last_commit_message.is_empty() { "plan" } else { "last commit" };
+    Paragraph::new(body).wrap(Wrap { trim: false }).block(Block::default().borders(Borders::ALL).title(title))
+}
+
+fn ci_log_pane(state: &DashboardState) -> Paragraph<'static> {
+    let lines: Vec<Line<'static>> = state.ci_log_tail.iter().cloned().map(Line::from).collect();
+    Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("ci log (l: full screen, p: pause, q: quit)"))
+}
@@ -0,0 +1,37 @@
+//! Lifecycle events an orchestrator run emits as it works through a step,
+//! consumed by anything that wants to observe a run without owning its
+//! logic (currently just the TUI dashboard).
+
+use tdd_core::{Role, StepFailureDetail};
+
+/// A single observable moment in a step's life. Carries only what's
+/// already known at the emission point in [`crate::orchestrator`] — no
+/// event here requires re-deriving anything the orchestrator hasn't
+/// already computed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StepEvent {
+    /// The current role's plan was written to `.tdd/plan`.
+    PlanWritten { role: Role, step: u32, plan: String },
+    /// A new edit attempt started for the current step.
+    AttemptStarted { role: Role, step: u32, attempt: u32 },
+    /// An attempt failed verification, with the same structured detail
+    /// that gets written to the step's `StepLog`.
+    AttemptFailed { role: Role, step: u32, attempt: u32, detail: StepFailureDetail },
+    /// A flaky test re-run recovered the test stage.
+    FlakyRerun { role: Role, step: u32, reruns: u32, tests: Vec<String> },
+    /// A verified step is waiting on `.tdd/review/decision-step-{step}`
+    /// under `workspace.review_mode: file`.
+    ReviewPending { role: Role, step: u32 },
+    /// The review poll is still waiting; emitted once per poll so the wait
+    /// stays visible instead of looking stalled.
+    ReviewWaiting { role: Role, step: u32, elapsed_secs: u64 },
+    /// A pending review was decided, one way or another.
+    ReviewDecided { role: Role, step: u32, decision: String },
+    /// The step verified and was committed.
+    StepCommitted { role: Role, step: u32, commit_message: String },
+    /// The step's elapsed time was checked against
+    /// `workspace.max_step_duration_secs` at a phase boundary, emitted
+    /// whether or not it was exceeded so a dashboard can show how close a
+    /// run is running to its ceiling.
+    DeadlineChecked { role: Role, step: u32, phase: String, elapsed_secs: u64, max_secs: u64, exceeded: bool },
+}
@@ -0,0 +1,14 @@
+//! The `tdd-cli tui` dashboard: a `ratatui`-based front end that observes
+//! the same [`StepEvent`](events::StepEvent) stream the orchestrator emits
+//! rather than duplicating any of its logic. [`events`] and [`viewmodel`]
+//! are plain, terminal-free code so the reducer can be unit tested; the
+//! actual rendering lives behind the `tui` feature in [`app`].
+
+pub mod events;
+pub mod viewmodel;
+
+#[cfg(feature = "tui")]
+mod app;
+
+#[cfg(feature = "tui")]
+pub use app::run;
@@ -0,0 +1,130 @@
+//! Reduces a stream of [`StepEvent`]s into the state the dashboard's three
+//! panes render, independent of any terminal library so it can be unit
+//! tested directly.
+
+use crate::tui::events::StepEvent;
+use tdd_core::{Role, StepFailureDetail};
+
+/// Where the current step's latest attempt stands.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum PhaseStatus {
+    #[default]
+    Pending,
+    Running,
+    Retrying {
+        attempt: u32,
+    },
+    Failed,
+    AwaitingReview,
+    Committed,
+}
+
+/// Everything the dashboard's three panes need to render: cycle/step
+/// progress, the latest commit message or in-flight plan excerpt, and a
+/// tail of CI output.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DashboardState {
+    pub current_role: Option<Role>,
+    pub current_step: u32,
+    pub phase_status: PhaseStatus,
+    pub plan_excerpt: String,
+    pub last_commit_message: String,
+    pub last_failure: Option<StepFailureDetail>,
+    pub ci_log_tail: Vec<String>,
+    pub flaky_notice: Option<String>,
+}
+
+const PLAN_EXCERPT_LINES: usize = 3;
+
+/// Folds one event into `state`. Pure and side-effect free, so a scripted
+/// event sequence can be replayed and asserted on directly.
+pub fn reduce(state: &mut DashboardState, event: StepEvent) {
+    match event {
+        StepEvent::PlanWritten { role, step, plan } => {
+            state.current_role = Some(role);
+            state.current_step = step;
+            state.phase_status = PhaseStatus::Pending;
+            state.plan_excerpt = plan.lines().take(PLAN_EXCERPT_LINES).collect::<Vec<_>>().join("\n");
+        }
+        StepEvent::AttemptStarted { role, step, attempt } => {
+            state.current_role = Some(role);
+            state.current_step = step;
+            state.phase_status = if attempt <= 1 { PhaseStatus::Running } else { PhaseStatus::Retrying { attempt } };
+        }
+        StepEvent::AttemptFailed { detail, .. } => {
+            state.phase_status = PhaseStatus::Failed;
+            if let StepFailureDetail::CiFailure { ref stderr_tail, .. } = detail {
+                state.ci_log_tail = stderr_tail.lines().map(str::to_string).collect();
+            }
+            state.last_failure = Some(detail);
+        }
+        StepEvent::FlakyRerun { reruns, tests, .. } => {
+            state.flaky_notice = Some(format!("{reruns} rerun(s) recovered: {}", tests.join(", ")));
+        }
+        StepEvent::ReviewPending { role, step } => {
+            state.current_role = Some(role);
+            state.current_step = step;
+            state.phase_status = PhaseStatus::AwaitingReview;
+        }
+        StepEvent::ReviewWaiting { .. } => {}
+        StepEvent::ReviewDecided { .. } => {}
+        StepEvent::StepCommitted { role, step, commit_message } => {
+            state.current_role = Some(role);
+            state.current_step = step;
+            state.phase_status = PhaseStatus::Committed;
+            state.last_commit_message = commit_message;
+            state.last_failure = None;
+            state.flaky_notice = None;
+        }
+        StepEvent::DeadlineChecked { phase, elapsed_secs, exceeded, .. } => {
+            if exceeded {
+                state.phase_status = PhaseStatus::Failed;
+                state.last_failure = Some(StepFailureDetail::DeadlineExceeded { phase_reached: phase, elapsed_secs });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_scripted_run_with_a_retry_and_a_failure_reduces_to_the_final_view() {
+        let mut state = DashboardState::default();
+
+        reduce(&mut state, StepEvent::PlanWritten { role: Role::Implementor, step: 3, plan: "add the happy path\nhandle the empty case\nrun the suite".to_string() });
+        assert_eq!(state.phase_status, PhaseStatus::Pending);
+        assert_eq!(state.plan_excerpt, "add the happy path\nhandle the empty case\nrun the suite");
+
+        reduce(&mut state, StepEvent::AttemptStarted { role: Role::Implementor, step: 3, attempt: 1 });
+        assert_eq!(state.phase_status, PhaseStatus::Running);
+
+        reduce(
+            &mut state,
+            StepEvent::AttemptFailed {
+                role: Role::Implementor,
+                step: 3,
+                attempt: 1,
+                detail: StepFailureDetail::CiFailure { stage: "check".to_string(), stderr_tail: "error[E0425]: cannot find value `b`".to_string() },
+            },
+        );
+        assert_eq!(state.phase_status, PhaseStatus::Failed);
+        assert_eq!(state.ci_log_tail, vec!["error[E0425]: cannot find value `b`".to_string()]);
+        assert!(state.last_failure.is_some());
+
+        reduce(&mut state, StepEvent::AttemptStarted { role: Role::Implementor, step: 3, attempt: 2 });
+        assert_eq!(state.phase_status, PhaseStatus::Retrying { attempt: 2 });
+
+        reduce(&mut state, StepEvent::FlakyRerun { role: Role::Implementor, step: 3, reruns: 1, tests: vec!["unrelated::tests::sometimes_fails".to_string()] });
+        assert_eq!(state.flaky_notice.as_deref(), Some("1 rerun(s) recovered: unrelated::tests::sometimes_fails"));
+
+        reduce(&mut state, StepEvent::StepCommitted { role: Role::Implementor, step: 3, commit_message: "feat: add addition".to_string() });
+        assert_eq!(state.phase_status, PhaseStatus::Committed);
+        assert_eq!(state.last_commit_message, "feat: add addition");
+        assert!(state.last_failure.is_none());
+        assert!(state.flaky_notice.is_none());
+        assert_eq!(state.current_role, Some(Role::Implementor));
+        assert_eq!(state.current_step, 3);
+    }
+}
@@ -0,0 +1,286 @@
+//! Implements `tdd-cli undo` and `tdd-cli redo`: reverting a step's commit
+//! without losing the plan, log, and file contents it produced.
+//!
+//! `undo` moves those artifacts into an archive directory under
+//! `.tdd/state/undone/` instead of deleting them, and pushes the archive
+//! onto a LIFO stack (`.tdd/state/undo-stack.json`). `redo` pops the stack,
+//! re-applies the archived file contents, re-runs CI, and commits with the
+//! original message plus a `(redone)` marker. A normal step taken after an
+//! undo should call [`clear_redo_stack`] so a stale archive never gets
+//! replayed onto an unrelated history.
+//!
+//! Only commits produced by [`crate::orchestrator::LoopOrchestrator`]'s own
+//! `role: step N` messages can be undone; anything else (including a
+//! standalone `style: apply rustfmt` commit from `separate_fmt_commits`) is
+//! left alone, since there's no reliable way to tell which step it belongs
+//! to from the message alone.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tdd_core::{Role, Runner, Vcs};
+use tdd_exec::{CommitAuthor, GitVcs};
+
+const UNDONE_DIR: &str = ".tdd/state/undone";
+const STACK_FILE: &str = ".tdd/state/undo-stack.json";
+
+/// One archived step: enough to explain what was undone and to redo it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UndoRecord {
+    pub step: u32,
+    pub role: Role,
+    pub reverted_commit: String,
+    pub commit_message: String,
+    /// Repo-relative path -> full file contents at the reverted commit,
+    /// the tree delta needed to re-apply the step on redo.
+    pub files: Vec<(String, String)>,
+    /// The exact `.tdd/plan` filename archived, run-id and all, so redo
+    /// restores it to that same path rather than recomputing one —
+    /// recomputing would collide if a fresh run of the same step
+    /// happened while this entry sat on the undo stack. `None` if the
+    /// step had no plan file to archive.
+    #[serde(default)]
+    pub plan_filename: Option<String>,
+    /// Same as `plan_filename`, for the `.tdd/logs` entry.
+    #[serde(default)]
+    pub log_filename: Option<String>,
+}
+
+/// Archives the most recent step's commit and resets the working tree to
+/// its parent, or (when it's the workspace's very first commit) back to
+/// the unborn state a fresh `git init` leaves `HEAD` in. Fails if `HEAD`
+/// isn't a step commit the orchestrator made.
+pub fn undo(repo_root: &Path) -> anyhow::Result<UndoRecord> {
+    let repo = git2::Repository::open(repo_root)?;
+    let mut head = repo.head()?;
+    let commit = head.peel_to_commit()?;
+    let commit_message = commit.message().unwrap_or_default().to_string();
+    let (role, step) = parse_step_commit(&commit_message)
+        .ok_or_else(|| anyhow::anyhow!("HEAD commit \"{}\" doesn't look like a step commit tdd-cli made", commit_message.trim()))?;
+
+    let files = changed_file_contents(&repo, &commit)?;
+    let plan_filename = current_artifact(&repo_root.join(".tdd/plan"), step, ".md");
+    let log_filename = current_artifact(&repo_root.join(".tdd/logs"), step, ".json");
+    let record = UndoRecord {
+        step,
+        role,
+        reverted_commit: commit.id().to_string(),
+        commit_message,
+        files,
+        plan_filename: plan_filename.clone(),
+        log_filename: log_filename.clone(),
+    };
+
+    let archive_dir = repo_root.join(UNDONE_DIR).join(archive_dir_name(step, role));
+    std::fs::create_dir_all(&archive_dir)?;
+    if let Some(name) = &plan_filename {
+        move_if_present(&repo_root.join(".tdd/plan").join(name), &archive_dir.join("plan.md"))?;
+    }
+    if let Some(name) = &log_filename {
+        move_if_present(&repo_root.join(".tdd/logs").join(name), &archive_dir.join("log.json"))?;
+    }
+    std::fs::write(archive_dir.join("record.json"), serde_json::to_string_pretty(&record)?)?;
+
+    push_stack(repo_root, &archive_dir_name(step, role))?;
+
+    match commit.parent(0) {
+        Ok(parent) => repo.reset(parent.as_object(), git2::ResetType::Hard, None)?,
+        Err(_) => {
+            // Undoing the workspace's first-ever commit: there's no parent
+            // to reset onto, so remove the files it added and leave the
+            // branch unborn, matching a fresh `git init`.
+            for (path, _) in &record.files {
+                std::fs::remove_file(repo_root.join(path)).ok();
+            }
+            head.delete()?;
+        }
+    }
+
+    Ok(record)
+}
+
+/// Pops the most recently undone step, re-applies its files, re-runs CI
+/// via `runner`, and commits the result with a `(redone)` marker. Fails
+/// (leaving the archive entry in place) if the stack is empty or CI
+/// doesn't pass on the re-applied files.
+pub fn redo(repo_root: &Path, runner: &dyn Runner) -> anyhow::Result<UndoRecord> {
+    let dir_name = pop_stack(repo_root)?.ok_or_else(|| anyhow::anyhow!("nothing to redo"))?;
+    let archive_dir = repo_root.join(UNDONE_DIR).join(&dir_name);
+
+    let redo_result = redo_archived_step(repo_root, runner, &archive_dir);
+    if redo_result.is_err() {
+        push_stack(repo_root, &dir_name)?;
+    }
+    redo_result
+}
+
+fn redo_archived_step(repo_root: &Path, runner: &dyn Runner, archive_dir: &Path) -> anyhow::Result<UndoRecord> {
+    let record: UndoRecord = serde_json::from_str(&std::fs::read_to_string(archive_dir.join("record.json"))?)?;
+
+    for (path, content) in &record.files {
+        let target = repo_root.join(path);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(target, content)?;
+    }
+    // Restored to the exact filename archived at undo time (run-id and
+    // all), not a freshly computed one: a fresh run of this step may
+    // have happened while the archive sat on the stack, and that run's
+    // files must be left alone.
+    if let Some(name) = &record.plan_filename {
+        move_if_present(&archive_dir.join("plan.md"), &repo_root.join(".tdd/plan").join(name))?;
+    }
+    if let Some(name) = &record.log_filename {
+        move_if_present(&archive_dir.join("log.json"), &repo_root.join(".tdd/logs").join(name))?;
+    }
+
+    let check = runner.check()?;
+    let test = runner.test()?;
+    // A Tester step's job is to hand off a *failing* test, so its CI
+    // verification is inverted relative to the other two roles, same as
+    // `LoopOrchestrator::next`.
+    let verified = match record.role {
+        Role::Tester => check.ok && !test.ok,
+        Role::Implementor | Role::Refactorer => check.ok && test.ok,
+    };
+    if !verified {
+        anyhow::bail!(
+            "redo of step {} ({}) failed to re-verify: {}",
+            record.step,
+            record.role,
+            if !check.ok { &check.stderr } else { &test.stderr }
+        );
+    }
+
+    let config = crate::config::TddConfig::load(&repo_root.join("tdd.yaml")).unwrap_or_default();
+    let vcs = GitVcs::new(repo_root, CommitAuthor {
+        name: config.commit.author_name,
+        email: config.commit.author_email,
+    });
+    vcs.stage_all()?;
+    vcs.commit(&format!("{} (redone)", record.commit_message))?;
+
+    std::fs::remove_dir_all(archive_dir)?;
+
+    Ok(record)
+}
+
+/// Discards every archived step still on the undo stack, e.g. because a
+/// fresh step made them obsolete. Returns whether there was anything to
+/// clear, so the caller can warn about it.
+pub fn clear_redo_stack(repo_root: &Path) -> anyhow::Result<bool> {
+    let stack = read_stack(repo_root)?;
+    if stack.is_empty() {
+        return Ok(false);
+    }
+    for dir_name in &stack {
+        std::fs::remove_dir_all(repo_root.join(UNDONE_DIR).join(dir_name)).ok();
+    }
+    std::fs::remove_file(repo_root.join(STACK_FILE))?;
+    Ok(true)
+}
+
+fn archive_dir_name(step: u32, role: Role) -> String {
+    format!("step-{step:03}-{role}")
+}
+
+/// Resolves `step`'s current artifact (the highest run-id, or the bare
+/// legacy filename) among `dir`'s entries, via
+/// [`tdd_core::artifacts::resolve_step`]. `None` if `dir` doesn't exist
+/// or holds nothing for `step`.
+fn current_artifact(dir: &Path, step: u32, extension: &str) -> Option<String> {
+    let names: Vec<String> = std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .collect();
+    let borrowed: Vec<&str> = names.iter().map(String::as_str).collect();
+    tdd_core::artifacts::resolve_step(borrowed, extension, step, None).map(str::to_string)
+}
+
+fn move_if_present(from: &Path, to: &Path) -> anyhow::Result<()> {
+    if from.exists() {
+        if let Some(parent) = to.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::rename(from, to)?;
+    }
+    Ok(())
+}
+
+/// Parses a commit message produced by `LoopOrchestrator`'s
+/// `"{commit_type}: step {n}"` convention back into a role and step.
+/// Ignores anything after the summary line, so an `Operator goal:` or
+/// `hooks-bypassed:` trailer doesn't prevent a match.
+pub(crate) fn parse_step_commit(message: &str) -> Option<(Role, u32)> {
+    let (commit_type, rest) = message.trim().split_once(": step ")?;
+    let summary_line = rest.split('\n').next().unwrap_or(rest);
+    let step: u32 = summary_line.trim().parse().ok()?;
+    let role = match commit_type {
+        "test" => Role::Tester,
+        "feat" => Role::Implementor,
+        "refactor" => Role::Refactorer,
+        _ => return None,
+    };
+    Some((role, step))
+}
+
+/// Reads the full contents of every file a commit added or changed
+/// relative to its parent, keyed by repo-relative path.
+fn changed_file_contents(repo: &git2::Repository, commit: &git2::Commit) -> anyhow::Result<Vec<(String, String)>> {
+    let tree = commit.tree()?;
+    let parent_tree = commit.parents().next().and_then(|p| p.tree().ok());
+    let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
+
+    let mut paths = Vec::new();
+    diff.foreach(
+        &mut |delta, _| {
+            if let Some(path) = delta.new_file().path() {
+                paths.push(path.to_path_buf());
+            }
+            true
+        },
+        None,
+        None,
+        None,
+    )?;
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let entry = tree.get_path(&path)?;
+            let blob = repo.find_blob(entry.id())?;
+            Ok((path.to_string_lossy().into_owned(), String::from_utf8_lossy(blob.content()).into_owned()))
+        })
+        .collect()
+}
+
+fn read_stack(repo_root: &Path) -> anyhow::Result<Vec<String>> {
+    let path = repo_root.join(STACK_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+}
+
+fn write_stack(repo_root: &Path, stack: &[String]) -> anyhow::Result<()> {
+    let path = repo_root.join(STACK_FILE);
+    std::fs::create_dir_all(path.parent().unwrap())?;
+    std::fs::write(path, serde_json::to_string_pretty(stack)?)?;
+    Ok(())
+}
+
+fn push_stack(repo_root: &Path, dir_name: &str) -> anyhow::Result<()> {
+    let mut stack = read_stack(repo_root)?;
+    stack.push(dir_name.to_string());
+    write_stack(repo_root, &stack)
+}
+
+fn pop_stack(repo_root: &Path) -> anyhow::Result<Option<String>> {
+    let mut stack = read_stack(repo_root)?;
+    let popped = stack.pop();
+    if popped.is_some() {
+        write_stack(repo_root, &stack)?;
+    }
+    Ok(popped)
+}
@@ -0,0 +1,69 @@
+//! Detects whether a workspace directory is writable or readable, so
+//! `status`/`doctor` can degrade gracefully on a read-only checkout (a CI
+//! artifact mount, say) and a real `run` can fail fast with a clear error
+//! instead of partway through a step.
+//!
+//! These check permission bits rather than attempting a real write or
+//! read, so the result reflects the checkout's intended access level
+//! consistently, even under a user (e.g. root) that would otherwise
+//! bypass Unix permission enforcement entirely.
+
+use std::path::Path;
+
+/// True when `path`'s own permission bits give its owner no write access.
+#[cfg(unix)]
+pub fn is_read_only(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path).map(|meta| meta.permissions().mode() & 0o200 == 0).unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+pub fn is_read_only(path: &Path) -> bool {
+    std::fs::metadata(path).map(|meta| meta.permissions().readonly()).unwrap_or(false)
+}
+
+/// True when `path`'s own permission bits give its owner neither read nor
+/// execute access, meaning a directory's entries can't be listed.
+#[cfg(unix)]
+pub fn is_unreadable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path).map(|meta| meta.permissions().mode() & 0o500 != 0o500).unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+pub fn is_unreadable(_path: &Path) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[cfg(unix)]
+    #[test]
+    fn a_directory_with_no_owner_write_bit_is_read_only() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempdir().unwrap();
+        std::fs::set_permissions(dir.path(), std::fs::Permissions::from_mode(0o555)).unwrap();
+        assert!(is_read_only(dir.path()));
+        std::fs::set_permissions(dir.path(), std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[test]
+    fn a_normal_directory_is_writable_and_readable() {
+        let dir = tempdir().unwrap();
+        assert!(!is_read_only(dir.path()));
+        assert!(!is_unreadable(dir.path()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn a_directory_with_no_owner_read_bit_is_unreadable() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempdir().unwrap();
+        std::fs::set_permissions(dir.path(), std::fs::Permissions::from_mode(0o300)).unwrap();
+        assert!(is_unreadable(dir.path()));
+        std::fs::set_permissions(dir.path(), std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+}
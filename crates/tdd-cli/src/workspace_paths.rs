@@ -0,0 +1,147 @@
+//! Resolves workspace-relative config paths (`kata_description`,
+//! `workspace.context_file`) against the current workspace root.
+//!
+//! Every path `tdd-cli` itself writes into `tdd.yaml` is relative, but a
+//! handwritten config, or one carried over from before a project directory
+//! was moved or renamed (or run inside a container with a different mount
+//! path), may still hold an absolute path pointing at a root that no
+//! longer exists. [`resolve`] tolerates that: it re-roots a stale absolute
+//! path onto the current workspace when a same-named file exists there,
+//! instead of failing to find a file that's really just sitting next door.
+
+use crate::config::TddConfig;
+use std::path::{Path, PathBuf};
+
+/// Joins `repo_root` with `configured`. When `configured` is itself
+/// relative this is exactly `repo_root.join(configured)`. When it's
+/// absolute but doesn't exist as given — the telltale sign of a path
+/// recorded under a workspace root that has since moved — and a
+/// same-named file exists under `repo_root`, that workspace-relative file
+/// is used instead. An absolute path that still resolves (or has no
+/// match under `repo_root`) is returned unchanged, so an intentionally
+/// shared file outside the workspace keeps working.
+pub fn resolve(repo_root: &Path, configured: &str) -> PathBuf {
+    let configured = Path::new(configured);
+    if configured.is_relative() {
+        return repo_root.join(configured);
+    }
+    if configured.exists() {
+        return configured.to_path_buf();
+    }
+    match configured.file_name() {
+        Some(name) if repo_root.join(name).exists() => repo_root.join(name),
+        _ => configured.to_path_buf(),
+    }
+}
+
+/// True when `configured` is an absolute path that no longer exists as
+/// given, the pattern left behind by a moved or renamed workspace root —
+/// worth a [`crate::doctor`] warning even though [`resolve`] works around
+/// it.
+pub fn is_stale_absolute(repo_root: &Path, configured: &str) -> bool {
+    let configured = Path::new(configured);
+    configured.is_absolute() && !configured.exists() && configured.file_name().is_some_and(|name| repo_root.join(name).exists())
+}
+
+/// One field `repair` rewrote or would rewrite, for `tdd-cli repair-paths`
+/// to report, with or without `--dry-run`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepairedPath {
+    pub key: &'static str,
+    pub from: String,
+    pub to: String,
+}
+
+/// Rewrites every stale absolute path in `config` to its workspace-relative
+/// form, re-rooted against `repo_root`, and returns what changed. Leaves
+/// `config` untouched when nothing is stale.
+pub fn repair(repo_root: &Path, config: &mut TddConfig) -> Vec<RepairedPath> {
+    let mut repaired = Vec::new();
+
+    if is_stale_absolute(repo_root, &config.kata_description) {
+        let relative = relative_form(repo_root, &config.kata_description);
+        repaired.push(RepairedPath { key: "kata_description", from: config.kata_description.clone(), to: relative.clone() });
+        config.kata_description = relative;
+    }
+
+    if is_stale_absolute(repo_root, &config.workspace.context_file) {
+        let relative = relative_form(repo_root, &config.workspace.context_file);
+        repaired.push(RepairedPath { key: "context_file", from: config.workspace.context_file.clone(), to: relative.clone() });
+        config.workspace.context_file = relative;
+    }
+
+    repaired
+}
+
+/// The workspace-relative form [`resolve`] would re-root a stale absolute
+/// path to, as a string fit for writing back to `tdd.yaml`. Only
+/// meaningful when [`is_stale_absolute`] already returned `true`.
+fn relative_form(repo_root: &Path, configured: &str) -> String {
+    resolve(repo_root, configured)
+        .strip_prefix(repo_root)
+        .unwrap_or_else(|_| Path::new(configured))
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn a_relative_path_is_joined_to_the_workspace_root() {
+        let dir = tempdir().unwrap();
+        assert_eq!(resolve(dir.path(), "kata.md"), dir.path().join("kata.md"));
+    }
+
+    #[test]
+    fn a_still_valid_absolute_path_is_left_alone() {
+        let dir = tempdir().unwrap();
+        let kata = dir.path().join("kata.md");
+        std::fs::write(&kata, "# Kata").unwrap();
+
+        assert_eq!(resolve(dir.path(), kata.to_str().unwrap()), kata);
+    }
+
+    #[test]
+    fn a_stale_absolute_path_is_re_rooted_when_the_filename_matches() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("kata.md"), "# Kata").unwrap();
+        let stale = "/this/root/no/longer/exists/kata.md";
+
+        assert_eq!(resolve(dir.path(), stale), dir.path().join("kata.md"));
+        assert!(is_stale_absolute(dir.path(), stale));
+    }
+
+    #[test]
+    fn an_absolute_path_with_no_match_anywhere_is_returned_unchanged() {
+        let dir = tempdir().unwrap();
+        let stale = "/this/root/no/longer/exists/kata.md";
+
+        assert_eq!(resolve(dir.path(), stale), PathBuf::from(stale));
+        assert!(!is_stale_absolute(dir.path(), stale));
+    }
+
+    #[test]
+    fn repair_rewrites_a_stale_kata_description_to_its_relative_form() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("kata.md"), "# Kata").unwrap();
+        let mut config = TddConfig { kata_description: "/old/root/kata.md".to_string(), ..TddConfig::default() };
+
+        let repaired = repair(dir.path(), &mut config);
+
+        assert_eq!(repaired, vec![RepairedPath { key: "kata_description", from: "/old/root/kata.md".to_string(), to: "kata.md".to_string() }]);
+        assert_eq!(config.kata_description, "kata.md");
+    }
+
+    #[test]
+    fn repair_leaves_a_config_with_no_stale_paths_untouched() {
+        let dir = tempdir().unwrap();
+        let mut config = TddConfig::default();
+        let before = config.kata_description.clone();
+
+        assert!(repair(dir.path(), &mut config).is_empty());
+        assert_eq!(config.kata_description, before);
+    }
+}
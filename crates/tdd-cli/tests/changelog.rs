@@ -0,0 +1,64 @@
+//! Exercises `tdd_cli::changelog::append_entry` against a real git
+//! workspace: the entry must land inside the same commit as the step it
+//! documents, and undoing that commit must revert the entry along with
+//! everything else it did.
+
+use tdd_cli::changelog::{append_entry, StepEntry};
+use tdd_core::{Role, Vcs};
+use tdd_exec::{CommitAuthor, GitVcs};
+use tdd_test_support::TestWorkspace;
+
+#[tokio::test]
+async fn a_changelog_entry_lands_in_the_same_commit_as_the_step_it_documents() {
+    let workspace = TestWorkspace::init().unwrap();
+    let root = workspace.root();
+    let vcs = GitVcs::new(root, CommitAuthor::default());
+    vcs.stage_all().unwrap();
+    vcs.commit("chore: scaffold").unwrap();
+
+    std::fs::write(root.join("src/lib.rs"), "pub fn add(a: i32, b: i32) -> i32 { a + b }\n").unwrap();
+    append_entry(
+        root,
+        "CHANGELOG.md",
+        StepEntry { role: Role::Implementor, summary: "handles addition", notes: "added the add function", files_changed: &["src/lib.rs".to_string()] },
+        false,
+        None,
+    )
+    .await
+    .unwrap();
+    vcs.stage_all().unwrap();
+    vcs.commit("feat: step 0").unwrap();
+
+    let state = vcs.read_state().unwrap();
+    assert!(state.last_diff.contains("CHANGELOG.md"));
+    assert!(state.last_diff.contains("handles addition"));
+    assert!(root.join("CHANGELOG.md").exists());
+}
+
+#[tokio::test]
+async fn undoing_the_step_also_reverts_its_changelog_entry() {
+    let workspace = TestWorkspace::init().unwrap();
+    let root = workspace.root();
+    let vcs = GitVcs::new(root, CommitAuthor::default());
+    vcs.stage_all().unwrap();
+    vcs.commit("chore: scaffold").unwrap();
+
+    std::fs::write(root.join("src/lib.rs"), "pub fn add(a: i32, b: i32) -> i32 { a + b }\n").unwrap();
+    append_entry(
+        root,
+        "CHANGELOG.md",
+        StepEntry { role: Role::Implementor, summary: "handles addition", notes: "added the add function", files_changed: &["src/lib.rs".to_string()] },
+        false,
+        None,
+    )
+    .await
+    .unwrap();
+    vcs.stage_all().unwrap();
+    vcs.commit("feat: step 0").unwrap();
+
+    tdd_cli::undo::undo(root).unwrap();
+
+    let state = vcs.read_state().unwrap();
+    assert!(state.last_commit_message.starts_with("chore: scaffold"));
+    assert!(!root.join("CHANGELOG.md").exists(), "undo should remove the file the reverted commit introduced");
+}
@@ -0,0 +1,34 @@
+//! Exercises the example programs under `examples/`: `cargo build
+//! --examples` only proves they compile, not that they still work.
+
+#[path = "../examples/embedded_run.rs"]
+mod embedded_run;
+
+#[path = "../examples/custom_runner.rs"]
+mod custom_runner;
+
+#[tokio::test]
+async fn embedded_run_example_produces_a_commit_per_step() {
+    let workspace = tempfile::tempdir().unwrap();
+
+    let (status, commits) = embedded_run::run_embedded_example(workspace.path()).await.unwrap();
+
+    assert_eq!(status.step_count, 2);
+    assert_eq!(commits.len(), 2);
+    assert!(commits[0].starts_with("test:"));
+    assert!(commits[1].starts_with("feat:"));
+}
+
+#[test]
+fn custom_runner_example_delegates_to_the_inner_runner() {
+    let workspace = tempfile::tempdir().unwrap();
+    tdd_cli::init::run(&tdd_cli::init::InitArgs {
+        path: workspace.path().to_path_buf(),
+        kata_url: None,
+    })
+    .unwrap();
+
+    let outcome = custom_runner::run_with_logging(workspace.path()).unwrap();
+
+    assert!(outcome.ok);
+}
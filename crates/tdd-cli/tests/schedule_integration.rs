@@ -0,0 +1,163 @@
+//! Drives `tdd_cli::schedule::run_loop` through two real ticks against a
+//! scripted LLM, using a sub-second interval so the test doesn't have to
+//! wait around. Calls `run_loop` with `once: true` twice in a row rather
+//! than letting it loop, so the test also proves restart-safety: the
+//! second call picks up the first tick's persisted state instead of
+//! re-running it.
+
+use std::cell::Cell;
+use std::sync::Arc;
+use std::time::Duration;
+use tdd_agents::agent_for_role;
+use tdd_cli::orchestrator::LoopOrchestrator;
+use tdd_cli::schedule::{self, ScheduleSpec};
+use tdd_core::{Agent, Role, Runner, RunnerOutcome};
+use tdd_exec::{CommitAuthor, GitVcs};
+use tdd_fixtures::ScriptedLlmClient;
+use tdd_test_support::TestWorkspace;
+
+/// A suite that's red on every third `test()` call (the tester's turn)
+/// and green on every other call, so two full Tester -> Implementor ->
+/// Refactorer cycles both run to completion without touching real cargo.
+struct CyclingRunner {
+    calls: Cell<u32>,
+}
+
+impl CyclingRunner {
+    fn new() -> Self {
+        Self { calls: Cell::new(0) }
+    }
+}
+
+impl Runner for CyclingRunner {
+    fn fmt_check(&self) -> anyhow::Result<RunnerOutcome> {
+        Ok(RunnerOutcome { ok: true, ..Default::default() })
+    }
+
+    fn fmt(&self) -> anyhow::Result<RunnerOutcome> {
+        Ok(RunnerOutcome { ok: true, ..Default::default() })
+    }
+
+    fn check(&self) -> anyhow::Result<RunnerOutcome> {
+        Ok(RunnerOutcome { ok: true, ..Default::default() })
+    }
+
+    fn test(&self) -> anyhow::Result<RunnerOutcome> {
+        let call = self.calls.get();
+        self.calls.set(call + 1);
+        Ok(RunnerOutcome { ok: !call.is_multiple_of(3), ..Default::default() })
+    }
+}
+
+fn edit_plan(path: &str, content: &str) -> String {
+    format!("{{\"edits\": [{{\"path\": \"{path}\", \"action\": \"upsert\", \"content\": \"{content}\"}}]}}")
+}
+
+fn scripted(role: Role, tick: u32) -> ScriptedLlmClient {
+    let plan = format!("{role} plan for tick {tick}");
+    let edit = match role {
+        Role::Tester => edit_plan(&format!("tests/tick{tick}_test.rs"), &format!("#[test]\\nfn tick_{tick}_test() {{}}\\n")),
+        Role::Implementor => edit_plan("src/lib.rs", &format!("pub fn tick_{tick}() {{}}\\n")),
+        Role::Refactorer => edit_plan(&format!(".tdd-scratch/tick{tick}.md"), &format!("tick {tick} refactor notes\\n")),
+    };
+    ScriptedLlmClient::new([plan, edit])
+}
+
+#[tokio::test]
+async fn two_scheduled_ticks_each_run_a_full_cycle_and_the_state_file_prevents_a_double_run() {
+    let workspace = TestWorkspace::init().unwrap();
+    let root = workspace.root();
+
+    let agents: Vec<Box<dyn Agent>> = vec![
+        Box::new(agent_for_role(
+            Role::Tester,
+            Arc::new(scripted(Role::Tester, 1)),
+            root,
+            false,
+            false,
+            tdd_agents::ScanPolicy { secret_scan: tdd_core::SecretScanMode::Error, unicode_policy: tdd_core::UnicodePolicy::default(), max_blob_bytes: 1024 * 1024, large_blob_policy: tdd_core::LargeBlobPolicy::Reject, readonly_paths: Vec::new(), manifest_policy: tdd_core::ManifestPolicy::default(), },
+            None,
+        )),
+        Box::new(agent_for_role(
+            Role::Implementor,
+            Arc::new(scripted(Role::Implementor, 1)),
+            root,
+            false,
+            false,
+            tdd_agents::ScanPolicy { secret_scan: tdd_core::SecretScanMode::Error, unicode_policy: tdd_core::UnicodePolicy::default(), max_blob_bytes: 1024 * 1024, large_blob_policy: tdd_core::LargeBlobPolicy::Reject, readonly_paths: Vec::new(), manifest_policy: tdd_core::ManifestPolicy::default(), },
+            None,
+        )),
+        Box::new(agent_for_role(
+            Role::Refactorer,
+            Arc::new(scripted(Role::Refactorer, 1)),
+            root,
+            false,
+            false,
+            tdd_agents::ScanPolicy { secret_scan: tdd_core::SecretScanMode::Error, unicode_policy: tdd_core::UnicodePolicy::default(), max_blob_bytes: 1024 * 1024, large_blob_policy: tdd_core::LargeBlobPolicy::Reject, readonly_paths: Vec::new(), manifest_policy: tdd_core::ManifestPolicy::default(), },
+            None,
+        )),
+    ];
+    let vcs = Box::new(GitVcs::new(root, CommitAuthor::default()));
+    let mut orchestrator = LoopOrchestrator::new(agents, vcs, root.to_path_buf(), String::new(), 3).unwrap().with_runner(Box::new(CyclingRunner::new()));
+
+    let spec = ScheduleSpec::Interval(Duration::from_millis(50));
+
+    schedule::run_loop(root, &mut orchestrator, "deadbeef".to_string(), &spec, 1, true).await.unwrap();
+
+    let after_first_tick = schedule::load_state(root).unwrap();
+    assert!(after_first_tick.last_scheduled_run.is_some(), "the first tick should have recorded a state file");
+    let run_after_first = tdd_cli::run_log::load(root).unwrap().unwrap();
+    assert_eq!(run_after_first.steps_executed, 3, "one cycle is three steps");
+
+    // Swap in the second tick's scripted responses for each role before
+    // the second `run_loop` call.
+    let mut orchestrator = LoopOrchestrator::new(
+        vec![
+            Box::new(agent_for_role(
+                Role::Tester,
+                Arc::new(scripted(Role::Tester, 2)),
+                root,
+                false,
+                false,
+                tdd_agents::ScanPolicy { secret_scan: tdd_core::SecretScanMode::Error, unicode_policy: tdd_core::UnicodePolicy::default(), max_blob_bytes: 1024 * 1024, large_blob_policy: tdd_core::LargeBlobPolicy::Reject, readonly_paths: Vec::new(), manifest_policy: tdd_core::ManifestPolicy::default(), },
+                None,
+            )) as Box<dyn Agent>,
+            Box::new(agent_for_role(
+                Role::Implementor,
+                Arc::new(scripted(Role::Implementor, 2)),
+                root,
+                false,
+                false,
+                tdd_agents::ScanPolicy { secret_scan: tdd_core::SecretScanMode::Error, unicode_policy: tdd_core::UnicodePolicy::default(), max_blob_bytes: 1024 * 1024, large_blob_policy: tdd_core::LargeBlobPolicy::Reject, readonly_paths: Vec::new(), manifest_policy: tdd_core::ManifestPolicy::default(), },
+                None,
+            )),
+            Box::new(agent_for_role(
+                Role::Refactorer,
+                Arc::new(scripted(Role::Refactorer, 2)),
+                root,
+                false,
+                false,
+                tdd_agents::ScanPolicy { secret_scan: tdd_core::SecretScanMode::Error, unicode_policy: tdd_core::UnicodePolicy::default(), max_blob_bytes: 1024 * 1024, large_blob_policy: tdd_core::LargeBlobPolicy::Reject, readonly_paths: Vec::new(), manifest_policy: tdd_core::ManifestPolicy::default(), },
+                None,
+            )),
+        ],
+        Box::new(GitVcs::new(root, CommitAuthor::default())),
+        root.to_path_buf(),
+        String::new(),
+        3,
+    )
+    .unwrap()
+    .with_runner(Box::new(CyclingRunner::new()));
+
+    schedule::run_loop(root, &mut orchestrator, "deadbeef".to_string(), &spec, 1, true).await.unwrap();
+
+    let after_second_tick = schedule::load_state(root).unwrap();
+    assert!(
+        after_second_tick.last_scheduled_run.unwrap() > after_first_tick.last_scheduled_run.unwrap(),
+        "the second tick should advance the state file past the first tick's timestamp, not re-run it"
+    );
+    let run_after_second = tdd_cli::run_log::load(root).unwrap().unwrap();
+    assert_eq!(run_after_second.steps_executed, 3);
+    assert!(root.join("tests/tick1_test.rs").exists());
+    assert!(root.join("tests/tick2_test.rs").exists());
+}
@@ -0,0 +1,155 @@
+//! Exercises `tdd-cli undo` and `tdd-cli redo` against a real workspace
+//! built the same way `embedded_run` sets one up: two scripted steps,
+//! then undo both, redo one, and check the commit graph and archive.
+
+#[path = "../examples/embedded_run.rs"]
+mod embedded_run;
+
+use tdd_core::{RunnerOutcome, Vcs};
+use tdd_exec::{CargoRunner, CommitAuthor, GitVcs};
+use tdd_test_support::TestWorkspace;
+
+#[tokio::test]
+async fn undoing_two_steps_and_redoing_one_leaves_the_expected_state() {
+    let workspace = tempfile::tempdir().unwrap();
+    let root = workspace.path();
+    let (status_before, commits) = embedded_run::run_embedded_example(root).await.unwrap();
+    assert_eq!(status_before.step_count, 2);
+
+    let undo_implementor = tdd_cli::undo::undo(root).unwrap();
+    assert_eq!(undo_implementor.step, 1);
+    assert_eq!(undo_implementor.commit_message, commits[1]);
+    assert!(root.join(".tdd/state/undone/step-001-implementor/record.json").exists());
+    assert!(root.join(".tdd/state/undone/step-001-implementor/plan.md").exists());
+
+    let undo_tester = tdd_cli::undo::undo(root).unwrap();
+    assert_eq!(undo_tester.step, 0);
+    assert!(root.join(".tdd/state/undone/step-000-tester").exists());
+
+    let vcs = GitVcs::new(root, CommitAuthor::default());
+    let state = vcs.read_state().unwrap();
+    assert!(state.last_commit_message.is_empty(), "both step commits should be reverted");
+    assert!(!root.join("tests/add_test.rs").exists(), "the tester's file should be gone after undo");
+
+    let runner = CargoRunner::new(root);
+    let redone = tdd_cli::undo::redo(root, &runner).unwrap();
+    assert_eq!(redone.step, 0);
+    assert!(!root.join(".tdd/state/undone/step-000-tester").exists(), "redo should consume its archive entry");
+    assert!(root.join("tests/add_test.rs").exists(), "redo should re-apply the archived file");
+
+    let state_after_redo = vcs.read_state().unwrap();
+    assert!(state_after_redo.last_commit_message.starts_with("test: step 0"));
+    assert!(state_after_redo.last_commit_message.contains("(redone)"));
+
+    let cleared = tdd_cli::undo::clear_redo_stack(root).unwrap();
+    assert!(cleared, "the implementor undo should still be on the stack");
+    assert!(!root.join(".tdd/state/undone/step-001-implementor").exists());
+    assert!(!tdd_cli::undo::clear_redo_stack(root).unwrap(), "a second clear should find nothing left");
+}
+
+#[test]
+fn redo_puts_the_archive_entry_back_when_ci_fails() {
+    let workspace = TestWorkspace::init().unwrap();
+    let root = workspace.root();
+
+    let vcs = GitVcs::new(root, CommitAuthor::default());
+    vcs.stage_all().unwrap();
+    vcs.commit("chore: scaffold").unwrap();
+
+    std::fs::write(root.join("src/lib.rs"), "pub fn add(a: i32, b: i32) -> i32 { a + b }\n").unwrap();
+    vcs.stage_all().unwrap();
+    vcs.commit("feat: step 0").unwrap();
+
+    tdd_cli::undo::undo(root).unwrap();
+
+    struct AlwaysFailingRunner;
+    impl tdd_core::Runner for AlwaysFailingRunner {
+        fn fmt_check(&self) -> anyhow::Result<RunnerOutcome> {
+            Ok(RunnerOutcome { ok: true, ..Default::default() })
+        }
+        fn fmt(&self) -> anyhow::Result<RunnerOutcome> {
+            Ok(RunnerOutcome { ok: true, ..Default::default() })
+        }
+        fn check(&self) -> anyhow::Result<RunnerOutcome> {
+            Ok(RunnerOutcome { ok: false, stderr: "clippy failed".to_string().into(), ..Default::default() })
+        }
+        fn test(&self) -> anyhow::Result<RunnerOutcome> {
+            Ok(RunnerOutcome { ok: true, ..Default::default() })
+        }
+    }
+
+    let error = tdd_cli::undo::redo(root, &AlwaysFailingRunner).unwrap_err();
+    assert!(error.to_string().contains("failed to re-verify"));
+    assert!(root.join(".tdd/state/undone/step-000-implementor").exists(), "the archive entry should not be consumed");
+}
+
+#[test]
+fn undo_then_a_fresh_rerun_then_redo_leaves_two_distinct_run_id_artifacts_for_the_same_step() {
+    let workspace = TestWorkspace::init().unwrap();
+    let root = workspace.root();
+
+    let vcs = GitVcs::new(root, CommitAuthor::default());
+    vcs.stage_all().unwrap();
+    vcs.commit("chore: scaffold").unwrap();
+
+    let plan_dir = root.join(".tdd/plan");
+    std::fs::create_dir_all(&plan_dir).unwrap();
+    std::fs::write(plan_dir.join(format!("{}.md", tdd_core::artifacts::format_stem(7, 0, "implementor"))), "the rejected plan").unwrap();
+    std::fs::write(root.join("src/lib.rs"), "pub fn add(a: i32, b: i32) -> i32 { a + b }\n").unwrap();
+    vcs.stage_all().unwrap();
+    vcs.commit("feat: step 7").unwrap();
+
+    let undone = tdd_cli::undo::undo(root).unwrap();
+    assert_eq!(undone.step, 7);
+    assert_eq!(undone.plan_filename, Some(format!("{}.md", tdd_core::artifacts::format_stem(7, 0, "implementor"))));
+    assert!(
+        !plan_dir.join(format!("{}.md", tdd_core::artifacts::format_stem(7, 0, "implementor"))).exists(),
+        "the rejected run should have been archived out of .tdd/plan"
+    );
+
+    // A fresh re-run of step 7 gets a new run-id, so its plan can't
+    // collide with the archived one even though both are for step 7.
+    // (undo's git reset cleaned up the now-empty .tdd/plan directory, so
+    // it needs recreating, same as `LoopOrchestrator::write_plan` does.)
+    std::fs::create_dir_all(&plan_dir).unwrap();
+    std::fs::write(plan_dir.join(format!("{}.md", tdd_core::artifacts::format_stem(7, 1, "implementor"))), "the kept plan").unwrap();
+    std::fs::write(root.join("src/lib.rs"), "pub fn add(a: i32, b: i32) -> i32 { a.wrapping_add(b) }\n").unwrap();
+    vcs.stage_all().unwrap();
+    vcs.commit("feat: step 7").unwrap();
+
+    struct AlwaysPassingRunner;
+    impl tdd_core::Runner for AlwaysPassingRunner {
+        fn fmt_check(&self) -> anyhow::Result<RunnerOutcome> {
+            Ok(RunnerOutcome { ok: true, ..Default::default() })
+        }
+        fn fmt(&self) -> anyhow::Result<RunnerOutcome> {
+            Ok(RunnerOutcome { ok: true, ..Default::default() })
+        }
+        fn check(&self) -> anyhow::Result<RunnerOutcome> {
+            Ok(RunnerOutcome { ok: true, ..Default::default() })
+        }
+        fn test(&self) -> anyhow::Result<RunnerOutcome> {
+            Ok(RunnerOutcome { ok: true, ..Default::default() })
+        }
+    }
+
+    tdd_cli::undo::redo(root, &AlwaysPassingRunner).unwrap();
+
+    let names: Vec<String> = std::fs::read_dir(&plan_dir)
+        .unwrap()
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .collect();
+    let borrowed: Vec<&str> = names.iter().map(String::as_str).collect();
+
+    assert_eq!(
+        tdd_core::artifacts::resolve_step(borrowed.clone(), ".md", 7, None),
+        Some(format!("{}.md", tdd_core::artifacts::format_stem(7, 1, "implementor")).as_str()),
+        "the kept (re-run) plan should resolve as the latest by default"
+    );
+    assert_eq!(
+        tdd_core::artifacts::resolve_step(borrowed, ".md", 7, Some(0)),
+        Some(format!("{}.md", tdd_core::artifacts::format_stem(7, 0, "implementor")).as_str()),
+        "the rejected plan should still be reachable by its exact run-id"
+    );
+}
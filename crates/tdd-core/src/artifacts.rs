@@ -0,0 +1,179 @@
+//! Formats and parses the `step-{n}-r{run_id}-{slug}` filenames shared by
+//! `.tdd/plan` and `.tdd/logs`, and picks the right one when a step has
+//! been executed more than once (e.g. undone, then re-run). Each
+//! execution of a step gets its own monotonic run-id — see
+//! `tdd-cli`'s `run_sequence` module, which persists the counter this
+//! crate's I/O-free convention keeps out of here — so a regenerated
+//! step never collides with an archived-then-restored one on the same
+//! path. A filename with no `r<id>-` segment is legacy (from before
+//! this scheme existed) and resolves to run-id 0.
+
+use crate::Role;
+
+/// A parsed `step-{n}-r{run_id}-{slug}` (or legacy `step-{n}-{slug}`)
+/// filename. `role` is `None` when `slug` is well-formed but doesn't map
+/// to a built-in [`Role`], in which case `slug` still holds the full role
+/// name so step numbering can advance correctly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepArtifactName {
+    pub step: u32,
+    pub run_id: u32,
+    pub role: Option<Role>,
+    pub slug: String,
+}
+
+/// Formats the filename stem (no extension) for `step`'s `run_id`th
+/// execution by `slug`. The step number is zero-padded so a directory
+/// listing sorts chronologically.
+pub fn format_stem(step: u32, run_id: u32, slug: &str) -> String {
+    format!("step-{step:05}-r{run_id}-{slug}")
+}
+
+/// Parses a `step-{n}-r{run_id}-{slug}{extension}` or legacy
+/// `step-{n}-{slug}{extension}` filename. Splits on the *first* hyphen
+/// after the step number only when what follows looks like an `r<id>-`
+/// run-id segment, so a legacy custom-role slug that happens to start
+/// the same way (e.g. `r2-pair`) is the one narrow ambiguity this
+/// doesn't resolve perfectly — identical to the pre-existing trade-off
+/// around hyphenated role slugs.
+pub fn parse_name(name: &str, extension: &str) -> Option<StepArtifactName> {
+    let stem = name.strip_prefix("step-")?.strip_suffix(extension)?;
+    let (step, rest) = stem.split_once('-')?;
+    if rest.is_empty() {
+        return None;
+    }
+    let step = step.parse().ok()?;
+
+    let (run_id, slug) = match rest.split_once('-') {
+        Some((run_part, slug)) if !slug.is_empty() && is_run_id_segment(run_part) => (run_part[1..].parse().ok()?, slug),
+        _ => (0, rest),
+    };
+
+    let role = Role::from_slug(slug);
+    Some(StepArtifactName {
+        step,
+        run_id,
+        role,
+        slug: slug.to_string(),
+    })
+}
+
+fn is_run_id_segment(segment: &str) -> bool {
+    segment.len() > 1 && segment.starts_with('r') && segment[1..].chars().all(|c| c.is_ascii_digit())
+}
+
+/// Resolves which of `names` (bare filenames, not paths) is "the"
+/// artifact for `step`: the highest run-id by default, or the exact
+/// `run_id` requested. `None` if nothing matches.
+pub fn resolve_step<'a>(names: impl IntoIterator<Item = &'a str>, extension: &str, step: u32, run_id: Option<u32>) -> Option<&'a str> {
+    let mut candidates = names.into_iter().filter(|name| parse_name(name, extension).is_some_and(|parsed| parsed.step == step));
+    match run_id {
+        Some(target) => candidates.find(|name| parse_name(name, extension).is_some_and(|parsed| parsed.run_id == target)),
+        None => candidates.max_by_key(|name| parse_name(name, extension).unwrap().run_id),
+    }
+}
+
+/// A warning for an artifact filename whose role slug doesn't map to a
+/// built-in [`Role`]. Collected rather than printed so callers (e.g.
+/// `status`) decide how and whether to surface it.
+pub fn unrecognized_role_warning(parsed: &StepArtifactName) -> Option<String> {
+    if parsed.role.is_some() {
+        return None;
+    }
+    Some(format!("step {} was taken by an unrecognized role \"{}\"", parsed.step, parsed.slug))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_stem_embeds_the_zero_padded_step_run_id_and_slug() {
+        assert_eq!(format_stem(7, 3, "implementor"), "step-00007-r3-implementor");
+    }
+
+    #[test]
+    fn a_formatted_stem_round_trips_through_parsing() {
+        let name = format!("{}.md", format_stem(7, 3, "implementor"));
+        let parsed = parse_name(&name, ".md").unwrap();
+        assert_eq!(parsed, StepArtifactName {
+            step: 7,
+            run_id: 3,
+            role: Some(Role::Implementor),
+            slug: "implementor".to_string(),
+        });
+    }
+
+    #[test]
+    fn legacy_filenames_without_a_run_id_resolve_to_run_id_zero() {
+        assert_eq!(
+            parse_name("step-1-tester.md", ".md"),
+            Some(StepArtifactName {
+                step: 1,
+                run_id: 0,
+                role: Some(Role::Tester),
+                slug: "tester".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn a_legacy_hyphenated_custom_role_slug_still_advances_step_numbering() {
+        let parsed = parse_name("step-004-code-reviewer.md", ".md").unwrap();
+        assert_eq!(parsed.step, 4);
+        assert_eq!(parsed.run_id, 0);
+        assert_eq!(parsed.role, None);
+        assert_eq!(parsed.slug, "code-reviewer");
+    }
+
+    #[test]
+    fn malformed_filenames_are_rejected() {
+        assert_eq!(parse_name("step-1-tester.json", ".md"), None);
+        assert_eq!(parse_name("notastep-1-tester.md", ".md"), None);
+        assert_eq!(parse_name("step-tester.md", ".md"), None);
+        assert_eq!(parse_name("step-1-.md", ".md"), None);
+        assert_eq!(parse_name("step-1.md", ".md"), None);
+    }
+
+    #[test]
+    fn resolve_step_picks_the_newer_by_default() {
+        let names = ["step-00007-r0-implementor.md", "step-00007-r1-implementor.md"];
+        assert_eq!(resolve_step(names, ".md", 7, None), Some("step-00007-r1-implementor.md"));
+    }
+
+    #[test]
+    fn resolve_step_picks_the_requested_run_id() {
+        let names = ["step-00007-r0-implementor.md", "step-00007-r1-implementor.md"];
+        assert_eq!(resolve_step(names, ".md", 7, Some(0)), Some("step-00007-r0-implementor.md"));
+    }
+
+    #[test]
+    fn resolve_step_ignores_other_steps() {
+        let names = ["step-00006-r0-tester.md", "step-00007-r0-implementor.md"];
+        assert_eq!(resolve_step(names, ".md", 7, None), Some("step-00007-r0-implementor.md"));
+    }
+
+    #[test]
+    fn resolve_step_resolves_legacy_fixtures_with_no_run_id_segment() {
+        let names = ["step-1-tester.md", "step-2-implementor.md"];
+        assert_eq!(resolve_step(names, ".md", 1, None), Some("step-1-tester.md"));
+    }
+
+    #[test]
+    fn resolve_step_returns_none_when_nothing_matches() {
+        let names = ["step-1-tester.md"];
+        assert_eq!(resolve_step(names, ".md", 9, None), None);
+    }
+
+    #[test]
+    fn unrecognized_role_warning_is_none_for_built_in_roles() {
+        let parsed = parse_name("step-1-tester.md", ".md").unwrap();
+        assert_eq!(unrecognized_role_warning(&parsed), None);
+    }
+
+    #[test]
+    fn unrecognized_role_warning_names_the_step_and_slug() {
+        let parsed = parse_name("step-4-code-reviewer.md", ".md").unwrap();
+        assert_eq!(unrecognized_role_warning(&parsed), Some("step 4 was taken by an unrecognized role \"code-reviewer\"".to_string()));
+    }
+}
@@ -0,0 +1,52 @@
+//! Flags content large enough to bloat git history before it's written —
+//! an agent once generated a 9 MB JSON fixture under `tests/fixtures/`
+//! and it sailed straight into history; every clone pays for it now.
+//! Severity is controlled by [`LargeBlobPolicy`]. Deliberately narrow: it
+//! only measures byte length, leaving what to do with an oversized file
+//! to the caller.
+
+use serde::{Deserialize, Serialize};
+
+/// The default `workspace.max_blob_kb`, in bytes, used when an agent is
+/// built directly rather than from `tdd.yaml`.
+pub const DEFAULT_MAX_BLOB_BYTES: u64 = 1024 * 1024;
+
+/// How an oversized file should be treated, set by `workspace.large_files`
+/// in `tdd.yaml`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LargeBlobPolicy {
+    /// Reject the step as retryable; the file is never written.
+    Reject,
+    /// Write the file, but flag the hit loudly in the commit body and log.
+    Warn,
+}
+
+/// Returns `content`'s size in bytes if it exceeds `max_bytes`, for the
+/// caller to attribute to a path.
+pub fn check(content: &str, max_bytes: u64) -> Option<u64> {
+    let size = content.len() as u64;
+    (size > max_bytes).then_some(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_under_the_limit_is_not_flagged() {
+        assert_eq!(check("small", 1024), None);
+    }
+
+    #[test]
+    fn content_over_the_limit_is_flagged_with_its_size() {
+        let content = "x".repeat(2048);
+        assert_eq!(check(&content, 1024), Some(2048));
+    }
+
+    #[test]
+    fn content_exactly_at_the_limit_is_not_flagged() {
+        let content = "x".repeat(1024);
+        assert_eq!(check(&content, 1024), None);
+    }
+}
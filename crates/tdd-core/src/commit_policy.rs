@@ -0,0 +1,118 @@
+//! Builds the conventional-commit messages the orchestrator persists after
+//! every successful step, following the format in the kata constitution.
+
+use crate::model::Role;
+
+/// The maximum length of a commit summary line (`"type: subject"`),
+/// matching the convention most git hosting CI enforces.
+pub const MAX_SUMMARY_LINE_LEN: usize = 72;
+
+/// Prepends `prefix` (e.g. a ticket reference like `KATA-123`) to
+/// `summary`, right after the conventional-commit type, and renders the
+/// full `"type: subject"` line. A `prefix` already present in `summary`
+/// (case-insensitive) is left alone rather than duplicated. When the
+/// combined line would exceed [`MAX_SUMMARY_LINE_LEN`], `summary` — never
+/// `prefix` — is truncated to fit.
+pub fn format_summary_line(commit_type: &str, summary: &str, prefix: Option<&str>) -> String {
+    let Some(prefix) = prefix.filter(|prefix| !prefix.is_empty()) else {
+        return format!("{commit_type}: {summary}");
+    };
+    if summary.to_lowercase().contains(&prefix.to_lowercase()) {
+        return format!("{commit_type}: {summary}");
+    }
+
+    let header = format!("{commit_type}: {prefix} ");
+    let budget = MAX_SUMMARY_LINE_LEN.saturating_sub(header.chars().count());
+    let summary: String = summary.chars().take(budget).collect();
+    format!("{header}{summary}")
+}
+
+/// Labels the `index`-th (0-based) of a step's ordered sub-commits (see
+/// [`crate::model::SubCommit`]) as `"{step}a"`, `"{step}b"`, ... so a step
+/// log or run summary can point at one commit among several without
+/// inventing a new step index for it.
+pub fn sub_commit_id(step: u32, index: usize) -> String {
+    let letter = (b'a' + (index % 26) as u8) as char;
+    format!("{step}{letter}")
+}
+
+/// Free-form context used to render a commit body.
+pub struct CommitContext<'a> {
+    pub role: Role,
+    pub step: u32,
+    pub kata_goal: &'a str,
+    pub summary: &'a str,
+    pub rationale: &'a [&'a str],
+    pub diff_summary: &'a [String],
+    pub verification: &'a str,
+}
+
+/// Renders a full conventional-commit message with the sections every
+/// downstream agent relies on to pick up context from the previous step.
+pub fn build_commit_message(ctx: &CommitContext<'_>) -> String {
+    let mut message = format!("{}: {}\n\n", ctx.role.commit_type(), ctx.summary);
+
+    message.push_str("Context:\n");
+    message.push_str(&format!("- Role: {}\n", ctx.role));
+    message.push_str(&format!("- Step: {}\n", ctx.step));
+    message.push_str(&format!("- Kata goal: {}\n\n", ctx.kata_goal));
+
+    message.push_str("Rationale:\n");
+    for line in ctx.rationale {
+        message.push_str(&format!("- {line}\n"));
+    }
+    message.push('\n');
+
+    message.push_str("Diff summary:\n");
+    for line in ctx.diff_summary {
+        message.push_str(&format!("- {line}\n"));
+    }
+    message.push('\n');
+
+    message.push_str("Verification:\n");
+    message.push_str(&format!("- {}\n", ctx.verification));
+
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_prefix_leaves_the_summary_untouched() {
+        assert_eq!(format_summary_line("test", "add failing case", None), "test: add failing case");
+    }
+
+    #[test]
+    fn the_prefix_lands_right_after_the_type() {
+        assert_eq!(
+            format_summary_line("test", "add failing case for empty input", Some("KATA-123")),
+            "test: KATA-123 add failing case for empty input"
+        );
+    }
+
+    #[test]
+    fn a_prefix_already_present_case_insensitively_is_not_duplicated() {
+        assert_eq!(
+            format_summary_line("test", "kata-123: add failing case", Some("KATA-123")),
+            "test: kata-123: add failing case"
+        );
+    }
+
+    #[test]
+    fn sub_commit_ids_letter_in_order_starting_from_a() {
+        assert_eq!(sub_commit_id(4, 0), "4a");
+        assert_eq!(sub_commit_id(4, 1), "4b");
+        assert_eq!(sub_commit_id(12, 2), "12c");
+    }
+
+    #[test]
+    fn an_oversized_summary_is_truncated_but_the_prefix_never_is() {
+        let long_summary = "a".repeat(100);
+        let message = format_summary_line("test", &long_summary, Some("KATA-123"));
+
+        assert!(message.starts_with("test: KATA-123 "));
+        assert_eq!(message.chars().count(), MAX_SUMMARY_LINE_LEN);
+    }
+}
@@ -0,0 +1,379 @@
+//! Detects pathological Unicode in agent-generated content before it's
+//! written to disk: bidirectional override characters (trojan-source
+//! style tricks, e.g. U+202E hiding the real order of a string literal)
+//! and zero-width characters that render invisibly but change what a
+//! diff or `rustfmt` actually sees. A model once emitted both inside a
+//! generated test; rustfmt passed and the code compiled, but the
+//! rendered diff was misleading and a security linter flagged the commit
+//! after the fact. A conservative whole-file scan (rather than trying to
+//! parse string/comment boundaries) is intentional: these characters have
+//! no legitimate reason to appear in source at all.
+//!
+//! Also flags identifiers that mix Unicode normalization forms: a name
+//! like `café` can be written with a precomposed `é` (NFC) or an `e`
+//! followed by a combining acute accent (NFD), and two occurrences that
+//! render identically can disagree byte-for-byte if only one of them was
+//! normalized — the same lookalike-name risk bidi overrides pose at the
+//! character level, just one level up at the level of a whole name.
+//!
+//! Severity is controlled per character class by [`UnicodePolicy`], the
+//! same reject/strip/warn shape the secret scanner doesn't need (a secret
+//! can't be redacted in place) but this check does, since stripping the
+//! offending characters is often the right fix. Bidi controls default to
+//! rejecting the step, since they can hide malicious content behind
+//! rendered text that doesn't match the real byte order; zero-width
+//! characters and mixed-normalization identifiers are often accidental
+//! (pasted from a web page, a smart-formatting tool, a non-US keyboard
+//! layout) and default to a warning instead.
+
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+use unicode_xid::UnicodeXID;
+
+/// How a Unicode hygiene hit should be treated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnicodeSeverity {
+    /// Reject the step as retryable; the file is never written.
+    Reject,
+    /// Remove the offending characters and write the stripped content.
+    Strip,
+    /// Write the file unchanged, but flag the hit loudly.
+    Warn,
+}
+
+/// `workspace.unicode_policy` in `tdd.yaml`: severity for each flagged
+/// character class, since bidi controls and zero-width characters pose
+/// different risks and warrant different defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnicodePolicy {
+    #[serde(default = "default_bidi_severity")]
+    pub bidi: UnicodeSeverity,
+    #[serde(default = "default_zero_width_severity")]
+    pub zero_width: UnicodeSeverity,
+    #[serde(default = "default_mixed_normalization_severity")]
+    pub mixed_normalization: UnicodeSeverity,
+}
+
+impl UnicodePolicy {
+    /// The configured severity for the class a given finding belongs to.
+    pub fn severity_for(&self, class: UnicodeCharClass) -> UnicodeSeverity {
+        match class {
+            UnicodeCharClass::Bidi => self.bidi,
+            UnicodeCharClass::ZeroWidth => self.zero_width,
+            UnicodeCharClass::MixedNormalization => self.mixed_normalization,
+        }
+    }
+}
+
+impl Default for UnicodePolicy {
+    fn default() -> Self {
+        Self { bidi: default_bidi_severity(), zero_width: default_zero_width_severity(), mixed_normalization: default_mixed_normalization_severity() }
+    }
+}
+
+fn default_bidi_severity() -> UnicodeSeverity {
+    UnicodeSeverity::Reject
+}
+
+fn default_zero_width_severity() -> UnicodeSeverity {
+    UnicodeSeverity::Warn
+}
+
+fn default_mixed_normalization_severity() -> UnicodeSeverity {
+    UnicodeSeverity::Warn
+}
+
+/// Which hygiene class a flagged character (or, for
+/// [`UnicodeCharClass::MixedNormalization`], a flagged identifier)
+/// belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnicodeCharClass {
+    Bidi,
+    ZeroWidth,
+    MixedNormalization,
+}
+
+impl std::fmt::Display for UnicodeCharClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            UnicodeCharClass::Bidi => "bidi control",
+            UnicodeCharClass::ZeroWidth => "zero-width",
+            UnicodeCharClass::MixedNormalization => "mixed normalization",
+        })
+    }
+}
+
+/// Bidirectional override/embedding controls: these can make a string
+/// literal render in an order that doesn't match its actual byte
+/// sequence, hiding malicious content from a reviewer scanning a diff.
+const BIDI_CONTROLS: &[char] = &[
+    '\u{202A}', // LEFT-TO-RIGHT EMBEDDING
+    '\u{202B}', // RIGHT-TO-LEFT EMBEDDING
+    '\u{202C}', // POP DIRECTIONAL FORMATTING
+    '\u{202D}', // LEFT-TO-RIGHT OVERRIDE
+    '\u{202E}', // RIGHT-TO-LEFT OVERRIDE
+    '\u{2066}', // LEFT-TO-RIGHT ISOLATE
+    '\u{2067}', // RIGHT-TO-LEFT ISOLATE
+    '\u{2068}', // FIRST STRONG ISOLATE
+    '\u{2069}', // POP DIRECTIONAL ISOLATE
+];
+
+/// Zero-width characters: invisible in a rendered diff, but present in
+/// the bytes a compiler or `rustfmt` sees.
+const ZERO_WIDTH: &[char] = &[
+    '\u{200B}', // ZERO WIDTH SPACE
+    '\u{200C}', // ZERO WIDTH NON-JOINER
+    '\u{200D}', // ZERO WIDTH JOINER
+    '\u{FEFF}', // ZERO WIDTH NO-BREAK SPACE (BOM)
+];
+
+/// Classifies a single character, if it's one this module flags.
+pub fn classify(c: char) -> Option<UnicodeCharClass> {
+    if BIDI_CONTROLS.contains(&c) {
+        Some(UnicodeCharClass::Bidi)
+    } else if ZERO_WIDTH.contains(&c) {
+        Some(UnicodeCharClass::ZeroWidth)
+    } else {
+        None
+    }
+}
+
+/// One flagged character, with enough detail for a retryable error
+/// message to name the exact code point and byte offset. `identifier` is
+/// set only for a [`UnicodeCharClass::MixedNormalization`] finding: there
+/// isn't one offending character to name there, so `code_point` instead
+/// names the first non-ASCII character in the flagged name and
+/// `identifier` carries the name itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnicodeFinding {
+    pub char_class: UnicodeCharClass,
+    pub code_point: u32,
+    pub byte_offset: usize,
+    pub identifier: Option<String>,
+}
+
+impl std::fmt::Display for UnicodeFinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.identifier {
+            Some(identifier) => write!(f, "identifier {identifier:?} ({}) at byte offset {}", self.char_class, self.byte_offset),
+            None => write!(f, "U+{:04X} ({}) at byte offset {}", self.code_point, self.char_class, self.byte_offset),
+        }
+    }
+}
+
+/// Scans `content` for bidi control and zero-width characters, in byte
+/// order.
+pub fn scan(content: &str) -> Vec<UnicodeFinding> {
+    content
+        .char_indices()
+        .filter_map(|(byte_offset, c)| {
+            classify(c).map(|char_class| UnicodeFinding {
+                char_class,
+                code_point: c as u32,
+                byte_offset,
+                identifier: None,
+            })
+        })
+        .collect()
+}
+
+/// Whether `c` can appear in an identifier-like run, for the purposes of
+/// [`scan_identifiers`]/[`normalize_identifiers`]. Uses the same
+/// `XID_Start`/`XID_Continue` grammar `rustc` itself lexes identifiers
+/// with, which — unlike a plain alphanumeric check — also covers the
+/// combining marks an NFD-decomposed accented letter is made of, so a
+/// run isn't artificially split in the middle of the very character this
+/// check exists to look at.
+fn is_identifier_char(c: char) -> bool {
+    c.is_xid_continue() || c == '_'
+}
+
+/// Scans `content` for identifier-like runs whose own text matches
+/// neither its fully NFC-normalized nor its fully NFD-normalized form —
+/// i.e. some of its characters are already composed while others in the
+/// same name are still decomposed. An identifier consistently in one
+/// normalization form or the other isn't flagged, even if that form is
+/// NFD; only a name whose normalization forms are themselves mixed is.
+/// ASCII-only identifiers are always equal to both forms and are never
+/// flagged.
+pub fn scan_identifiers(content: &str) -> Vec<UnicodeFinding> {
+    let mut findings = Vec::new();
+    let mut current = String::new();
+    let mut start = 0;
+
+    for (byte_offset, c) in content.char_indices() {
+        if is_identifier_char(c) {
+            if current.is_empty() {
+                start = byte_offset;
+            }
+            current.push(c);
+        } else if let Some(finding) = mixed_normalization_finding(&current, start) {
+            findings.push(finding);
+            current.clear();
+        } else {
+            current.clear();
+        }
+    }
+    if let Some(finding) = mixed_normalization_finding(&current, start) {
+        findings.push(finding);
+    }
+
+    findings
+}
+
+fn mixed_normalization_finding(identifier: &str, byte_offset: usize) -> Option<UnicodeFinding> {
+    if identifier.is_empty() || identifier.is_ascii() {
+        return None;
+    }
+    let nfc: String = identifier.nfc().collect();
+    let nfd: String = identifier.nfd().collect();
+    if identifier == nfc || identifier == nfd {
+        return None;
+    }
+    let code_point = identifier.chars().find(|c| !c.is_ascii())?.into();
+    Some(UnicodeFinding { char_class: UnicodeCharClass::MixedNormalization, code_point, byte_offset, identifier: Some(identifier.to_string()) })
+}
+
+/// Rewrites every identifier [`scan_identifiers`] would flag into its
+/// NFC-normalized form, leaving everything else (including identifiers
+/// already consistent in NFC or NFD) byte-for-byte unchanged — the
+/// identifier-level equivalent of [`strip`] for the character-level
+/// checks.
+pub fn normalize_identifiers(content: &str) -> String {
+    let mut result = String::with_capacity(content.len());
+    let mut current = String::new();
+
+    for c in content.chars() {
+        if is_identifier_char(c) {
+            current.push(c);
+        } else {
+            push_normalized_identifier(&current, &mut result);
+            current.clear();
+            result.push(c);
+        }
+    }
+    push_normalized_identifier(&current, &mut result);
+
+    result
+}
+
+fn push_normalized_identifier(identifier: &str, result: &mut String) {
+    if mixed_normalization_finding(identifier, 0).is_some() {
+        result.extend(identifier.nfc());
+    } else {
+        result.push_str(identifier);
+    }
+}
+
+/// Removes every character whose class satisfies `should_strip`, leaving
+/// everything else byte-for-byte unchanged.
+pub fn strip_matching(content: &str, mut should_strip: impl FnMut(UnicodeCharClass) -> bool) -> String {
+    content.chars().filter(|c| !matches!(classify(*c), Some(class) if should_strip(class))).collect()
+}
+
+/// Removes every flagged character, of either class, from `content`.
+pub fn strip(content: &str) -> String {
+    strip_matching(content, |_| true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_a_bidi_override() {
+        let findings = scan("let s = \"safe\u{202E}evil\";");
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].char_class, UnicodeCharClass::Bidi);
+        assert_eq!(findings[0].code_point, 0x202E);
+    }
+
+    #[test]
+    fn detects_a_zero_width_joiner() {
+        let findings = scan("a\u{200D}b");
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].char_class, UnicodeCharClass::ZeroWidth);
+        assert_eq!(findings[0].code_point, 0x200D);
+    }
+
+    #[test]
+    fn byte_offsets_are_reported_for_multiple_hits() {
+        let findings = scan("ab\u{200B}cd\u{202E}ef");
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].byte_offset, 2);
+        assert_eq!(findings[1].byte_offset, "ab\u{200B}cd".len());
+    }
+
+    #[test]
+    fn strip_removes_only_the_flagged_characters() {
+        let stripped = strip("safe\u{202E}evil\u{200B} text");
+        assert_eq!(stripped, "safeevil text");
+    }
+
+    #[test]
+    fn strip_matching_removes_only_the_requested_class() {
+        let stripped = strip_matching("safe\u{202E}evil\u{200B} text", |class| class == UnicodeCharClass::ZeroWidth);
+        assert_eq!(stripped, "safe\u{202E}evil text");
+    }
+
+    #[test]
+    fn clean_content_passes_untouched() {
+        let content = "fn add(a: i32, b: i32) -> i32 { a + b }";
+        assert!(scan(content).is_empty());
+        assert_eq!(strip(content), content);
+    }
+
+    #[test]
+    fn default_policy_rejects_bidi_but_only_warns_on_zero_width_and_mixed_normalization() {
+        let policy = UnicodePolicy::default();
+        assert_eq!(policy.severity_for(UnicodeCharClass::Bidi), UnicodeSeverity::Reject);
+        assert_eq!(policy.severity_for(UnicodeCharClass::ZeroWidth), UnicodeSeverity::Warn);
+        assert_eq!(policy.severity_for(UnicodeCharClass::MixedNormalization), UnicodeSeverity::Warn);
+    }
+
+    /// `cafe` + combining acute accent (NFD) followed by an underscore and
+    /// a precomposed `é` (NFC) — one identifier whose own text is neither
+    /// fully NFC nor fully NFD.
+    fn mixed_normalization_identifier() -> &'static str {
+        "cafe\u{0301}_caf\u{00E9}"
+    }
+
+    #[test]
+    fn detects_an_identifier_mixing_normalization_forms() {
+        let identifier = mixed_normalization_identifier();
+        let findings = scan_identifiers(&format!("let {identifier} = 1;"));
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].char_class, UnicodeCharClass::MixedNormalization);
+        assert_eq!(findings[0].identifier.as_deref(), Some(identifier));
+        assert_eq!(findings[0].byte_offset, "let ".len());
+    }
+
+    #[test]
+    fn an_identifier_consistently_in_nfd_is_not_flagged() {
+        let identifier = "cafe\u{0301}_cafe\u{0301}";
+        assert!(scan_identifiers(&format!("let {identifier} = 1;")).is_empty());
+    }
+
+    #[test]
+    fn an_identifier_consistently_in_nfc_is_not_flagged() {
+        let identifier = "caf\u{00E9}_caf\u{00E9}";
+        assert!(scan_identifiers(&format!("let {identifier} = 1;")).is_empty());
+    }
+
+    #[test]
+    fn an_ascii_only_identifier_is_never_flagged() {
+        assert!(scan_identifiers("fn add(a: i32, b: i32) -> i32 { a + b }").is_empty());
+    }
+
+    #[test]
+    fn normalize_identifiers_rewrites_a_mixed_identifier_to_nfc_and_leaves_the_rest_alone() {
+        let identifier = mixed_normalization_identifier();
+        let normalized = normalize_identifiers(&format!("let {identifier} = 1;"));
+
+        let expected: String = identifier.nfc().collect();
+        assert_eq!(normalized, format!("let {expected} = 1;"));
+        assert!(scan_identifiers(&normalized).is_empty());
+    }
+}
@@ -0,0 +1,53 @@
+//! A small humanized-duration formatter, used wherever a timestamp needs
+//! to be shown as a relative age (`"2h ago"`) rather than an absolute
+//! instant. Deliberately built on [`std::time::Duration`] instead of a
+//! calendar library: every caller already has an elapsed duration in
+//! hand, and "how long ago" only needs one unit of precision.
+
+use std::time::Duration;
+
+/// Renders `age` as a single rounded-down unit, e.g. `"just now"`,
+/// `"5m ago"`, `"2h ago"`, `"3d ago"`. Never panics; a zero duration (or
+/// one shorter than a minute) renders as `"just now"`.
+pub fn humanize_age(age: Duration) -> String {
+    let seconds = age.as_secs();
+    if seconds < 60 {
+        return "just now".to_string();
+    }
+    if seconds < 3600 {
+        return format!("{}m ago", seconds / 60);
+    }
+    if seconds < 86_400 {
+        return format!("{}h ago", seconds / 3600);
+    }
+    format!("{}d ago", seconds / 86_400)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anything_under_a_minute_is_just_now() {
+        assert_eq!(humanize_age(Duration::from_secs(0)), "just now");
+        assert_eq!(humanize_age(Duration::from_secs(59)), "just now");
+    }
+
+    #[test]
+    fn minutes_round_down() {
+        assert_eq!(humanize_age(Duration::from_secs(60)), "1m ago");
+        assert_eq!(humanize_age(Duration::from_secs(119)), "1m ago");
+    }
+
+    #[test]
+    fn hours_round_down() {
+        assert_eq!(humanize_age(Duration::from_secs(3600)), "1h ago");
+        assert_eq!(humanize_age(Duration::from_secs(7199)), "1h ago");
+    }
+
+    #[test]
+    fn days_round_down() {
+        assert_eq!(humanize_age(Duration::from_secs(86_400)), "1d ago");
+        assert_eq!(humanize_age(Duration::from_secs(200_000)), "2d ago");
+    }
+}
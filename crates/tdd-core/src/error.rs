@@ -0,0 +1,52 @@
+//! Error types shared by the domain model and orchestrator.
+
+use thiserror::Error;
+
+/// Errors raised while orchestrating a TDD cycle.
+#[derive(Debug, Error)]
+pub enum CoreError {
+    #[error("agent for role {0:?} is not registered")]
+    MissingAgent(crate::model::Role),
+
+    #[error("step {step} exceeded {max_attempts} attempts for role {role:?}")]
+    AttemptsExhausted {
+        role: crate::model::Role,
+        step: u32,
+        max_attempts: u32,
+    },
+
+    #[error("step {step} ({role:?}) was rejected in review: {reason}")]
+    ReviewRejected {
+        role: crate::model::Role,
+        step: u32,
+        reason: String,
+    },
+
+    #[error("step {step} ({role:?}) timed out waiting for a review decision")]
+    ReviewTimedOut {
+        role: crate::model::Role,
+        step: u32,
+    },
+
+    #[error("step {step} ({role:?}) exceeded its {max_secs}s deadline in the {phase_reached} phase")]
+    StepDeadlineExceeded {
+        role: crate::model::Role,
+        step: u32,
+        phase_reached: String,
+        max_secs: u64,
+    },
+
+    #[error("workspace already reached configured max_steps ({max}); completed {completed} steps. Raise workspace.max_steps, archive this kata, or pass --ignore-max-steps to run anyway")]
+    MaxStepsReached { completed: u32, max: u32 },
+
+    #[error("post-processor {name:?} rejected step {step} ({role:?}): {message}")]
+    PostProcessorRejected {
+        role: crate::model::Role,
+        step: u32,
+        name: String,
+        message: String,
+    },
+
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
@@ -0,0 +1,202 @@
+//! A serializable summary of why a step failed, independent of whichever
+//! error type produced it. `RunnerError`-, `VcsError`-, and `LlmError`-style
+//! failures all get flattened into anyhow chains once they cross an `?`
+//! boundary, which loses the structure that JSON summaries and step logs
+//! need; this type is what survives that boundary.
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::RunnerOutcome;
+use crate::secrets::Redactor;
+
+/// Structured detail behind a step failure. The originating error type
+/// (`ExecError`, `LlmError`, ...) remains the canonical error; this is a
+/// lossy, serializable projection of it for callers that only see JSON.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum StepFailureDetail {
+    /// A check/test/fmt run did not pass.
+    CiFailure { stage: String, stderr_tail: String },
+    /// The agent's edit response could not be parsed as a valid edit plan.
+    PlanParse { message: String },
+    /// A Tester's edit plan `use`d the library crate under the wrong name.
+    ImportMismatch { found: String, suggested: String },
+    /// An edit plan, commit message, or note contained a secret-shaped
+    /// token under `workspace.secret_scan: error`.
+    SecretLeak { path: String, excerpt: String },
+    /// A refactor step changed the public API surface the guard protects.
+    ApiSurfaceViolation { description: String },
+    /// The LLM provider call failed.
+    Llm { message: String },
+    /// A git operation failed.
+    Vcs { message: String },
+    /// The step exceeded `workspace.max_step_duration_secs` before it
+    /// could finish, and was rolled back at the named phase boundary.
+    DeadlineExceeded { phase_reached: String, elapsed_secs: u64 },
+    /// A [`crate::post_process::StepPostProcessor`] returned an error, or a
+    /// file it added failed the same readonly/secret checks an edit plan
+    /// goes through.
+    PostProcessorRejected { name: String, message: String },
+    /// A Tester step added one or more `#[test]` functions but none of
+    /// them actually ran in the test stage's output — the suite reported
+    /// green (or failed for an unrelated reason) without ever exercising
+    /// the new test. See [`Self::vacuous_test`].
+    VacuousTest { test_names: Vec<String>, guidance: String },
+    /// The repository's own `pre-commit` or `commit-msg` hook rejected a
+    /// bot commit under `git.hooks: run`. See
+    /// `tdd_exec::ExecError::HookRejected`.
+    HookRejected { hook: String, stderr: String },
+    /// Anything else, carried as a message only.
+    Other { message: String },
+}
+
+/// How many trailing lines of stderr to keep in a [`StepFailureDetail::CiFailure`].
+const STDERR_TAIL_LINES: usize = 20;
+
+impl StepFailureDetail {
+    /// Builds a [`StepFailureDetail::CiFailure`] from a failed [`RunnerOutcome`],
+    /// trimming its stderr to the last few lines most likely to explain why.
+    /// When the stderr itself was too large to keep resident, the tail is
+    /// taken from the in-memory portion only, with a marker noting where
+    /// the rest spilled to.
+    pub fn ci_failure(stage: &str, outcome: &RunnerOutcome) -> Self {
+        let mut stderr_tail = tail_lines(&outcome.stderr, STDERR_TAIL_LINES);
+        if let Some(marker) = outcome.stderr.truncation_marker() {
+            stderr_tail.push_str("\n... ");
+            stderr_tail.push_str(&marker);
+        }
+        StepFailureDetail::CiFailure { stage: stage.to_string(), stderr_tail }
+    }
+
+    /// Builds a [`StepFailureDetail::VacuousTest`] naming the test
+    /// functions that never ran, with a fixed guidance message pointing
+    /// at the usual causes: a feature gate that isn't enabled in the
+    /// test build, a module that isn't declared, or a test file cargo
+    /// doesn't pick up from its current location.
+    pub fn vacuous_test(test_names: Vec<String>) -> Self {
+        StepFailureDetail::VacuousTest {
+            test_names,
+            guidance: "none of these added tests ran; likely causes are a feature gate that isn't enabled for `cargo test`, \
+a test module that isn't declared (missing `mod tests;` or `#[cfg(test)]`), or a test file cargo doesn't discover from its current location"
+                .to_string(),
+        }
+    }
+
+    /// Scrubs resolved LLM credentials out of this detail's free-text
+    /// fields, leaving structured fields (stages, paths, counts) alone.
+    /// Used by callers that build a detail from an error chain that might
+    /// echo a credential back (a provider's error page quoting the
+    /// request it rejected), rather than from output that already passed
+    /// through a [`Redactor`] on its way here.
+    pub fn redact(self, redactor: &Redactor) -> Self {
+        match self {
+            StepFailureDetail::CiFailure { stage, stderr_tail } => StepFailureDetail::CiFailure {
+                stage,
+                stderr_tail: redactor.redact(&stderr_tail),
+            },
+            StepFailureDetail::PlanParse { message } => StepFailureDetail::PlanParse { message: redactor.redact(&message) },
+            StepFailureDetail::Llm { message } => StepFailureDetail::Llm { message: redactor.redact(&message) },
+            StepFailureDetail::Vcs { message } => StepFailureDetail::Vcs { message: redactor.redact(&message) },
+            StepFailureDetail::PostProcessorRejected { name, message } => StepFailureDetail::PostProcessorRejected {
+                name,
+                message: redactor.redact(&message),
+            },
+            StepFailureDetail::Other { message } => StepFailureDetail::Other { message: redactor.redact(&message) },
+            StepFailureDetail::HookRejected { hook, stderr } => StepFailureDetail::HookRejected { hook, stderr: redactor.redact(&stderr) },
+            other @ (StepFailureDetail::ImportMismatch { .. }
+            | StepFailureDetail::SecretLeak { .. }
+            | StepFailureDetail::ApiSurfaceViolation { .. }
+            | StepFailureDetail::DeadlineExceeded { .. }
+            | StepFailureDetail::VacuousTest { .. }) => other,
+        }
+    }
+}
+
+fn tail_lines(text: &str, max_lines: usize) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let start = lines.len().saturating_sub(max_lines);
+    lines[start..].join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::CapturedOutput;
+
+    #[test]
+    fn ci_failure_keeps_only_the_last_lines_of_stderr() {
+        let outcome = RunnerOutcome {
+            ok: false,
+            stdout: String::new().into(),
+            stderr: (1..=25).map(|n| format!("line {n}")).collect::<Vec<_>>().join("\n").into(),
+            ..Default::default()
+        };
+
+        let detail = StepFailureDetail::ci_failure("check", &outcome);
+
+        match detail {
+            StepFailureDetail::CiFailure { stage, stderr_tail } => {
+                assert_eq!(stage, "check");
+                assert!(!stderr_tail.contains("line 1\n"));
+                assert!(stderr_tail.starts_with("line 6"));
+                assert!(stderr_tail.ends_with("line 25"));
+            }
+            other => panic!("expected a CiFailure, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn a_truncated_stderr_keeps_its_marker_in_the_failure_detail() {
+        let outcome = RunnerOutcome {
+            ok: false,
+            stdout: String::new().into(),
+            stderr: CapturedOutput::capped("line 1\nline 2\nline 3\n".to_string(), 6),
+            ..Default::default()
+        };
+
+        let detail = StepFailureDetail::ci_failure("test", &outcome);
+
+        match detail {
+            StepFailureDetail::CiFailure { stderr_tail, .. } => {
+                assert!(stderr_tail.contains("[truncated; showing"), "expected a truncation marker, got {stderr_tail:?}");
+            }
+            other => panic!("expected a CiFailure, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn redact_scrubs_a_credential_out_of_a_free_text_message_but_leaves_structured_fields_alone() {
+        let redactor = crate::secrets::Redactor::new([("OPENAI_API_KEY", "sk-test-abc123")]);
+        let detail = StepFailureDetail::Llm {
+            message: "request failed: Authorization: Bearer sk-test-abc123".to_string(),
+        };
+
+        let redacted = detail.redact(&redactor);
+
+        match redacted {
+            StepFailureDetail::Llm { message } => {
+                assert!(!message.contains("sk-test-abc123"));
+                assert!(message.contains("«redacted:OPENAI_API_KEY»"));
+            }
+            other => panic!("expected an Llm detail, got {other:?}"),
+        }
+
+        let mismatch = StepFailureDetail::ImportMismatch {
+            found: "sk-test-abc123".to_string(),
+            suggested: "tdd_core".to_string(),
+        };
+        assert_eq!(mismatch.clone().redact(&redactor), mismatch);
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let detail = StepFailureDetail::ApiSurfaceViolation {
+            description: "removed pub fn add".to_string(),
+        };
+
+        let json = serde_json::to_string(&detail).unwrap();
+        let restored: StepFailureDetail = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(detail, restored);
+    }
+}
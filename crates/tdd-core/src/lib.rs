@@ -0,0 +1,89 @@
+//! Domain model, orchestrator traits, and commit policy shared by the rest
+//! of the workspace. This crate has no I/O of its own: it defines the
+//! shapes that `tdd-exec`, `tdd-llm`, `tdd-agents`, and `tdd-cli` implement
+//! or consume.
+
+pub mod artifacts;
+pub mod blob_size;
+pub mod commit_policy;
+pub mod content_checks;
+pub mod duration;
+pub mod error;
+pub mod failure_detail;
+pub mod manifest_guard;
+pub mod model;
+pub mod path_glob;
+pub mod post_process;
+pub mod secrets;
+pub mod template;
+
+pub use artifacts::StepArtifactName;
+pub use blob_size::{LargeBlobPolicy, DEFAULT_MAX_BLOB_BYTES};
+pub use commit_policy::{build_commit_message, CommitContext};
+pub use content_checks::{UnicodeCharClass, UnicodeFinding, UnicodePolicy, UnicodeSeverity};
+pub use duration::humanize_age;
+pub use error::CoreError;
+pub use failure_detail::StepFailureDetail;
+pub use manifest_guard::{ManifestChange, ManifestChangePolicy, ManifestPolicy, ManifestViolation};
+pub use post_process::{NotesFooterProcessor, StepPostProcessor};
+pub use secrets::{Redactor, SecretFinding, SecretScanMode};
+pub use model::{
+    Agent, CapturedOutput, CommandSpec, Orchestrator, RepoState, Role, Runner, RunnerOutcome, StepContext, StepResult, SubCommit, Vcs,
+    DEFAULT_CAPTURE_LIMIT_BYTES,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn role_cycles_through_red_green_refactor() {
+        assert_eq!(Role::Tester.next(), Role::Implementor);
+        assert_eq!(Role::Implementor.next(), Role::Refactorer);
+        assert_eq!(Role::Refactorer.next(), Role::Implementor);
+    }
+
+    #[test]
+    fn from_slug_maps_built_in_roles_and_rejects_unknown_ones() {
+        assert_eq!(Role::from_slug("tester"), Some(Role::Tester));
+        assert_eq!(Role::from_slug("implementor"), Some(Role::Implementor));
+        assert_eq!(Role::from_slug("refactorer"), Some(Role::Refactorer));
+        assert_eq!(Role::from_slug("code-reviewer"), None);
+        assert_eq!(Role::from_slug(""), None);
+    }
+
+    #[test]
+    fn command_spec_round_trips_through_yaml() {
+        let skip: CommandSpec = serde_yaml::from_str("skip").unwrap();
+        assert_eq!(skip, CommandSpec::Skip);
+        assert_eq!(serde_yaml::to_string(&skip).unwrap().trim(), "skip");
+
+        let command: CommandSpec = serde_yaml::from_str("[cargo, test]").unwrap();
+        assert_eq!(command, CommandSpec::Command(vec!["cargo".to_string(), "test".to_string()]));
+        assert!(!skip.is_enabled());
+        assert!(command.is_enabled());
+    }
+
+    #[test]
+    fn command_spec_rejects_a_string_other_than_skip() {
+        assert!(serde_yaml::from_str::<CommandSpec>("nope").is_err());
+    }
+
+    #[test]
+    fn commit_message_includes_all_sections() {
+        let ctx = CommitContext {
+            role: Role::Tester,
+            step: 1,
+            kata_goal: "Add two numbers",
+            summary: "add failing test for addition",
+            rationale: &["smallest slice of behavior"],
+            diff_summary: &["tests/lib.rs: add failing test".to_string()],
+            verification: "cargo test fails as expected",
+        };
+        let message = build_commit_message(&ctx);
+        assert!(message.starts_with("test: add failing test for addition"));
+        assert!(message.contains("- Role: tester"));
+        assert!(message.contains("- Step: 1"));
+        assert!(message.contains("Verification:"));
+    }
+}
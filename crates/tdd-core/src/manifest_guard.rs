@@ -0,0 +1,463 @@
+//! Classifies what actually changed between two versions of a `Cargo.toml`
+//! and decides whether the change is the kind an agent should be allowed
+//! to make mid-kata. An edition bump or a `[profile.release]` tweak still
+//! compiles, but it changes language semantics or build output in a way
+//! that makes earlier commits non-reproducible against the tree an agent
+//! is now editing — the same hazard `workspace.readonly_paths` guards for
+//! source files, applied to the manifest itself.
+//!
+//! This crate has no I/O of its own (see the module-level doc comment on
+//! [`crate`]), so both manifest texts are handed in by the caller; see
+//! [`crate::path_glob`] for the analogous split between a pure matcher
+//! here and the file-touching caller in `tdd-agents`. This codebase has
+//! no separate "dependency gate" to classify dependency table changes
+//! against (there's no dependency allow-list or version-pinning check
+//! anywhere in the workspace), so [`ManifestChange::Dependency`] is
+//! classified for visibility in the step log but never rejected here —
+//! until such a gate exists, dependency additions and version bumps pass
+//! through this analyzer unchanged by design.
+//!
+//! Deliberately not a full TOML parser: tables are split on `[...]`
+//! headers and keys on the first `=` per line, the same line-based
+//! approach `tdd-exec`'s `cargo_meta` module uses for `package.name`.
+//! Multi-line inline tables and arrays aren't supported; every
+//! dependency declaration this module understands fits on one line,
+//! which covers every manifest in this workspace.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// How an edition or profile change is treated, set by
+/// `workspace.manifest_policy` in `tdd.yaml`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ManifestChangePolicy {
+    /// Let the change through.
+    Allow,
+    /// Reject the step as retryable, naming the change and why.
+    Reject,
+}
+
+/// `workspace.manifest_policy`: what's allowed when an edit plan touches
+/// `Cargo.toml`. Package metadata (version, description, authors, ...)
+/// is always allowed and a dependency's features may always be changed
+/// once the dependency itself already exists, so neither has a knob
+/// here — only the two changes that alter compiled semantics do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestPolicy {
+    #[serde(default = "default_reject")]
+    pub edition: ManifestChangePolicy,
+    #[serde(default = "default_reject")]
+    pub profile: ManifestChangePolicy,
+}
+
+impl Default for ManifestPolicy {
+    fn default() -> Self {
+        Self { edition: ManifestChangePolicy::Reject, profile: ManifestChangePolicy::Reject }
+    }
+}
+
+fn default_reject() -> ManifestChangePolicy {
+    ManifestChangePolicy::Reject
+}
+
+/// One classified difference between a manifest's before and after text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManifestChange {
+    /// `package.edition` changed.
+    Edition { from: Option<String>, to: Option<String> },
+    /// A key under `[profile.*]` was added, changed, or removed.
+    Profile { table: String, key: String, from: Option<String>, to: Option<String> },
+    /// An existing dependency's `features` list changed; the dependency
+    /// itself (version, source) is unchanged.
+    Feature { dependency: String, from: Option<String>, to: Option<String> },
+    /// A `[package]` key other than `edition` was added, changed, or
+    /// removed.
+    Metadata { key: String, from: Option<String>, to: Option<String> },
+    /// A dependency was added, removed, or had anything besides its
+    /// `features` list change. See the module doc comment: always
+    /// allowed, no dependency gate exists to defer to.
+    Dependency { name: String, from: Option<String>, to: Option<String> },
+}
+
+impl fmt::Display for ManifestChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ManifestChange::Edition { from, to } => {
+                write!(f, "package.edition changed from {} to {}", describe(from), describe(to))
+            }
+            ManifestChange::Profile { table, key, from, to } => {
+                write!(f, "{table}.{key} changed from {} to {}", describe(from), describe(to))
+            }
+            ManifestChange::Feature { dependency, from, to } => {
+                write!(f, "{dependency}'s features changed from {} to {}", describe(from), describe(to))
+            }
+            ManifestChange::Metadata { key, from, to } => {
+                write!(f, "package.{key} changed from {} to {}", describe(from), describe(to))
+            }
+            ManifestChange::Dependency { name, from, to } => {
+                write!(f, "dependency {name} changed from {} to {}", describe(from), describe(to))
+            }
+        }
+    }
+}
+
+fn describe(value: &Option<String>) -> String {
+    value.clone().unwrap_or_else(|| "(unset)".to_string())
+}
+
+/// A [`ManifestChange`] rejected by [`ManifestPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestViolation(pub ManifestChange);
+
+impl fmt::Display for ManifestViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rejected by workspace.manifest_policy: {}", self.0)
+    }
+}
+
+impl std::error::Error for ManifestViolation {}
+
+/// Classifies every difference between `before` and `after`, then applies
+/// `policy`. Returns the full classification on success (for the step
+/// log, even when nothing was rejected) or the first change `policy`
+/// rejects.
+pub fn check(before: &str, after: &str, policy: &ManifestPolicy) -> Result<Vec<ManifestChange>, ManifestViolation> {
+    let changes = classify(before, after);
+    for change in &changes {
+        let rejected = match change {
+            ManifestChange::Edition { .. } => policy.edition == ManifestChangePolicy::Reject,
+            ManifestChange::Profile { .. } => policy.profile == ManifestChangePolicy::Reject,
+            ManifestChange::Feature { .. } | ManifestChange::Metadata { .. } | ManifestChange::Dependency { .. } => false,
+        };
+        if rejected {
+            return Err(ManifestViolation(change.clone()));
+        }
+    }
+    Ok(changes)
+}
+
+/// Classifies every difference between `before` and `after` without
+/// applying any policy.
+pub fn classify(before: &str, after: &str) -> Vec<ManifestChange> {
+    let before = parse_tables(before);
+    let after = parse_tables(after);
+    let mut changes = Vec::new();
+
+    classify_package(&before, &after, &mut changes);
+    classify_profiles(&before, &after, &mut changes);
+    classify_dependencies(&before, &after, &mut changes);
+
+    changes
+}
+
+type Table = BTreeMap<String, String>;
+
+/// Splits a manifest into `[table.name]` headers and their `key = value`
+/// lines. See the module doc comment for what this deliberately doesn't
+/// handle.
+fn parse_tables(manifest: &str) -> BTreeMap<String, Table> {
+    let mut tables: BTreeMap<String, Table> = BTreeMap::new();
+    let mut current = String::new();
+    for line in manifest.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if trimmed.starts_with('[') {
+            current = trimmed.trim_start_matches('[').trim_end_matches(']').trim().to_string();
+            tables.entry(current.clone()).or_default();
+            continue;
+        }
+        if let Some((key, value)) = trimmed.split_once('=') {
+            tables.entry(current.clone()).or_default().insert(key.trim().to_string(), value.trim().to_string());
+        }
+    }
+    tables
+}
+
+fn classify_package(before: &BTreeMap<String, Table>, after: &BTreeMap<String, Table>, changes: &mut Vec<ManifestChange>) {
+    let empty = Table::new();
+    let before_package = before.get("package").unwrap_or(&empty);
+    let after_package = after.get("package").unwrap_or(&empty);
+
+    for key in all_keys(before_package, after_package) {
+        let from = before_package.get(&key).cloned();
+        let to = after_package.get(&key).cloned();
+        if from == to {
+            continue;
+        }
+        if key == "edition" {
+            changes.push(ManifestChange::Edition { from, to });
+        } else {
+            changes.push(ManifestChange::Metadata { key, from, to });
+        }
+    }
+}
+
+fn classify_profiles(before: &BTreeMap<String, Table>, after: &BTreeMap<String, Table>, changes: &mut Vec<ManifestChange>) {
+    let empty = Table::new();
+    for table in profile_tables(before).chain(profile_tables(after)).collect::<std::collections::BTreeSet<_>>() {
+        let before_table = before.get(&table).unwrap_or(&empty);
+        let after_table = after.get(&table).unwrap_or(&empty);
+        for key in all_keys(before_table, after_table) {
+            let from = before_table.get(&key).cloned();
+            let to = after_table.get(&key).cloned();
+            if from != to {
+                changes.push(ManifestChange::Profile { table: table.clone(), key, from, to });
+            }
+        }
+    }
+}
+
+fn profile_tables(tables: &BTreeMap<String, Table>) -> impl Iterator<Item = String> + '_ {
+    tables.keys().filter(|name| name.starts_with("profile.")).cloned()
+}
+
+const DEPENDENCY_TABLES: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+fn classify_dependencies(before: &BTreeMap<String, Table>, after: &BTreeMap<String, Table>, changes: &mut Vec<ManifestChange>) {
+    let empty = Table::new();
+
+    for table_name in DEPENDENCY_TABLES {
+        let before_table = before.get(table_name).unwrap_or(&empty);
+        let after_table = after.get(table_name).unwrap_or(&empty);
+        for name in all_keys(before_table, after_table) {
+            let from = before_table.get(&name).cloned();
+            let to = after_table.get(&name).cloned();
+            if from == to {
+                continue;
+            }
+            changes.push(classify_dependency_value(name, from, to));
+        }
+    }
+
+    for table_name in before.keys().chain(after.keys()).collect::<std::collections::BTreeSet<_>>() {
+        let Some(dependency_name) = dependency_sub_table_name(table_name) else {
+            continue;
+        };
+        let before_table = before.get(table_name).unwrap_or(&empty);
+        let after_table = after.get(table_name).unwrap_or(&empty);
+        if before_table.is_empty() && after_table.is_empty() {
+            continue;
+        }
+        classify_dependency_sub_table(dependency_name, before_table, after_table, changes);
+    }
+}
+
+fn dependency_sub_table_name(table_name: &str) -> Option<&str> {
+    DEPENDENCY_TABLES.iter().find_map(|prefix| table_name.strip_prefix(&format!("{prefix}.")))
+}
+
+/// A dependency's own `[dependencies.name]` sub-table: if `features` is
+/// the only key that differs, it's a feature change on an existing
+/// dependency; anything else (including the sub-table appearing or
+/// disappearing entirely) is a dependency change.
+fn classify_dependency_sub_table(name: &str, before: &Table, after: &Table, changes: &mut Vec<ManifestChange>) {
+    let differing: Vec<String> = all_keys(before, after).into_iter().filter(|key| before.get(key) != after.get(key)).collect();
+    if differing.is_empty() {
+        return;
+    }
+    if before.is_empty() || after.is_empty() || differing != vec!["features".to_string()] {
+        changes.push(ManifestChange::Dependency {
+            name: name.to_string(),
+            from: render_table(before),
+            to: render_table(after),
+        });
+        return;
+    }
+    changes.push(ManifestChange::Feature {
+        dependency: name.to_string(),
+        from: before.get("features").cloned(),
+        to: after.get("features").cloned(),
+    });
+}
+
+fn render_table(table: &Table) -> Option<String> {
+    if table.is_empty() {
+        return None;
+    }
+    Some(table.iter().map(|(key, value)| format!("{key} = {value}")).collect::<Vec<_>>().join(", "))
+}
+
+/// A single `name = ...` line in `[dependencies]` itself, where a value
+/// is either a bare version string (`"1"`) or an inline table
+/// (`{ version = "1", features = ["derive"] }`). Splits the latter apart
+/// so a features-only change is distinguishable from a version bump.
+fn classify_dependency_value(name: String, from: Option<String>, to: Option<String>) -> ManifestChange {
+    let (Some(before_value), Some(after_value)) = (&from, &to) else {
+        return ManifestChange::Dependency { name, from, to };
+    };
+
+    let Some(before_fields) = parse_inline_table(before_value) else {
+        return ManifestChange::Dependency { name, from, to };
+    };
+    let Some(after_fields) = parse_inline_table(after_value) else {
+        return ManifestChange::Dependency { name, from, to };
+    };
+
+    let before_rest = without_key(&before_fields, "features");
+    let after_rest = without_key(&after_fields, "features");
+    if before_rest == after_rest && before_fields.get("features") != after_fields.get("features") {
+        ManifestChange::Feature { dependency: name, from: before_fields.get("features").cloned(), to: after_fields.get("features").cloned() }
+    } else {
+        ManifestChange::Dependency { name, from, to }
+    }
+}
+
+fn without_key(table: &Table, excluded: &str) -> Table {
+    table.iter().filter(|(key, _)| key.as_str() != excluded).map(|(key, value)| (key.clone(), value.clone())).collect()
+}
+
+/// Parses `{ key = value, key = value }` into its fields. `None` for a
+/// bare scalar (`"1"`) — that's a plain version string, not an inline
+/// table.
+fn parse_inline_table(value: &str) -> Option<Table> {
+    let value = value.trim();
+    let inner = value.strip_prefix('{')?.strip_suffix('}')?;
+    let mut fields = Table::new();
+    for field in split_top_level(inner, ',') {
+        if let Some((key, value)) = field.split_once('=') {
+            fields.insert(key.trim().to_string(), value.trim().to_string());
+        }
+    }
+    Some(fields)
+}
+
+/// Splits `s` on `sep`, ignoring any `sep` nested inside `[...]` or
+/// `{...}` — needed so `features = ["a", "b"]` isn't split on the comma
+/// between `"a"` and `"b"`.
+fn split_top_level(s: &str, sep: char) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0i32;
+    for ch in s.chars() {
+        match ch {
+            '[' | '{' => {
+                depth += 1;
+                current.push(ch);
+            }
+            ']' | '}' => {
+                depth -= 1;
+                current.push(ch);
+            }
+            c if c == sep && depth == 0 => {
+                parts.push(std::mem::take(&mut current));
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.trim().is_empty() {
+        parts.push(current);
+    }
+    parts
+}
+
+fn all_keys(a: &Table, b: &Table) -> Vec<String> {
+    let mut keys: Vec<String> = a.keys().chain(b.keys()).cloned().collect();
+    keys.sort();
+    keys.dedup();
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(edition: &str, version: &str) -> String {
+        format!("[package]\nname = \"kata\"\nversion = \"{version}\"\nedition = \"{edition}\"\n")
+    }
+
+    #[test]
+    fn an_edition_bump_is_rejected_with_the_policy_message() {
+        let before = package("2021", "0.1.0");
+        let after = package("2024", "0.1.0");
+
+        let error = check(&before, &after, &ManifestPolicy::default()).unwrap_err();
+
+        assert!(matches!(error.0, ManifestChange::Edition { .. }));
+        assert!(error.to_string().contains("package.edition changed from \"2021\" to \"2024\""));
+    }
+
+    #[test]
+    fn adding_a_feature_to_an_existing_dependency_passes() {
+        let before = "[dependencies.serde]\nversion = \"1\"\n";
+        let after = "[dependencies.serde]\nversion = \"1\"\nfeatures = [\"derive\"]\n";
+
+        let changes = check(before, after, &ManifestPolicy::default()).unwrap();
+
+        assert_eq!(changes, vec![ManifestChange::Feature { dependency: "serde".to_string(), from: None, to: Some("[\"derive\"]".to_string()) }]);
+    }
+
+    #[test]
+    fn adding_a_feature_to_an_inline_dependency_table_passes() {
+        let before = "[dependencies]\nserde = { version = \"1\" }\n";
+        let after = "[dependencies]\nserde = { version = \"1\", features = [\"derive\"] }\n";
+
+        let changes = check(before, after, &ManifestPolicy::default()).unwrap();
+
+        assert_eq!(
+            changes,
+            vec![ManifestChange::Feature { dependency: "serde".to_string(), from: None, to: Some("[\"derive\"]".to_string()) }]
+        );
+    }
+
+    #[test]
+    fn a_profile_release_change_is_rejected() {
+        let before = "[profile.release]\nopt-level = 2\n";
+        let after = "[profile.release]\nopt-level = 3\n";
+
+        let error = check(before, after, &ManifestPolicy::default()).unwrap_err();
+
+        assert!(matches!(error.0, ManifestChange::Profile { ref table, ref key, .. } if table == "profile.release" && key == "opt-level"));
+    }
+
+    #[test]
+    fn a_package_version_bump_passes_as_metadata() {
+        let before = package("2021", "0.1.0");
+        let after = package("2021", "0.2.0");
+
+        let changes = check(&before, &after, &ManifestPolicy::default()).unwrap();
+
+        assert_eq!(changes, vec![ManifestChange::Metadata { key: "version".to_string(), from: Some("\"0.1.0\"".to_string()), to: Some("\"0.2.0\"".to_string()) }]);
+    }
+
+    #[test]
+    fn a_new_dependency_is_classified_as_a_dependency_change_and_always_passes() {
+        let before = "[dependencies]\n";
+        let after = "[dependencies]\nserde = \"1\"\n";
+
+        let changes = check(before, after, &ManifestPolicy::default()).unwrap();
+
+        assert_eq!(changes, vec![ManifestChange::Dependency { name: "serde".to_string(), from: None, to: Some("\"1\"".to_string()) }]);
+    }
+
+    #[test]
+    fn a_dependency_version_bump_alongside_a_feature_is_still_a_dependency_change() {
+        let before = "[dependencies]\nserde = { version = \"1\", features = [\"derive\"] }\n";
+        let after = "[dependencies]\nserde = { version = \"2\", features = [\"derive\", \"rc\"] }\n";
+
+        let changes = check(before, after, &ManifestPolicy::default()).unwrap();
+
+        assert!(matches!(&changes[0], ManifestChange::Dependency { name, .. } if name == "serde"));
+    }
+
+    #[test]
+    fn an_allow_policy_lets_an_edition_bump_through() {
+        let before = package("2021", "0.1.0");
+        let after = package("2024", "0.1.0");
+        let policy = ManifestPolicy { edition: ManifestChangePolicy::Allow, profile: ManifestChangePolicy::Reject };
+
+        let changes = check(&before, &after, &policy).unwrap();
+
+        assert_eq!(changes, vec![ManifestChange::Edition { from: Some("\"2021\"".to_string()), to: Some("\"2024\"".to_string()) }]);
+    }
+
+    #[test]
+    fn no_changes_to_an_unrelated_file_yields_no_classification() {
+        let manifest = package("2021", "0.1.0");
+
+        assert_eq!(classify(&manifest, &manifest), Vec::new());
+    }
+}
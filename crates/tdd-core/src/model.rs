@@ -0,0 +1,415 @@
+//! Domain types shared across the workspace: the agent roles, the context
+//! handed to an agent at the start of a step, and the result an agent
+//! produces after editing the working tree.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::PathBuf;
+
+/// The three roles that rotate through the red-green-refactor loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Tester,
+    Implementor,
+    Refactorer,
+}
+
+impl Role {
+    /// The role that follows this one in the red-green-refactor cycle.
+    pub fn next(self) -> Role {
+        match self {
+            Role::Tester => Role::Implementor,
+            Role::Implementor => Role::Refactorer,
+            Role::Refactorer => Role::Implementor,
+        }
+    }
+
+    /// The conventional-commit type this role's commits should use.
+    pub fn commit_type(self) -> &'static str {
+        match self {
+            Role::Tester => "test",
+            Role::Implementor => "feat",
+            Role::Refactorer => "refactor",
+        }
+    }
+
+    /// The inverse of [`Display`](fmt::Display): maps a role slug (as it
+    /// appears in `step-{n}-{slug}` artifact names) back to a built-in
+    /// [`Role`]. Returns `None` for anything else, including a
+    /// well-formed but unrecognized slug (e.g. a custom role) — callers
+    /// that need to keep step numbering advancing in that case should
+    /// fall back to the raw slug rather than treating `None` as a parse
+    /// failure.
+    pub fn from_slug(slug: &str) -> Option<Role> {
+        match slug {
+            "tester" => Some(Role::Tester),
+            "implementor" => Some(Role::Implementor),
+            "refactorer" => Some(Role::Refactorer),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Role::Tester => "tester",
+            Role::Implementor => "implementor",
+            Role::Refactorer => "refactorer",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Everything an agent needs to know to plan and perform a single step.
+#[derive(Debug, Clone)]
+pub struct StepContext {
+    pub role: Role,
+    pub step_index: u32,
+    pub kata_description: String,
+    pub git_last_commit_msg: String,
+    pub git_last_diff: String,
+    pub repo_snapshot_paths: Vec<String>,
+    /// A subset of `repo_snapshot_paths` touched by recent commits, most
+    /// recently touched first, used to rank the "Tracked files" prompt
+    /// section so recently-edited files outrank untouched ones.
+    pub recently_changed_paths: Vec<String>,
+    /// The maximum number of paths to list in the "Tracked files" prompt
+    /// section before collapsing the rest into a trailing count.
+    pub file_list_limit: usize,
+    /// The workspace's standing-instructions file, if one exists and isn't
+    /// empty. Meant for house style that shouldn't clutter `kata.md`.
+    pub standing_instructions: String,
+    /// A one-off nudge from `--goal`, pending in
+    /// `.tdd/state/next-goal.txt`, if one was stored for this step. Takes
+    /// priority over `kata_description` but never overrides a role's own
+    /// constraints. Consumed (the file removed) once this step commits.
+    pub user_goal: Option<String>,
+    /// The identifier the library crate is imported under (`Cargo.toml`'s
+    /// `package.name` with dashes turned into underscores), when it could
+    /// be resolved. Surfaced prominently so the Tester doesn't guess a
+    /// `use` path that doesn't match the actual crate.
+    pub crate_name: Option<String>,
+    /// Globs (see [`crate::path_glob`]) the workspace has marked
+    /// read-only, rendered as a "Do not modify" list so agents are told
+    /// up front instead of learning about them via a rejected edit plan.
+    /// Empty means `workspace.readonly_paths` is unset.
+    pub readonly_paths: Vec<String>,
+    /// Plans proposed by earlier steps in the same `--plan-only` preview
+    /// run, none of which were actually applied. Empty outside of
+    /// `--plan-only`, where each step's real edits make this redundant.
+    pub previously_proposed: Vec<String>,
+    /// A human-readable summary of what changed in the tracked files
+    /// since this role's own previous turn (not just since the last
+    /// commit), when an earlier fingerprint for this role could be
+    /// found. `None` on this role's first turn, or if the fingerprint
+    /// was lost (e.g. to an undo or a cleaned `.tdd/state`).
+    pub since_last_turn: Option<String>,
+    /// How many times this step's `edit()` has already been retried: `0`
+    /// on the first attempt, `1` by the second, and so on. Lets an agent
+    /// escalate its sampling settings (see [`tdd_llm::SamplingOverride`])
+    /// to break out of a deterministic failure loop instead of resending
+    /// an identical request every attempt.
+    pub attempt_index: u32,
+}
+
+/// The outcome of an agent's edit phase, ready to be verified and committed.
+#[derive(Debug, Clone, Default)]
+pub struct StepResult {
+    pub files_changed: Vec<String>,
+    pub commit_message: String,
+    pub notes: String,
+    /// When an agent split its edit plan into an ordered `"commits"`
+    /// array, the groups to verify cumulatively but commit separately,
+    /// in order. Empty for the common flat-plan case, in which `commit_message`
+    /// and `notes` above are what gets committed.
+    pub sub_commits: Vec<SubCommit>,
+    /// How this step's `Cargo.toml` edit (if any) was classified by
+    /// `workspace.manifest_policy`, for the step log. Empty when the
+    /// step didn't touch `Cargo.toml`. See [`crate::manifest_guard`].
+    pub manifest_changes: Vec<crate::manifest_guard::ManifestChange>,
+    /// The role's configured base temperature, before any retry
+    /// escalation, for the step log. See
+    /// `roles.<role>.retry_temperature_bump`.
+    pub base_temperature: f32,
+    /// The temperature this attempt's chat calls actually used, after
+    /// `retry_temperature_bump * attempt_index` was applied and clamped
+    /// to the provider's valid range, for the step log. Equal to
+    /// `base_temperature` on a step's first attempt.
+    pub effective_temperature: f32,
+}
+
+/// One ordered group within a step whose edit plan split across several
+/// commits, all verified together but committed one at a time. See
+/// `tdd_agents::edit_plan::EditPlan`'s `commits` form.
+#[derive(Debug, Clone, Default)]
+pub struct SubCommit {
+    pub commit_message: String,
+    pub notes: String,
+    pub files: Vec<String>,
+}
+
+/// A role implementation capable of planning and applying a single step.
+#[async_trait]
+pub trait Agent: Send + Sync {
+    fn role(&self) -> Role;
+    async fn plan(&self, ctx: &StepContext) -> anyhow::Result<String>;
+    async fn edit(&self, ctx: &StepContext) -> anyhow::Result<StepResult>;
+}
+
+/// Drives the rotation of roles across the lifetime of a run.
+#[async_trait]
+pub trait Orchestrator {
+    fn current_role(&self) -> Role;
+    async fn next(&mut self) -> anyhow::Result<()>;
+}
+
+/// A read-only view of the working tree used to build a [`StepContext`].
+#[derive(Debug, Clone, Default)]
+pub struct RepoState {
+    pub last_commit_message: String,
+    pub last_diff: String,
+    pub files: Vec<String>,
+}
+
+/// The default cap, per stream, on how much of a command's output
+/// [`CapturedOutput`] keeps resident before the rest spills to disk.
+pub const DEFAULT_CAPTURE_LIMIT_BYTES: usize = 256 * 1024;
+
+/// A command's stdout or stderr, capped at a configurable size so one
+/// chatty test run can't balloon a step's memory use or its JSON log.
+/// Beyond the cap, `inline` holds only the leading portion and the full
+/// text is expected to have been written to `spill_path` by the caller
+/// that captured it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CapturedOutput {
+    pub inline: String,
+    pub truncated: bool,
+    pub spill_path: Option<PathBuf>,
+    pub total_bytes: u64,
+}
+
+impl CapturedOutput {
+    /// Caps `text` at `limit` bytes, cutting back to the nearest `char`
+    /// boundary rather than splitting one. `spill_path` is left unset;
+    /// the caller fills it in once the full text is actually on disk.
+    pub fn capped(text: String, limit: usize) -> Self {
+        let total_bytes = text.len() as u64;
+        if text.len() <= limit {
+            return Self { inline: text, truncated: false, spill_path: None, total_bytes };
+        }
+        let mut cut = limit;
+        while cut > 0 && !text.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        Self { inline: text[..cut].to_string(), truncated: true, spill_path: None, total_bytes }
+    }
+}
+
+impl From<String> for CapturedOutput {
+    /// Wraps `text` with no cap, for call sites (mostly tests) that just
+    /// want a small fixed string and don't need truncation at all.
+    fn from(text: String) -> Self {
+        let total_bytes = text.len() as u64;
+        Self { inline: text, truncated: false, spill_path: None, total_bytes }
+    }
+}
+
+impl std::ops::Deref for CapturedOutput {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.inline
+    }
+}
+
+impl CapturedOutput {
+    /// `"[truncated; showing N of M bytes, full output at <path>]"` when
+    /// the full output didn't fit inline, `None` otherwise — the note
+    /// [`fmt::Display`] appends, and what callers that render only the
+    /// tail of `inline` (e.g. [`crate::failure_detail::StepFailureDetail::ci_failure`])
+    /// append themselves to stay honest about what they cut.
+    pub fn truncation_marker(&self) -> Option<String> {
+        if !self.truncated {
+            return None;
+        }
+        let mut marker = format!("[truncated; showing {} of {} bytes", self.inline.len(), self.total_bytes);
+        if let Some(path) = &self.spill_path {
+            marker.push_str(&format!(", full output at {}", path.display()));
+        }
+        marker.push(']');
+        Some(marker)
+    }
+}
+
+impl fmt::Display for CapturedOutput {
+    /// The inline portion, followed by a truncation marker (and the spill
+    /// path, if known) when the full output didn't fit.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.inline)?;
+        if let Some(marker) = self.truncation_marker() {
+            write!(f, "\n... {marker}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The result of running a single `cargo` (or other language) subcommand.
+#[derive(Debug, Clone, Default)]
+pub struct RunnerOutcome {
+    pub ok: bool,
+    pub stdout: CapturedOutput,
+    pub stderr: CapturedOutput,
+    /// Set when the stage was configured with [`CommandSpec::Skip`]
+    /// instead of actually running a command. A skipped stage counts as
+    /// passing (`ok` is `true`) so it doesn't block verification.
+    pub skipped: bool,
+}
+
+impl RunnerOutcome {
+    /// The outcome for a stage a workspace opted out of with
+    /// [`CommandSpec::Skip`]: treated as passing, since there was nothing
+    /// to fail.
+    pub fn skipped() -> Self {
+        Self {
+            ok: true,
+            skipped: true,
+            ..Default::default()
+        }
+    }
+}
+
+/// A verification stage's command, or an explicit opt-out. Lets a
+/// documentation-only kata skip `check` entirely instead of working
+/// around an empty command list with a no-op like `["true"]`, which would
+/// otherwise show up in logs implying a check actually ran.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandSpec {
+    Skip,
+    Command(Vec<String>),
+}
+
+impl CommandSpec {
+    pub fn is_enabled(&self) -> bool {
+        matches!(self, CommandSpec::Command(_))
+    }
+}
+
+impl Serialize for CommandSpec {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            CommandSpec::Skip => serializer.serialize_str("skip"),
+            CommandSpec::Command(command) => command.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for CommandSpec {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Skip(String),
+            Command(Vec<String>),
+        }
+
+        match Raw::deserialize(deserializer)? {
+            Raw::Skip(marker) if marker == "skip" => Ok(CommandSpec::Skip),
+            Raw::Skip(other) => Err(serde::de::Error::custom(format!(
+                "expected a command list or the string \"skip\", got {other:?}"
+            ))),
+            Raw::Command(command) => Ok(CommandSpec::Command(command)),
+        }
+    }
+}
+
+/// Language-aware verification commands run after every edit.
+pub trait Runner {
+    /// Checks formatting without changing any files.
+    fn fmt_check(&self) -> anyhow::Result<RunnerOutcome>;
+    /// Reformats the working tree in place.
+    fn fmt(&self) -> anyhow::Result<RunnerOutcome>;
+    fn check(&self) -> anyhow::Result<RunnerOutcome>;
+    fn test(&self) -> anyhow::Result<RunnerOutcome>;
+}
+
+/// Version control operations the orchestrator needs to hand context
+/// between agents and to persist their progress. `Send + Sync` so an
+/// implementor can be shared as `Arc<dyn Vcs>` across threads without a
+/// wrapper: every method here takes `&self` and is expected to open
+/// whatever backing handle it needs fresh per call (see
+/// `tdd_exec::GitVcs`, which opens a new `git2::Repository` each time)
+/// rather than holding one behind a lock, so there's no shared mutable
+/// state to race or poison.
+pub trait Vcs: Send + Sync {
+    fn init_if_needed(&self) -> anyhow::Result<()>;
+    fn read_state(&self) -> anyhow::Result<RepoState>;
+    fn stage_all(&self) -> anyhow::Result<()>;
+    fn commit(&self, message: &str) -> anyhow::Result<String>;
+    /// A unified diff of `paths` in the working tree against `HEAD`, for
+    /// handing an uncommitted step's changes to a reviewer.
+    fn diff_against_head(&self, paths: &[String]) -> anyhow::Result<String>;
+    /// Restores `paths` to their `HEAD` content, or removes them if `HEAD`
+    /// has no such file — discarding an uncommitted step's edits when a
+    /// reviewer rejects them rather than leaving a half-applied working
+    /// tree behind.
+    fn discard_paths(&self, paths: &[String]) -> anyhow::Result<()>;
+    /// When `HEAD` was committed, for rendering a relative age (see
+    /// [`crate::duration::humanize_age`]) instead of trusting a log
+    /// file's own timestamp, which may be missing or clock-skewed.
+    /// `None` if there's no commit yet.
+    fn head_commit_timestamp(&self) -> anyhow::Result<Option<std::time::SystemTime>>;
+    /// Paths touched by any of the last `n_commits` commits, most
+    /// recently touched first, deduplicated. Used to rank the "Tracked
+    /// files" prompt section so recently-edited files outrank untouched
+    /// ones. Empty on a repo with no commits yet.
+    fn recently_changed_paths(&self, n_commits: usize) -> anyhow::Result<Vec<String>>;
+    /// A unified diff between two commit-ish revisions, `from` exclusive
+    /// to `to` inclusive (same sense as `git diff from..to`). `from` of
+    /// `None` diffs against the empty tree, for a range starting at the
+    /// repository's very first commit. Used by `tdd-cli diff` to render an
+    /// aggregate view of a whole session.
+    fn diff_range(&self, from: Option<&str>, to: &str) -> anyhow::Result<String>;
+    /// The `--stat`-style summary table of [`Self::diff_range`]'s same
+    /// range: one line per changed file plus a totals line, without the
+    /// full patch body.
+    fn diff_range_stat(&self, from: Option<&str>, to: &str) -> anyhow::Result<String>;
+    /// Creates branch `name` pointing at `commit` (a commit-ish revision),
+    /// without checking it out. Used by `--review-branch` mode to cut a
+    /// dedicated branch per red-green-refactor cycle off the integration
+    /// branch's tip.
+    fn create_branch_from(&self, name: &str, commit: &str) -> anyhow::Result<()>;
+    /// Checks out branch `name`, updating `HEAD` and the working tree.
+    fn checkout(&self, name: &str) -> anyhow::Result<()>;
+    /// Merges branch `name` into the currently checked-out branch:
+    /// fast-forwards when the current branch is a direct ancestor of
+    /// `name`, otherwise (or when `no_ff` is set) creates a merge commit.
+    /// Returns the resulting commit id.
+    fn merge_ff(&self, name: &str, no_ff: bool) -> anyhow::Result<String>;
+    /// Whether `HEAD` points directly at a commit rather than a branch —
+    /// the state a CI checkout leaves a repo in at a PR merge commit.
+    /// Commits made on a detached `HEAD` are unreachable as soon as the
+    /// checkout ends, so callers use this to decide whether to refuse or
+    /// to create a branch first.
+    fn is_detached(&self) -> anyhow::Result<bool>;
+    /// `HEAD`'s commit id, for recording a baseline to [`Self::reset_hard`]
+    /// back to later. Used by `tdd-cli experiment` to snapshot the
+    /// workspace once up front rather than re-deriving a baseline per
+    /// trial.
+    fn head_commit_id(&self) -> anyhow::Result<String>;
+    /// Whether the working tree and index have no changes relative to
+    /// `HEAD`: no staged changes, no modified tracked files, and no
+    /// untracked (non-ignored) files. Checked by `tdd-cli experiment`
+    /// before its first [`Self::reset_hard`], since that call discards
+    /// exactly these things with no way back.
+    fn is_clean(&self) -> anyhow::Result<bool>;
+    /// Resets the working tree and index to `commit` and removes every
+    /// untracked file and directory (ignored paths such as `target/` are
+    /// left alone), restoring the workspace to the state it was in at
+    /// that commit. Unlike [`Self::discard_paths`], which targets
+    /// specific paths, this is a whole-workspace rollback — used by
+    /// `tdd-cli experiment` to isolate one trial's edits from the next.
+    fn reset_hard(&self, commit: &str) -> anyhow::Result<()>;
+}
@@ -0,0 +1,83 @@
+//! A small glob matcher for repo-relative paths, shared by every feature
+//! that lets a workspace config list `[globs]` against the working tree
+//! (currently just `workspace.readonly_paths`). Not a general-purpose
+//! glob engine: `*` matches within one path segment, `**` matches zero or
+//! more whole segments, `?` matches one character within a segment, and
+//! matching is always against `/`-separated segments regardless of the
+//! host OS.
+
+/// Returns whether `path` (repo-relative, `/`-separated) matches `glob`.
+pub fn matches(glob: &str, path: &str) -> bool {
+    let glob_segments: Vec<&str> = glob.split('/').collect();
+    let path_segments: Vec<&str> = path.split('/').collect();
+    matches_segments(&glob_segments, &path_segments)
+}
+
+fn matches_segments(glob: &[&str], path: &[&str]) -> bool {
+    match glob.first() {
+        None => path.is_empty(),
+        Some(&"**") => {
+            matches_segments(&glob[1..], path)
+                || (!path.is_empty() && matches_segments(glob, &path[1..]))
+        }
+        Some(segment) => {
+            !path.is_empty() && matches_segment(segment, path[0]) && matches_segments(&glob[1..], &path[1..])
+        }
+    }
+}
+
+/// Matches a single path segment against a single glob segment containing
+/// `*` (zero or more characters) and `?` (exactly one character).
+fn matches_segment(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    matches_chars(&pattern, &text)
+}
+
+fn matches_chars(pattern: &[char], text: &[char]) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some('*') => {
+            matches_chars(&pattern[1..], text) || (!text.is_empty() && matches_chars(pattern, &text[1..]))
+        }
+        Some('?') => !text.is_empty() && matches_chars(&pattern[1..], &text[1..]),
+        Some(&c) => !text.is_empty() && text[0] == c && matches_chars(&pattern[1..], &text[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn an_exact_path_matches_itself() {
+        assert!(matches("contracts/billing.rs", "contracts/billing.rs"));
+        assert!(!matches("contracts/billing.rs", "contracts/other.rs"));
+    }
+
+    #[test]
+    fn a_single_star_matches_within_one_segment_only() {
+        assert!(matches("contracts/*.rs", "contracts/billing.rs"));
+        assert!(!matches("contracts/*.rs", "contracts/nested/billing.rs"));
+    }
+
+    #[test]
+    fn a_double_star_matches_any_number_of_segments() {
+        assert!(matches("contracts/**", "contracts/billing.rs"));
+        assert!(matches("contracts/**", "contracts/nested/billing.rs"));
+        assert!(matches("contracts/**", "contracts"));
+        assert!(!matches("contracts/**", "src/contracts/billing.rs"));
+    }
+
+    #[test]
+    fn a_question_mark_matches_exactly_one_character() {
+        assert!(matches("contracts/v?.rs", "contracts/v1.rs"));
+        assert!(!matches("contracts/v?.rs", "contracts/v10.rs"));
+    }
+
+    #[test]
+    fn a_leading_double_star_matches_nested_paths_anywhere() {
+        assert!(matches("**/contracts/*.rs", "src/contracts/billing.rs"));
+        assert!(matches("**/contracts/*.rs", "contracts/billing.rs"));
+    }
+}
@@ -0,0 +1,98 @@
+//! An extension point for enriching a successful `edit()` result before
+//! it's verified and committed, e.g. appending standing notes or
+//! generating a companion file, without patching the role's own
+//! [`crate::model::Agent`] impl.
+
+use crate::model::{Role, StepContext, StepResult};
+
+/// Applied, in registration order, to every role's successful
+/// [`crate::model::Agent::edit`] result. A processor that needs to write a
+/// file of its own captures the repo root at construction time, the same
+/// way [`crate::model::Agent`] implementations do; it reports the write
+/// back by adding the path to `result.files_changed` so the orchestrator
+/// picks it up.
+pub trait StepPostProcessor: Send + Sync {
+    /// A short identifier recorded in the step log for traceability.
+    fn name(&self) -> &str;
+
+    /// Transforms `result`. Returning `Err` vetoes the step as retryable,
+    /// the same as any other `edit()` failure.
+    fn process(&self, role: Role, ctx: &StepContext, result: StepResult) -> anyhow::Result<StepResult>;
+}
+
+/// A built-in example processor: appends a fixed footer to every step's
+/// `notes`, independent of role or context.
+pub struct NotesFooterProcessor {
+    footer: String,
+}
+
+impl NotesFooterProcessor {
+    pub fn new(footer: impl Into<String>) -> Self {
+        Self { footer: footer.into() }
+    }
+}
+
+impl StepPostProcessor for NotesFooterProcessor {
+    fn name(&self) -> &str {
+        "notes-footer"
+    }
+
+    fn process(&self, _role: Role, _ctx: &StepContext, mut result: StepResult) -> anyhow::Result<StepResult> {
+        if !result.notes.is_empty() {
+            result.notes.push('\n');
+        }
+        result.notes.push_str(&self.footer);
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> StepContext {
+        StepContext {
+            role: Role::Implementor,
+            step_index: 0,
+            kata_description: String::new(),
+            git_last_commit_msg: String::new(),
+            git_last_diff: String::new(),
+            repo_snapshot_paths: Vec::new(),
+            recently_changed_paths: Vec::new(),
+            file_list_limit: 20,
+            standing_instructions: String::new(),
+            user_goal: None,
+            crate_name: None,
+            readonly_paths: Vec::new(),
+            previously_proposed: Vec::new(),
+            since_last_turn: None,
+            attempt_index: 0,
+        }
+    }
+
+    fn result_with_notes(notes: &str) -> StepResult {
+        StepResult {
+            files_changed: vec!["src/lib.rs".to_string()],
+            commit_message: "feat: add".to_string(),
+            notes: notes.to_string(),
+            sub_commits: Vec::new(),
+            manifest_changes: Vec::new(),
+            base_temperature: 0.0,
+            effective_temperature: 0.0,
+        }
+    }
+
+    #[test]
+    fn appends_the_footer_after_existing_notes() {
+        let processor = NotesFooterProcessor::new("Generated by the kata's automated reviewer.");
+        let result = processor.process(Role::Implementor, &context(), result_with_notes("implemented add()")).unwrap();
+        assert_eq!(result.notes, "implemented add()\nGenerated by the kata's automated reviewer.");
+    }
+
+    #[test]
+    fn appends_the_footer_alone_when_there_were_no_notes() {
+        let processor = NotesFooterProcessor::new("Generated by the kata's automated reviewer.");
+        let result = processor.process(Role::Implementor, &context(), result_with_notes("")).unwrap();
+        assert_eq!(result.notes, "Generated by the kata's automated reviewer.");
+    }
+}
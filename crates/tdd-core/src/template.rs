@@ -0,0 +1,77 @@
+//! A minimal `{{key}}` substitution template engine, just enough for
+//! rendering short, mostly-literal snippets (currently a changelog
+//! entry) without pulling in a templating crate. See [`crate::path_glob`]
+//! for the sibling pattern-matching primitive this mirrors.
+
+use std::collections::HashMap;
+
+/// Replaces every `{{key}}` placeholder in `template` with its value from
+/// `vars`. A placeholder with no matching key, or an unterminated `{{`,
+/// is left untouched, so a partially-filled template still reads as a
+/// template rather than silently losing text.
+pub fn render(template: &str, vars: &HashMap<&str, String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after_open = &rest[start + 2..];
+        match after_open.find("}}") {
+            Some(end) => {
+                let key = after_open[..end].trim();
+                match vars.get(key) {
+                    Some(value) => out.push_str(value),
+                    None => {
+                        out.push_str("{{");
+                        out.push_str(&after_open[..end]);
+                        out.push_str("}}");
+                    }
+                }
+                rest = &after_open[end + 2..];
+            }
+            None => {
+                out.push_str("{{");
+                rest = after_open;
+                break;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_placeholders_are_substituted() {
+        let mut vars = HashMap::new();
+        vars.insert("name", "world".to_string());
+        assert_eq!(render("hello {{name}}!", &vars), "hello world!");
+    }
+
+    #[test]
+    fn whitespace_inside_braces_is_ignored() {
+        let mut vars = HashMap::new();
+        vars.insert("name", "world".to_string());
+        assert_eq!(render("hello {{ name }}!", &vars), "hello world!");
+    }
+
+    #[test]
+    fn an_unknown_placeholder_is_left_untouched() {
+        let vars = HashMap::new();
+        assert_eq!(render("hello {{name}}!", &vars), "hello {{name}}!");
+    }
+
+    #[test]
+    fn an_unterminated_placeholder_is_left_untouched() {
+        let vars = HashMap::new();
+        assert_eq!(render("hello {{name", &vars), "hello {{name");
+    }
+
+    #[test]
+    fn a_template_with_no_placeholders_passes_through_unchanged() {
+        let vars = HashMap::new();
+        assert_eq!(render("just plain text", &vars), "just plain text");
+    }
+}
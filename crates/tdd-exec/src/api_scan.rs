@@ -0,0 +1,185 @@
+//! A cheap public-API surface scanner used to guard Refactorer steps: it
+//! extracts the public items in `src/` before and after a refactor and
+//! flags anything an external consumer could have depended on that was
+//! removed or changed shape.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// One public item found while scanning a crate's `src/` tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiItem {
+    pub kind: &'static str,
+    pub name: String,
+    /// Number of parameters, for `fn` items only.
+    pub arity: Option<usize>,
+}
+
+/// All public items found across a `src/` tree, keyed by name so the same
+/// name in two scans can be compared directly.
+#[derive(Debug, Clone, Default)]
+pub struct ApiSurface {
+    items: BTreeMap<String, ApiItem>,
+}
+
+impl ApiSurface {
+    /// Walks every `.rs` file under `src_dir` and records its public
+    /// `fn`, `struct`, `enum`, `trait`, and `mod` items.
+    pub fn scan(src_dir: &Path) -> anyhow::Result<Self> {
+        let mut items = BTreeMap::new();
+        if !src_dir.exists() {
+            return Ok(Self { items });
+        }
+
+        for entry in walkdir::WalkDir::new(src_dir)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|e| e.path().extension().and_then(|ext| ext.to_str()) == Some("rs"))
+        {
+            let source = std::fs::read_to_string(entry.path())?;
+            let Ok(file) = syn::parse_file(&source) else {
+                continue;
+            };
+            collect_items(&file.items, &mut items);
+        }
+
+        Ok(Self { items })
+    }
+}
+
+fn collect_items(syn_items: &[syn::Item], out: &mut BTreeMap<String, ApiItem>) {
+    for item in syn_items {
+        let public = match item {
+            syn::Item::Fn(i) => is_pub(&i.vis).then(|| ApiItem {
+                kind: "fn",
+                name: i.sig.ident.to_string(),
+                arity: Some(i.sig.inputs.len()),
+            }),
+            syn::Item::Struct(i) => is_pub(&i.vis).then(|| ApiItem {
+                kind: "struct",
+                name: i.ident.to_string(),
+                arity: None,
+            }),
+            syn::Item::Enum(i) => is_pub(&i.vis).then(|| ApiItem {
+                kind: "enum",
+                name: i.ident.to_string(),
+                arity: None,
+            }),
+            syn::Item::Trait(i) => is_pub(&i.vis).then(|| ApiItem {
+                kind: "trait",
+                name: i.ident.to_string(),
+                arity: None,
+            }),
+            syn::Item::Mod(i) => {
+                if let Some((_, inner)) = &i.content {
+                    collect_items(inner, out);
+                }
+                is_pub(&i.vis).then(|| ApiItem {
+                    kind: "mod",
+                    name: i.ident.to_string(),
+                    arity: None,
+                })
+            }
+            _ => None,
+        };
+
+        if let Some(item) = public {
+            out.insert(format!("{}:{}", item.kind, item.name), item);
+        }
+    }
+}
+
+fn is_pub(vis: &syn::Visibility) -> bool {
+    matches!(vis, syn::Visibility::Public(_))
+}
+
+/// Describes one incompatible change between two surface scans: an item
+/// that used to exist and either vanished or changed shape.
+#[derive(Debug, Clone)]
+pub struct ApiBreak {
+    pub kind: &'static str,
+    pub name: String,
+    pub description: String,
+}
+
+/// Compares `before` to `after` and returns every public item that was
+/// removed or had its shape (currently: `fn` arity) change. Additions are
+/// not breaks.
+pub fn diff_surfaces(before: &ApiSurface, after: &ApiSurface) -> Vec<ApiBreak> {
+    let mut breaks = Vec::new();
+
+    for (key, item) in &before.items {
+        match after.items.get(key) {
+            None => breaks.push(ApiBreak {
+                kind: item.kind,
+                name: item.name.clone(),
+                description: format!("public {} `{}` was removed", item.kind, item.name),
+            }),
+            Some(after_item) if after_item.arity != item.arity => breaks.push(ApiBreak {
+                kind: item.kind,
+                name: item.name.clone(),
+                description: format!(
+                    "public {} `{}` changed arity from {:?} to {:?}",
+                    item.kind, item.name, item.arity, after_item.arity
+                ),
+            }),
+            Some(_) => {}
+        }
+    }
+
+    breaks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write_src(dir: &Path, content: &str) {
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(dir.join("lib.rs"), content).unwrap();
+    }
+
+    #[test]
+    fn detects_a_removed_public_function() {
+        let dir = tempdir().unwrap();
+        write_src(dir.path(), "pub fn add(a: i32, b: i32) -> i32 { a + b }\n");
+        let before = ApiSurface::scan(dir.path()).unwrap();
+
+        write_src(dir.path(), "fn add(a: i32, b: i32) -> i32 { a + b }\n");
+        let after = ApiSurface::scan(dir.path()).unwrap();
+
+        let breaks = diff_surfaces(&before, &after);
+        assert_eq!(breaks.len(), 1);
+        assert!(breaks[0].description.contains("add"));
+    }
+
+    #[test]
+    fn detects_a_changed_arity() {
+        let dir = tempdir().unwrap();
+        write_src(dir.path(), "pub fn add(a: i32, b: i32) -> i32 { a + b }\n");
+        let before = ApiSurface::scan(dir.path()).unwrap();
+
+        write_src(dir.path(), "pub fn add(a: i32, b: i32, c: i32) -> i32 { a + b + c }\n");
+        let after = ApiSurface::scan(dir.path()).unwrap();
+
+        let breaks = diff_surfaces(&before, &after);
+        assert_eq!(breaks.len(), 1);
+        assert!(breaks[0].description.contains("arity"));
+    }
+
+    #[test]
+    fn additions_are_not_breaks() {
+        let dir = tempdir().unwrap();
+        write_src(dir.path(), "pub fn add(a: i32, b: i32) -> i32 { a + b }\n");
+        let before = ApiSurface::scan(dir.path()).unwrap();
+
+        write_src(
+            dir.path(),
+            "pub fn add(a: i32, b: i32) -> i32 { a + b }\npub fn sub(a: i32, b: i32) -> i32 { a - b }\n",
+        );
+        let after = ApiSurface::scan(dir.path()).unwrap();
+
+        assert!(diff_surfaces(&before, &after).is_empty());
+    }
+}
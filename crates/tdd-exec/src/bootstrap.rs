@@ -0,0 +1,218 @@
+//! Runs a one-time environment-provisioning command (installing a
+//! toolchain version, pulling dependencies not vendored in the repo),
+//! skipping it on later runs unless the command or the script it invokes
+//! changed since the last successful run.
+
+use crate::process::{run_command_captured, CaptureConfig};
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use tdd_core::{Redactor, RunnerOutcome};
+
+/// What happened when [`BootstrapRunner::run`] was asked to provision the
+/// environment.
+#[derive(Debug, Clone)]
+pub struct BootstrapResult {
+    /// Whether the command actually ran; `false` means it was skipped.
+    pub ran: bool,
+    /// Why it ran or was skipped, e.g. `"environment definition changed —
+    /// run again"` or `"up to date"`, recorded alongside the result in a
+    /// step log or `doctor` report.
+    pub reason: String,
+    /// The command's outcome, present only when `ran` is `true`.
+    pub outcome: Option<RunnerOutcome>,
+}
+
+/// Runs `command` at most once per environment definition: a hash of the
+/// command array plus the contents of the script it invokes (when its
+/// first or second argument is an existing file under `repo_root`),
+/// compared against a hash this runner itself wrote into `marker_path`
+/// after the last successful run. A marker that exists but is empty is
+/// treated as an unconditional skip, so a legacy `touch`'d marker from
+/// before content-awareness keeps working.
+pub struct BootstrapRunner {
+    repo_root: PathBuf,
+    command: Vec<String>,
+    marker_path: PathBuf,
+    redactor: Option<Redactor>,
+}
+
+impl BootstrapRunner {
+    pub fn new(repo_root: impl Into<PathBuf>, command: Vec<String>, marker_path: impl Into<PathBuf>) -> Self {
+        Self {
+            repo_root: repo_root.into(),
+            command,
+            marker_path: marker_path.into(),
+            redactor: None,
+        }
+    }
+
+    /// Redacts known credential values out of the bootstrap command's
+    /// captured output — a provisioning script echoing its environment is
+    /// exactly the case this was added for. `None` (the default) redacts
+    /// nothing.
+    pub fn with_redactor(mut self, redactor: Option<Redactor>) -> Self {
+        self.redactor = redactor;
+        self
+    }
+
+    /// The hash [`Self::run`] compares against the marker's stored
+    /// contents.
+    pub fn environment_hash(&self) -> String {
+        environment_hash(&self.repo_root, &self.command)
+    }
+
+    /// Whether the stored marker's hash is stale relative to the current
+    /// command and script contents, without running anything. `None` when
+    /// there's no hash-bearing marker to compare (missing, or a legacy
+    /// empty marker) — that isn't staleness, just nothing recorded yet.
+    pub fn is_stale(&self) -> Option<bool> {
+        let stored = std::fs::read_to_string(self.repo_root.join(&self.marker_path)).ok()?;
+        let stored = stored.trim();
+        if stored.is_empty() {
+            return None;
+        }
+        Some(stored != self.environment_hash())
+    }
+
+    pub fn run(&self) -> anyhow::Result<BootstrapResult> {
+        let marker = self.repo_root.join(&self.marker_path);
+        let expected = self.environment_hash();
+
+        if let Ok(stored) = std::fs::read_to_string(&marker) {
+            let stored = stored.trim();
+            if stored.is_empty() {
+                return Ok(BootstrapResult { ran: false, reason: "skipped: legacy empty marker present".to_string(), outcome: None });
+            }
+            if stored == expected {
+                return Ok(BootstrapResult { ran: false, reason: "up to date".to_string(), outcome: None });
+            }
+        }
+
+        let reason = if marker.exists() { "environment definition changed — run again".to_string() } else { "first run".to_string() };
+
+        let (program, args) = self.command.split_first().ok_or_else(|| anyhow::anyhow!("bootstrap command is empty"))?;
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        let outcome = run_command_captured(
+            program,
+            &args,
+            &self.repo_root,
+            &CaptureConfig {
+                label: "bootstrap".to_string(),
+                redactor: self.redactor.clone(),
+                ..Default::default()
+            },
+        )?;
+
+        if outcome.ok {
+            if let Some(parent) = marker.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&marker, &expected)?;
+        }
+
+        Ok(BootstrapResult { ran: true, reason, outcome: Some(outcome) })
+    }
+}
+
+/// The environment-definition hash: the command array plus the contents
+/// of the script it invokes, when its first or second argument names an
+/// existing file under `repo_root`.
+fn environment_hash(repo_root: &Path, command: &[String]) -> String {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    command.hash(&mut hasher);
+    for arg in command.iter().take(2) {
+        if let Ok(contents) = std::fs::read_to_string(repo_root.join(arg)) {
+            contents.hash(&mut hasher);
+        }
+    }
+    format!("{:016x}", hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write_script(dir: &Path, contents: &str) -> PathBuf {
+        let script = dir.join("bootstrap.sh");
+        std::fs::write(&script, contents).unwrap();
+        script
+    }
+
+    #[test]
+    fn the_first_run_executes_the_command_and_writes_a_hash_marker() {
+        let dir = tempdir().unwrap();
+        write_script(dir.path(), "#!/bin/sh\nexit 0\n");
+        let runner = BootstrapRunner::new(dir.path(), vec!["sh".into(), "bootstrap.sh".into()], "marker");
+
+        let result = runner.run().unwrap();
+
+        assert!(result.ran);
+        assert_eq!(result.reason, "first run");
+        assert!(result.outcome.unwrap().ok);
+        let marker = std::fs::read_to_string(dir.path().join("marker")).unwrap();
+        assert_eq!(marker, runner.environment_hash());
+    }
+
+    #[test]
+    fn a_second_run_with_an_unchanged_script_is_skipped() {
+        let dir = tempdir().unwrap();
+        write_script(dir.path(), "#!/bin/sh\nexit 0\n");
+        let runner = BootstrapRunner::new(dir.path(), vec!["sh".into(), "bootstrap.sh".into()], "marker");
+        runner.run().unwrap();
+
+        let result = runner.run().unwrap();
+
+        assert!(!result.ran);
+        assert_eq!(result.reason, "up to date");
+    }
+
+    #[test]
+    fn editing_the_script_re_triggers_a_run() {
+        let dir = tempdir().unwrap();
+        write_script(dir.path(), "#!/bin/sh\nexit 0\n");
+        let runner = BootstrapRunner::new(dir.path(), vec!["sh".into(), "bootstrap.sh".into()], "marker");
+        runner.run().unwrap();
+
+        write_script(dir.path(), "#!/bin/sh\n# toolchain bumped\nexit 0\n");
+        let result = runner.run().unwrap();
+
+        assert!(result.ran);
+        assert_eq!(result.reason, "environment definition changed — run again");
+    }
+
+    #[test]
+    fn a_legacy_empty_marker_skips_unconditionally() {
+        let dir = tempdir().unwrap();
+        write_script(dir.path(), "#!/bin/sh\nexit 0\n");
+        std::fs::write(dir.path().join("marker"), "").unwrap();
+        let runner = BootstrapRunner::new(dir.path(), vec!["sh".into(), "bootstrap.sh".into()], "marker");
+
+        let result = runner.run().unwrap();
+
+        assert!(!result.ran);
+        assert_eq!(result.reason, "skipped: legacy empty marker present");
+    }
+
+    #[test]
+    fn is_stale_reports_a_mismatch_after_the_script_changes() {
+        let dir = tempdir().unwrap();
+        write_script(dir.path(), "#!/bin/sh\nexit 0\n");
+        let runner = BootstrapRunner::new(dir.path(), vec!["sh".into(), "bootstrap.sh".into()], "marker");
+        runner.run().unwrap();
+        assert_eq!(runner.is_stale(), Some(false));
+
+        write_script(dir.path(), "#!/bin/sh\n# toolchain bumped\nexit 0\n");
+        assert_eq!(runner.is_stale(), Some(true));
+    }
+
+    #[test]
+    fn is_stale_is_none_without_a_hash_bearing_marker() {
+        let dir = tempdir().unwrap();
+        let runner = BootstrapRunner::new(dir.path(), vec!["sh".into(), "bootstrap.sh".into()], "marker");
+        assert_eq!(runner.is_stale(), None);
+
+        std::fs::write(dir.path().join("marker"), "").unwrap();
+        assert_eq!(runner.is_stale(), None);
+    }
+}
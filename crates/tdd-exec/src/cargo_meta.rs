@@ -0,0 +1,62 @@
+//! Resolves the Rust identifier code must `use` to reach the workspace's
+//! library crate, read from its own `Cargo.toml`. Exists because a kata's
+//! package name ("string-calculator") and the module path agents import it
+//! under ("string_calculator") diverge whenever the name contains a dash,
+//! and an agent guessing the wrong one burns an attempt on a compile error.
+
+use std::path::Path;
+
+/// Reads `Cargo.toml`'s `package.name` under `repo_root` and returns the
+/// identifier it's imported under (dashes become underscores). `None` if
+/// the manifest is missing or has no readable `package.name`.
+pub fn resolve_crate_name(repo_root: &Path) -> Option<String> {
+    let manifest = std::fs::read_to_string(repo_root.join("Cargo.toml")).ok()?;
+    package_name(&manifest).map(|name| name.replace('-', "_"))
+}
+
+/// Extracts `package.name`'s value from raw `Cargo.toml` text without a
+/// full TOML parser: finds the `[package]` table, then the first `name =`
+/// line before the next `[`-led table header.
+fn package_name(manifest: &str) -> Option<String> {
+    let package_start = manifest.lines().position(|line| line.trim() == "[package]")?;
+    manifest
+        .lines()
+        .skip(package_start + 1)
+        .take_while(|line| !line.trim_start().starts_with('['))
+        .find_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            if key.trim() != "name" {
+                return None;
+            }
+            Some(value.trim().trim_matches('"').to_string())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn converts_dashes_in_the_package_name_to_underscores() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"string-calculator\"\nedition = \"2021\"\n").unwrap();
+
+        assert_eq!(resolve_crate_name(dir.path()), Some("string_calculator".to_string()));
+    }
+
+    #[test]
+    fn ignores_a_name_key_outside_the_package_table() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nversion = \"0.1.0\"\n\n[dependencies]\nname = \"not-the-package\"\n").unwrap();
+
+        assert_eq!(resolve_crate_name(dir.path()), None);
+    }
+
+    #[test]
+    fn returns_none_when_the_manifest_is_missing() {
+        let dir = tempdir().unwrap();
+
+        assert_eq!(resolve_crate_name(dir.path()), None);
+    }
+}
@@ -0,0 +1,65 @@
+//! Content hashing for skipping a verification stage between retry
+//! attempts within the same step when its inputs haven't changed. See
+//! [`crate::runner::CargoRunner`], whose stages this hashes the inputs of.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+/// Hashes the current on-disk contents of `paths`, relative to
+/// `repo_root`, in the order given. A path that doesn't exist (a file an
+/// attempt deleted) hashes as absent rather than erroring, so deleting a
+/// stage's input still changes the hash instead of panicking. Callers
+/// must pass the same `paths` across calls to get a comparable hash —
+/// this has no notion of "this stage's inputs" on its own.
+pub fn hash_stage_inputs(repo_root: &Path, paths: &[String]) -> String {
+    let mut hasher = DefaultHasher::new();
+    for path in paths {
+        path.hash(&mut hasher);
+        match std::fs::read(repo_root.join(path)) {
+            Ok(bytes) => bytes.hash(&mut hasher),
+            Err(_) => "<missing>".hash(&mut hasher),
+        }
+    }
+    format!("{:016x}", hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn the_same_file_contents_hash_the_same() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("a.rs"), "fn a() {}").unwrap();
+
+        let first = hash_stage_inputs(dir.path(), &["a.rs".to_string()]);
+        let second = hash_stage_inputs(dir.path(), &["a.rs".to_string()]);
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn editing_a_hashed_file_changes_the_hash() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("a.rs"), "fn a() {}").unwrap();
+        let before = hash_stage_inputs(dir.path(), &["a.rs".to_string()]);
+
+        std::fs::write(dir.path().join("a.rs"), "fn a() { /* changed */ }").unwrap();
+        let after = hash_stage_inputs(dir.path(), &["a.rs".to_string()]);
+
+        assert_ne!(before, after);
+    }
+
+    #[test]
+    fn a_missing_file_hashes_differently_than_a_present_one() {
+        let dir = tempdir().unwrap();
+        let absent = hash_stage_inputs(dir.path(), &["a.rs".to_string()]);
+
+        std::fs::write(dir.path().join("a.rs"), "fn a() {}").unwrap();
+        let present = hash_stage_inputs(dir.path(), &["a.rs".to_string()]);
+
+        assert_ne!(absent, present);
+    }
+}
@@ -0,0 +1,92 @@
+//! Parses `rustc`/`cargo check` diagnostics out of a check stage's
+//! stderr, for orchestrator logic that needs to tell "doesn't compile
+//! because a referenced item doesn't exist yet" apart from any other
+//! compile error.
+
+/// One `error[E####]` diagnostic and the file its first `--> path:line:col`
+/// location line points at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompileError {
+    pub code: String,
+    pub file: String,
+}
+
+/// Parses every `error[E####]: ...` diagnostic out of `stderr`, pairing
+/// each with the file from its first location line. Diagnostics without a
+/// recognizable location line are dropped rather than guessed at.
+pub fn parse_compile_errors(stderr: &str) -> Vec<CompileError> {
+    let mut errors = Vec::new();
+    let mut pending_code: Option<String> = None;
+    for line in stderr.lines() {
+        let trimmed = line.trim_start();
+        if let Some(code) = trimmed.strip_prefix("error[").and_then(|rest| rest.split(']').next()) {
+            pending_code = Some(code.to_string());
+            continue;
+        }
+        if let Some(code) = pending_code.take() {
+            if let Some(location) = trimmed.strip_prefix("--> ") {
+                if let Some(file) = location.split(':').next() {
+                    errors.push(CompileError { code, file: file.to_string() });
+                }
+            }
+        }
+    }
+    errors
+}
+
+/// Whether every parsed compile error in `stderr` is an unresolved-name
+/// diagnostic (`E0425` "cannot find value", `E0433` "failed to resolve")
+/// located in one of `test_files` — the shape of a type-driven kata's
+/// intentionally uncompilable starting point, as opposed to an unrelated
+/// compile error the agent should still be held to.
+pub fn is_missing_item_only(stderr: &str, test_files: &[String]) -> bool {
+    let errors = parse_compile_errors(stderr);
+    !errors.is_empty() && errors.iter().all(|error| matches!(error.code.as_str(), "E0425" | "E0433") && test_files.iter().any(|file| file == &error.file))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_code_and_file_of_each_diagnostic() {
+        let stderr = "error[E0425]: cannot find value `b` in this scope\n --> tests/api.rs:3:24\n  |\n3 | ... b ...\n";
+
+        let errors = parse_compile_errors(stderr);
+
+        assert_eq!(errors, vec![CompileError { code: "E0425".to_string(), file: "tests/api.rs".to_string() }]);
+    }
+
+    #[test]
+    fn a_missing_function_referenced_only_from_the_new_test_is_tolerated() {
+        let stderr = "error[E0425]: cannot find function `add` in this scope\n --> tests/api.rs:2:5\n";
+
+        assert!(is_missing_item_only(stderr, &["tests/api.rs".to_string()]));
+    }
+
+    #[test]
+    fn a_missing_type_referenced_via_an_unresolved_path_is_tolerated() {
+        let stderr = "error[E0433]: failed to resolve: use of undeclared type `Matrix`\n --> tests/matrix.rs:4:9\n";
+
+        assert!(is_missing_item_only(stderr, &["tests/matrix.rs".to_string()]));
+    }
+
+    #[test]
+    fn an_error_outside_the_step_s_own_test_files_is_not_tolerated() {
+        let stderr = "error[E0425]: cannot find value `b` in this scope\n --> src/lib.rs:3:24\n";
+
+        assert!(!is_missing_item_only(stderr, &["tests/api.rs".to_string()]));
+    }
+
+    #[test]
+    fn an_unrelated_compile_error_is_not_tolerated() {
+        let stderr = "error[E0308]: mismatched types\n --> tests/api.rs:3:24\n";
+
+        assert!(!is_missing_item_only(stderr, &["tests/api.rs".to_string()]));
+    }
+
+    #[test]
+    fn no_diagnostics_at_all_is_not_tolerated() {
+        assert!(!is_missing_item_only("", &["tests/api.rs".to_string()]));
+    }
+}
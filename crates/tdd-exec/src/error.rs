@@ -0,0 +1,85 @@
+//! Error types for process execution and git operations.
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ExecError {
+    #[error("failed to spawn `{command}`: {source}")]
+    Spawn {
+        command: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("git operation failed: {0}")]
+    Git(#[from] git2::Error),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    /// A repository's `pre-commit` or `commit-msg` hook exited non-zero
+    /// under `git.hooks: run`.
+    #[error("`{hook}` hook rejected the commit: {stderr}")]
+    HookRejected { hook: String, stderr: String },
+}
+
+impl From<&ExecError> for tdd_core::StepFailureDetail {
+    fn from(error: &ExecError) -> Self {
+        match error {
+            ExecError::Git(_) => tdd_core::StepFailureDetail::Vcs { message: error.to_string() },
+            ExecError::Spawn { .. } | ExecError::Io(_) => tdd_core::StepFailureDetail::Other { message: error.to_string() },
+            ExecError::HookRejected { hook, stderr } => tdd_core::StepFailureDetail::HookRejected {
+                hook: hook.clone(),
+                stderr: stderr.clone(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_git_error_becomes_a_vcs_detail() {
+        let error = ExecError::Git(git2::Error::from_str("object not found"));
+        let detail = tdd_core::StepFailureDetail::from(&error);
+        assert_eq!(
+            detail,
+            tdd_core::StepFailureDetail::Vcs {
+                message: error.to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn a_spawn_error_becomes_an_other_detail() {
+        let error = ExecError::Spawn {
+            command: "cargo".to_string(),
+            source: std::io::Error::other("not found"),
+        };
+        let detail = tdd_core::StepFailureDetail::from(&error);
+        assert_eq!(
+            detail,
+            tdd_core::StepFailureDetail::Other {
+                message: error.to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn a_hook_rejected_error_becomes_a_hook_rejected_detail() {
+        let error = ExecError::HookRejected {
+            hook: "pre-commit".to_string(),
+            stderr: "file.bin is over the 1 MB limit".to_string(),
+        };
+        let detail = tdd_core::StepFailureDetail::from(&error);
+        assert_eq!(
+            detail,
+            tdd_core::StepFailureDetail::HookRejected {
+                hook: "pre-commit".to_string(),
+                stderr: "file.bin is over the 1 MB limit".to_string(),
+            }
+        );
+    }
+}
@@ -0,0 +1,373 @@
+//! A [`tdd_core::Vcs`] implementation backed by `git2`.
+
+use git2::{DiffOptions, Repository, Signature};
+use std::path::{Path, PathBuf};
+use tdd_core::{RepoState, Vcs};
+
+/// Author identity used for bot commits.
+pub struct CommitAuthor {
+    pub name: String,
+    pub email: String,
+}
+
+impl Default for CommitAuthor {
+    fn default() -> Self {
+        Self {
+            name: "TDD Machine".to_string(),
+            email: "tdd@local".to_string(),
+        }
+    }
+}
+
+/// Git-backed implementation of [`Vcs`].
+pub struct GitVcs {
+    repo_root: PathBuf,
+    author: CommitAuthor,
+}
+
+impl GitVcs {
+    pub fn new(repo_root: impl Into<PathBuf>, author: CommitAuthor) -> Self {
+        Self {
+            repo_root: repo_root.into(),
+            author,
+        }
+    }
+
+    /// Opens an existing repository for read-only callers (`status`,
+    /// `doctor`): fails fast with a clear error if `repo_root` isn't a git
+    /// repository, rather than silently falling back to
+    /// [`Vcs::init_if_needed`]'s `Repository::init` the way a real run
+    /// would.
+    pub fn open_existing(repo_root: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let repo_root = repo_root.into();
+        Repository::open(&repo_root)?;
+        Ok(Self::new(repo_root, CommitAuthor::default()))
+    }
+
+    fn open(&self) -> anyhow::Result<Repository> {
+        Ok(Repository::open(&self.repo_root)?)
+    }
+
+    /// The repository's actual working directory, as git2 resolves it —
+    /// distinct from the configured `repo_root` only for a linked
+    /// worktree opened via some other path, or never, for a bare repo.
+    /// Callers that compute paths relative to "the repo" (staging,
+    /// pathspec diffs, changed-path queries) should resolve against this
+    /// rather than assuming `repo_root` and the working directory match.
+    pub fn workdir(&self) -> anyhow::Result<PathBuf> {
+        self.open()?
+            .workdir()
+            .map(Path::to_path_buf)
+            .ok_or_else(|| anyhow::anyhow!("{} is a bare repository with no working directory", self.repo_root.display()))
+    }
+}
+
+impl Vcs for GitVcs {
+    #[tracing::instrument(skip(self), fields(repo_root = %self.repo_root.display()))]
+    fn init_if_needed(&self) -> anyhow::Result<()> {
+        if Repository::open(&self.repo_root).is_err() {
+            Repository::init(&self.repo_root)?;
+            tracing::info!("initialized git repository");
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    fn read_state(&self) -> anyhow::Result<RepoState> {
+        let repo = self.open()?;
+
+        let (last_commit_message, last_diff) = match repo.head().ok().and_then(|h| h.peel_to_commit().ok()) {
+            Some(commit) => {
+                let message = commit.message().unwrap_or_default().to_string();
+                let tree = commit.tree()?;
+                let parent_tree = commit.parents().next().and_then(|p| p.tree().ok());
+                let mut opts = DiffOptions::new();
+                let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut opts))?;
+                let mut buf = String::new();
+                diff.print(git2::DiffFormat::Patch, |_, _, line| {
+                    if let Ok(content) = std::str::from_utf8(line.content()) {
+                        buf.push_str(content);
+                    }
+                    true
+                })?;
+                (message, buf)
+            }
+            None => (String::new(), String::new()),
+        };
+
+        let files = walkdir::WalkDir::new(&self.repo_root)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_file())
+            .filter(|entry| !entry.path().components().any(|c| c.as_os_str() == ".git"))
+            .filter_map(|entry| {
+                entry
+                    .path()
+                    .strip_prefix(&self.repo_root)
+                    .ok()
+                    .map(|p| p.to_string_lossy().into_owned())
+            })
+            .collect();
+
+        Ok(RepoState {
+            last_commit_message,
+            last_diff,
+            files,
+        })
+    }
+
+    #[tracing::instrument(skip(self))]
+    fn stage_all(&self) -> anyhow::Result<()> {
+        let repo = self.open()?;
+        let mut index = repo.index()?;
+        index.add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)?;
+        index.write()?;
+        Ok(())
+    }
+
+    /// The commit message is intentionally not logged as a span field: it
+    /// often carries a diff-derived summary and shouldn't be duplicated
+    /// into trace output.
+    #[tracing::instrument(skip(self, message))]
+    fn commit(&self, message: &str) -> anyhow::Result<String> {
+        let repo = self.open()?;
+        let mut index = repo.index()?;
+        let tree_id = index.write_tree()?;
+        let tree = repo.find_tree(tree_id)?;
+        let signature = Signature::now(&self.author.name, &self.author.email)?;
+
+        let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+
+        let commit_id = repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &parents)?;
+        tracing::info!(commit = %commit_id, "created commit");
+        Ok(commit_id.to_string())
+    }
+
+    fn diff_against_head(&self, paths: &[String]) -> anyhow::Result<String> {
+        let repo = self.open()?;
+        let head_tree = repo.head().ok().and_then(|h| h.peel_to_tree().ok());
+
+        let mut opts = DiffOptions::new();
+        for path in paths {
+            opts.pathspec(path);
+        }
+        let diff = repo.diff_tree_to_workdir_with_index(head_tree.as_ref(), Some(&mut opts))?;
+
+        let mut buf = String::new();
+        diff.print(git2::DiffFormat::Patch, |_, _, line| {
+            if let Ok(content) = std::str::from_utf8(line.content()) {
+                buf.push_str(content);
+            }
+            true
+        })?;
+        Ok(buf)
+    }
+
+    fn discard_paths(&self, paths: &[String]) -> anyhow::Result<()> {
+        let repo = self.open()?;
+        let head_tree = repo.head().ok().and_then(|h| h.peel_to_tree().ok());
+
+        for path in paths {
+            let target = self.repo_root.join(path);
+            match head_tree.as_ref().and_then(|tree| tree.get_path(Path::new(path)).ok()) {
+                Some(entry) => {
+                    let blob = repo.find_blob(entry.id())?;
+                    if let Some(parent) = target.parent() {
+                        std::fs::create_dir_all(parent)?;
+                    }
+                    std::fs::write(&target, blob.content())?;
+                }
+                None => {
+                    if target.exists() {
+                        std::fs::remove_file(&target)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn head_commit_timestamp(&self) -> anyhow::Result<Option<std::time::SystemTime>> {
+        let repo = self.open()?;
+        let Some(commit) = repo.head().ok().and_then(|h| h.peel_to_commit().ok()) else {
+            return Ok(None);
+        };
+        let seconds = commit.time().seconds();
+        Ok(Some(std::time::UNIX_EPOCH + std::time::Duration::from_secs(seconds.max(0) as u64)))
+    }
+
+    fn recently_changed_paths(&self, n_commits: usize) -> anyhow::Result<Vec<String>> {
+        let repo = self.open()?;
+        let Some(head) = repo.head().ok().and_then(|h| h.peel_to_commit().ok()) else {
+            return Ok(Vec::new());
+        };
+
+        let mut paths = Vec::new();
+        let mut commit = Some(head);
+        for _ in 0..n_commits {
+            let Some(current) = commit else { break };
+            let tree = current.tree()?;
+            let parent_tree = current.parents().next().and_then(|p| p.tree().ok());
+            let mut opts = DiffOptions::new();
+            let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut opts))?;
+            diff.foreach(
+                &mut |delta, _| {
+                    if let Some(path) = delta.new_file().path().map(|p| p.to_string_lossy().into_owned()) {
+                        if !paths.contains(&path) {
+                            paths.push(path);
+                        }
+                    }
+                    true
+                },
+                None,
+                None,
+                None,
+            )?;
+            commit = current.parents().next();
+        }
+
+        Ok(paths)
+    }
+
+    fn diff_range(&self, from: Option<&str>, to: &str) -> anyhow::Result<String> {
+        let repo = self.open()?;
+        let diff = self.diff_range_diff(&repo, from, to)?;
+
+        let mut buf = String::new();
+        diff.print(git2::DiffFormat::Patch, |_, _, line| {
+            if let Ok(content) = std::str::from_utf8(line.content()) {
+                buf.push_str(content);
+            }
+            true
+        })?;
+        Ok(buf)
+    }
+
+    fn diff_range_stat(&self, from: Option<&str>, to: &str) -> anyhow::Result<String> {
+        let repo = self.open()?;
+        let diff = self.diff_range_diff(&repo, from, to)?;
+        let stats = diff.stats()?;
+        let buf = stats.to_buf(git2::DiffStatsFormat::FULL, 80)?;
+        Ok(buf.as_str().unwrap_or_default().to_string())
+    }
+
+    fn create_branch_from(&self, name: &str, commit: &str) -> anyhow::Result<()> {
+        let repo = self.open()?;
+        let commit = repo.revparse_single(commit)?.peel_to_commit()?;
+        repo.branch(name, &commit, false)?;
+        Ok(())
+    }
+
+    fn checkout(&self, name: &str) -> anyhow::Result<()> {
+        let repo = self.open()?;
+        let (object, reference) = repo.revparse_ext(name)?;
+        repo.checkout_tree(&object, None)?;
+        match reference {
+            Some(reference) => repo.set_head(reference.name().ok_or_else(|| anyhow::anyhow!("branch {name} has no name"))?)?,
+            None => repo.set_head_detached(object.id())?,
+        }
+        Ok(())
+    }
+
+    fn merge_ff(&self, name: &str, no_ff: bool) -> anyhow::Result<String> {
+        let repo = self.open()?;
+        let their_commit = repo.find_branch(name, git2::BranchType::Local)?.get().peel_to_commit()?;
+        let our_head = repo.head()?.peel_to_commit()?;
+
+        if !no_ff && repo.graph_descendant_of(their_commit.id(), our_head.id())? {
+            let head_ref_name = repo.head()?.name().ok_or_else(|| anyhow::anyhow!("HEAD has no branch name to fast-forward"))?.to_string();
+            let mut head_ref = repo.find_reference(&head_ref_name)?;
+            head_ref.set_target(their_commit.id(), "fast-forward merge")?;
+            repo.set_head(&head_ref_name)?;
+            repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))?;
+            return Ok(their_commit.id().to_string());
+        }
+
+        let tree_id = repo.merge_commits(&our_head, &their_commit, None)?.write_tree_to(&repo)?;
+        let tree = repo.find_tree(tree_id)?;
+        let signature = Signature::now(&self.author.name, &self.author.email)?;
+        let message = format!("Merge branch '{name}'");
+        let commit_id = repo.commit(Some("HEAD"), &signature, &signature, &message, &tree, &[&our_head, &their_commit])?;
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))?;
+        Ok(commit_id.to_string())
+    }
+
+    fn is_detached(&self) -> anyhow::Result<bool> {
+        Ok(self.open()?.head_detached()?)
+    }
+
+    fn head_commit_id(&self) -> anyhow::Result<String> {
+        Ok(self.open()?.head()?.peel_to_commit()?.id().to_string())
+    }
+
+    fn reset_hard(&self, commit: &str) -> anyhow::Result<()> {
+        let repo = self.open()?;
+        let object = repo.revparse_single(commit)?;
+        repo.reset(&object, git2::ResetType::Hard, None)?;
+        self.remove_untracked(&repo)
+    }
+
+    fn is_clean(&self) -> anyhow::Result<bool> {
+        let repo = self.open()?;
+        let mut options = git2::StatusOptions::new();
+        options.include_untracked(true).recurse_untracked_dirs(true).include_ignored(false);
+        let statuses = repo.statuses(Some(&mut options))?;
+        Ok(statuses.is_empty())
+    }
+}
+
+impl GitVcs {
+    fn diff_range_diff<'repo>(&self, repo: &'repo Repository, from: Option<&str>, to: &str) -> anyhow::Result<git2::Diff<'repo>> {
+        let from_tree = from.map(|from| repo.revparse_single(from)?.peel_to_tree()).transpose()?;
+        let to_tree = repo.revparse_single(to)?.peel_to_tree()?;
+        Ok(repo.diff_tree_to_tree(from_tree.as_ref(), Some(&to_tree), None)?)
+    }
+
+    /// Removes every untracked (non-ignored) file `reset_hard` leaves
+    /// behind, most-nested first so a now-empty directory can be removed
+    /// right after its last file.
+    fn remove_untracked(&self, repo: &Repository) -> anyhow::Result<()> {
+        let mut options = git2::StatusOptions::new();
+        options.include_untracked(true).recurse_untracked_dirs(true).include_ignored(false);
+        let workdir = self.workdir()?;
+        let mut paths: Vec<PathBuf> = repo
+            .statuses(Some(&mut options))?
+            .iter()
+            .filter(|entry| entry.status().contains(git2::Status::WT_NEW))
+            .filter_map(|entry| entry.path().map(|path| workdir.join(path)))
+            .collect();
+        paths.sort_by_key(|path| std::cmp::Reverse(path.components().count()));
+        for path in paths {
+            if path.is_dir() {
+                let _ = std::fs::remove_dir_all(&path);
+            } else {
+                let _ = std::fs::remove_file(&path);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The branch currently checked out at `repo_root`, for callers (e.g.
+/// `run --review-branch`) that need to remember which branch to return
+/// cycle branches to without hardcoding a name like `main`. Errors if
+/// `HEAD` is detached, since there's no branch to return to.
+pub fn current_branch_name(repo_root: &Path) -> anyhow::Result<String> {
+    let repo = Repository::open(repo_root)?;
+    let head = repo.head()?;
+    let name = head
+        .shorthand()
+        .ok_or_else(|| anyhow::anyhow!("HEAD is detached; --review-branch needs a branch to return cycles to"))?;
+    Ok(name.to_string())
+}
+
+/// Returns the path to the `.git` directory's parent, i.e. the repo root,
+/// discovering it from any path inside the working tree.
+pub fn discover_repo_root(start: &Path) -> anyhow::Result<PathBuf> {
+    let repo = Repository::discover(start)?;
+    Ok(repo
+        .workdir()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| start.to_path_buf()))
+}
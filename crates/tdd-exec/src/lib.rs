@@ -0,0 +1,115 @@
+//! Process execution, git operations, and filesystem utilities used by the
+//! orchestrator to verify and persist each agent's work.
+
+pub mod api_scan;
+pub mod bootstrap;
+pub mod cargo_meta;
+pub mod ci_cache;
+pub mod compile_errors;
+pub mod error;
+pub mod git;
+pub mod process;
+pub mod runner;
+
+pub use api_scan::{diff_surfaces, ApiBreak, ApiSurface};
+pub use bootstrap::{BootstrapResult, BootstrapRunner};
+pub use cargo_meta::resolve_crate_name;
+pub use ci_cache::hash_stage_inputs;
+pub use compile_errors::{is_missing_item_only, parse_compile_errors, CompileError};
+pub use error::ExecError;
+pub use git::{current_branch_name, discover_repo_root, CommitAuthor, GitVcs};
+pub use process::{run_command, run_command_captured, CaptureConfig};
+pub use runner::CargoRunner;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tdd_core::Vcs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn init_if_needed_creates_a_repo_once() {
+        let dir = tempdir().unwrap();
+        let vcs = GitVcs::new(dir.path(), CommitAuthor::default());
+
+        vcs.init_if_needed().unwrap();
+        vcs.init_if_needed().unwrap();
+
+        assert!(dir.path().join(".git").exists());
+    }
+
+    #[test]
+    fn stage_and_commit_round_trips_through_read_state() {
+        let dir = tempdir().unwrap();
+        let vcs = GitVcs::new(dir.path(), CommitAuthor::default());
+        vcs.init_if_needed().unwrap();
+
+        std::fs::write(dir.path().join("kata.md"), "# Kata\n").unwrap();
+        vcs.stage_all().unwrap();
+        vcs.commit("test: seed kata description").unwrap();
+
+        let state = vcs.read_state().unwrap();
+        assert_eq!(state.last_commit_message.trim(), "test: seed kata description");
+        assert!(state.files.contains(&"kata.md".to_string()));
+    }
+
+    #[test]
+    fn concurrent_read_state_and_commit_calls_from_two_threads_both_succeed() {
+        // GitVcs opens a fresh `Repository` per call instead of holding
+        // one behind a lock, so there's no shared mutable state for one
+        // thread's operation to race or poison for the other.
+        let dir = tempdir().unwrap();
+        let vcs = std::sync::Arc::new(GitVcs::new(dir.path(), CommitAuthor::default()));
+        vcs.init_if_needed().unwrap();
+        std::fs::write(dir.path().join("a.txt"), "a").unwrap();
+        vcs.stage_all().unwrap();
+        vcs.commit("test: seed a").unwrap();
+
+        let reader = {
+            let vcs = vcs.clone();
+            std::thread::spawn(move || for _ in 0..20 {
+                vcs.read_state().unwrap();
+            })
+        };
+        let committer = {
+            let vcs = vcs.clone();
+            let dir = dir.path().to_path_buf();
+            std::thread::spawn(move || for i in 0..20 {
+                std::fs::write(dir.join("b.txt"), i.to_string()).unwrap();
+                vcs.stage_all().unwrap();
+                vcs.commit(&format!("test: update b ({i})")).unwrap();
+            })
+        };
+
+        reader.join().unwrap();
+        committer.join().unwrap();
+
+        let state = vcs.read_state().unwrap();
+        assert_eq!(state.last_commit_message.trim(), "test: update b (19)");
+    }
+
+    #[test]
+    fn a_panic_in_one_operation_never_poisons_a_later_one() {
+        // There's no mutex guarding repository state for a panic to
+        // poison; a fresh `Repository::open` on the next call is
+        // unaffected by an earlier thread unwinding mid-operation.
+        let dir = tempdir().unwrap();
+        let vcs = std::sync::Arc::new(GitVcs::new(dir.path(), CommitAuthor::default()));
+        vcs.init_if_needed().unwrap();
+        std::fs::write(dir.path().join("a.txt"), "a").unwrap();
+        vcs.stage_all().unwrap();
+        vcs.commit("test: seed a").unwrap();
+
+        let panicking = {
+            let vcs = vcs.clone();
+            std::thread::spawn(move || {
+                let _state = vcs.read_state().unwrap();
+                panic!("simulated failure mid-operation");
+            })
+        };
+        assert!(panicking.join().is_err());
+
+        let state = vcs.read_state().unwrap();
+        assert_eq!(state.last_commit_message.trim(), "test: seed a");
+    }
+}
@@ -0,0 +1,172 @@
+//! Runs external commands and captures their output for the `Runner`
+//! implementations.
+
+use crate::error::ExecError;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Instant;
+use tdd_core::{CapturedOutput, Redactor, RunnerOutcome, DEFAULT_CAPTURE_LIMIT_BYTES};
+
+/// Runs `program args...` in `cwd`, returning captured stdout/stderr and
+/// whether the process exited successfully. Output is capped at
+/// [`DEFAULT_CAPTURE_LIMIT_BYTES`] per stream with no spill-to-disk; callers
+/// that want the full output preserved past the cap should use
+/// [`run_command_captured`] instead.
+#[tracing::instrument(skip(cwd), fields(elapsed_ms, ok, exit_code))]
+pub fn run_command(program: &str, args: &[&str], cwd: &Path) -> Result<RunnerOutcome, ExecError> {
+    run_command_captured(program, args, cwd, &CaptureConfig::default())
+}
+
+/// How much of a command's output [`run_command_captured`] keeps resident,
+/// and where the rest goes when a stream runs past that cap.
+#[derive(Debug, Clone)]
+pub struct CaptureConfig {
+    pub limit_bytes: usize,
+    /// Directory a stream that exceeds `limit_bytes` is spilled to, as
+    /// `<spill_dir>/<label>.stdout` / `<label>.stderr`. `None` means the
+    /// excess is simply dropped, as it always was before capping existed.
+    pub spill_dir: Option<PathBuf>,
+    /// The stage name a spilled file is prefixed with (`"check"`, `"test"`, ...).
+    pub label: String,
+    /// Applied to both streams before capping or spilling, so a credential
+    /// a command happens to echo (a bootstrap script dumping its env, a
+    /// proxy error page quoting the request it rejected) never reaches the
+    /// in-memory outcome or a spill file in the clear. `None` redacts
+    /// nothing — the behavior before this existed.
+    pub redactor: Option<Redactor>,
+}
+
+impl Default for CaptureConfig {
+    fn default() -> Self {
+        Self {
+            limit_bytes: DEFAULT_CAPTURE_LIMIT_BYTES,
+            spill_dir: None,
+            label: "command".to_string(),
+            redactor: None,
+        }
+    }
+}
+
+/// Runs `program args...` in `cwd` like [`run_command`], but caps each
+/// stream at `config.limit_bytes` and, when `config.spill_dir` is set and a
+/// stream runs over that cap, writes the full stream to
+/// `<spill_dir>/<label>.<stream>` so nothing is lost — only the in-memory
+/// copy is trimmed.
+#[tracing::instrument(skip(cwd, config), fields(elapsed_ms, ok, exit_code))]
+pub fn run_command_captured(program: &str, args: &[&str], cwd: &Path, config: &CaptureConfig) -> Result<RunnerOutcome, ExecError> {
+    let started = Instant::now();
+    let output = Command::new(program)
+        .args(args)
+        .current_dir(cwd)
+        .output()
+        .map_err(|source| ExecError::Spawn {
+            command: format!("{program} {}", args.join(" ")),
+            source,
+        })?;
+    let elapsed_ms = started.elapsed().as_millis();
+
+    let span = tracing::Span::current();
+    span.record("elapsed_ms", elapsed_ms);
+    span.record("ok", output.status.success());
+    span.record("exit_code", output.status.code().unwrap_or(-1));
+    tracing::info!(elapsed_ms, ok = output.status.success(), exit_code = output.status.code(), "ran command");
+
+    let stdout = capture_stream(String::from_utf8_lossy(&output.stdout).into_owned(), config, "stdout")?;
+    let stderr = capture_stream(String::from_utf8_lossy(&output.stderr).into_owned(), config, "stderr")?;
+
+    Ok(RunnerOutcome {
+        ok: output.status.success(),
+        stdout,
+        stderr,
+        skipped: false,
+    })
+}
+
+/// Caps `text` per `config`, spilling the full text to
+/// `<spill_dir>/<label>.<stream>` when it was too large to keep resident and
+/// a spill directory is configured. Redacted via `config.redactor`, if set,
+/// before either the cap or the spill sees it.
+fn capture_stream(text: String, config: &CaptureConfig, stream: &str) -> Result<CapturedOutput, ExecError> {
+    let text = match &config.redactor {
+        Some(redactor) => redactor.redact(&text),
+        None => text,
+    };
+    // Only clone `text` when it will actually be spilled: the common case
+    // (output under the cap, or over it with no spill dir configured)
+    // should hand `capped` the only copy instead of paying for a second
+    // one that's never read.
+    let truncated = text.len() > config.limit_bytes;
+    let full_text = if truncated && config.spill_dir.is_some() { Some(text.clone()) } else { None };
+
+    let mut captured = CapturedOutput::capped(text, config.limit_bytes);
+    if let (true, Some(full_text)) = (captured.truncated, full_text) {
+        let spill_dir = config.spill_dir.as_ref().expect("full_text is only set when spill_dir is Some");
+        std::fs::create_dir_all(spill_dir)?;
+        let spill_path = spill_dir.join(format!("{}.{stream}", config.label));
+        std::fs::write(&spill_path, &full_text)?;
+        captured.spill_path = Some(spill_path);
+    }
+    Ok(captured)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn output_under_the_limit_stays_inline_with_no_spill() {
+        let dir = tempdir().unwrap();
+        let config = CaptureConfig {
+            limit_bytes: 1024,
+            spill_dir: Some(dir.path().join(".tdd/logs/raw")),
+            label: "test".to_string(),
+            ..Default::default()
+        };
+
+        let outcome = run_command_captured("echo", &["hello"], dir.path(), &config).unwrap();
+
+        assert!(outcome.ok);
+        assert!(!outcome.stdout.truncated);
+        assert!(outcome.stdout.spill_path.is_none());
+        assert!(outcome.stdout.inline.contains("hello"));
+    }
+
+    #[test]
+    fn output_over_the_limit_spills_the_full_stream_to_disk() {
+        let dir = tempdir().unwrap();
+        let spill_dir = dir.path().join(".tdd/logs/raw");
+        let config = CaptureConfig {
+            limit_bytes: 4,
+            spill_dir: Some(spill_dir.clone()),
+            label: "test".to_string(),
+            ..Default::default()
+        };
+
+        let outcome = run_command_captured("echo", &["hello world"], dir.path(), &config).unwrap();
+
+        assert!(outcome.stdout.truncated);
+        assert_eq!(outcome.stdout.inline.len(), 4);
+        let spill_path = outcome.stdout.spill_path.as_ref().expect("stdout should have spilled");
+        assert_eq!(spill_path, &spill_dir.join("test.stdout"));
+        let spilled = std::fs::read_to_string(spill_path).unwrap();
+        assert_eq!(spilled.trim_end(), "hello world");
+        assert_eq!(outcome.stdout.total_bytes, spilled.len() as u64);
+    }
+
+    #[test]
+    fn output_over_the_limit_with_no_spill_dir_is_truncated_without_a_spill_path() {
+        let dir = tempdir().unwrap();
+        let config = CaptureConfig {
+            limit_bytes: 4,
+            spill_dir: None,
+            label: "test".to_string(),
+            ..Default::default()
+        };
+
+        let outcome = run_command_captured("echo", &["hello world"], dir.path(), &config).unwrap();
+
+        assert!(outcome.stdout.truncated);
+        assert!(outcome.stdout.spill_path.is_none());
+    }
+}
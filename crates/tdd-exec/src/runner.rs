@@ -0,0 +1,154 @@
+//! A [`tdd_core::Runner`] implementation for Rust, backed by `cargo`.
+
+use crate::process::{run_command_captured, CaptureConfig};
+use std::path::{Path, PathBuf};
+use tdd_core::{CommandSpec, Redactor, Runner, RunnerOutcome, DEFAULT_CAPTURE_LIMIT_BYTES};
+
+/// Runs `cargo fmt`, `cargo clippy`, and `cargo test` inside a repo root.
+/// A stage set to [`CommandSpec::Skip`] (see [`with_stage_config`]) is
+/// never shelled out to; its `Runner` method returns
+/// [`RunnerOutcome::skipped`] instead. Output past [`with_capture_limit`]
+/// spills to `<repo_root>/.tdd/logs/raw/<stage>.<stream>` rather than being
+/// dropped, so a chatty `cargo test` doesn't balloon a step's memory use.
+///
+/// [`with_stage_config`]: CargoRunner::with_stage_config
+/// [`with_capture_limit`]: CargoRunner::with_capture_limit
+pub struct CargoRunner {
+    repo_root: PathBuf,
+    fmt_enabled: bool,
+    check_enabled: bool,
+    test_enabled: bool,
+    capture_limit_bytes: usize,
+    redactor: Option<Redactor>,
+}
+
+impl CargoRunner {
+    pub fn new(repo_root: impl Into<PathBuf>) -> Self {
+        Self {
+            repo_root: repo_root.into(),
+            fmt_enabled: true,
+            check_enabled: true,
+            test_enabled: true,
+            capture_limit_bytes: DEFAULT_CAPTURE_LIMIT_BYTES,
+            redactor: None,
+        }
+    }
+
+    /// Applies a `tdd.yaml` `ci` section's per-stage opt-outs. A stage
+    /// left as [`CommandSpec::Command`] keeps running the same built-in
+    /// `cargo` invocation; only whether it runs at all is configurable.
+    pub fn with_stage_config(mut self, fmt_cmd: &CommandSpec, check_cmd: &CommandSpec, test_cmd: &CommandSpec) -> Self {
+        self.fmt_enabled = fmt_cmd.is_enabled();
+        self.check_enabled = check_cmd.is_enabled();
+        self.test_enabled = test_cmd.is_enabled();
+        self
+    }
+
+    /// Caps how much of a stage's stdout/stderr stays resident, per stream.
+    /// Output beyond the cap still spills to disk in full; this only
+    /// controls how much comes back inline.
+    pub fn with_capture_limit(mut self, limit_bytes: usize) -> Self {
+        self.capture_limit_bytes = limit_bytes;
+        self
+    }
+
+    /// Redacts known credential values out of every stage's captured
+    /// output before it's kept resident or spilled to disk. `None` (the
+    /// default) redacts nothing, for the `--debug-unredacted-logs` escape
+    /// hatch.
+    pub fn with_redactor(mut self, redactor: Option<Redactor>) -> Self {
+        self.redactor = redactor;
+        self
+    }
+
+    fn spill_dir(&self) -> PathBuf {
+        self.repo_root.join(".tdd/logs/raw")
+    }
+
+    fn capture_config(&self, label: &str) -> CaptureConfig {
+        CaptureConfig {
+            limit_bytes: self.capture_limit_bytes,
+            spill_dir: Some(self.spill_dir()),
+            label: label.to_string(),
+            redactor: self.redactor.clone(),
+        }
+    }
+}
+
+impl Runner for CargoRunner {
+    fn fmt_check(&self) -> anyhow::Result<RunnerOutcome> {
+        if !self.fmt_enabled {
+            return Ok(RunnerOutcome::skipped());
+        }
+        Ok(run_command_captured(
+            "cargo",
+            &["fmt", "--all", "--", "--check"],
+            &self.repo_root,
+            &self.capture_config("fmt_check"),
+        )?)
+    }
+
+    fn fmt(&self) -> anyhow::Result<RunnerOutcome> {
+        if !self.fmt_enabled {
+            return Ok(RunnerOutcome::skipped());
+        }
+        Ok(run_command_captured("cargo", &["fmt", "--all"], &self.repo_root, &self.capture_config("fmt"))?)
+    }
+
+    fn check(&self) -> anyhow::Result<RunnerOutcome> {
+        if !self.check_enabled {
+            return Ok(RunnerOutcome::skipped());
+        }
+        Ok(run_command_captured(
+            "cargo",
+            &["clippy", "--all-targets", "--", "-D", "warnings"],
+            &self.repo_root,
+            &self.capture_config("check"),
+        )?)
+    }
+
+    fn test(&self) -> anyhow::Result<RunnerOutcome> {
+        if !self.test_enabled {
+            return Ok(RunnerOutcome::skipped());
+        }
+        Ok(run_command_captured("cargo", &["test", "--all"], &self.repo_root, &self.capture_config("test"))?)
+    }
+}
+
+/// Convenience constructor mirroring `CargoRunner::new` for callers that
+/// only have a borrowed path.
+pub fn cargo_runner(repo_root: &Path) -> CargoRunner {
+    CargoRunner::new(repo_root.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn a_skipped_stage_never_shells_out_and_counts_as_passing() {
+        let dir = tempdir().unwrap();
+        let runner = CargoRunner::new(dir.path()).with_stage_config(
+            &CommandSpec::Skip,
+            &CommandSpec::Command(vec!["cargo".into(), "clippy".into()]),
+            &CommandSpec::Command(vec!["cargo".into(), "test".into()]),
+        );
+
+        let outcome = runner.fmt_check().unwrap();
+        assert!(outcome.ok);
+        assert!(outcome.skipped);
+        let outcome = runner.fmt().unwrap();
+        assert!(outcome.ok);
+        assert!(outcome.skipped);
+    }
+
+    #[test]
+    fn an_enabled_stage_is_not_marked_skipped() {
+        let dir = tempdir().unwrap();
+        let runner = CargoRunner::new(dir.path());
+        // fmt_check runs for real here; just assert it reports as not skipped.
+        let outcome = runner.fmt_check().unwrap();
+        assert!(!outcome.skipped);
+    }
+}
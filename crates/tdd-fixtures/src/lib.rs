@@ -0,0 +1,67 @@
+//! Sample katas and a scripted LLM client used by e2e tests and examples
+//! so the orchestrator loop can be exercised without a real provider.
+
+use async_trait::async_trait;
+use std::sync::Mutex;
+use tdd_llm::{LlmClient, Message};
+
+/// The String Calculator kata description, used as the default fixture
+/// for e2e validation of the orchestrator loop.
+pub const STRING_CALCULATOR_KATA: &str = include_str!("../katas/string-calculator.md");
+
+/// An [`LlmClient`] that plays back a fixed sequence of responses and
+/// records every call it receives, so a test can assert both on what an
+/// agent said and on what it was told. Panics if asked for more responses
+/// than were scripted.
+pub struct ScriptedLlmClient {
+    responses: Mutex<std::collections::VecDeque<(String, Option<String>)>>,
+    calls: Mutex<Vec<Vec<Message>>>,
+}
+
+impl ScriptedLlmClient {
+    pub fn new(responses: impl IntoIterator<Item = String>) -> Self {
+        Self::new_with_finish_reasons(responses.into_iter().map(|response| (response, None)))
+    }
+
+    /// Like [`Self::new`], but scripts each response's `finish_reason`
+    /// alongside its content, for exercising `chat_with_finish_reason`.
+    pub fn new_with_finish_reasons(responses: impl IntoIterator<Item = (String, Option<String>)>) -> Self {
+        Self {
+            responses: Mutex::new(responses.into_iter().collect()),
+            calls: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// The messages passed to every `chat` call so far, in order.
+    pub fn calls(&self) -> Vec<Vec<Message>> {
+        self.calls.lock().expect("calls mutex poisoned").clone()
+    }
+}
+
+#[async_trait]
+impl LlmClient for ScriptedLlmClient {
+    async fn chat(&self, messages: Vec<Message>) -> anyhow::Result<String> {
+        Ok(self.chat_with_finish_reason(messages).await?.0)
+    }
+
+    async fn chat_with_finish_reason(&self, messages: Vec<Message>) -> anyhow::Result<(String, Option<String>)> {
+        self.calls.lock().expect("calls mutex poisoned").push(messages);
+        self.responses
+            .lock()
+            .expect("scripted responses mutex poisoned")
+            .pop_front()
+            .ok_or_else(|| anyhow::anyhow!("ScriptedLlmClient ran out of scripted responses"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn scripted_client_plays_back_responses_in_order() {
+        let client = ScriptedLlmClient::new(["first".to_string(), "second".to_string()]);
+        assert_eq!(client.chat(vec![]).await.unwrap(), "first");
+        assert_eq!(client.chat(vec![]).await.unwrap(), "second");
+    }
+}
@@ -0,0 +1,94 @@
+//! A cooperative cancellation signal shared between an in-flight chat
+//! call and whatever decided the step it belongs to should stop — a
+//! deadline elapsing, or the orchestrator tearing down after an abort.
+//! Unlike a dropped future, signalling through a token lets the client
+//! notice mid-request and fail fast with [`crate::error::LlmError::Cancelled`]
+//! instead of leaving the request running to completion in the background.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::Notify;
+
+/// A clonable handle to a single cancellation signal. Cloning shares the
+/// same underlying signal; any clone can call [`Self::cancel`], and every
+/// clone observes it.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Marks this token (and every clone of it) as cancelled.
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+        self.inner.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Resolves once [`Self::cancel`] has been called, for use as the
+    /// losing branch of a `tokio::select!` against an in-flight request.
+    /// Returns immediately if already cancelled.
+    pub async fn cancelled(&self) {
+        if self.is_cancelled() {
+            return;
+        }
+        loop {
+            let notified = self.inner.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+            if self.is_cancelled() {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn cancelled_resolves_immediately_if_cancel_was_already_called() {
+        let token = CancellationToken::new();
+        token.cancel();
+
+        tokio::time::timeout(std::time::Duration::from_millis(50), token.cancelled()).await.expect("should not time out");
+    }
+
+    #[tokio::test]
+    async fn cancelled_resolves_once_a_clone_cancels() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+
+        let waiter = tokio::spawn(async move { token.cancelled().await });
+        tokio::task::yield_now().await;
+        clone.cancel();
+
+        tokio::time::timeout(std::time::Duration::from_millis(50), waiter).await.expect("should not time out").unwrap();
+    }
+
+    #[tokio::test]
+    async fn an_uncancelled_token_never_resolves_cancelled_within_a_short_wait() {
+        let token = CancellationToken::new();
+
+        let result = tokio::time::timeout(std::time::Duration::from_millis(20), token.cancelled()).await;
+
+        assert!(result.is_err(), "cancelled() resolved without cancel() being called");
+        assert!(!token.is_cancelled());
+    }
+}
@@ -0,0 +1,447 @@
+//! A generic chat client trait and an OpenAI-compatible HTTP implementation.
+
+use crate::cancel::CancellationToken;
+use crate::config::{LlmConnection, RoleModelConfig};
+use crate::error::{classify_reqwest_error, LlmError};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// A single chat message, following the OpenAI chat-completions shape.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Message {
+    pub role: String,
+    pub content: String,
+}
+
+impl Message {
+    pub fn system(content: impl Into<String>) -> Self {
+        Self {
+            role: "system".to_string(),
+            content: content.into(),
+        }
+    }
+
+    pub fn user(content: impl Into<String>) -> Self {
+        Self {
+            role: "user".to_string(),
+            content: content.into(),
+        }
+    }
+
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Self {
+            role: "assistant".to_string(),
+            content: content.into(),
+        }
+    }
+}
+
+/// A chat-completion backend, implemented once per provider protocol.
+#[async_trait]
+pub trait LlmClient: Send + Sync {
+    async fn chat(&self, messages: Vec<Message>) -> anyhow::Result<String>;
+
+    /// Like [`Self::chat`], but also surfaces the provider's
+    /// `finish_reason` (e.g. `"length"` when the response was cut off at
+    /// the model's output limit) when the backend reports one. Defaults
+    /// to `None`, so a client that doesn't know about finish reasons only
+    /// has to implement `chat`.
+    async fn chat_with_finish_reason(&self, messages: Vec<Message>) -> anyhow::Result<(String, Option<String>)> {
+        Ok((self.chat(messages).await?, None))
+    }
+
+    /// Like [`Self::chat_with_finish_reason`], but lets the caller override
+    /// the per-role sampling settings for this call alone, e.g. to escalate
+    /// the temperature on a retry. Defaults to ignoring `override_` and
+    /// falling back to [`Self::chat_with_finish_reason`], so a client that
+    /// doesn't support per-call overrides only has to implement `chat`.
+    async fn chat_with_sampling_override(&self, messages: Vec<Message>, override_: Option<SamplingOverride>) -> anyhow::Result<(String, Option<String>)> {
+        let _ = override_;
+        self.chat_with_finish_reason(messages).await
+    }
+}
+
+/// Per-call sampling settings, overriding a role's configured defaults for
+/// one [`LlmClient::chat_with_sampling_override`] call. Currently just the
+/// temperature, since that's the only setting
+/// `roles.<role>.retry_temperature_bump` escalates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SamplingOverride {
+    pub temperature: f32,
+}
+
+/// The provider's valid temperature range, shared by every client that
+/// applies a [`SamplingOverride`] so escalation can't push a request past
+/// what the backend accepts.
+pub const TEMPERATURE_RANGE: std::ops::RangeInclusive<f32> = 0.0..=2.0;
+
+#[derive(Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    temperature: f32,
+    messages: &'a [Message],
+}
+
+#[derive(Deserialize)]
+struct ChatResponse {
+    choices: Vec<ChatChoice>,
+    #[serde(default)]
+    usage: Option<ChatUsage>,
+}
+
+#[derive(Deserialize)]
+struct ChatChoice {
+    message: Message,
+    #[serde(default)]
+    finish_reason: Option<String>,
+}
+
+/// Token accounting, when the provider reports it. Absent for providers
+/// that don't (or when the field isn't present at all).
+#[derive(Deserialize)]
+struct ChatUsage {
+    #[serde(default)]
+    prompt_tokens: u32,
+    #[serde(default)]
+    completion_tokens: u32,
+    #[serde(default)]
+    total_tokens: u32,
+}
+
+/// Calls any OpenAI-compatible `/chat/completions` endpoint.
+pub struct OpenAiCompatibleClient {
+    http: reqwest::Client,
+    connection: LlmConnection,
+    role_config: RoleModelConfig,
+    api_key: Option<String>,
+    last_elapsed: Mutex<Option<Duration>>,
+    cancellation: Option<CancellationToken>,
+}
+
+impl OpenAiCompatibleClient {
+    pub fn new(connection: LlmConnection, role_config: RoleModelConfig) -> anyhow::Result<Self> {
+        connection.validate()?;
+        let api_key = std::env::var(&connection.api_key_env).ok();
+        let builder = reqwest::Client::builder()
+            .timeout(Duration::from_secs(connection.request_timeout_secs))
+            .connect_timeout(Duration::from_secs(connection.connect_timeout_secs));
+        let http = connection.http.apply(builder)?.build()?;
+        Ok(Self {
+            http,
+            connection,
+            role_config,
+            api_key,
+            last_elapsed: Mutex::new(None),
+            cancellation: None,
+        })
+    }
+
+    /// Arms this client with a token the orchestrator can cancel to abort
+    /// an in-flight request promptly, instead of leaving it to run to
+    /// completion in the background after the step that started it gives up.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// The wall-clock time the most recent `chat` call took, recorded
+    /// regardless of whether it succeeded, for transcript/usage logs.
+    pub fn last_elapsed(&self) -> Option<Duration> {
+        *self.last_elapsed.lock().expect("last_elapsed mutex poisoned")
+    }
+}
+
+#[async_trait]
+impl LlmClient for OpenAiCompatibleClient {
+    async fn chat(&self, messages: Vec<Message>) -> anyhow::Result<String> {
+        Ok(self.chat_with_finish_reason(messages).await?.0)
+    }
+
+    async fn chat_with_finish_reason(&self, messages: Vec<Message>) -> anyhow::Result<(String, Option<String>)> {
+        self.chat_with_sampling_override(messages, None).await
+    }
+
+    /// `messages` is skipped from the span: it's the actual conversation
+    /// content and must never end up in trace output, even at debug level.
+    /// `temperature` records the value actually sent, after `override_` (if
+    /// any) was clamped to [`TEMPERATURE_RANGE`], so a retry's escalated
+    /// temperature is auditable from the transcript alone.
+    #[tracing::instrument(skip(self, messages), fields(model = %self.role_config.model, temperature, elapsed_ms, prompt_tokens, completion_tokens, total_tokens))]
+    async fn chat_with_sampling_override(&self, messages: Vec<Message>, override_: Option<SamplingOverride>) -> anyhow::Result<(String, Option<String>)> {
+        let temperature = override_.map_or(self.role_config.temperature, |o| o.temperature).clamp(*TEMPERATURE_RANGE.start(), *TEMPERATURE_RANGE.end());
+        tracing::Span::current().record("temperature", temperature);
+
+        let url = format!("{}/chat/completions", self.connection.base_url.trim_end_matches('/'));
+        let request = ChatRequest {
+            model: &self.role_config.model,
+            temperature,
+            messages: &messages,
+        };
+
+        let mut builder = self.http.post(url).json(&request);
+        if let Some(key) = &self.api_key {
+            builder = builder.bearer_auth(key);
+        }
+
+        let started = Instant::now();
+        let request = async {
+            let response = builder.send().await?.error_for_status()?;
+            response.json::<ChatResponse>().await
+        };
+        let result = match &self.cancellation {
+            Some(token) => tokio::select! {
+                result = request => Some(result),
+                _ = token.cancelled() => None,
+            },
+            None => Some(request.await),
+        };
+        let elapsed = started.elapsed();
+        *self.last_elapsed.lock().expect("last_elapsed mutex poisoned") = Some(elapsed);
+
+        let span = tracing::Span::current();
+        span.record("elapsed_ms", elapsed.as_millis());
+
+        let Some(result) = result else {
+            tracing::info!("chat completion cancelled");
+            return Err(LlmError::Cancelled.into());
+        };
+
+        let body = result.map_err(|error| {
+            classify_reqwest_error(error, self.connection.request_timeout_secs, self.connection.connect_timeout_secs)
+        })?;
+
+        if let Some(usage) = &body.usage {
+            span.record("prompt_tokens", usage.prompt_tokens);
+            span.record("completion_tokens", usage.completion_tokens);
+            span.record("total_tokens", usage.total_tokens);
+        }
+        tracing::info!(
+            elapsed_ms = elapsed.as_millis() as u64,
+            total_tokens = body.usage.as_ref().map(|u| u.total_tokens),
+            "chat completion received"
+        );
+
+        let choice = body.choices.into_iter().next().ok_or_else(|| anyhow::anyhow!("LLM response contained no choices"))?;
+        Ok((choice.message.content, choice.finish_reason))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{LlmError, TimeoutPhase};
+    use std::net::TcpListener;
+
+    /// Accepts a single connection and then never responds, to trigger a
+    /// request-phase (not connect-phase) timeout.
+    fn spawn_wedged_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let _stream = listener.accept();
+            std::thread::sleep(Duration::from_secs(30));
+        });
+        format!("http://{addr}")
+    }
+
+    #[tokio::test]
+    async fn classifies_a_wedged_connection_as_a_request_timeout() {
+        let base_url = spawn_wedged_server();
+        let client = OpenAiCompatibleClient::new(
+            LlmConnection {
+                provider: "test-provider".to_string(),
+                base_url,
+                api_key_env: "LLM_API_KEY_UNSET_IN_TEST".to_string(),
+                request_timeout_secs: 1,
+                connect_timeout_secs: 1,
+                allow_file_requests: false,
+                http: crate::config::HttpConfig::default(),
+            },
+            RoleModelConfig {
+                model: "test-model".to_string(),
+                temperature: 0.0,
+                endpoint: None,
+                retry_temperature_bump: 0.0,
+            },
+        )
+        .unwrap();
+
+        let error = client.chat(vec![Message::user("hi")]).await.unwrap_err();
+        let llm_error = error.downcast_ref::<LlmError>().expect("expected a classified LlmError");
+        assert!(matches!(
+            llm_error,
+            LlmError::Timeout {
+                phase: TimeoutPhase::Request,
+                ..
+            }
+        ));
+        assert!(client.last_elapsed().is_some());
+    }
+
+    #[tokio::test]
+    async fn cancelling_mid_call_returns_cancelled_within_the_request_timeout() {
+        let base_url = spawn_wedged_server();
+        let token = crate::cancel::CancellationToken::new();
+        let client = OpenAiCompatibleClient::new(
+            LlmConnection {
+                provider: "test-provider".to_string(),
+                base_url,
+                api_key_env: "LLM_API_KEY_UNSET_IN_TEST".to_string(),
+                request_timeout_secs: 30,
+                connect_timeout_secs: 30,
+                allow_file_requests: false,
+                http: crate::config::HttpConfig::default(),
+            },
+            RoleModelConfig {
+                model: "test-model".to_string(),
+                temperature: 0.0,
+                endpoint: None,
+                retry_temperature_bump: 0.0,
+            },
+        )
+        .unwrap()
+        .with_cancellation(token.clone());
+
+        let call = tokio::spawn(async move { client.chat(vec![Message::user("hi")]).await });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        token.cancel();
+
+        let result = tokio::time::timeout(Duration::from_secs(1), call).await.expect("should not time out").unwrap();
+        let error = result.unwrap_err();
+        assert!(matches!(error.downcast_ref::<LlmError>(), Some(LlmError::Cancelled)));
+    }
+
+    #[tokio::test]
+    async fn retry_wrapper_retries_a_timeout_and_eventually_gives_up() {
+        let base_url = spawn_wedged_server();
+        let client = OpenAiCompatibleClient::new(
+            LlmConnection {
+                provider: "test-provider".to_string(),
+                base_url,
+                api_key_env: "LLM_API_KEY_UNSET_IN_TEST".to_string(),
+                request_timeout_secs: 1,
+                connect_timeout_secs: 1,
+                allow_file_requests: false,
+                http: crate::config::HttpConfig::default(),
+            },
+            RoleModelConfig {
+                model: "test-model".to_string(),
+                temperature: 0.0,
+                endpoint: None,
+                retry_temperature_bump: 0.0,
+            },
+        )
+        .unwrap();
+        let retrying = crate::retry::RetryingLlmClient::new(client, 2, Duration::from_millis(1));
+
+        let error = retrying.chat(vec![Message::user("hi")]).await.unwrap_err();
+        assert!(error.downcast_ref::<LlmError>().unwrap().is_retryable());
+    }
+
+    /// Accepts one connection, replies with a canned `chat/completions`
+    /// response, and hands the raw request text back over the channel so a
+    /// test can inspect what was actually sent.
+    fn spawn_recording_server() -> (String, std::sync::mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            use std::io::{Read, Write};
+            let (mut stream, _) = listener.accept().unwrap();
+            stream.set_read_timeout(Some(Duration::from_millis(200))).unwrap();
+            let mut body = Vec::new();
+            let mut buf = [0u8; 4096];
+            loop {
+                match stream.read(&mut buf) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => body.extend_from_slice(&buf[..n]),
+                }
+            }
+            let response_body = r#"{"choices":[{"message":{"role":"assistant","content":"ok"}}]}"#;
+            let response = format!("HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}", response_body.len(), response_body);
+            let _ = stream.write_all(response.as_bytes());
+            let _ = tx.send(String::from_utf8_lossy(&body).to_string());
+        });
+        (format!("http://{addr}"), rx)
+    }
+
+    fn test_connection(base_url: String) -> LlmConnection {
+        LlmConnection {
+            provider: "test-provider".to_string(),
+            base_url,
+            api_key_env: "LLM_API_KEY_UNSET_IN_TEST".to_string(),
+            request_timeout_secs: 5,
+            connect_timeout_secs: 5,
+            allow_file_requests: false,
+            http: crate::config::HttpConfig::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn a_sampling_override_replaces_the_role_s_configured_temperature() {
+        let (base_url, requests) = spawn_recording_server();
+        let client = OpenAiCompatibleClient::new(
+            test_connection(base_url),
+            RoleModelConfig {
+                model: "test-model".to_string(),
+                temperature: 0.2,
+                endpoint: None,
+                retry_temperature_bump: 0.0,
+            },
+        )
+        .unwrap();
+
+        client
+            .chat_with_sampling_override(vec![Message::user("hi")], Some(SamplingOverride { temperature: 0.9 }))
+            .await
+            .unwrap();
+
+        let request = requests.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert!(request.contains("\"temperature\":0.9"), "request was: {request}");
+    }
+
+    #[tokio::test]
+    async fn a_sampling_override_above_the_valid_range_is_clamped() {
+        let (base_url, requests) = spawn_recording_server();
+        let client = OpenAiCompatibleClient::new(
+            test_connection(base_url),
+            RoleModelConfig {
+                model: "test-model".to_string(),
+                temperature: 0.2,
+                endpoint: None,
+                retry_temperature_bump: 0.0,
+            },
+        )
+        .unwrap();
+
+        client
+            .chat_with_sampling_override(vec![Message::user("hi")], Some(SamplingOverride { temperature: 5.0 }))
+            .await
+            .unwrap();
+
+        let request = requests.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert!(request.contains("\"temperature\":2.0"), "request was: {request}");
+    }
+
+    #[tokio::test]
+    async fn no_override_sends_the_role_s_configured_temperature_unchanged() {
+        let (base_url, requests) = spawn_recording_server();
+        let client = OpenAiCompatibleClient::new(
+            test_connection(base_url),
+            RoleModelConfig {
+                model: "test-model".to_string(),
+                temperature: 0.2,
+                endpoint: None,
+                retry_temperature_bump: 0.0,
+            },
+        )
+        .unwrap();
+
+        client.chat_with_finish_reason(vec![Message::user("hi")]).await.unwrap();
+
+        let request = requests.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert!(request.contains("\"temperature\":0.2"), "request was: {request}");
+    }
+}
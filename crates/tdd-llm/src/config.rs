@@ -0,0 +1,211 @@
+//! Configuration for per-role LLM routing, mirroring the `roles` and `llm`
+//! sections of `tdd.yaml`.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Model settings for a single role.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoleModelConfig {
+    pub model: String,
+    #[serde(default = "default_temperature")]
+    pub temperature: f32,
+    /// The name of the `llm_endpoints` entry this role talks to. Falls
+    /// back to `default_endpoint`, and then to the legacy single `llm:`
+    /// block, when unset.
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    /// Added to `temperature` per retry attempt on this role's step (0 on
+    /// the first attempt, the full bump by the second, and so on), to
+    /// break out of a deterministic failure loop where an identical
+    /// prompt at the same temperature keeps producing the same wrong
+    /// response. Clamped to the provider's valid range along with the
+    /// base temperature. Defaults to `0.0`, i.e. no escalation. Forced to
+    /// `0.0` regardless of this setting by `--deterministic`. See
+    /// [`crate::SamplingOverride`].
+    #[serde(default)]
+    pub retry_temperature_bump: f32,
+}
+
+fn default_temperature() -> f32 {
+    0.3
+}
+
+/// Connection settings shared by every role's client.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmConnection {
+    /// Identifies the backend this connection talks to (e.g. `"openai"`,
+    /// `"github_copilot"`), independent of `base_url` and `api_key_env`.
+    /// Used to notice and account for a provider switch mid-kata rather
+    /// than silently mixing logs and usage across providers.
+    pub provider: String,
+    pub base_url: String,
+    pub api_key_env: String,
+    #[serde(default = "default_request_timeout_secs")]
+    pub request_timeout_secs: u64,
+    #[serde(default = "default_connect_timeout_secs")]
+    pub connect_timeout_secs: u64,
+    /// Lets an agent trade one extra round trip for exact file contents
+    /// instead of guessing what to include in its initial context.
+    #[serde(default)]
+    pub allow_file_requests: bool,
+    /// Proxy and TLS settings for reaching the LLM endpoint, e.g. through
+    /// a corporate egress proxy with a private CA.
+    #[serde(default)]
+    pub http: HttpConfig,
+}
+
+/// Proxy and TLS settings applied to an HTTP client, shared by the LLM
+/// client and anything else in `tdd-cli` that talks to an external
+/// endpoint (the doctor reachability check, the webhook notifier).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HttpConfig {
+    /// Forwarded to `reqwest::Proxy::all`. Unset falls back to reqwest's
+    /// standard `HTTP_PROXY`/`HTTPS_PROXY`/`ALL_PROXY` environment-variable
+    /// behavior.
+    #[serde(default)]
+    pub proxy: Option<String>,
+    /// Forwarded to the configured proxy's `NoProxy` list. Has no effect
+    /// unless `proxy` is also set.
+    #[serde(default)]
+    pub no_proxy: Option<String>,
+    /// A PEM file added to the client's trust store, for a private CA.
+    #[serde(default)]
+    pub ca_bundle: Option<PathBuf>,
+    /// Disables TLS certificate verification entirely. Every client built
+    /// with this set prints a loud warning; only meant for reaching a
+    /// local self-signed endpoint (e.g. Ollama over HTTPS) during setup.
+    #[serde(default)]
+    pub accept_invalid_certs: bool,
+}
+
+impl HttpConfig {
+    /// Applies these settings to a client builder, reading `ca_bundle`
+    /// from disk if set. Fails with a clear error if the proxy URL or the
+    /// CA bundle's contents don't parse.
+    pub fn apply(&self, mut builder: reqwest::ClientBuilder) -> anyhow::Result<reqwest::ClientBuilder> {
+        if let Some(proxy) = &self.proxy {
+            let mut proxy = reqwest::Proxy::all(proxy).map_err(|error| anyhow::anyhow!("llm.http.proxy \"{proxy}\" is not a valid proxy URL: {error}"))?;
+            if let Some(no_proxy) = &self.no_proxy {
+                proxy = proxy.no_proxy(reqwest::NoProxy::from_string(no_proxy));
+            }
+            builder = builder.proxy(proxy);
+        }
+
+        if let Some(ca_bundle) = &self.ca_bundle {
+            let pem = std::fs::read(ca_bundle)
+                .map_err(|error| anyhow::anyhow!("couldn't read llm.http.ca_bundle at {}: {error}", ca_bundle.display()))?;
+            let cert = reqwest::Certificate::from_pem(&pem)
+                .map_err(|error| anyhow::anyhow!("llm.http.ca_bundle at {} isn't a valid PEM certificate: {error}", ca_bundle.display()))?;
+            builder = builder.add_root_certificate(cert);
+        }
+
+        if self.accept_invalid_certs {
+            eprintln!("WARNING      llm.http.accept_invalid_certs is set: TLS certificate verification is disabled for this run");
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+
+        Ok(builder)
+    }
+}
+
+fn default_request_timeout_secs() -> u64 {
+    120
+}
+
+fn default_connect_timeout_secs() -> u64 {
+    10
+}
+
+impl LlmConnection {
+    /// Rejects a configuration with a zero timeout, which would make
+    /// every call fail (or never fail) instantly.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.request_timeout_secs == 0 {
+            anyhow::bail!("llm.request_timeout_secs must be greater than zero");
+        }
+        if self.connect_timeout_secs == 0 {
+            anyhow::bail!("llm.connect_timeout_secs must be greater than zero");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connection() -> LlmConnection {
+        LlmConnection {
+            provider: "ollama".to_string(),
+            base_url: "http://localhost:11434/v1".to_string(),
+            api_key_env: "LLM_API_KEY".to_string(),
+            request_timeout_secs: 120,
+            connect_timeout_secs: 10,
+            allow_file_requests: false,
+            http: HttpConfig::default(),
+        }
+    }
+
+    #[test]
+    fn rejects_a_zero_request_timeout() {
+        let mut connection = connection();
+        connection.request_timeout_secs = 0;
+        assert!(connection.validate().is_err());
+    }
+
+    #[test]
+    fn rejects_a_zero_connect_timeout() {
+        let mut connection = connection();
+        connection.connect_timeout_secs = 0;
+        assert!(connection.validate().is_err());
+    }
+
+    #[test]
+    fn accepts_positive_timeouts() {
+        assert!(connection().validate().is_ok());
+    }
+
+    #[test]
+    fn http_settings_round_trip_through_yaml_with_defaults_when_omitted() {
+        let mut connection = connection();
+        let plain: LlmConnection = serde_yaml::from_str(&serde_yaml::to_string(&connection).unwrap()).unwrap();
+        assert!(plain.http.proxy.is_none());
+        assert!(!plain.http.accept_invalid_certs);
+
+        connection.http = HttpConfig {
+            proxy: Some("http://proxy.internal:3128".to_string()),
+            no_proxy: Some("localhost,127.0.0.1".to_string()),
+            ca_bundle: Some(PathBuf::from("/etc/tdd/corp-ca.pem")),
+            accept_invalid_certs: false,
+        };
+        let round_tripped: LlmConnection = serde_yaml::from_str(&serde_yaml::to_string(&connection).unwrap()).unwrap();
+        assert_eq!(round_tripped.http.proxy.as_deref(), Some("http://proxy.internal:3128"));
+        assert_eq!(round_tripped.http.no_proxy.as_deref(), Some("localhost,127.0.0.1"));
+        assert_eq!(round_tripped.http.ca_bundle, Some(PathBuf::from("/etc/tdd/corp-ca.pem")));
+    }
+
+    #[test]
+    fn an_unreadable_ca_bundle_path_is_a_clear_error() {
+        let http = HttpConfig {
+            ca_bundle: Some(PathBuf::from("/nonexistent/corp-ca.pem")),
+            ..HttpConfig::default()
+        };
+        let error = http.apply(reqwest::Client::builder()).unwrap_err();
+        assert!(error.to_string().contains("couldn't read llm.http.ca_bundle"));
+    }
+
+    #[test]
+    fn a_ca_bundle_that_isnt_valid_pem_is_a_clear_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("corp-ca.pem");
+        std::fs::write(&path, "this is not a certificate").unwrap();
+
+        let http = HttpConfig {
+            ca_bundle: Some(path),
+            ..HttpConfig::default()
+        };
+        let error = http.apply(reqwest::Client::builder()).unwrap_err();
+        assert!(error.to_string().contains("isn't a valid PEM certificate"));
+    }
+}
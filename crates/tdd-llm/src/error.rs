@@ -0,0 +1,79 @@
+//! Error classification for LLM HTTP calls, so retry/backoff logic and
+//! error-explanation layers can tell a wedged connection from a genuine
+//! provider failure.
+
+use thiserror::Error;
+
+/// Which phase of the HTTP request timed out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeoutPhase {
+    Connect,
+    Request,
+}
+
+/// Errors raised while talking to an LLM provider.
+#[derive(Debug, Error)]
+pub enum LlmError {
+    #[error("{phase:?} phase timed out after {seconds}s")]
+    Timeout { phase: TimeoutPhase, seconds: u64 },
+
+    #[error("LLM request failed: {0}")]
+    Network(#[from] reqwest::Error),
+
+    #[error("LLM request cancelled")]
+    Cancelled,
+}
+
+impl LlmError {
+    /// Timeouts are transient; every other failure is treated as a hard
+    /// error by the retry wrapper. A cancellation is never retried: the
+    /// caller no longer wants the result.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, LlmError::Timeout { .. })
+    }
+}
+
+impl From<&LlmError> for tdd_core::StepFailureDetail {
+    fn from(error: &LlmError) -> Self {
+        tdd_core::StepFailureDetail::Llm { message: error.to_string() }
+    }
+}
+
+/// Turns a `reqwest::Error` into an [`LlmError`], distinguishing a
+/// connect-phase timeout from a request-phase one.
+pub fn classify_reqwest_error(error: reqwest::Error, request_timeout_secs: u64, connect_timeout_secs: u64) -> LlmError {
+    if error.is_timeout() {
+        let phase = if error.is_connect() {
+            TimeoutPhase::Connect
+        } else {
+            TimeoutPhase::Request
+        };
+        let seconds = match phase {
+            TimeoutPhase::Connect => connect_timeout_secs,
+            TimeoutPhase::Request => request_timeout_secs,
+        };
+        LlmError::Timeout { phase, seconds }
+    } else {
+        LlmError::Network(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_timeout_becomes_an_llm_detail_with_the_same_message() {
+        let error = LlmError::Timeout {
+            phase: TimeoutPhase::Connect,
+            seconds: 10,
+        };
+        let detail = tdd_core::StepFailureDetail::from(&error);
+        assert_eq!(
+            detail,
+            tdd_core::StepFailureDetail::Llm {
+                message: error.to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,27 @@
+//! Client and adapters for OpenAI-compatible LLM providers, with per-role
+//! model and temperature routing.
+
+pub mod cancel;
+pub mod client;
+pub mod config;
+pub mod error;
+pub mod retry;
+
+pub use cancel::CancellationToken;
+pub use client::{LlmClient, Message, OpenAiCompatibleClient, SamplingOverride, TEMPERATURE_RANGE};
+pub use config::{HttpConfig, LlmConnection, RoleModelConfig};
+pub use error::{classify_reqwest_error, LlmError, TimeoutPhase};
+pub use retry::RetryingLlmClient;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn system_and_user_messages_carry_the_right_role() {
+        let system = Message::system("be terse");
+        let user = Message::user("hello");
+        assert_eq!(system.role, "system");
+        assert_eq!(user.role, "user");
+    }
+}
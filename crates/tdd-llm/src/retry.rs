@@ -0,0 +1,187 @@
+//! Retries a chat call when it fails with a retryable [`LlmError`], such
+//! as a connect or request timeout against a wedged local model.
+
+use crate::cancel::CancellationToken;
+use crate::client::{LlmClient, Message, SamplingOverride};
+use crate::error::LlmError;
+use async_trait::async_trait;
+use std::time::Duration;
+
+/// Wraps an [`LlmClient`], retrying `chat` up to `max_attempts` times when
+/// the failure is a timeout.
+pub struct RetryingLlmClient<C> {
+    inner: C,
+    max_attempts: u32,
+    backoff: Duration,
+    cancellation: Option<CancellationToken>,
+}
+
+impl<C: LlmClient> RetryingLlmClient<C> {
+    pub fn new(inner: C, max_attempts: u32, backoff: Duration) -> Self {
+        Self {
+            inner,
+            max_attempts,
+            backoff,
+            cancellation: None,
+        }
+    }
+
+    /// Arms this wrapper with a token so cancellation is checked before
+    /// starting another attempt and while sleeping out the backoff,
+    /// instead of only taking effect on the next call.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+}
+
+#[async_trait]
+impl<C: LlmClient> LlmClient for RetryingLlmClient<C> {
+    async fn chat(&self, messages: Vec<Message>) -> anyhow::Result<String> {
+        Ok(self.chat_with_finish_reason(messages).await?.0)
+    }
+
+    async fn chat_with_finish_reason(&self, messages: Vec<Message>) -> anyhow::Result<(String, Option<String>)> {
+        self.chat_with_sampling_override(messages, None).await
+    }
+
+    async fn chat_with_sampling_override(&self, messages: Vec<Message>, override_: Option<SamplingOverride>) -> anyhow::Result<(String, Option<String>)> {
+        let mut attempts = 0;
+        loop {
+            if let Some(token) = &self.cancellation {
+                if token.is_cancelled() {
+                    return Err(LlmError::Cancelled.into());
+                }
+            }
+            attempts += 1;
+            match self.inner.chat_with_sampling_override(messages.clone(), override_).await {
+                Ok(result) => return Ok(result),
+                Err(error) => {
+                    let retryable = error.downcast_ref::<LlmError>().map(LlmError::is_retryable).unwrap_or(false);
+                    if !retryable || attempts >= self.max_attempts {
+                        return Err(error);
+                    }
+                    match &self.cancellation {
+                        Some(token) => tokio::select! {
+                            _ = tokio::time::sleep(self.backoff) => {}
+                            _ = token.cancelled() => return Err(LlmError::Cancelled.into()),
+                        },
+                        None => tokio::time::sleep(self.backoff).await,
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::TimeoutPhase;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    struct FlakyClient {
+        failures_remaining: AtomicU32,
+    }
+
+    #[async_trait]
+    impl LlmClient for FlakyClient {
+        async fn chat(&self, _messages: Vec<Message>) -> anyhow::Result<String> {
+            if self.failures_remaining.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1)).is_ok() {
+                return Err(LlmError::Timeout {
+                    phase: TimeoutPhase::Request,
+                    seconds: 1,
+                }
+                .into());
+            }
+            Ok("recovered".to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_a_timeout_until_it_succeeds() {
+        let client = RetryingLlmClient::new(
+            FlakyClient {
+                failures_remaining: AtomicU32::new(2),
+            },
+            5,
+            Duration::from_millis(1),
+        );
+
+        let content = client.chat(vec![]).await.unwrap();
+        assert_eq!(content, "recovered");
+    }
+
+    #[tokio::test]
+    async fn a_cancelled_token_stops_the_retry_loop_without_a_new_attempt() {
+        let attempts = Arc::new(AtomicU32::new(0));
+        let client = RetryingLlmClient::new(
+            CountingFlakyClient {
+                attempts: attempts.clone(),
+            },
+            5,
+            Duration::from_millis(50),
+        );
+        let token = CancellationToken::new();
+        let client = client.with_cancellation(token.clone());
+        token.cancel();
+
+        let error = client.chat(vec![]).await.unwrap_err();
+
+        assert!(matches!(error.downcast_ref::<LlmError>(), Some(LlmError::Cancelled)));
+        assert_eq!(attempts.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn cancelling_during_backoff_stops_before_the_next_attempt() {
+        let attempts = Arc::new(AtomicU32::new(0));
+        let client = RetryingLlmClient::new(
+            CountingFlakyClient {
+                attempts: attempts.clone(),
+            },
+            5,
+            Duration::from_secs(30),
+        );
+        let token = CancellationToken::new();
+        let client = client.with_cancellation(token.clone());
+
+        let call = tokio::spawn(async move { client.chat(vec![]).await });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        token.cancel();
+
+        let result = tokio::time::timeout(Duration::from_secs(1), call).await.expect("should not time out").unwrap();
+        assert!(matches!(result.unwrap_err().downcast_ref::<LlmError>(), Some(LlmError::Cancelled)));
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    struct CountingFlakyClient {
+        attempts: Arc<AtomicU32>,
+    }
+
+    #[async_trait]
+    impl LlmClient for CountingFlakyClient {
+        async fn chat(&self, _messages: Vec<Message>) -> anyhow::Result<String> {
+            self.attempts.fetch_add(1, Ordering::SeqCst);
+            Err(LlmError::Timeout {
+                phase: TimeoutPhase::Request,
+                seconds: 1,
+            }
+            .into())
+        }
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_attempts() {
+        let client = RetryingLlmClient::new(
+            FlakyClient {
+                failures_remaining: AtomicU32::new(10),
+            },
+            3,
+            Duration::from_millis(1),
+        );
+
+        let error = client.chat(vec![]).await.unwrap_err();
+        assert!(error.downcast_ref::<LlmError>().unwrap().is_retryable());
+    }
+}
@@ -0,0 +1,95 @@
+//! A scaffolded kata workspace in a temp directory, for integration tests
+//! and examples that need a real `tdd-cli init`-shaped repo on disk
+//! without repeating its setup by hand.
+
+use std::path::{Path, PathBuf};
+use tdd_exec::{CommitAuthor, GitVcs};
+
+/// A `tdd-cli init`-scaffolded workspace rooted in a [`tempfile::TempDir`],
+/// removed from disk when this value drops.
+pub struct TestWorkspace {
+    dir: tempfile::TempDir,
+}
+
+impl TestWorkspace {
+    /// Runs `tdd-cli init` in a fresh temp directory, the same way a real
+    /// user's first command would, so tests exercise the actual on-disk
+    /// shape (`Cargo.toml`, `kata.md`, `tdd.yaml`, a git repo) rather than
+    /// a hand-assembled approximation of it.
+    pub fn init() -> anyhow::Result<Self> {
+        let dir = tempfile::tempdir()?;
+        tdd_cli::init::run(&tdd_cli::init::InitArgs {
+            path: dir.path().to_path_buf(),
+            kata_url: None,
+        })?;
+        Ok(Self { dir })
+    }
+
+    /// The workspace's root directory.
+    pub fn root(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Overwrites `kata.md` with `markdown`, replacing the placeholder
+    /// `init` wrote.
+    pub fn write_kata(&self, markdown: &str) -> anyhow::Result<()> {
+        std::fs::write(self.root().join("kata.md"), markdown)?;
+        Ok(())
+    }
+
+    /// Writes `content` to `relative_path` under the workspace root,
+    /// creating any missing parent directories.
+    pub fn write(&self, relative_path: &str, content: &str) -> anyhow::Result<()> {
+        let target = self.root().join(relative_path);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(target, content)?;
+        Ok(())
+    }
+
+    /// Full path to `relative_path` under the workspace root.
+    pub fn path(&self, relative_path: &str) -> PathBuf {
+        self.root().join(relative_path)
+    }
+
+    /// A [`GitVcs`] bound to this workspace's root, with the default
+    /// commit author `init` already used to set up the repo.
+    pub fn vcs(&self) -> GitVcs {
+        GitVcs::new(self.root(), CommitAuthor::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn init_scaffolds_the_expected_files() {
+        let workspace = TestWorkspace::init().unwrap();
+
+        assert!(workspace.path("Cargo.toml").is_file());
+        assert!(workspace.path("kata.md").is_file());
+        assert!(workspace.path("tdd.yaml").is_file());
+    }
+
+    #[test]
+    fn write_kata_replaces_the_placeholder_description() {
+        let workspace = TestWorkspace::init().unwrap();
+
+        workspace.write_kata("# String Calculator\n").unwrap();
+
+        let contents = std::fs::read_to_string(workspace.path("kata.md")).unwrap();
+        assert_eq!(contents, "# String Calculator\n");
+    }
+
+    #[test]
+    fn write_creates_missing_parent_directories() {
+        let workspace = TestWorkspace::init().unwrap();
+
+        workspace.write("src/extra.rs", "pub fn extra() {}\n").unwrap();
+
+        let contents = std::fs::read_to_string(workspace.path("src/extra.rs")).unwrap();
+        assert_eq!(contents, "pub fn extra() {}\n");
+    }
+}